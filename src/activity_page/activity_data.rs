@@ -30,6 +30,10 @@ impl ActivityDetails {
         self.activity_num
     }
 
+    pub fn activity_type(&self) -> &ActivityType {
+        &self.activity_type
+    }
+
     fn to_vec(&self) -> Vec<String> {
         vec![
             self.created_on.clone(),
@@ -154,7 +158,10 @@ impl ActivityData {
 
         let target_activity_num = self.activities[index].activity_num();
 
-        let target_txs = self.activity_txs.get(&target_activity_num).unwrap();
+        // A reconciliation that did not need any new tx has no txs saved
+        let Some(target_txs) = self.activity_txs.get(&target_activity_num) else {
+            return Vec::new();
+        };
 
         let mut txs = Vec::new();
 
@@ -178,6 +185,38 @@ impl ActivityData {
             smaller_num = None;
         }
 
+        // Undo, redo, imports and tx method merges show the txs as they were saved
+        if let ActivityType::Undo(_)
+        | ActivityType::Redo(_)
+        | ActivityType::Import(..)
+        | ActivityType::MergeTxMethods(..) = self.activities[index].activity_type
+        {
+            smaller_num = None;
+        }
+
+        // Reconciliations only change the status so only the reconciled txs are shown
+        if let ActivityType::Reconcile(..) = self.activities[index].activity_type {
+            for pair in target_txs.chunks(2) {
+                txs.push(pair[0].to_vec(None));
+            }
+
+            return txs;
+        }
+
+        // Tag changes save each changed tx after and before the change
+        if let ActivityType::RenameTag(_, _)
+        | ActivityType::MergeTags(_, _)
+        | ActivityType::RemoveTag(_) = self.activities[index].activity_type
+        {
+            for pair in target_txs.chunks(2) {
+                for tx in pair {
+                    txs.push(tx.to_vec(Some(pair[0].insertion_id)));
+                }
+            }
+
+            return txs;
+        }
+
         for tx in target_txs {
             let mut data = tx.to_vec(smaller_num);
             if is_swap {
@@ -198,7 +237,12 @@ impl ActivityData {
     pub fn add_extra_field(&self, index: usize) -> bool {
         let target_activity = self.activities.get(index).unwrap();
 
-        if let ActivityType::EditTX(_) = target_activity.activity_type {
+        if let ActivityType::EditTX(_)
+        | ActivityType::RevertEdit(_)
+        | ActivityType::RenameTag(_, _)
+        | ActivityType::MergeTags(_, _)
+        | ActivityType::RemoveTag(_) = target_activity.activity_type
+        {
             return true;
         }
 
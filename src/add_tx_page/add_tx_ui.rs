@@ -10,7 +10,7 @@ use crate::home_page::BALANCE_BOLD;
 use crate::outputs::TxType;
 use crate::page_handler::{HomeRow, TxTab, BACKGROUND, BLUE, BOX, GRAY, RED, TEXT};
 use crate::tx_handler::TxData;
-use crate::utility::{get_all_tx_methods, main_block, styled_block};
+use crate::utility::{get_all_tx_methods, main_block, styled_block, CurrencyConverter};
 
 /// The function draws the Add Transaction page of the interface.
 #[cfg(not(tarpaulin_include))]
@@ -33,10 +33,12 @@ pub fn add_tx_ui(
     use ratatui::layout::Position;
 
     let all_methods = get_all_tx_methods(conn);
+    let converter = CurrencyConverter::new(conn);
+    let total_header = converter.total_header();
     // get the data to insert into the Status widget of this page
 
     let status_data = add_tx_data.get_tx_status();
-    // Contains date, details, from method, to method, amount, tx type, tags.
+    // Contains date, details, from method, to method, amount, tx type, tags, autofill, splits and tx status.
     // Except to method, rest will be used for the widgets
     let input_data = add_tx_data.get_all_texts();
     // The index of the cursor position
@@ -51,7 +53,13 @@ pub fn add_tx_ui(
         TxType::Transfer => "From Method",
     };
 
-    // divide the terminal into 3 parts vertically
+    // Transfers cannot be split so the splits chunk is only shown for income and expense
+    let splits_height = match tx_type {
+        TxType::IncomeExpense => 3,
+        TxType::Transfer => 0,
+    };
+
+    // divide the terminal into 5 parts vertically
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -62,11 +70,19 @@ pub fn add_tx_ui(
             Constraint::Length(3),
             // details input chunk
             Constraint::Length(3),
+            // splits input chunk
+            Constraint::Length(splits_height),
             // status chunk
             Constraint::Percentage(100),
         ])
         .split(size);
 
+    // the details chunk also contains the tx status input box
+    let details_chunk = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
+        .split(chunks[2]);
+
     // based on the tx type divide the first chunk into 5 or 6 parts horizontally
     // this chunk contains the input boxes take takes input
     let input_chunk = {
@@ -229,7 +245,10 @@ pub fn add_tx_ui(
                 Cell::from(c).style(Style::default().fg(BLUE))
             } else if c.contains('↓') {
                 Cell::from(c).style(Style::default().fg(RED))
-            } else if all_methods.contains(&c) || BALANCE_BOLD.contains(&c.as_str()) {
+            } else if all_methods.contains(&c)
+                || BALANCE_BOLD.contains(&c.as_str())
+                || c == total_header
+            {
                 Cell::from(c).style(Style::default().add_modifier(Modifier::BOLD))
             } else {
                 Cell::from(c)
@@ -241,8 +260,14 @@ pub fn add_tx_ui(
             .style(Style::default().fg(TEXT))
     });
 
+    // The totals leave out the tx methods without an exchange rate
+    let balance_title = match converter.missing_rates_note() {
+        Some(note) => format!("Balance Change | {note}"),
+        None => "Balance Change".to_string(),
+    };
+
     let balance_area = Table::new(bal_data, width_data.to_owned())
-        .block(styled_block("Balance Change"))
+        .block(styled_block(&balance_title))
         .style(Style::default().fg(BOX));
 
     let mut status_text = vec![];
@@ -285,6 +310,10 @@ pub fn add_tx_ui(
 
     let mut tags_text = Line::from(format!("{} ", input_data[6]));
 
+    let splits_text = Line::from(format!("{} ", input_data[8]));
+
+    let tx_status_text = Line::from(format!("{} ", input_data[9]));
+
     match add_tx_tab {
         TxTab::Details => {
             details_text = Line::from(vec![
@@ -353,6 +382,16 @@ pub fn add_tx_ui(
         .block(styled_block("Tags"))
         .alignment(Alignment::Left);
 
+    let splits_sec = Paragraph::new(splits_text)
+        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .block(styled_block("Splits (Tag | Amount | Note; ...)"))
+        .alignment(Alignment::Left);
+
+    let tx_status_sec = Paragraph::new(tx_status_text)
+        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .block(styled_block("TX Status"))
+        .alignment(Alignment::Left);
+
     // We will be adding a cursor based on which tab is selected + the selected index.
     // This was created utilizing the tui-rs example named user_input.rs
     match add_tx_tab {
@@ -361,8 +400,12 @@ pub fn add_tx_ui(
             y: input_chunk[0].y + 1,
         }),
         TxTab::Details => f.set_cursor_position(Position {
-            x: chunks[2].x + current_index as u16 + 1,
-            y: chunks[2].y + 1,
+            x: details_chunk[0].x + current_index as u16 + 1,
+            y: details_chunk[0].y + 1,
+        }),
+        TxTab::Status => f.set_cursor_position(Position {
+            x: details_chunk[1].x + current_index as u16 + 1,
+            y: details_chunk[1].y + 1,
         }),
         TxTab::TxType => f.set_cursor_position(Position {
             x: input_chunk[1].x + current_index as u16 + 1,
@@ -372,6 +415,10 @@ pub fn add_tx_ui(
             x: input_chunk[2].x + current_index as u16 + 1,
             y: input_chunk[2].y + 1,
         }),
+        TxTab::Splits => f.set_cursor_position(Position {
+            x: chunks[3].x + current_index as u16 + 1,
+            y: chunks[3].y + 1,
+        }),
         _ => {}
     }
 
@@ -407,8 +454,9 @@ pub fn add_tx_ui(
     }
 
     f.render_widget(balance_area, chunks[0]);
-    f.render_widget(details_sec, chunks[2]);
-    f.render_widget(status_sec, chunks[3]);
+    f.render_widget(details_sec, details_chunk[0]);
+    f.render_widget(tx_status_sec, details_chunk[1]);
+    f.render_widget(status_sec, chunks[4]);
     f.render_widget(date_sec, input_chunk[0]);
     f.render_widget(tx_type_sec, input_chunk[1]);
     f.render_widget(from_method_sec, input_chunk[2]);
@@ -417,6 +465,7 @@ pub fn add_tx_ui(
         TxType::IncomeExpense => {
             f.render_widget(amount_sec, input_chunk[3]);
             f.render_widget(tags_sec, input_chunk[4]);
+            f.render_widget(splits_sec, chunks[3]);
         }
         TxType::Transfer => {
             f.render_widget(to_method_sec, input_chunk[3]);
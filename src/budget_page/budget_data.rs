@@ -0,0 +1,167 @@
+use chrono::Datelike;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::db::{get_all_budgets, Budget, BudgetPeriod, MONTHS};
+use crate::summary_page::SummaryData;
+use crate::utility::{get_all_years, get_year_range, Cent};
+
+/// The progress of a budget in the selected month
+pub struct BudgetStatus {
+    pub budget: Budget,
+    /// The unspent amount carried over from the earlier periods
+    pub rollover: Cent,
+    pub spent: Cent,
+}
+
+impl BudgetStatus {
+    /// The amount that can be spent in the period including the rollover
+    #[must_use]
+    pub fn available(&self) -> Cent {
+        self.budget.amount + self.rollover
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> Cent {
+        self.available() - self.spent
+    }
+
+    /// How much of the available amount has been spent. Can go above 100
+    #[must_use]
+    pub fn used_percentage(&self) -> f64 {
+        if self.available() <= Cent::default() {
+            return if self.spent.is_zero() { 0.0 } else { 100.0 };
+        }
+        (self.spent.as_f64() / self.available().as_f64()) * 100.0
+    }
+
+    #[must_use]
+    pub fn is_exceeded(&self) -> bool {
+        self.spent > self.available()
+    }
+
+    fn to_vec(&self) -> Vec<String> {
+        let rollover = if self.budget.rollover {
+            self.rollover.to_string()
+        } else {
+            String::from("-")
+        };
+
+        vec![
+            self.budget.tag.clone(),
+            self.budget.period.to_str().to_string(),
+            self.budget.amount.to_string(),
+            rollover,
+            self.spent.to_string(),
+            self.remaining().to_string(),
+            format!("{:.2}", self.used_percentage()),
+        ]
+    }
+}
+
+/// Contains every budget that applies to the selected month along with how much of it was spent.
+/// The spending is calculated from the same txs as the Summary page, in the base currency
+pub struct BudgetData {
+    budgets: Vec<BudgetStatus>,
+}
+
+impl BudgetData {
+    pub fn new(month: usize, year: usize, summary_data: &SummaryData, conn: &Connection) -> Self {
+        let (start_year, _) = get_year_range(conn).expect("could not get the year range");
+        let target_year = start_year + year as i32;
+
+        // The tag expenses of every month in the year range, keyed by the month index of the range
+        let all_expenses: HashMap<i32, HashMap<String, Cent>> = (0..get_all_years(conn).len())
+            .flat_map(|year| (0..MONTHS.len()).map(move |month| (month, year)))
+            .map(|(month, year)| {
+                (
+                    month as i32 + year as i32 * 12,
+                    summary_data.get_tag_expenses(month, year),
+                )
+            })
+            .collect();
+
+        // Returns the expense of the tag in the given month. Months outside the year range have no txs
+        let get_month_spent = |tag: &str, month: usize, year: i32| {
+            all_expenses
+                .get(&(month as i32 + (year - start_year) * 12))
+                .and_then(|expenses| expenses.get(tag))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let get_spent = |budget: &Budget, month: usize, year: i32| match budget.period {
+            BudgetPeriod::Monthly => get_month_spent(&budget.tag, month, year),
+            BudgetPeriod::Yearly => (0..MONTHS.len())
+                .map(|month| get_month_spent(&budget.tag, month, year))
+                .sum(),
+        };
+
+        let budgets = get_all_budgets(conn)
+            .into_iter()
+            .filter(|budget| budget.is_active(month, target_year))
+            .map(|budget| {
+                let mut rollover = Cent::default();
+
+                if budget.rollover {
+                    for (period_month, period_year) in
+                        get_earlier_periods(&budget, month, target_year)
+                    {
+                        let left = budget.amount + rollover
+                            - get_spent(&budget, period_month, period_year);
+                        rollover = left.max(Cent::default());
+                    }
+                }
+
+                let spent = get_spent(&budget, month, target_year);
+
+                BudgetStatus {
+                    budget,
+                    rollover,
+                    spent,
+                }
+            })
+            .collect();
+
+        BudgetData { budgets }
+    }
+
+    /// Convert all budgets to a Vector where each value of the vector is the data of a budget
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.budgets.iter().map(BudgetStatus::to_vec).collect()
+    }
+
+    pub fn get_budget(&self, index: usize) -> &BudgetStatus {
+        &self.budgets[index]
+    }
+
+    /// Returns the budgets that have been spent above the available amount
+    pub fn get_exceeded_budgets(&self) -> Vec<&BudgetStatus> {
+        self.budgets
+            .iter()
+            .filter(|status| status.is_exceeded())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.budgets.is_empty()
+    }
+}
+
+/// Returns the month and year of every period of the budget from its start till the period the given
+/// month is in. Yearly periods use the first month
+fn get_earlier_periods(budget: &Budget, month: usize, year: i32) -> Vec<(usize, i32)> {
+    let start_year = budget.start_date.year();
+
+    match budget.period {
+        BudgetPeriod::Monthly => {
+            let start = start_year * 12 + budget.start_date.month0() as i32;
+            let end = year * 12 + month as i32;
+
+            (start..end)
+                .map(|index| ((index % 12) as usize, index / 12))
+                .collect()
+        }
+        BudgetPeriod::Yearly => (start_year..year).map(|year| (0, year)).collect(),
+    }
+}
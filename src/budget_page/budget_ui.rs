@@ -0,0 +1,161 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Gauge, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::budget_page::BudgetData;
+use crate::page_handler::{
+    BudgetTab, IndexedData, TableData, BACKGROUND, BOX, HEADER, RED, SELECTED, TEXT,
+};
+use crate::utility::{create_tab, main_block, styled_block};
+
+/// The amount of characters the progress bar of each budget takes
+const BAR_WIDTH: usize = 20;
+
+/// Shows the budgets of the selected month along with how much of them was spent
+#[cfg(not(tarpaulin_include))]
+pub fn budget_ui(
+    f: &mut Frame,
+    months: &IndexedData,
+    years: &IndexedData,
+    current_tab: &BudgetTab,
+    budget_data: &BudgetData,
+    table_data: &mut TableData,
+) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let mut table_name = "Budgets".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("Budgets: {}", table_data.items.len());
+    }
+
+    let header_cells = [
+        "Tag",
+        "Period",
+        "Budget",
+        "Rollover",
+        "Spent",
+        "Remaining",
+        "Used %",
+        "Progress",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().enumerate().map(|(index, item)| {
+        let status = budget_data.get_budget(index);
+
+        // The tag can contain numbers so it doesn't get any commas
+        let mut cells: Vec<Cell> = item
+            .iter()
+            .enumerate()
+            .map(|(index, c)| {
+                if index == 0 {
+                    Cell::from(c.to_string())
+                } else {
+                    Cell::from(c.separate_with_commas())
+                }
+            })
+            .collect();
+
+        let filled = ((status.used_percentage() / 100.0).min(1.0) * BAR_WIDTH as f64) as usize;
+        cells.push(Cell::from(format!(
+            "{}{}",
+            "█".repeat(filled),
+            "░".repeat(BAR_WIDTH - filled)
+        )));
+
+        let text_color = if status.is_exceeded() { RED } else { TEXT };
+
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(BACKGROUND).fg(text_color))
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(18),
+            Constraint::Percentage(8),
+            Constraint::Percentage(11),
+            Constraint::Percentage(11),
+            Constraint::Percentage(11),
+            Constraint::Percentage(11),
+            Constraint::Percentage(7),
+            Constraint::Percentage(23),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    let mut month_tab = create_tab(months, "Months");
+    let mut year_tab = create_tab(years, "Years");
+
+    match current_tab {
+        BudgetTab::Months => {
+            month_tab = month_tab
+                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+        }
+        BudgetTab::Years => {
+            year_tab = year_tab
+                .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(SELECTED));
+        }
+        BudgetTab::List => {
+            if table_data.state.selected().is_some() {
+                table_area = table_area
+                    .highlight_symbol(">> ")
+                    .row_highlight_style(Style::default().bg(SELECTED));
+            }
+        }
+    }
+
+    // The progress of the selected budget
+    let gauge = if let Some(index) = table_data.state.selected() {
+        let status = budget_data.get_budget(index);
+        let gauge_color = if status.is_exceeded() { RED } else { BOX };
+
+        Gauge::default()
+            .block(styled_block(&status.budget.tag))
+            .gauge_style(Style::default().fg(gauge_color).bg(BACKGROUND))
+            .ratio((status.used_percentage() / 100.0).clamp(0.0, 1.0))
+            .label(format!(
+                "Spent {} of {}. Remaining {} ({:.2}%)",
+                status.spent.to_string().separate_with_commas(),
+                status.available().to_string().separate_with_commas(),
+                status.remaining().to_string().separate_with_commas(),
+                status.used_percentage()
+            ))
+    } else {
+        Gauge::default()
+            .block(styled_block("Progress"))
+            .gauge_style(Style::default().fg(BOX).bg(BACKGROUND))
+            .ratio(0.0)
+            .label("Select a budget to see its progress")
+    };
+
+    f.render_widget(year_tab, chunks[0]);
+    f.render_widget(month_tab, chunks[1]);
+    f.render_stateful_widget(table_area, chunks[2], &mut table_data.state);
+    f.render_widget(gauge, chunks[3]);
+}
@@ -2,9 +2,12 @@ use chrono::naive::NaiveDate;
 use rusqlite::Connection;
 use std::collections::HashMap;
 
-use crate::db::{MONTHS, YEARS};
+use crate::db::MONTHS;
 use crate::page_handler::IndexedData;
-use crate::utility::get_all_txs;
+use crate::utility::{
+    get_all_tx_methods, get_all_txs, get_all_years, is_in_tag_subtree, is_income_like, split_tags,
+    Cent,
+};
 
 /// Stores every transaction in the database and along with
 /// all balance amount after each transaction was committed
@@ -12,6 +15,8 @@ use crate::utility::get_all_txs;
 pub struct ChartData {
     all_txs: HashMap<i32, Vec<Vec<String>>>,
     all_balance: HashMap<i32, Vec<Vec<String>>>,
+    total_years: usize,
+    tx_methods: Vec<String>,
 }
 
 impl ChartData {
@@ -19,7 +24,8 @@ impl ChartData {
     pub fn new(conn: &Connection) -> Self {
         let mut all_txs = HashMap::new();
         let mut all_balance = HashMap::new();
-        for x in 0..YEARS.len() {
+        let total_years = get_all_years(conn).len();
+        for x in 0..total_years {
             for i in 0..MONTHS.len() {
                 let target_id = i as i32 + (x as i32 * 12);
                 let (t, b, _) = get_all_txs(conn, i, x);
@@ -31,6 +37,8 @@ impl ChartData {
         ChartData {
             all_txs,
             all_balance,
+            total_years,
+            tx_methods: get_all_tx_methods(conn),
         }
     }
 
@@ -57,7 +65,7 @@ impl ChartData {
             }
             // 2 = all time mode. Select every single data
             2 => {
-                for x in 0..YEARS.len() {
+                for x in 0..self.total_years {
                     for i in 0..MONTHS.len() {
                         let target_id = i as i32 + (x as i32 * 12);
                         for i in &self.all_txs[&target_id] {
@@ -106,7 +114,7 @@ impl ChartData {
             }
             // 2 = all time mode. Select every single data
             2 => {
-                for x in 0..YEARS.len() {
+                for x in 0..self.total_years {
                     for i in 0..MONTHS.len() {
                         let target_id = i as i32 + (x as i32 * 12);
                         for i in &self.all_txs[&target_id] {
@@ -123,4 +131,55 @@ impl ChartData {
         }
         (to_return_tx, to_return_balance)
     }
+
+    /// Returns the txs that have the given tag or any child of it. The balances are the total
+    /// change of all methods from only these txs, starting at 0
+    pub fn get_tag_data(
+        &self,
+        mode: &IndexedData,
+        month: usize,
+        year: usize,
+        tag: &str,
+    ) -> (Vec<&Vec<String>>, Vec<Vec<String>>) {
+        let (all_txs, _) = self.get_data(mode, month, year);
+
+        let mut balances = vec![Cent::default(); self.tx_methods.len()];
+
+        let mut to_return_tx = vec![];
+        let mut to_return_balance = vec![];
+
+        for tx in all_txs {
+            if !split_tags(&tx[5])
+                .iter()
+                .any(|tx_tag| is_in_tag_subtree(tx_tag, tag))
+            {
+                continue;
+            }
+
+            let amount: Cent = tx[3].parse().unwrap();
+
+            // Method name with whether the amount was added to it
+            let tx_changes: Vec<(&str, bool)> = if tx[4] == "Transfer" {
+                let split_method = tx[2].split(" to ").collect::<Vec<&str>>();
+                vec![(split_method[0], false), (split_method[1], true)]
+            } else {
+                vec![(&tx[2], is_income_like(&tx[4]))]
+            };
+
+            for (method, added) in tx_changes {
+                if let Some(index) = self.tx_methods.iter().position(|m| m == method) {
+                    if added {
+                        balances[index] += amount;
+                    } else {
+                        balances[index] -= amount;
+                    }
+                }
+            }
+
+            to_return_tx.push(tx);
+            to_return_balance.push(balances.iter().map(ToString::to_string).collect());
+        }
+
+        (to_return_tx, to_return_balance)
+    }
 }
@@ -9,10 +9,11 @@ use rusqlite::Connection;
 use std::collections::HashMap;
 
 use crate::chart_page::ChartData;
-use crate::page_handler::{ChartTab, IndexedData, BACKGROUND, BOX, SELECTED};
+use crate::db::{Goal, MarketValues};
+use crate::page_handler::{ChartTab, IndexedData, BACKGROUND, BOX, RED, SELECTED};
 use crate::utility::{
     create_tab, create_tab_activation, get_all_tx_methods, get_all_tx_methods_cumulative,
-    main_block,
+    main_block, Cent, CurrencyConverter,
 };
 
 /// Creates the balance chart from the transactions
@@ -28,10 +29,25 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
     chart_hidden_mode: bool,
     loop_remaining: &mut Option<f64>,
     chart_activated_methods: &HashMap<String, bool, S>,
+    chart_tag: Option<&str>,
+    chart_goal: Option<&Goal>,
+    chart_market_value: bool,
     conn: &Connection,
 ) {
     let size = f.area();
-    let (all_txs, all_balance) = chart_data.get_data(mode_selection, months.index, years.index);
+
+    // The balances of a tag are calculated from its txs so they are kept here
+    let tag_balances: Vec<Vec<String>>;
+
+    let (all_txs, all_balance) = match chart_tag {
+        Some(tag) => {
+            let (tag_txs, balances) =
+                chart_data.get_tag_data(mode_selection, months.index, years.index, tag);
+            tag_balances = balances;
+            (tag_txs, tag_balances.iter().collect())
+        }
+        None => chart_data.get_data(mode_selection, months.index, years.index),
+    };
 
     // divide the terminal into various chunks to draw the interface. This is a vertical chunk
     let mut main_layout = Layout::default().direction(Direction::Vertical).margin(2);
@@ -100,6 +116,7 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
 
     let all_tx_methods = get_all_tx_methods(conn);
     let all_tx_methods_cumulative = get_all_tx_methods_cumulative(conn);
+    let converter = CurrencyConverter::new(conn);
 
     // a vector containing another vector vec![X, Y] with coordinate of where to render chart points
     let mut datasets: Vec<Vec<(f64, f64)>> = Vec::new();
@@ -123,7 +140,14 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
     // if there are no transactions, we will create an empty chart
     if !all_txs.is_empty() {
         // contains all dates of the transactions
-        let all_dates = chart_data.get_all_dates(mode_selection, months.index, years.index);
+        let all_dates = if chart_tag.is_some() {
+            all_txs
+                .iter()
+                .map(|tx| NaiveDate::parse_from_str(&tx[0], "%d-%m-%Y").unwrap())
+                .collect()
+        } else {
+            chart_data.get_all_dates(mode_selection, months.index, years.index)
+        };
 
         let mut checking_date = NaiveDate::parse_from_str(&all_txs[0][0], "%d-%m-%Y").unwrap();
 
@@ -203,10 +227,13 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
 
                 for method_index in 0..all_tx_methods_cumulative.len() {
                     // keep track of the highest and the lowest point of the balance
+                    // The cumulative balance is converted to the base currency
                     let current_balance = if method_index != all_tx_methods.len() {
-                        let balance = current_balances[method_index].parse::<f64>().unwrap();
-                        cumulative_balance += balance;
-                        balance
+                        let balance = current_balances[method_index].parse::<Cent>().unwrap();
+                        cumulative_balance += converter
+                            .to_base(balance, &all_tx_methods[method_index])
+                            .as_f64();
+                        balance.as_f64()
                     } else {
                         cumulative_balance
                     };
@@ -308,6 +335,61 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
     } else {
         *loop_remaining = None;
     }
+
+    // The market value of the holdings of each active tx method, valued with the prices of each day.
+    // Not shown for a tag as holdings do not belong to any tag
+    let mut market_datasets: Vec<(String, Vec<(f64, f64)>)> = Vec::new();
+
+    if chart_market_value && chart_tag.is_none() && !all_txs.is_empty() {
+        let market_values = MarketValues::new(conn);
+        let first_date = NaiveDate::parse_from_str(&all_txs[0][0], "%d-%m-%Y").unwrap();
+
+        for method in market_values.tx_methods() {
+            if !chart_activated_methods
+                .get(&method)
+                .copied()
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let mut points = Vec::new();
+            let mut axis = 0.0;
+
+            while axis < current_axis {
+                let date = first_date + Duration::days(axis as i64);
+                let value = market_values.method_value(&method, Some(date)).as_f64();
+
+                if value > highest_balance {
+                    highest_balance = value;
+                } else if value < lowest_balance {
+                    lowest_balance = value;
+                }
+
+                points.push((axis, value));
+                axis += 1.0;
+            }
+
+            market_datasets.push((format!("{method} Market"), points));
+        }
+    }
+
+    // The target of the goal as a straight line across the whole chart
+    let mut goal_line = Vec::new();
+
+    if let Some(goal) = chart_goal {
+        let target = goal.target.as_f64();
+
+        if target > highest_balance {
+            highest_balance = target;
+        } else if target < lowest_balance {
+            lowest_balance = target;
+        }
+
+        goal_line.push((0.0, target));
+        goal_line.push(((current_axis - 1.0).max(0.0), target));
+    }
+
     // add a 10% extra value to the highest and the lowest balance
     // so the chart can properly render
     highest_balance += highest_balance * 5.0 / 100.0;
@@ -351,9 +433,19 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
             continue;
         }
 
+        let dataset_name = if i == all_tx_methods.len() {
+            format!(
+                "{} {}",
+                all_tx_methods_cumulative[i],
+                converter.base_currency()
+            )
+        } else {
+            all_tx_methods_cumulative[i].clone()
+        };
+
         final_dataset.push(
             Dataset::default()
-                .name(all_tx_methods_cumulative[i].clone())
+                .name(dataset_name)
                 .marker(Marker::Braille)
                 .graph_type(GraphType::Line)
                 .style(
@@ -365,8 +457,50 @@ pub fn chart_ui<S: ::std::hash::BuildHasher>(
         );
     }
 
+    for (dataset_name, points) in &market_datasets {
+        if color_list.is_empty() {
+            color_list.push(Color::Cyan);
+        }
+
+        final_dataset.push(
+            Dataset::default()
+                .name(dataset_name.clone())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(
+                    Style::default()
+                        .fg(color_list.pop().unwrap())
+                        .bg(BACKGROUND),
+                )
+                .data(points),
+        );
+    }
+
+    if let Some(goal) = chart_goal {
+        final_dataset.push(
+            Dataset::default()
+                .name(format!("Goal: {} {}", goal.name, goal.target))
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(RED).bg(BACKGROUND))
+                .data(&goal_line),
+        );
+    }
+
+    // The converted total leaves out the tx methods without an exchange rate
+    let chart_title = match (chart_tag, converter.missing_rates_note()) {
+        (Some(tag), Some(note)) => format!("Tag: {tag} | {note}"),
+        (Some(tag), None) => format!("Tag: {tag}"),
+        (None, Some(note)) => note,
+        (None, None) => String::new(),
+    };
+
     let chart = Chart::new(final_dataset)
-        .block(Block::default().style(Style::default().bg(BACKGROUND).fg(BOX)))
+        .block(
+            Block::default()
+                .title(chart_title)
+                .style(Style::default().bg(BACKGROUND).fg(BOX)),
+        )
         .style(Style::default().bg(BACKGROUND).fg(BOX))
         .x_axis(
             Axis::default()
@@ -0,0 +1,187 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, Result, Savepoint};
+
+use crate::db::{TagMove, BUDGETS_TABLE};
+use crate::utility::Cent;
+
+/// How often the amount of a budget resets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BudgetPeriod {
+    Monthly,
+    Yearly,
+}
+
+impl BudgetPeriod {
+    #[must_use]
+    pub fn from_s(period: &str) -> Option<Self> {
+        match period.trim().to_lowercase().as_str() {
+            "m" | "month" | "monthly" => Some(BudgetPeriod::Monthly),
+            "y" | "year" | "yearly" => Some(BudgetPeriod::Yearly),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Monthly => "Monthly",
+            BudgetPeriod::Yearly => "Yearly",
+        }
+    }
+}
+
+/// A spending limit for a tag. A parent tag such as `Food` also counts the expenses of its child tags.
+/// With rollover, the unspent amount of a period gets added to the next period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    pub budget_id: i32,
+    pub tag: String,
+    pub period: BudgetPeriod,
+    pub amount: Cent,
+    pub rollover: bool,
+    /// The first day of the first month the budget applies to
+    pub start_date: NaiveDate,
+}
+
+impl Budget {
+    /// Returns whether the budget applies to the given month of the given year
+    #[must_use]
+    pub fn is_active(&self, month: usize, year: i32) -> bool {
+        match self.period {
+            BudgetPeriod::Monthly => {
+                (year, month as u32 + 1) >= (self.start_date.year(), self.start_date.month())
+            }
+            BudgetPeriod::Yearly => year >= self.start_date.year(),
+        }
+    }
+}
+
+/// Saves a new budget. Returns the id it was saved with
+pub fn add_budget(budget: &Budget, conn: &mut Connection) -> Result<i32> {
+    let sp = conn.savepoint()?;
+
+    sp.execute(
+        "INSERT INTO budgets (tag, period, amount, rollover, start_date) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (
+            &budget.tag,
+            budget.period.to_str(),
+            budget.amount,
+            budget.rollover,
+            budget.start_date.to_string(),
+        ),
+    )?;
+
+    let budget_id = sp.last_insert_rowid() as i32;
+
+    sp.commit()?;
+    Ok(budget_id)
+}
+
+/// Replaces the saved data of a budget
+pub fn update_budget(budget: &Budget, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    let updated = sp.execute(
+        "UPDATE budgets SET tag = ?1, period = ?2, amount = ?3, rollover = ?4, start_date = ?5
+        WHERE budget_id = ?6",
+        (
+            &budget.tag,
+            budget.period.to_str(),
+            budget.amount,
+            budget.rollover,
+            budget.start_date.to_string(),
+            budget.budget_id,
+        ),
+    )?;
+
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    sp.commit()
+}
+
+pub fn delete_budget(budget_id: i32, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute("DELETE FROM budgets WHERE budget_id = ?1", [budget_id])?;
+    sp.commit()
+}
+
+/// Moves the budgets of each old tag to its new tag. A budget stays on the old tag if the new tag
+/// already has a budget with the same period. Returns the moved budgets
+pub fn rename_budget_tags(
+    tag_changes: &[(String, String)],
+    sp: &Savepoint,
+) -> Result<Vec<TagMove>> {
+    // Every budget is looked up before moving any so a budget moved to a tag that is also renamed stays there
+    let mut to_move = Vec::new();
+
+    for (old_tag, new_tag) in tag_changes {
+        let mut statement = sp.prepare("SELECT budget_id FROM budgets WHERE tag = ?1")?;
+        let budget_ids = statement
+            .query_map([old_tag], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+
+        for budget_id in budget_ids {
+            to_move.push(TagMove {
+                moved_table: BUDGETS_TABLE.to_string(),
+                row_id: budget_id,
+                old_tag: old_tag.clone(),
+                new_tag: new_tag.clone(),
+            });
+        }
+    }
+
+    let mut moved = Vec::new();
+
+    for tag_move in to_move {
+        let updated = sp.execute(
+            "UPDATE OR IGNORE budgets SET tag = ?1 WHERE budget_id = ?2",
+            (&tag_move.new_tag, tag_move.row_id),
+        )?;
+
+        if updated == 1 {
+            moved.push(tag_move);
+        }
+    }
+
+    Ok(moved)
+}
+
+/// Moves a single budget to the given tag
+pub fn set_budget_tag(budget_id: i32, tag: &str, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "UPDATE budgets SET tag = ?1 WHERE budget_id = ?2",
+        (tag, budget_id),
+    )?;
+    Ok(())
+}
+
+/// Returns every saved budget ordered by the tag
+pub fn get_all_budgets(conn: &Connection) -> Vec<Budget> {
+    let mut statement = conn
+        .prepare(
+            "SELECT budget_id, tag, period, amount, rollover, start_date FROM budgets
+            ORDER BY tag, period",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| {
+            let period: String = row.get(2)?;
+            let start_date: String = row.get(5)?;
+
+            Ok(Budget {
+                budget_id: row.get(0)?,
+                tag: row.get(1)?,
+                period: BudgetPeriod::from_s(&period).expect("invalid saved budget period"),
+                amount: row.get(3)?,
+                rollover: row.get(4)?,
+                start_date: NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+                    .expect("invalid saved date"),
+            })
+        })
+        .expect("could not get budgets")
+        .flatten()
+        .collect()
+}
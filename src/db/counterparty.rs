@@ -0,0 +1,231 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result, Savepoint};
+use std::collections::BTreeMap;
+
+use crate::utility::{reverse_date_format, Cent};
+
+/// Money given to a counterparty. It leaves the tx method and the counterparty owes it back
+pub const LEND_TX_TYPE: &str = "Lend";
+
+/// Money taken from a counterparty. It enters the tx method and is owed back to the counterparty
+pub const BORROW_TX_TYPE: &str = "Borrow";
+
+/// A counterparty paying back what was lent to them
+pub const REPAY_IN_TX_TYPE: &str = "Repay In";
+
+/// Paying back what was borrowed from a counterparty
+pub const REPAY_OUT_TX_TYPE: &str = "Repay Out";
+
+/// Every debt tx type. These txs are only added from the Debts page so they get linked to a counterparty
+pub const DEBT_TX_TYPES: [&str; 4] = [
+    LEND_TX_TYPE,
+    BORROW_TX_TYPE,
+    REPAY_IN_TX_TYPE,
+    REPAY_OUT_TX_TYPE,
+];
+
+/// What a debt tx does with the counterparty. The direction of a repayment comes from
+/// who owes whom at the time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebtAction {
+    Lend,
+    Borrow,
+    Repay,
+}
+
+impl DebtAction {
+    #[must_use]
+    pub fn from_s(action: &str) -> Option<Self> {
+        match action.trim().to_lowercase().as_str() {
+            "l" | "lend" => Some(DebtAction::Lend),
+            "b" | "borrow" => Some(DebtAction::Borrow),
+            "r" | "repay" => Some(DebtAction::Repay),
+            _ => None,
+        }
+    }
+}
+
+/// A Lend, Borrow or Repay tx with a counterparty that is yet to be added
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewDebtTx {
+    pub counterparty: String,
+    pub action: DebtAction,
+    pub date: NaiveDate,
+    pub tx_method: String,
+    pub amount: Cent,
+    pub details: String,
+}
+
+/// A person money was lent to or borrowed from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterparty {
+    pub name: String,
+    /// Positive when the counterparty owes the money, negative when it is owed to them
+    pub outstanding: Cent,
+}
+
+impl Counterparty {
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        let status = match self.outstanding.value() {
+            0 => "Settled",
+            value if value > 0 => "Owes you",
+            _ => "You owe",
+        };
+
+        vec![
+            self.name.clone(),
+            self.outstanding.abs().to_string(),
+            status.to_string(),
+        ]
+    }
+}
+
+/// A debt tx with a counterparty along with how much of it is not settled yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtItem {
+    pub id_num: i32,
+    pub date: String,
+    pub details: String,
+    pub tx_method: String,
+    pub tx_type: String,
+    pub amount: Cent,
+    /// The part of the amount that is not settled by a repayment yet
+    pub open: Cent,
+}
+
+impl DebtItem {
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        vec![
+            reverse_date_format(self.date.clone()),
+            self.details.clone(),
+            self.tx_method.clone(),
+            self.tx_type.clone(),
+            self.amount.to_string(),
+            self.open.to_string(),
+        ]
+    }
+}
+
+/// Returns how much the tx changes what the counterparty owes. Lending and repaying
+/// what was borrowed increase it, borrowing and getting repaid decrease it
+#[must_use]
+pub fn get_debt_change(tx_type: &str, amount: Cent) -> Cent {
+    match tx_type {
+        LEND_TX_TYPE | REPAY_OUT_TX_TYPE => amount,
+        BORROW_TX_TYPE | REPAY_IN_TX_TYPE => -amount,
+        _ => Cent::default(),
+    }
+}
+
+/// Links a tx to the counterparty it is with
+pub fn link_counterparty(id_num: i32, counterparty: &str, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "INSERT OR REPLACE INTO tx_counterparties (id_num, counterparty) VALUES (?1, ?2)",
+        (id_num, counterparty),
+    )?;
+    Ok(())
+}
+
+/// Returns every counterparty that has at least one existing tx ordered by name.
+/// Amounts are added as they are saved without any currency conversion
+pub fn get_all_counterparties(conn: &Connection) -> Result<Vec<Counterparty>> {
+    let mut statement = conn.prepare(
+        "SELECT tx_counterparties.counterparty, tx_all.tx_type, tx_all.amount FROM tx_counterparties
+        INNER JOIN tx_all ON tx_all.id_num = tx_counterparties.id_num",
+    )?;
+    let mut rows = statement.query([])?;
+
+    let mut outstanding: BTreeMap<String, Cent> = BTreeMap::new();
+
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(0)?;
+        let tx_type: String = row.get(1)?;
+        let amount: Cent = row.get(2)?;
+
+        *outstanding.entry(name).or_default() += get_debt_change(&tx_type, amount);
+    }
+
+    Ok(outstanding
+        .into_iter()
+        .map(|(name, outstanding)| Counterparty { name, outstanding })
+        .collect())
+}
+
+/// Returns what the counterparty owes. Negative if the money is owed to them
+pub fn get_counterparty_outstanding(counterparty: &str, conn: &Connection) -> Result<Cent> {
+    Ok(get_counterparty_history(counterparty, conn)?
+        .iter()
+        .map(|item| get_debt_change(&item.tx_type, item.amount))
+        .sum())
+}
+
+/// Returns every existing tx with the counterparty from the oldest to the newest. Each repayment
+/// settles the oldest open items in the other direction first. A repayment larger than every open
+/// item stays open with the rest of its amount
+pub fn get_counterparty_history(counterparty: &str, conn: &Connection) -> Result<Vec<DebtItem>> {
+    let mut statement = conn.prepare(
+        "SELECT tx_all.id_num, date, details, tx_method, tx_type, amount FROM tx_counterparties
+        INNER JOIN tx_all ON tx_all.id_num = tx_counterparties.id_num
+        WHERE counterparty = ? ORDER BY date, tx_all.id_num",
+    )?;
+
+    let mut items = statement
+        .query_map([counterparty], |row| {
+            Ok(DebtItem {
+                id_num: row.get(0)?,
+                date: row.get(1)?,
+                details: row.get(2)?,
+                tx_method: row.get(3)?,
+                tx_type: row.get(4)?,
+                amount: row.get(5)?,
+                open: Cent::default(),
+            })
+        })?
+        .collect::<Result<Vec<DebtItem>>>()?;
+
+    // Index of every item that is not settled yet along with its signed open amount
+    let mut open_items: Vec<(usize, Cent)> = Vec::new();
+
+    for index in 0..items.len() {
+        let mut change = get_debt_change(&items[index].tx_type, items[index].amount);
+
+        if matches!(
+            items[index].tx_type.as_str(),
+            REPAY_IN_TX_TYPE | REPAY_OUT_TX_TYPE
+        ) {
+            for (open_index, open) in &mut open_items {
+                if change.is_zero() {
+                    break;
+                }
+
+                // Only items in the other direction get settled
+                if (open.value() > 0) == (change.value() > 0) {
+                    continue;
+                }
+
+                let settled = open.abs().min(change.abs());
+
+                if open.value() > 0 {
+                    *open -= settled;
+                    change += settled;
+                } else {
+                    *open += settled;
+                    change -= settled;
+                }
+
+                items[*open_index].open = open.abs();
+            }
+
+            open_items.retain(|(_, open)| !open.is_zero());
+        }
+
+        if !change.is_zero() {
+            items[index].open = change.abs();
+            open_items.push((index, change));
+        }
+    }
+
+    Ok(items)
+}
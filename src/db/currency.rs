@@ -0,0 +1,111 @@
+use rusqlite::{Connection, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::outputs::RateImportError;
+use crate::utility::is_valid_currency;
+
+/// Changes the currency of the given tx method. Existing balances are kept as they are
+pub fn set_tx_method_currency(
+    tx_method: &str,
+    currency: &str,
+    conn: &mut Connection,
+) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    sp.execute(
+        "INSERT INTO tx_methods (name, currency) VALUES (?1, ?2)
+        ON CONFLICT(name) DO UPDATE SET currency = excluded.currency",
+        [tx_method, currency],
+    )?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Changes the currency that all converted totals are shown in
+pub fn set_base_currency(currency: &str, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    sp.execute(
+        "UPDATE settings SET value = ?1 WHERE name = 'base_currency'",
+        [currency],
+    )?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Saves the given exchange rates. If a rate already exists for a currency pair, it gets replaced.
+/// Each rate is in the format of `(from currency, to currency, rate)`
+pub fn set_exchange_rates(rates: &[(String, String, f64)], conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    for (from_currency, to_currency, rate) in rates {
+        sp.execute(
+            "INSERT INTO exchange_rates (from_currency, to_currency, rate) VALUES (?1, ?2, ?3)
+            ON CONFLICT(from_currency, to_currency) DO UPDATE SET rate = excluded.rate",
+            (from_currency, to_currency, rate),
+        )?;
+    }
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Parses exchange rates from CSV content. Each line must be `from currency,to currency,rate`.
+/// Empty lines are skipped and the first line is skipped if it is a header
+pub fn parse_exchange_rates(content: &str) -> Result<Vec<(String, String, f64)>, RateImportError> {
+    let mut rates = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let values = line.split(',').map(str::trim).collect::<Vec<&str>>();
+
+        let parsed_rate = if values.len() == 3 {
+            values[2].parse::<f64>().ok()
+        } else {
+            None
+        };
+
+        let Some(rate) = parsed_rate else {
+            // a header line is allowed at the top. Example: from,to,rate
+            if index == 0 && values.len() == 3 {
+                continue;
+            }
+            return Err(RateImportError::InvalidLine(index + 1, line.to_string()));
+        };
+
+        let from_currency = values[0].to_uppercase();
+        let to_currency = values[1].to_uppercase();
+
+        if !is_valid_currency(&from_currency)
+            || !is_valid_currency(&to_currency)
+            || from_currency == to_currency
+            || !rate.is_finite()
+            || rate <= 0.0
+        {
+            return Err(RateImportError::InvalidLine(index + 1, line.to_string()));
+        }
+
+        rates.push((from_currency, to_currency, rate));
+    }
+
+    Ok(rates)
+}
+
+/// Reads a CSV file of exchange rates and saves all of them. Nothing is saved if any line is invalid.
+/// Returns the amount of rates that were saved
+pub fn import_exchange_rates(path: &Path, conn: &mut Connection) -> Result<usize, RateImportError> {
+    let content = fs::read_to_string(path).map_err(RateImportError::ReadFailed)?;
+    let rates = parse_exchange_rates(&content)?;
+
+    set_exchange_rates(&rates, conn).map_err(RateImportError::SavingFailed)?;
+
+    Ok(rates.len())
+}
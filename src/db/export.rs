@@ -0,0 +1,304 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::db::get_method_change;
+use crate::outputs::ExportError;
+use crate::utility::{get_all_tx_methods, Cent};
+
+/// The names of the columns that can be exported in the default order
+pub const EXPORT_COLUMNS: [&str; 8] = [
+    "date", "details", "method", "amount", "type", "tags", "id", "balance",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportColumn {
+    Date,
+    Details,
+    TxMethod,
+    Amount,
+    TxType,
+    Tags,
+    IdNum,
+    /// The running balance of every tx method after the tx. Takes one column per tx method
+    Balance,
+}
+
+impl ExportColumn {
+    #[must_use]
+    pub fn from_s(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "date" => Some(ExportColumn::Date),
+            "details" => Some(ExportColumn::Details),
+            "method" => Some(ExportColumn::TxMethod),
+            "amount" => Some(ExportColumn::Amount),
+            "type" => Some(ExportColumn::TxType),
+            "tags" => Some(ExportColumn::Tags),
+            "id" => Some(ExportColumn::IdNum),
+            "balance" => Some(ExportColumn::Balance),
+            _ => None,
+        }
+    }
+}
+
+/// Which txs to export
+#[derive(Debug)]
+pub enum ExportSource {
+    /// Every tx between the two dates, both inclusive
+    DateRange(NaiveDate, NaiveDate),
+    /// The txs with the given id nums. Used for exporting the current search result
+    TxIds(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub columns: Vec<ExportColumn>,
+    pub delimiter: char,
+    /// A chrono format string. Example: `%d-%m-%Y`
+    pub date_format: String,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            columns: EXPORT_COLUMNS
+                .iter()
+                .filter_map(|column| ExportColumn::from_s(column))
+                .collect(),
+            delimiter: ',',
+            date_format: "%Y-%m-%d".to_string(),
+        }
+    }
+}
+
+/// The arguments of the non-interactive `export` command
+#[derive(Debug)]
+pub struct ExportCommand {
+    pub source: ExportSource,
+    pub options: ExportOptions,
+    pub output: PathBuf,
+}
+
+/// Parses comma separated column names. Example: `date,details,amount`
+pub fn parse_export_columns(value: &str) -> Result<Vec<ExportColumn>, ExportError> {
+    let columns = value
+        .split(',')
+        .filter(|column| !column.trim().is_empty())
+        .map(|column| {
+            ExportColumn::from_s(column)
+                .ok_or_else(|| ExportError::InvalidColumn(column.to_string()))
+        })
+        .collect::<Result<Vec<ExportColumn>, ExportError>>()?;
+
+    if columns.is_empty() {
+        return Err(ExportError::InvalidColumn(value.to_string()));
+    }
+
+    Ok(columns)
+}
+
+/// Parses the delimiter. `tab` and `\t` are accepted for a tab. Quotes and new lines cannot be used
+pub fn parse_export_delimiter(value: &str) -> Result<char, ExportError> {
+    if value == "tab" || value == "\\t" {
+        return Ok('\t');
+    }
+
+    let mut chars = value.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(delimiter), None) if !matches!(delimiter, '"' | '\n' | '\r') => Ok(delimiter),
+        _ => Err(ExportError::InvalidDelimiter(value.to_string())),
+    }
+}
+
+/// Checks that the date format only contains valid chrono specifiers
+pub fn check_export_date_format(value: &str) -> Result<(), ExportError> {
+    if value.is_empty() || StrftimeItems::new(value).any(|item| matches!(item, Item::Error)) {
+        return Err(ExportError::InvalidDateFormat(value.to_string()));
+    }
+    Ok(())
+}
+
+/// Parses the arguments after `export`. `--output` is required. Without `--from` and `--to` every tx is exported
+///
+/// Example: `--from 2023-01-01 --to 2023-12-31 --columns date,amount --delimiter ; --date-format %d-%m-%Y --output txs.csv`
+pub fn parse_export_args(args: &[String]) -> Result<ExportCommand, ExportError> {
+    let mut options = ExportOptions::default();
+    let mut from_date = NaiveDate::MIN;
+    let mut to_date = NaiveDate::MAX;
+    let mut output = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            return Err(ExportError::InvalidArgument(arg.to_string()));
+        };
+
+        match arg.as_str() {
+            "--from" | "--to" => {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| ExportError::InvalidDate(value.to_string()))?;
+
+                if arg == "--from" {
+                    from_date = date;
+                } else {
+                    to_date = date;
+                }
+            }
+            "--columns" => options.columns = parse_export_columns(value)?,
+            "--delimiter" => options.delimiter = parse_export_delimiter(value)?,
+            "--date-format" => {
+                check_export_date_format(value)?;
+                options.date_format = value.to_string();
+            }
+            "--output" => output = Some(PathBuf::from(value)),
+            _ => return Err(ExportError::InvalidArgument(arg.to_string())),
+        }
+    }
+
+    let Some(output) = output else {
+        return Err(ExportError::InvalidArgument("--output".to_string()));
+    };
+
+    Ok(ExportCommand {
+        source: ExportSource::DateRange(from_date, to_date),
+        options,
+        output,
+    })
+}
+
+/// Quotes the value if it contains the delimiter, a quote or a line break
+fn escape_csv_value(value: &str, delimiter: char) -> String {
+    if value.contains(delimiter) || value.contains(['"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Creates the rows of the export with the header row at the top. Txs are in the same order as the
+/// Home page and the running balances are calculated from every tx since the beginning
+pub fn get_export_rows(
+    source: &ExportSource,
+    options: &ExportOptions,
+    conn: &Connection,
+) -> Result<Vec<Vec<String>>, ExportError> {
+    check_export_date_format(&options.date_format)?;
+
+    let all_methods = get_all_tx_methods(conn);
+
+    let mut header = Vec::new();
+
+    for column in &options.columns {
+        match column {
+            ExportColumn::Date => header.push("Date".to_string()),
+            ExportColumn::Details => header.push("Details".to_string()),
+            ExportColumn::TxMethod => header.push("Transaction Method".to_string()),
+            ExportColumn::Amount => header.push("Amount".to_string()),
+            ExportColumn::TxType => header.push("Transaction Type".to_string()),
+            ExportColumn::Tags => header.push("Tags".to_string()),
+            ExportColumn::IdNum => header.push("ID".to_string()),
+            ExportColumn::Balance => {
+                header.extend(all_methods.iter().map(|method| format!("{method} Balance")));
+            }
+        }
+    }
+
+    let tx_ids = match source {
+        ExportSource::TxIds(ids) => ids.iter().map(String::as_str).collect(),
+        ExportSource::DateRange(..) => HashSet::new(),
+    };
+
+    let mut statement = conn
+        .prepare(
+            "SELECT date, details, tx_method, amount, tx_type, id_num, tags FROM tx_all
+            ORDER BY date, id_num",
+        )
+        .map_err(ExportError::QueryFailed)?;
+
+    let mut query_rows = statement.query([]).map_err(ExportError::QueryFailed)?;
+
+    let mut balances = vec![Cent::default(); all_methods.len()];
+    let mut rows = vec![header];
+
+    while let Some(row) = query_rows.next().map_err(ExportError::QueryFailed)? {
+        let date: String = row.get(0).map_err(ExportError::QueryFailed)?;
+        let details: String = row.get(1).map_err(ExportError::QueryFailed)?;
+        let tx_method: String = row.get(2).map_err(ExportError::QueryFailed)?;
+        let amount: Cent = row.get(3).map_err(ExportError::QueryFailed)?;
+        let tx_type: String = row.get(4).map_err(ExportError::QueryFailed)?;
+        let id_num: i32 = row.get(5).map_err(ExportError::QueryFailed)?;
+        let tags: Option<String> = row.get(6).map_err(ExportError::QueryFailed)?;
+
+        for (balance, method) in balances.iter_mut().zip(all_methods.iter()) {
+            *balance += get_method_change(method, &tx_method, &tx_type, amount);
+        }
+
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap();
+        let id_num = id_num.to_string();
+
+        let is_exported = match source {
+            ExportSource::DateRange(from_date, to_date) => date >= *from_date && date <= *to_date,
+            ExportSource::TxIds(_) => tx_ids.contains(id_num.as_str()),
+        };
+
+        if !is_exported {
+            continue;
+        }
+
+        let mut export_row = Vec::new();
+
+        for column in &options.columns {
+            match column {
+                ExportColumn::Date => {
+                    export_row.push(date.format(&options.date_format).to_string());
+                }
+                ExportColumn::Details => export_row.push(details.clone()),
+                ExportColumn::TxMethod => export_row.push(tx_method.clone()),
+                ExportColumn::Amount => export_row.push(amount.to_string()),
+                ExportColumn::TxType => export_row.push(tx_type.clone()),
+                ExportColumn::Tags => export_row.push(tags.clone().unwrap_or_default()),
+                ExportColumn::IdNum => export_row.push(id_num.clone()),
+                ExportColumn::Balance => {
+                    export_row.extend(balances.iter().map(ToString::to_string));
+                }
+            }
+        }
+
+        rows.push(export_row);
+    }
+
+    Ok(rows)
+}
+
+/// Writes the txs of the source to a CSV file. Returns the amount of txs that were exported
+pub fn export_txs(
+    source: &ExportSource,
+    options: &ExportOptions,
+    path: &Path,
+    conn: &Connection,
+) -> Result<usize, ExportError> {
+    let rows = get_export_rows(source, options, conn)?;
+
+    let mut content = String::new();
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .map(|value| escape_csv_value(value, options.delimiter))
+            .collect::<Vec<String>>()
+            .join(&options.delimiter.to_string());
+
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    fs::write(path, content).map_err(ExportError::WriteFailed)?;
+
+    // The header is not a tx
+    Ok(rows.len() - 1)
+}
@@ -0,0 +1,246 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result, Savepoint};
+
+use crate::db::{get_all_tx_splits, TagMove, GOALS_TABLE};
+use crate::utility::{is_expense_like, is_in_tag_subtree, split_tags, Cent, CurrencyConverter};
+
+/// Where the saved amount of a goal comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoalSource {
+    /// The current balance of the tx method, in the currency of the method
+    TxMethod(String),
+    /// The total of every tx with the tag or any child of it, in the base currency
+    Tag(String),
+}
+
+impl GoalSource {
+    /// The name of the tx method or the tag
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            GoalSource::TxMethod(name) | GoalSource::Tag(name) => name,
+        }
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GoalSource::TxMethod(_) => "TX Method",
+            GoalSource::Tag(_) => "Tag",
+        }
+    }
+}
+
+/// A savings target that should be reached by the target date
+#[derive(Debug, Clone, PartialEq)]
+pub struct Goal {
+    pub goal_id: i32,
+    pub name: String,
+    pub target: Cent,
+    pub target_date: NaiveDate,
+    pub source: GoalSource,
+}
+
+impl Goal {
+    /// Returns the tx method and the tag columns of the goal. Only one of them is set
+    fn source_columns(&self) -> (Option<&str>, Option<&str>) {
+        match &self.source {
+            GoalSource::TxMethod(method) => (Some(method), None),
+            GoalSource::Tag(tag) => (None, Some(tag)),
+        }
+    }
+}
+
+/// Saves a new goal. Returns the id it was saved with
+pub fn add_goal(goal: &Goal, conn: &mut Connection) -> Result<i32> {
+    let sp = conn.savepoint()?;
+
+    let (tx_method, tag) = goal.source_columns();
+
+    sp.execute(
+        "INSERT INTO goals (name, target, target_date, tx_method, tag) VALUES (?1, ?2, ?3, ?4, ?5)",
+        (
+            &goal.name,
+            goal.target,
+            goal.target_date.to_string(),
+            tx_method,
+            tag,
+        ),
+    )?;
+
+    let goal_id = sp.last_insert_rowid() as i32;
+
+    sp.commit()?;
+    Ok(goal_id)
+}
+
+/// Replaces the saved data of a goal
+pub fn update_goal(goal: &Goal, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    let (tx_method, tag) = goal.source_columns();
+
+    let updated = sp.execute(
+        "UPDATE goals SET name = ?1, target = ?2, target_date = ?3, tx_method = ?4, tag = ?5
+        WHERE goal_id = ?6",
+        (
+            &goal.name,
+            goal.target,
+            goal.target_date.to_string(),
+            tx_method,
+            tag,
+            goal.goal_id,
+        ),
+    )?;
+
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    sp.commit()
+}
+
+pub fn delete_goal(goal_id: i32, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute("DELETE FROM goals WHERE goal_id = ?1", [goal_id])?;
+    sp.commit()
+}
+
+/// Moves the goals of each old tag to its new tag. Returns the moved goals
+pub fn rename_goal_tags(tag_changes: &[(String, String)], sp: &Savepoint) -> Result<Vec<TagMove>> {
+    // Every goal is looked up before moving any so a goal moved to a tag that is also renamed stays there
+    let mut moved = Vec::new();
+
+    for (old_tag, new_tag) in tag_changes {
+        let mut statement = sp.prepare("SELECT goal_id FROM goals WHERE tag = ?1")?;
+        let goal_ids = statement
+            .query_map([old_tag], |row| row.get(0))?
+            .collect::<Result<Vec<i32>>>()?;
+
+        for goal_id in goal_ids {
+            moved.push(TagMove {
+                moved_table: GOALS_TABLE.to_string(),
+                row_id: goal_id,
+                old_tag: old_tag.clone(),
+                new_tag: new_tag.clone(),
+            });
+        }
+    }
+
+    for tag_move in &moved {
+        set_goal_tag(tag_move.row_id, &tag_move.new_tag, sp)?;
+    }
+
+    Ok(moved)
+}
+
+/// Moves a single goal to the given tag
+pub fn set_goal_tag(goal_id: i32, tag: &str, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "UPDATE goals SET tag = ?1 WHERE goal_id = ?2",
+        (tag, goal_id),
+    )?;
+    Ok(())
+}
+
+/// Moves the goals of a tx method to `new_name`
+pub fn rename_goal_tx_method(old_name: &str, new_name: &str, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "UPDATE goals SET tx_method = ?2 WHERE tx_method = ?1",
+        [old_name, new_name],
+    )?;
+    Ok(())
+}
+
+/// Returns the number of goals that track the balance of the tx method
+pub fn get_goal_tx_method_usage(method: &str, conn: &Connection) -> Result<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM goals WHERE tx_method = ?1",
+        [method],
+        |row| row.get(0),
+    )
+}
+
+/// Returns every saved goal ordered by the target date
+pub fn get_all_goals(conn: &Connection) -> Vec<Goal> {
+    let mut statement = conn
+        .prepare(
+            "SELECT goal_id, name, target, target_date, tx_method, tag FROM goals
+            ORDER BY target_date, name",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| {
+            let target_date: String = row.get(3)?;
+            let tx_method: Option<String> = row.get(4)?;
+            let tag: Option<String> = row.get(5)?;
+
+            let source = match (tx_method, tag) {
+                (Some(method), _) => GoalSource::TxMethod(method),
+                (None, Some(tag)) => GoalSource::Tag(tag),
+                (None, None) => unreachable!("goals always have a tx method or a tag"),
+            };
+
+            Ok(Goal {
+                goal_id: row.get(0)?,
+                name: row.get(1)?,
+                target: row.get(2)?,
+                target_date: NaiveDate::parse_from_str(&target_date, "%Y-%m-%d")
+                    .expect("invalid saved date"),
+                source,
+            })
+        })
+        .expect("could not get goals")
+        .flatten()
+        .collect()
+}
+
+/// Returns the total of every tx with the tag or any child of it in the base currency.
+/// Income and transfers add to the total while expenses take from it. Txs with split lines
+/// only count the lines of the tag
+pub fn get_tag_savings(tag: &str, conn: &Connection) -> Result<Cent> {
+    let converter = CurrencyConverter::new(conn);
+    let all_splits = get_all_tx_splits(conn);
+
+    let mut statement =
+        conn.prepare("SELECT id_num, tx_method, amount, tx_type, tags FROM tx_all")?;
+    let mut rows = statement.query([])?;
+
+    let mut total = Cent::default();
+
+    while let Some(row) = rows.next()? {
+        let id_num: i32 = row.get(0)?;
+        let tx_method: String = row.get(1)?;
+        let amount: Cent = row.get(2)?;
+        let tx_type: String = row.get(3)?;
+        let tags: String = row.get(4)?;
+
+        let tag_amount = match all_splits.get(&id_num) {
+            Some(splits) => splits
+                .iter()
+                .filter(|split| is_in_tag_subtree(&split.tag, tag))
+                .map(|split| split.amount)
+                .sum(),
+            None if split_tags(&tags)
+                .iter()
+                .any(|tx_tag| is_in_tag_subtree(tx_tag, tag)) =>
+            {
+                amount
+            }
+            None => continue,
+        };
+
+        // A transfer is converted with the currency of the method receiving it
+        let method = tx_method.split(" to ").last().unwrap_or(&tx_method);
+        let tag_amount = converter.to_base(tag_amount, method);
+
+        if is_expense_like(&tx_type) {
+            total -= tag_amount;
+        } else {
+            total += tag_amount;
+        }
+    }
+
+    Ok(total)
+}
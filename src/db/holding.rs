@@ -0,0 +1,281 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result, Savepoint};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::outputs::PriceImportError;
+use crate::utility::{Cent, CurrencyConverter};
+
+/// An investment held in a tx method. The cost basis is the total amount paid for the whole quantity
+/// and every amount is in the currency of the tx method
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Holding {
+    pub tx_method: String,
+    pub symbol: String,
+    pub quantity: f64,
+    pub cost_basis: Cent,
+}
+
+/// The price of a single unit of a symbol on a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct Price {
+    pub symbol: String,
+    pub date: NaiveDate,
+    pub price: Cent,
+}
+
+/// Returns whether the symbol can be saved. Symbols cannot be empty or contain a comma
+#[must_use]
+pub fn is_valid_symbol(symbol: &str) -> bool {
+    !symbol.trim().is_empty() && !symbol.contains(',')
+}
+
+/// Saves the holding. If the tx method already holds the symbol, it gets replaced.
+/// A quantity of 0 removes the holding
+pub fn set_holding(holding: &Holding, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    if holding.quantity == 0.0 {
+        sp.execute(
+            "DELETE FROM holdings WHERE tx_method = ?1 AND symbol = ?2",
+            [&holding.tx_method, &holding.symbol],
+        )?;
+    } else {
+        sp.execute(
+            "INSERT INTO holdings (tx_method, symbol, quantity, cost_basis) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(tx_method, symbol) DO UPDATE SET quantity = excluded.quantity,
+            cost_basis = excluded.cost_basis",
+            (
+                &holding.tx_method,
+                &holding.symbol,
+                holding.quantity,
+                holding.cost_basis,
+            ),
+        )?;
+    }
+
+    sp.commit()
+}
+
+/// Moves the holdings of the old tx method to the new one. If the new method already holds a symbol,
+/// the quantity and the cost basis get added to it
+pub fn rename_holding_tx_method(old_name: &str, new_name: &str, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "INSERT INTO holdings (tx_method, symbol, quantity, cost_basis)
+        SELECT ?2, symbol, quantity, cost_basis FROM holdings WHERE tx_method = ?1
+        ON CONFLICT(tx_method, symbol) DO UPDATE SET quantity = quantity + excluded.quantity,
+        cost_basis = cost_basis + excluded.cost_basis",
+        [old_name, new_name],
+    )?;
+
+    sp.execute("DELETE FROM holdings WHERE tx_method = ?1", [old_name])?;
+    Ok(())
+}
+
+/// Returns the number of holdings in the tx method
+pub fn get_holding_tx_method_usage(method: &str, conn: &Connection) -> Result<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM holdings WHERE tx_method = ?1",
+        [method],
+        |row| row.get(0),
+    )
+}
+
+/// Returns every saved holding ordered by the tx method and the symbol
+pub fn get_all_holdings(conn: &Connection) -> Vec<Holding> {
+    let mut statement = conn
+        .prepare(
+            "SELECT tx_method, symbol, quantity, cost_basis FROM holdings ORDER BY tx_method, symbol",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| {
+            Ok(Holding {
+                tx_method: row.get(0)?,
+                symbol: row.get(1)?,
+                quantity: row.get(2)?,
+                cost_basis: row.get(3)?,
+            })
+        })
+        .expect("could not get holdings")
+        .flatten()
+        .collect()
+}
+
+/// Saves the given prices. If a symbol already has a price on the date, it gets replaced
+pub fn set_prices(prices: &[Price], conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    for price in prices {
+        sp.execute(
+            "INSERT INTO prices (symbol, date, price) VALUES (?1, ?2, ?3)
+            ON CONFLICT(symbol, date) DO UPDATE SET price = excluded.price",
+            (&price.symbol, price.date.to_string(), price.price),
+        )?;
+    }
+
+    sp.commit()
+}
+
+/// Parses prices from CSV content. Each line must be `symbol,date,price` with the date as YYYY-MM-DD.
+/// Empty lines are skipped and the first line is skipped if it is a header
+pub fn parse_prices(content: &str) -> Result<Vec<Price>, PriceImportError> {
+    let mut prices = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let values = line.split(',').map(str::trim).collect::<Vec<&str>>();
+
+        let parsed_price = if values.len() == 3 {
+            values[2].parse::<Cent>().ok()
+        } else {
+            None
+        };
+
+        let Some(price) = parsed_price else {
+            // a header line is allowed at the top. Example: symbol,date,price
+            if index == 0 && values.len() == 3 {
+                continue;
+            }
+            return Err(PriceImportError::InvalidLine(index + 1, line.to_string()));
+        };
+
+        let Ok(date) = NaiveDate::parse_from_str(values[1], "%Y-%m-%d") else {
+            return Err(PriceImportError::InvalidLine(index + 1, line.to_string()));
+        };
+
+        if !is_valid_symbol(values[0]) || price.value() < 0 {
+            return Err(PriceImportError::InvalidLine(index + 1, line.to_string()));
+        }
+
+        prices.push(Price {
+            symbol: values[0].to_uppercase(),
+            date,
+            price,
+        });
+    }
+
+    Ok(prices)
+}
+
+/// Reads a CSV file of prices and saves all of them. Nothing is saved if any line is invalid.
+/// Returns the amount of prices that were saved
+pub fn import_prices(path: &Path, conn: &mut Connection) -> Result<usize, PriceImportError> {
+    let content = fs::read_to_string(path).map_err(PriceImportError::ReadFailed)?;
+    let prices = parse_prices(&content)?;
+
+    set_prices(&prices, conn).map_err(PriceImportError::SavingFailed)?;
+
+    Ok(prices.len())
+}
+
+/// Returns every saved price of every symbol from the oldest to the newest
+fn get_all_prices(conn: &Connection) -> HashMap<String, Vec<(NaiveDate, Cent)>> {
+    let mut statement = conn
+        .prepare("SELECT symbol, date, price FROM prices ORDER BY symbol, date")
+        .expect("could not prepare statement");
+
+    let mut prices: HashMap<String, Vec<(NaiveDate, Cent)>> = HashMap::new();
+
+    let rows = statement
+        .query_map([], |row| {
+            let date: String = row.get(1)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                NaiveDate::parse_from_str(&date, "%Y-%m-%d").expect("invalid saved date"),
+                row.get::<_, Cent>(2)?,
+            ))
+        })
+        .expect("could not get prices")
+        .flatten();
+
+    for (symbol, date, price) in rows {
+        prices.entry(symbol).or_default().push((date, price));
+    }
+
+    prices
+}
+
+/// Every holding along with the price history of their symbols. Used to value the holdings on any date
+pub struct MarketValues {
+    holdings: Vec<Holding>,
+    prices: HashMap<String, Vec<(NaiveDate, Cent)>>,
+}
+
+impl MarketValues {
+    pub fn new(conn: &Connection) -> Self {
+        MarketValues {
+            holdings: get_all_holdings(conn),
+            prices: get_all_prices(conn),
+        }
+    }
+
+    /// Returns the last price of the symbol on or before the date or the latest price if no date is given
+    #[must_use]
+    pub fn price_on(&self, symbol: &str, date: Option<NaiveDate>) -> Option<Cent> {
+        let prices = self.prices.get(symbol)?;
+
+        let Some(date) = date else {
+            return prices.last().map(|(_, price)| *price);
+        };
+
+        let position = prices.partition_point(|(price_date, _)| *price_date <= date);
+
+        position.checked_sub(1).map(|index| prices[index].1)
+    }
+
+    /// Returns the value of the holding on the date. The cost basis is used until the symbol has a price
+    #[must_use]
+    pub fn holding_value(&self, holding: &Holding, date: Option<NaiveDate>) -> Cent {
+        self.price_on(&holding.symbol, date)
+            .map_or(holding.cost_basis, |price| price.convert(holding.quantity))
+    }
+
+    /// Returns the value of every holding of the tx method on the date in the currency of the method
+    #[must_use]
+    pub fn method_value(&self, tx_method: &str, date: Option<NaiveDate>) -> Cent {
+        self.holdings
+            .iter()
+            .filter(|holding| holding.tx_method == tx_method)
+            .map(|holding| self.holding_value(holding, date))
+            .sum()
+    }
+
+    /// Returns the value of every holding on the date converted to the base currency
+    #[must_use]
+    pub fn total_value(&self, date: Option<NaiveDate>, converter: &CurrencyConverter) -> Cent {
+        self.holdings
+            .iter()
+            .map(|holding| converter.to_base(self.holding_value(holding, date), &holding.tx_method))
+            .sum()
+    }
+
+    /// Returns every tx method that has at least one holding
+    #[must_use]
+    pub fn tx_methods(&self) -> Vec<String> {
+        let mut methods = self
+            .holdings
+            .iter()
+            .map(|holding| holding.tx_method.clone())
+            .collect::<Vec<String>>();
+        methods.dedup();
+        methods
+    }
+
+    #[must_use]
+    pub fn holdings(&self) -> &[Holding] {
+        &self.holdings
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.holdings.is_empty()
+    }
+}
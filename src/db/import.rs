@@ -0,0 +1,480 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result, Savepoint};
+use std::fs;
+use std::mem;
+use std::path::Path;
+
+use crate::db::check_export_date_format;
+use crate::outputs::{ImportError, VerifyingOutput};
+use crate::page_handler::{ActivityType, DateType};
+use crate::tx_handler::add_tx_in_savepoint;
+use crate::utility::traits::DataVerifier;
+use crate::utility::{add_new_activity, add_new_activity_tx, get_last_tx, Cent};
+
+/// Where the amount of each row of a bank statement is read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportAmount {
+    /// A single column where negative amounts are expenses and the rest are incomes
+    Single(usize),
+    /// A debit column with the expenses and a credit column with the incomes
+    DebitCredit(usize, usize),
+}
+
+/// Which tx method the rows of a bank statement go to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportTxMethod {
+    /// Every row goes to this tx method
+    Fixed(String),
+    /// Each row has the tx method in this column
+    Column(usize),
+}
+
+/// How the columns of a bank statement CSV map to a tx. Saved by name so the same mapping can be
+/// used for every statement of a bank. Column numbers start from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProfile {
+    pub name: String,
+    pub delimiter: char,
+    pub has_header: bool,
+    pub date_column: usize,
+    /// A chrono format string. Example: `%d/%m/%Y`
+    pub date_format: String,
+    pub details_column: usize,
+    pub amount: ImportAmount,
+    /// Either `.` or `,`. The other one is ignored as a thousands separator
+    pub decimal_separator: char,
+    pub tx_method: ImportTxMethod,
+}
+
+impl Default for ImportProfile {
+    fn default() -> Self {
+        ImportProfile {
+            name: String::new(),
+            delimiter: ',',
+            has_header: true,
+            date_column: 1,
+            date_format: "%Y-%m-%d".to_string(),
+            details_column: 2,
+            amount: ImportAmount::Single(3),
+            decimal_separator: '.',
+            tx_method: ImportTxMethod::Fixed(String::new()),
+        }
+    }
+}
+
+impl DataVerifier for ImportProfile {}
+
+/// A row of a bank statement that passed the verification and is ready to be added
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTx {
+    /// The date as YYYY-MM-DD
+    pub date: String,
+    pub details: String,
+    pub tx_method: String,
+    pub amount: String,
+    pub tx_type: String,
+}
+
+/// Checks that the profile has a name, valid separators, a valid date format and that every column
+/// number is at least 1
+pub fn check_import_profile(profile: &ImportProfile) -> Result<(), ImportError> {
+    if profile.name.trim().is_empty() {
+        return Err(ImportError::InvalidProfile(
+            "The profile name cannot be empty".to_string(),
+        ));
+    }
+
+    if matches!(profile.delimiter, '"' | '\n' | '\r') {
+        return Err(ImportError::InvalidProfile(format!(
+            "{} is not a valid delimiter",
+            profile.delimiter
+        )));
+    }
+
+    if !matches!(profile.decimal_separator, '.' | ',') {
+        return Err(ImportError::InvalidProfile(format!(
+            "{} is not a valid decimal separator. Use . or ,",
+            profile.decimal_separator
+        )));
+    }
+
+    if check_export_date_format(&profile.date_format).is_err() {
+        return Err(ImportError::InvalidProfile(format!(
+            "{} is not a valid date format. Example: %d/%m/%Y",
+            profile.date_format
+        )));
+    }
+
+    let mut columns = vec![profile.date_column, profile.details_column];
+
+    match profile.amount {
+        ImportAmount::Single(column) => columns.push(column),
+        ImportAmount::DebitCredit(debit, credit) => columns.extend([debit, credit]),
+    }
+
+    if let ImportTxMethod::Column(column) = profile.tx_method {
+        columns.push(column);
+    }
+
+    if columns.contains(&0) {
+        return Err(ImportError::InvalidProfile(
+            "Column numbers start from 1".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Saves the profile. An existing profile with the same name gets replaced
+pub fn save_import_profile(profile: &ImportProfile, conn: &Connection) -> Result<(), ImportError> {
+    check_import_profile(profile)?;
+
+    let (amount_column, debit_column, credit_column) = match profile.amount {
+        ImportAmount::Single(column) => (Some(column), None, None),
+        ImportAmount::DebitCredit(debit, credit) => (None, Some(debit), Some(credit)),
+    };
+
+    let (tx_method, tx_method_column) = match &profile.tx_method {
+        ImportTxMethod::Fixed(method) => (Some(method.as_str()), None),
+        ImportTxMethod::Column(column) => (None, Some(*column)),
+    };
+
+    conn.execute(
+        "INSERT OR REPLACE INTO import_profiles (name, delimiter, has_header, date_column,
+        date_format, details_column, amount_column, debit_column, credit_column, decimal_separator,
+        tx_method, tx_method_column) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        (
+            profile.name.trim(),
+            profile.delimiter.to_string(),
+            profile.has_header,
+            profile.date_column,
+            &profile.date_format,
+            profile.details_column,
+            amount_column,
+            debit_column,
+            credit_column,
+            profile.decimal_separator.to_string(),
+            tx_method,
+            tx_method_column,
+        ),
+    )
+    .map_err(ImportError::SavingFailed)?;
+
+    Ok(())
+}
+
+/// Returns every saved import profile ordered by the name
+pub fn get_import_profiles(conn: &Connection) -> Vec<ImportProfile> {
+    let mut statement = conn
+        .prepare(
+            "SELECT name, delimiter, has_header, date_column, date_format, details_column,
+            amount_column, debit_column, credit_column, decimal_separator, tx_method, tx_method_column
+            FROM import_profiles ORDER BY name",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| {
+            let delimiter: String = row.get(1)?;
+            let decimal_separator: String = row.get(9)?;
+
+            let amount = match row.get::<_, Option<usize>>(6)? {
+                Some(column) => ImportAmount::Single(column),
+                None => ImportAmount::DebitCredit(row.get(7)?, row.get(8)?),
+            };
+
+            let tx_method = match row.get::<_, Option<String>>(10)? {
+                Some(method) => ImportTxMethod::Fixed(method),
+                None => ImportTxMethod::Column(row.get(11)?),
+            };
+
+            Ok(ImportProfile {
+                name: row.get(0)?,
+                delimiter: delimiter.chars().next().unwrap(),
+                has_header: row.get(2)?,
+                date_column: row.get(3)?,
+                date_format: row.get(4)?,
+                details_column: row.get(5)?,
+                amount,
+                decimal_separator: decimal_separator.chars().next().unwrap(),
+                tx_method,
+            })
+        })
+        .expect("could not get import profiles")
+        .flatten()
+        .collect()
+}
+
+/// Moves the import profiles of the old tx method to the new one
+pub fn rename_import_profile_tx_method(
+    old_name: &str,
+    new_name: &str,
+    sp: &Savepoint,
+) -> Result<()> {
+    sp.execute(
+        "UPDATE import_profiles SET tx_method = ?1 WHERE tx_method = ?2",
+        [new_name, old_name],
+    )?;
+    Ok(())
+}
+
+/// Splits the content of a CSV file into its records with the line number each record starts at.
+/// A quoted value can contain line breaks so a record continues on the next line until its quotes
+/// are closed. Empty lines outside of quotes are skipped
+fn split_csv_records(content: &str) -> Vec<(usize, String)> {
+    let mut records = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut in_quotes = false;
+
+    for (index, line) in content.lines().enumerate() {
+        match current.as_mut() {
+            Some((_, record)) => {
+                record.push('\n');
+                record.push_str(line);
+            }
+            None if line.trim().is_empty() => continue,
+            None => current = Some((index + 1, line.to_string())),
+        }
+
+        // Two quotes inside a quoted value flip the state twice so they leave it unchanged
+        if line.matches('"').count() % 2 == 1 {
+            in_quotes = !in_quotes;
+        }
+
+        if !in_quotes {
+            records.extend(current.take());
+        }
+    }
+
+    // A quote that is never closed keeps the rest of the file in the last record
+    records.extend(current);
+
+    records
+}
+
+/// Splits a CSV line by the delimiter. Values can be quoted to contain the delimiter and a quote
+/// inside a quoted value is written as two quotes
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            _ if c == delimiter && !in_quotes => values.push(mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    values.push(current);
+
+    values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .collect()
+}
+
+/// Parses an amount of a bank statement. The separator that is not the decimal separator is
+/// ignored along with spaces and currency symbols. A minus sign or brackets make the amount negative.
+/// Returns None if the value is empty or not an amount. Example: `-1.234,50`, `(12.50)`, `$1,200`
+#[must_use]
+pub fn parse_statement_amount(value: &str, decimal_separator: char) -> Option<Cent> {
+    let value = value.trim();
+
+    let is_valid_char = |c: char| {
+        c.is_ascii_digit()
+            || matches!(c, '.' | ',' | ' ' | '\'' | '-' | '+' | '(' | ')' | '$')
+            || !c.is_ascii()
+    };
+
+    if !value.chars().any(|c| c.is_ascii_digit()) || !value.chars().all(is_valid_char) {
+        return None;
+    }
+
+    let is_negative = value.contains('-') || (value.starts_with('(') && value.ends_with(')'));
+
+    let number = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == decimal_separator)
+        .map(|c| if c == decimal_separator { '.' } else { c })
+        .collect::<String>();
+
+    let amount = number.parse::<Cent>().ok()?;
+
+    if is_negative {
+        Some(-amount)
+    } else {
+        Some(amount)
+    }
+}
+
+/// Turns the rows of a bank statement into txs with the column mapping of the profile.
+/// Every row gets verified the same way as the Add TX page and the first invalid row returns an error
+/// with the line the row starts at. Empty lines are skipped
+pub fn parse_statement(
+    content: &str,
+    profile: &ImportProfile,
+    conn: &Connection,
+) -> Result<Vec<ImportedTx>, ImportError> {
+    check_import_profile(profile)?;
+
+    let mut txs = Vec::new();
+    let mut header_skipped = !profile.has_header;
+
+    for (line_num, record) in split_csv_records(content) {
+        if !header_skipped {
+            header_skipped = true;
+            continue;
+        }
+
+        let values = split_csv_line(&record, profile.delimiter);
+
+        let invalid = |reason: String| ImportError::InvalidRow(line_num, reason);
+
+        let get_value = |column: usize| {
+            values
+                .get(column - 1)
+                .map(String::as_str)
+                .ok_or_else(|| invalid(format!("Column {column} was not found")))
+        };
+
+        let parse_amount = |column: usize| {
+            let value = get_value(column)?;
+
+            if value.is_empty() {
+                return Ok(None);
+            }
+
+            parse_statement_amount(value, profile.decimal_separator)
+                .map(Some)
+                .ok_or_else(|| invalid(format!("{value} is not a valid amount")))
+        };
+
+        let date_value = get_value(profile.date_column)?;
+
+        let mut date = NaiveDate::parse_from_str(date_value, &profile.date_format)
+            .map_err(|_| {
+                invalid(format!(
+                    "{date_value} does not match the date format {}",
+                    profile.date_format
+                ))
+            })?
+            .to_string();
+
+        let (amount, tx_type) = match profile.amount {
+            ImportAmount::Single(column) => match parse_amount(column)? {
+                Some(amount) if amount.value() < 0 => (amount.abs(), "Expense"),
+                Some(amount) => (amount, "Income"),
+                None => return Err(invalid("The amount is empty".to_string())),
+            },
+            ImportAmount::DebitCredit(debit_column, credit_column) => {
+                match (parse_amount(debit_column)?, parse_amount(credit_column)?) {
+                    (Some(debit), _) if !debit.is_zero() => (debit.abs(), "Expense"),
+                    (_, Some(credit)) => (credit.abs(), "Income"),
+                    (Some(debit), None) => (debit, "Expense"),
+                    (None, None) => {
+                        return Err(invalid("Both debit and credit are empty".to_string()))
+                    }
+                }
+            }
+        };
+
+        let mut amount = amount.to_string();
+        let mut tx_type = tx_type.to_string();
+
+        let mut tx_method = match &profile.tx_method {
+            ImportTxMethod::Fixed(method) => method.to_string(),
+            ImportTxMethod::Column(column) => get_value(*column)?.to_string(),
+        };
+        let method_value = tx_method.clone();
+
+        for status in [
+            profile.verify_date(&mut date, &DateType::Exact),
+            profile.verify_amount(&mut amount),
+            profile.verify_tx_type(&mut tx_type),
+        ] {
+            if let VerifyingOutput::NotAccepted(e) = status {
+                return Err(invalid(e.to_string()));
+            }
+        }
+
+        if !matches!(
+            profile.verify_tx_method(&mut tx_method, conn),
+            VerifyingOutput::Accepted(_)
+        ) {
+            return Err(invalid(format!(
+                "{method_value} is not a Transaction Method"
+            )));
+        }
+
+        txs.push(ImportedTx {
+            date,
+            details: get_value(profile.details_column)?.to_string(),
+            tx_method,
+            amount,
+            tx_type,
+        });
+    }
+
+    Ok(txs)
+}
+
+/// Adds every tx in a single savepoint and saves them as one "Import" activity so the whole
+/// import can be undone at once. The profile is saved in the same savepoint
+fn add_imported_txs(
+    txs: &[ImportedTx],
+    profile: &ImportProfile,
+    conn: &mut Connection,
+) -> Result<(), ImportError> {
+    let sp = conn.savepoint().map_err(ImportError::SavingFailed)?;
+
+    save_import_profile(profile, &sp)?;
+
+    let activity_num = add_new_activity(
+        ActivityType::Import(Some(profile.name.clone()), Some(txs.len())),
+        &sp,
+    );
+
+    for tx in txs {
+        add_tx_in_savepoint(
+            &tx.date,
+            &tx.details,
+            &tx.tx_method,
+            &tx.amount,
+            &tx.tx_type,
+            "Unknown",
+            None,
+            &sp,
+        )
+        .map_err(ImportError::SavingFailed)?;
+
+        add_new_activity_tx(&get_last_tx(&sp), activity_num, &sp);
+    }
+
+    sp.commit().map_err(ImportError::SavingFailed)
+}
+
+/// Reads a bank statement CSV with the profile and adds all of its txs. Nothing is added and the
+/// profile is not saved if any row is invalid. Returns the amount of txs that were added
+pub fn import_txs(
+    path: &Path,
+    profile: &ImportProfile,
+    conn: &mut Connection,
+) -> Result<usize, ImportError> {
+    let content = fs::read_to_string(path).map_err(ImportError::ReadFailed)?;
+    let txs = parse_statement(&content, profile, conn)?;
+
+    if txs.is_empty() {
+        return Err(ImportError::NoTransactions);
+    }
+
+    add_imported_txs(&txs, profile, conn)?;
+
+    Ok(txs.len())
+}
@@ -0,0 +1,313 @@
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, Error as sqlError, Result, Savepoint};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+
+use crate::db::{QueryBuilder, MONTHS};
+use crate::utility::{
+    get_all_tx_methods, get_last_balance_id, get_year_range, is_income_like, Cent,
+};
+
+/// A row of `balance_all` or `changes_all` that does not match the txs in `tx_all`
+#[derive(Debug, PartialEq)]
+pub enum LedgerDifference {
+    /// Label of the `balance_all` row, the method, the saved balance and the expected balance
+    Balance(String, String, Cent, Cent),
+    /// The tx `id_num`, the column, the saved value and the expected value
+    Changes(i32, String, String, String),
+    /// A tx that has no `changes_all` row
+    MissingChanges(i32),
+    /// A `changes_all` row that has no tx
+    UnknownChanges(i32),
+}
+
+impl Display for LedgerDifference {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerDifference::Balance(row, method, saved, expected) => write!(
+                f,
+                "Balance of {method} at {row}: saved {saved}, expected {expected}"
+            ),
+            LedgerDifference::Changes(id_num, column, saved, expected) => write!(
+                f,
+                "Changes of {column} for transaction {id_num}: saved {saved}, expected {expected}"
+            ),
+            LedgerDifference::MissingChanges(id_num) => {
+                write!(f, "Transaction {id_num} has no changes saved")
+            }
+            LedgerDifference::UnknownChanges(id_num) => {
+                write!(
+                    f,
+                    "Changes are saved for transaction {id_num} that does not exist"
+                )
+            }
+        }
+    }
+}
+
+/// The `balance_all` and `changes_all` data that the txs in `tx_all` result in
+struct LedgerData {
+    /// `balance_all` `id_num` with the balance of all methods. Rows that are not here stay at 0
+    balances: BTreeMap<i32, Vec<Cent>>,
+    /// tx `id_num` with the date and the changes of all methods
+    changes: BTreeMap<i32, (String, Vec<String>)>,
+}
+
+/// Goes through all txs in date and `id_num` order and calculates the balance and the changes after each of them.
+/// Months with txs get the balance of all methods after the last tx of the month
+/// and the last row gets the absolute final balance
+fn calculate_ledger(all_methods: &[String], conn: &Connection) -> Result<LedgerData> {
+    let (start_year, _) = get_year_range(conn)?;
+    let last_balance_id = get_last_balance_id(conn)?;
+
+    let mut balances: HashMap<&str, Cent> = all_methods
+        .iter()
+        .map(|method| (method.as_str(), Cent::default()))
+        .collect();
+
+    let mut ledger = LedgerData {
+        balances: BTreeMap::new(),
+        changes: BTreeMap::new(),
+    };
+
+    let get_balances = |balances: &HashMap<&str, Cent>| {
+        all_methods
+            .iter()
+            .map(|method| balances[method.as_str()])
+            .collect::<Vec<Cent>>()
+    };
+
+    let mut statement = conn.prepare(
+        "SELECT date, tx_method, amount, tx_type, id_num FROM tx_all ORDER BY date, id_num",
+    )?;
+
+    let mut rows = statement.query([])?;
+
+    while let Some(row) = rows.next()? {
+        let date: String = row.get(0)?;
+        let tx_method: String = row.get(1)?;
+        let amount: Cent = row.get(2)?;
+        let tx_type: String = row.get(3)?;
+        let id_num: i32 = row.get(4)?;
+
+        // 2025-05-10 with the year range starting at 2022 = 5 + (3 * 12)
+        let split_date = date.split('-').collect::<Vec<&str>>();
+        let year = split_date[0].parse::<i32>().unwrap() - start_year;
+        let month = split_date[1].parse::<i32>().unwrap();
+        let target_id_num = month + (year * 12);
+
+        // Method name with whether the amount was added to it
+        let mut tx_changes: Vec<(&str, bool)> = Vec::new();
+
+        if tx_type == "Transfer" {
+            let split_method = tx_method.split(" to ").collect::<Vec<&str>>();
+            tx_changes.push((split_method[0], false));
+            tx_changes.push((split_method[1], true));
+        } else {
+            tx_changes.push((&tx_method, is_income_like(&tx_type)));
+        }
+
+        for (method, added) in &tx_changes {
+            let Some(balance) = balances.get_mut(*method) else {
+                return Err(sqlError::InvalidColumnName((*method).to_string()));
+            };
+
+            if *added {
+                *balance += amount;
+            } else {
+                *balance -= amount;
+            }
+        }
+
+        let changes_data = all_methods
+            .iter()
+            .map(|method| {
+                match tx_changes
+                    .iter()
+                    .find(|(changed_method, _)| changed_method == method)
+                {
+                    Some((_, true)) => format!("↑{amount}"),
+                    Some((_, false)) => format!("↓{amount}"),
+                    None => Cent::default().to_string(),
+                }
+            })
+            .collect::<Vec<String>>();
+
+        ledger.changes.insert(id_num, (date, changes_data));
+        ledger
+            .balances
+            .insert(target_id_num, get_balances(&balances));
+    }
+
+    ledger
+        .balances
+        .insert(last_balance_id, get_balances(&balances));
+
+    Ok(ledger)
+}
+
+/// Replaces every `balance_all` row and every `changes_all` row with the data calculated from `tx_all`
+pub fn recalculate_ledger(sp: &Savepoint) -> Result<()> {
+    let all_methods = get_all_tx_methods(sp);
+    let ledger = calculate_ledger(&all_methods, sp)?;
+
+    let mut reset_query = QueryBuilder::update("balance_all");
+    for method in &all_methods {
+        reset_query = reset_query.set(method, 0);
+    }
+    reset_query.execute(sp)?;
+
+    for (id_num, balances) in ledger.balances {
+        let mut balance_query = QueryBuilder::update("balance_all");
+        for (method, balance) in all_methods.iter().zip(balances) {
+            balance_query = balance_query.set(method, balance);
+        }
+
+        balance_query
+            .where_cond("id_num = ?", [id_num])
+            .execute(sp)?;
+    }
+
+    QueryBuilder::delete("changes_all").execute(sp)?;
+
+    for (id_num, (date, changes)) in ledger.changes {
+        let mut changes_query = QueryBuilder::insert("changes_all")
+            .set("date", date)
+            .set("id_num", id_num);
+        for (method, change) in all_methods.iter().zip(changes) {
+            changes_query = changes_query.set(method, change);
+        }
+
+        changes_query.execute(sp)?;
+    }
+
+    Ok(())
+}
+
+/// Rebuilds `balance_all` and `changes_all` from the txs in `tx_all`.
+/// Used to repair the tables if they ever go out of sync with the txs
+pub fn rebuild_ledger(conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    recalculate_ledger(&sp)?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Compares `balance_all` and `changes_all` with the data calculated from `tx_all` without
+/// writing anything. Returns all rows that do not match
+pub fn verify_ledger(conn: &Connection) -> Result<Vec<LedgerDifference>> {
+    let all_methods = get_all_tx_methods(conn);
+    let mut ledger = calculate_ledger(&all_methods, conn)?;
+    let (start_year, _) = get_year_range(conn)?;
+    let last_balance_id = get_last_balance_id(conn)?;
+
+    let mut differences = Vec::new();
+
+    // A month row with 0 balance means the balance is carried from an earlier month, so rows are
+    // compared by the balance the app reads from them instead of the saved value
+    let mut saved_balances = vec![Cent::default(); all_methods.len()];
+    let mut expected_balances = vec![Cent::default(); all_methods.len()];
+
+    let mut statement = conn.prepare("SELECT * FROM balance_all ORDER BY id_num")?;
+    let mut rows = statement.query([])?;
+
+    while let Some(row) = rows.next()? {
+        let id_num: i32 = row.get(0)?;
+
+        let row_label = if id_num == last_balance_id {
+            "Final Balance".to_string()
+        } else {
+            let month = MONTHS[(id_num as usize - 1) % 12];
+            let year = start_year + (id_num - 1) / 12;
+            format!("{month} {year}")
+        };
+
+        let expected_row = ledger
+            .balances
+            .remove(&id_num)
+            .unwrap_or_else(|| vec![Cent::default(); all_methods.len()]);
+
+        for (i, method) in all_methods.iter().enumerate() {
+            let saved: Cent = row.get(i + 1)?;
+            let expected = expected_row[i];
+
+            // Rows where both are carried only repeat a difference of an earlier row
+            let carried = saved.is_zero() && expected.is_zero();
+
+            if id_num == last_balance_id {
+                saved_balances[i] = saved;
+                expected_balances[i] = expected;
+            } else {
+                if !saved.is_zero() {
+                    saved_balances[i] = saved;
+                }
+                if !expected.is_zero() {
+                    expected_balances[i] = expected;
+                }
+            }
+
+            if saved_balances[i] != expected_balances[i] && (!carried || id_num == last_balance_id)
+            {
+                differences.push(LedgerDifference::Balance(
+                    row_label.clone(),
+                    method.to_string(),
+                    saved_balances[i],
+                    expected_balances[i],
+                ));
+            }
+        }
+    }
+
+    let mut statement = conn.prepare("SELECT * FROM changes_all ORDER BY id_num")?;
+    let mut rows = statement.query([])?;
+
+    while let Some(row) = rows.next()? {
+        let id_num: i32 = row.get(1)?;
+
+        let Some((expected_date, expected_changes)) = ledger.changes.remove(&id_num) else {
+            differences.push(LedgerDifference::UnknownChanges(id_num));
+            continue;
+        };
+
+        let saved_date = get_text_value(row.get_ref(0)?);
+
+        if saved_date != expected_date {
+            differences.push(LedgerDifference::Changes(
+                id_num,
+                "date".to_string(),
+                saved_date,
+                expected_date,
+            ));
+        }
+
+        for (i, (method, expected)) in all_methods.iter().zip(expected_changes).enumerate() {
+            let saved = get_text_value(row.get_ref(i + 2)?);
+
+            if saved != expected {
+                differences.push(LedgerDifference::Changes(
+                    id_num,
+                    method.to_string(),
+                    saved,
+                    expected,
+                ));
+            }
+        }
+    }
+
+    for id_num in ledger.changes.into_keys() {
+        differences.push(LedgerDifference::MissingChanges(id_num));
+    }
+
+    Ok(differences)
+}
+
+/// `changes_all` columns that were added later keep the 0.00 default as a number in the old rows
+fn get_text_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Text(text) => String::from_utf8_lossy(text).to_string(),
+        ValueRef::Integer(number) => format!("{number:.2}"),
+        ValueRef::Real(number) => format!("{number:.2}"),
+        ValueRef::Null | ValueRef::Blob(_) => String::new(),
+    }
+}
@@ -0,0 +1,257 @@
+use rusqlite::{Connection, Result as sqlResult, Savepoint};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::db::{
+    add_activity_tag_moves, add_archived_column, add_budgets, add_counterparties, add_goals,
+    add_holdings, add_import_profiles, add_recurring_txs, add_tags_column, add_tx_method_kind,
+    add_tx_splits, add_tx_status, migrate_to_activities, migrate_to_cents, migrate_to_currencies,
+    migrate_to_tags, migrate_to_year_range, update_balance_type, DB_VERSION,
+};
+use crate::outputs::MigrationError;
+use crate::utility::{
+    check_old_balance_sql, get_all_table_names, get_all_tx_columns, get_db_version,
+};
+
+/// A single schema change that moves the DB to `version`
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    pub migrate: fn(&Savepoint) -> sqlResult<()>,
+}
+
+/// Every migration in the order they must run. The version of the last one is `DB_VERSION`.
+/// New migrations must only ever be added at the end
+pub const MIGRATIONS: [Migration; 18] = [
+    Migration {
+        version: 1,
+        description: "Add the tags column",
+        migrate: add_tags_column,
+    },
+    Migration {
+        version: 2,
+        description: "Change balance type from TEXT to REAL",
+        migrate: update_balance_type,
+    },
+    Migration {
+        version: 3,
+        description: "Add activity tables",
+        migrate: migrate_to_activities,
+    },
+    Migration {
+        version: 4,
+        description: "Add the year range table",
+        migrate: migrate_to_year_range,
+    },
+    Migration {
+        version: 5,
+        description: "Save amounts and balances as integer cents",
+        migrate: migrate_to_cents,
+    },
+    Migration {
+        version: 6,
+        description: "Add tx method currencies and exchange rates",
+        migrate: migrate_to_currencies,
+    },
+    Migration {
+        version: 7,
+        description: "Add the archived flag to tx methods",
+        migrate: add_archived_column,
+    },
+    Migration {
+        version: 8,
+        description: "Move tags to their own tables",
+        migrate: migrate_to_tags,
+    },
+    Migration {
+        version: 9,
+        description: "Add split lines to txs",
+        migrate: add_tx_splits,
+    },
+    Migration {
+        version: 10,
+        description: "Add recurring txs",
+        migrate: add_recurring_txs,
+    },
+    Migration {
+        version: 11,
+        description: "Add budgets",
+        migrate: add_budgets,
+    },
+    Migration {
+        version: 12,
+        description: "Add savings goals",
+        migrate: add_goals,
+    },
+    Migration {
+        version: 13,
+        description: "Add the status of txs",
+        migrate: add_tx_status,
+    },
+    Migration {
+        version: 14,
+        description: "Add the kind of tx methods",
+        migrate: add_tx_method_kind,
+    },
+    Migration {
+        version: 15,
+        description: "Add counterparties of debt txs",
+        migrate: add_counterparties,
+    },
+    Migration {
+        version: 16,
+        description: "Add investment holdings and prices",
+        migrate: add_holdings,
+    },
+    Migration {
+        version: 17,
+        description: "Add bank statement import profiles",
+        migrate: add_import_profiles,
+    },
+    Migration {
+        version: 18,
+        description: "Add the budget and goal moves of tag activities",
+        migrate: add_activity_tag_moves,
+    },
+];
+
+/// Databases created before the schema version was saved have `user_version` 0.
+/// Finds the last migration such a database already has by checking its tables and columns
+fn get_legacy_version(conn: &Connection) -> i32 {
+    let all_tables = get_all_table_names(conn);
+
+    if !get_all_tx_columns(conn).contains(&"tags".to_string()) {
+        0
+    } else if check_old_balance_sql(conn) {
+        1
+    } else if !all_tables.contains(&"activities".to_string()) {
+        2
+    } else if !all_tables.contains(&"year_range".to_string()) {
+        3
+    } else {
+        4
+    }
+}
+
+/// Returns the schema version of the DB. Errors if the DB is from a newer version of the app
+pub fn get_schema_version(conn: &Connection) -> Result<i32, MigrationError> {
+    let db_version = get_db_version(conn);
+
+    if db_version > DB_VERSION {
+        return Err(MigrationError::NewerVersion(db_version, DB_VERSION));
+    }
+
+    if db_version == 0 {
+        Ok(get_legacy_version(conn))
+    } else {
+        Ok(db_version)
+    }
+}
+
+/// Returns all migrations that the DB does not have yet
+pub fn get_pending_migrations(
+    conn: &Connection,
+) -> Result<Vec<&'static Migration>, MigrationError> {
+    let schema_version = get_schema_version(conn)?;
+
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > schema_version)
+        .collect())
+}
+
+/// Runs every pending migration in order. A backup of the DB at the starting version is taken once,
+/// then each migration runs inside a savepoint and the DB version gets updated.
+/// If a migration fails, only that migration is reversed. Returns the amount of migrations that ran
+pub fn run_migrations(conn: &mut Connection) -> Result<usize, MigrationError> {
+    let pending_migrations = get_pending_migrations(conn)?;
+
+    if pending_migrations.is_empty() {
+        return Ok(0);
+    }
+
+    backup_db(pending_migrations[0].version - 1, conn)?;
+
+    // Tables get rebuilt during migrations. Foreign keys would cascade delete the referencing rows
+    // and without legacy alter table the references would follow the renamed old table.
+    // Both of these pragmas are no-op inside a savepoint so they are set here
+    let to_error = |e| MigrationError::MigrationFailed(pending_migrations[0].version, e);
+
+    let foreign_keys: bool = conn
+        .pragma_query_value(None, "foreign_keys", |row| row.get(0))
+        .map_err(to_error)?;
+
+    set_migration_pragmas(conn, false, true).map_err(to_error)?;
+
+    let mut result = Ok(pending_migrations.len());
+
+    for migration in pending_migrations {
+        if let Err(e) = apply_migration(migration, conn) {
+            result = Err(e);
+            break;
+        }
+    }
+
+    // Failing to reset the pragmas doesn't leave the DB in a bad state, so the result is kept as is
+    let _ = set_migration_pragmas(conn, foreign_keys, false);
+
+    result
+}
+
+fn set_migration_pragmas(
+    conn: &Connection,
+    foreign_keys: bool,
+    legacy_alter_table: bool,
+) -> sqlResult<()> {
+    conn.pragma_update(None, "foreign_keys", foreign_keys)?;
+    conn.pragma_update(None, "legacy_alter_table", legacy_alter_table)
+}
+
+fn apply_migration(migration: &Migration, conn: &mut Connection) -> Result<(), MigrationError> {
+    let to_error = |e| MigrationError::MigrationFailed(migration.version, e);
+
+    let sp = conn.savepoint().map_err(to_error)?;
+
+    (migration.migrate)(&sp).map_err(to_error)?;
+
+    sp.pragma_update(None, "user_version", migration.version)
+        .map_err(to_error)?;
+
+    sp.commit().map_err(to_error)
+}
+
+/// Returns the location where the backup of the given DB at the given version will be saved.
+/// Example: `data.sqlite` at version 4 is saved as `data_backup_v4.sqlite`
+#[must_use]
+pub fn get_migration_backup_path(db_path: &Path, version: i32) -> PathBuf {
+    let file_stem = db_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    db_path.with_file_name(format!("{file_stem}_backup_v{version}.sqlite"))
+}
+
+/// Saves a copy of the DB next to the DB file before the pending migrations run.
+/// Nothing is saved for in memory DBs. Returns the location of the backup
+pub fn backup_db(version: i32, conn: &Connection) -> Result<Option<PathBuf>, MigrationError> {
+    let db_path = match conn.path() {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => return Ok(None),
+    };
+
+    let backup_path = get_migration_backup_path(&db_path, version);
+
+    // VACUUM INTO does not overwrite an existing file
+    if backup_path.exists() {
+        let _ = fs::remove_file(&backup_path);
+    }
+
+    conn.execute(
+        "VACUUM INTO ?1",
+        [backup_path.to_string_lossy().to_string()],
+    )
+    .map_err(MigrationError::BackupFailed)?;
+
+    Ok(Some(backup_path))
+}
@@ -1,5 +1,37 @@
+mod budget;
+mod counterparty;
+mod currency;
+mod export;
+mod goal;
+mod holding;
+mod import;
+mod ledger;
+mod migration;
+mod opening;
+mod query;
+mod reconcile;
+mod recurring;
 mod setup;
+mod splits;
+mod status;
+mod tags;
 mod update;
 
+pub use budget::*;
+pub use counterparty::*;
+pub use currency::*;
+pub use export::*;
+pub use goal::*;
+pub use holding::*;
+pub use import::*;
+pub use ledger::*;
+pub use migration::*;
+pub use opening::*;
+pub use query::*;
+pub use reconcile::*;
+pub use recurring::*;
 pub use setup::*;
+pub use splits::*;
+pub use status::*;
+pub use tags::*;
 pub use update::*;
@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+use rusqlite::{Connection, Result};
+
+use crate::tx_handler::add_tx_in_savepoint;
+use crate::utility::Cent;
+
+/// The tx type of the balance a tx method starts with. It changes the balance like an income
+/// but it is not counted as one
+pub const OPENING_BALANCE_TX_TYPE: &str = "Opening Balance";
+
+/// The balance a tx method starts with along with the date it starts from
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningBalance {
+    pub tx_method: String,
+    pub date: NaiveDate,
+    pub amount: Cent,
+}
+
+/// Adds an Opening Balance tx for each of the given balances in a single savepoint. Balances of 0
+/// are skipped
+pub fn add_opening_balances(
+    opening_balances: &[OpeningBalance],
+    conn: &mut Connection,
+) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    for opening_balance in opening_balances {
+        if opening_balance.amount.is_zero() {
+            continue;
+        }
+
+        add_tx_in_savepoint(
+            &opening_balance.date.to_string(),
+            OPENING_BALANCE_TX_TYPE,
+            &opening_balance.tx_method,
+            &opening_balance.amount.to_string(),
+            OPENING_BALANCE_TX_TYPE,
+            "Unknown",
+            None,
+            &sp,
+        )?;
+    }
+
+    sp.commit()
+}
@@ -0,0 +1,170 @@
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Result};
+
+/// Quotes a table or column name such as a tx method so it can be used inside a query.
+/// Example: `Cash "Main"` becomes `"Cash ""Main"""`
+#[must_use]
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quotes all given column names and joins them with a comma
+#[must_use]
+pub fn quote_columns(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Escapes `%`, `_` and `\` so a value matches itself inside a `LIKE` pattern that uses `ESCAPE '\'`
+#[must_use]
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+enum QueryType {
+    Select(String),
+    Update(String),
+    Insert(String),
+    Delete(String),
+}
+
+/// Builds a query where every value is bound as a parameter and every column name is quoted.
+///
+/// ```ignore
+/// let (query, params) = QueryBuilder::update("balance_all")
+///     .set("Cash", 100)
+///     .where_cond("id_num = ?", [5])
+///     .build();
+/// ```
+pub struct QueryBuilder {
+    query_type: QueryType,
+    columns: Vec<String>,
+    values: Vec<Value>,
+    conditions: Vec<String>,
+    condition_values: Vec<Value>,
+    order_by: Option<String>,
+}
+
+impl QueryBuilder {
+    fn new(query_type: QueryType) -> Self {
+        QueryBuilder {
+            query_type,
+            columns: Vec::new(),
+            values: Vec::new(),
+            conditions: Vec::new(),
+            condition_values: Vec::new(),
+            order_by: None,
+        }
+    }
+
+    /// Starts a select query of the given columns. `columns` are used as is, only the table gets quoted
+    #[must_use]
+    pub fn select(columns: &str, table: &str) -> Self {
+        QueryBuilder::new(QueryType::Select(format!(
+            "SELECT {columns} FROM {}",
+            quote_identifier(table)
+        )))
+    }
+
+    #[must_use]
+    pub fn update(table: &str) -> Self {
+        QueryBuilder::new(QueryType::Update(quote_identifier(table)))
+    }
+
+    #[must_use]
+    pub fn insert(table: &str) -> Self {
+        QueryBuilder::new(QueryType::Insert(quote_identifier(table)))
+    }
+
+    #[must_use]
+    pub fn delete(table: &str) -> Self {
+        QueryBuilder::new(QueryType::Delete(quote_identifier(table)))
+    }
+
+    /// Adds a column with the value to set on update or insert queries
+    #[must_use]
+    pub fn set<T: Into<Value>>(mut self, column: &str, value: T) -> Self {
+        self.columns.push(quote_identifier(column));
+        self.values.push(value.into());
+        self
+    }
+
+    /// Adds a condition that all rows must match. Every `?` inside the condition
+    /// is bound to the given values in order
+    #[must_use]
+    pub fn where_cond<T: Into<Value>>(
+        mut self,
+        condition: &str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect::<Vec<Value>>();
+
+        debug_assert_eq!(
+            condition.matches('?').count(),
+            values.len(),
+            "every ? in the condition needs a value"
+        );
+
+        self.conditions.push(format!("({condition})"));
+        self.condition_values.extend(values);
+        self
+    }
+
+    /// Orders the selected rows. The given value is used as is
+    #[must_use]
+    pub fn order_by(mut self, order: &str) -> Self {
+        self.order_by = Some(order.to_string());
+        self
+    }
+
+    /// Returns the final query and the values to bind to it
+    #[must_use]
+    pub fn build(self) -> (String, Vec<Value>) {
+        let mut query = match &self.query_type {
+            QueryType::Select(select_query) => select_query.to_string(),
+            QueryType::Update(table) => {
+                let set_values = self
+                    .columns
+                    .iter()
+                    .map(|column| format!("{column} = ?"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!("UPDATE {table} SET {set_values}")
+            }
+            QueryType::Insert(table) => format!(
+                "INSERT INTO {table} ({}) VALUES ({})",
+                self.columns.join(", "),
+                vec!["?"; self.columns.len()].join(", ")
+            ),
+            QueryType::Delete(table) => format!("DELETE FROM {table}"),
+        };
+
+        if !self.conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&self.conditions.join(" AND "));
+        }
+
+        if let Some(order) = &self.order_by {
+            query.push_str(" ORDER BY ");
+            query.push_str(order);
+        }
+
+        let mut values = self.values;
+        values.extend(self.condition_values);
+
+        (query, values)
+    }
+
+    /// Builds and runs the query. Returns the amount of rows that got changed
+    pub fn execute(self, conn: &Connection) -> Result<usize> {
+        let (query, values) = self.build();
+        conn.execute(&query, params_from_iter(values))
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, Result};
+
+use crate::db::{get_saved_tx_status, set_tx_status, RECONCILED_TX_STATUS};
+use crate::outputs::ReconcileError;
+use crate::page_handler::ActivityType;
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, get_last_time_balance, get_tx_id_num, get_year_range,
+    is_expense_like, is_income_like, Cent,
+};
+
+/// A tx method being matched against the balance of a bank statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciliation {
+    pub tx_method: String,
+    pub statement_date: NaiveDate,
+    pub statement_balance: Cent,
+}
+
+/// A tx of the reconciled tx method that is not reconciled yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconcileTx {
+    pub id_num: i32,
+    pub date: String,
+    pub details: String,
+    pub tx_type: String,
+    /// How much the tx changed the balance of the reconciled tx method
+    pub change: Cent,
+    pub status: String,
+}
+
+/// Returns how much a tx changed the balance of the given tx method. 0 if it does not touch the method
+pub fn get_method_change(method: &str, tx_method: &str, tx_type: &str, amount: Cent) -> Cent {
+    match tx_type {
+        "Transfer" => {
+            let split_method = tx_method.split(" to ").collect::<Vec<&str>>();
+            let mut change = Cent::default();
+
+            if split_method[0] == method {
+                change -= amount;
+            }
+            if split_method[1] == method {
+                change += amount;
+            }
+            change
+        }
+        _ if tx_method != method => Cent::default(),
+        _ if is_income_like(tx_type) => amount,
+        _ if is_expense_like(tx_type) => -amount,
+        _ => Cent::default(),
+    }
+}
+
+/// Returns the balance of the tx method at the end of the given date. Starts from the `balance_all`
+/// balance of the previous month and adds the txs of the month up to the date
+pub fn get_balance_on(method: &str, date: NaiveDate, conn: &Connection) -> Result<Cent> {
+    let (start_year, _) = get_year_range(conn)?;
+
+    let mut balance = if date.year() < start_year {
+        Cent::default()
+    } else {
+        let year = (date.year() - start_year) as usize;
+        get_last_time_balance(
+            date.month0() as usize,
+            year,
+            &vec![method.to_string()],
+            conn,
+        )[method]
+    };
+
+    let month_start = date.with_day(1).unwrap().to_string();
+
+    let mut statement = conn.prepare(
+        "SELECT tx_method, amount, tx_type FROM tx_all WHERE date BETWEEN date(?) AND date(?)",
+    )?;
+    let mut rows = statement.query([month_start, date.to_string()])?;
+
+    while let Some(row) = rows.next()? {
+        let tx_method: String = row.get(0)?;
+        let amount: Cent = row.get(1)?;
+        let tx_type: String = row.get(2)?;
+
+        balance += get_method_change(method, &tx_method, &tx_type, amount);
+    }
+
+    Ok(balance)
+}
+
+/// Returns every tx of the tx method up to and including the given date that is not reconciled yet
+pub fn get_unreconciled_txs(
+    method: &str,
+    date: NaiveDate,
+    conn: &Connection,
+) -> Result<Vec<ReconcileTx>> {
+    let mut statement = conn.prepare(
+        "SELECT date, details, tx_method, amount, tx_type, id_num, status FROM tx_all
+        WHERE date <= date(?) AND status != ? ORDER BY date, id_num",
+    )?;
+    let mut rows = statement.query([date.to_string(), RECONCILED_TX_STATUS.to_string()])?;
+
+    let mut txs = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let tx_method: String = row.get(2)?;
+        let amount: Cent = row.get(3)?;
+        let tx_type: String = row.get(4)?;
+
+        let touches_method = tx_method == method || tx_method.split(" to ").any(|m| m == method);
+
+        if !touches_method {
+            continue;
+        }
+
+        txs.push(ReconcileTx {
+            id_num: row.get(5)?,
+            date: row.get(0)?,
+            details: row.get(1)?,
+            change: get_method_change(method, &tx_method, &tx_type, amount),
+            tx_type,
+            status: row.get(6)?,
+        });
+    }
+
+    Ok(txs)
+}
+
+/// Returns the balance of the tx method at the statement date counting only the reconciled txs
+pub fn get_reconciled_balance(reconciliation: &Reconciliation, conn: &Connection) -> Result<Cent> {
+    let balance = get_balance_on(
+        &reconciliation.tx_method,
+        reconciliation.statement_date,
+        conn,
+    )?;
+
+    let unreconciled: Cent = get_unreconciled_txs(
+        &reconciliation.tx_method,
+        reconciliation.statement_date,
+        conn,
+    )?
+    .iter()
+    .map(|tx| tx.change)
+    .sum();
+
+    Ok(balance - unreconciled)
+}
+
+/// Marks the given txs as reconciled if they bring the reconciled balance to the statement balance.
+/// The reconciliation is saved as an activity with each tx after and before the change
+pub fn reconcile_txs(
+    reconciliation: &Reconciliation,
+    id_nums: &[i32],
+    conn: &mut Connection,
+) -> Result<(), ReconcileError> {
+    let unreconciled = get_unreconciled_txs(
+        &reconciliation.tx_method,
+        reconciliation.statement_date,
+        conn,
+    )
+    .map_err(ReconcileError::UpdateFailed)?;
+
+    let ticked_total: Cent = unreconciled
+        .iter()
+        .filter(|tx| id_nums.contains(&tx.id_num))
+        .map(|tx| tx.change)
+        .sum();
+
+    let reconciled_balance =
+        get_reconciled_balance(reconciliation, conn).map_err(ReconcileError::UpdateFailed)?;
+
+    let difference = reconciliation.statement_balance - reconciled_balance - ticked_total;
+
+    if !difference.is_zero() {
+        return Err(ReconcileError::NotBalanced(difference.to_string()));
+    }
+
+    let sp = conn.savepoint().map_err(ReconcileError::UpdateFailed)?;
+
+    let mut changed_txs = Vec::new();
+
+    for tx in unreconciled
+        .iter()
+        .filter(|tx| id_nums.contains(&tx.id_num))
+    {
+        let old_tx = get_tx_id_num(tx.id_num, &sp);
+
+        set_tx_status(tx.id_num, RECONCILED_TX_STATUS, &sp)
+            .map_err(ReconcileError::UpdateFailed)?;
+
+        let mut new_tx = old_tx.clone();
+        new_tx[8] = RECONCILED_TX_STATUS.to_string();
+        changed_txs.push((new_tx, old_tx));
+    }
+
+    let activity_num = add_new_activity(
+        ActivityType::Reconcile(
+            Some(reconciliation.tx_method.clone()),
+            Some(reconciliation.statement_date.to_string()),
+            Some(reconciliation.statement_balance.to_string()),
+        ),
+        &sp,
+    );
+
+    for (new_tx, old_tx) in &changed_txs {
+        add_new_activity_tx(new_tx, activity_num, &sp);
+        add_new_activity_tx(old_tx, activity_num, &sp);
+    }
+
+    sp.commit().map_err(ReconcileError::UpdateFailed)
+}
+
+/// Returns an error if the tx is reconciled. Reconciled txs can only be changed by undoing the reconciliation
+pub fn check_tx_editable(id_num: i32, conn: &Connection) -> Result<(), ReconcileError> {
+    let status = get_saved_tx_status(id_num, conn).map_err(ReconcileError::UpdateFailed)?;
+
+    if status == RECONCILED_TX_STATUS {
+        return Err(ReconcileError::Locked(id_num));
+    }
+    Ok(())
+}
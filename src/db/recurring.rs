@@ -0,0 +1,411 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+use rusqlite::{Connection, OptionalExtension, Result, Savepoint};
+
+use crate::db::MAX_YEAR;
+use crate::utility::Cent;
+
+/// The unit a recurring tx repeats in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecurringUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RecurringUnit {
+    #[must_use]
+    pub fn from_s(unit: &str) -> Option<Self> {
+        match unit.trim().to_lowercase().as_str() {
+            "day" | "days" | "daily" => Some(RecurringUnit::Day),
+            "week" | "weeks" | "weekly" => Some(RecurringUnit::Week),
+            "month" | "months" | "monthly" => Some(RecurringUnit::Month),
+            "year" | "years" | "yearly" => Some(RecurringUnit::Year),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            RecurringUnit::Day => "day",
+            RecurringUnit::Week => "week",
+            RecurringUnit::Month => "month",
+            RecurringUnit::Year => "year",
+        }
+    }
+
+    fn every_one(&self) -> &'static str {
+        match self {
+            RecurringUnit::Day => "daily",
+            RecurringUnit::Week => "weekly",
+            RecurringUnit::Month => "monthly",
+            RecurringUnit::Year => "yearly",
+        }
+    }
+}
+
+/// When a recurring tx is due. Monthly schedules can be set to a day of the month,
+/// otherwise every occurrence is counted from the start date
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecurringSchedule {
+    pub unit: RecurringUnit,
+    pub interval: u32,
+    pub day_of_month: Option<u32>,
+}
+
+impl RecurringSchedule {
+    /// Parses schedules such as `monthly`, `every 2 weeks` or `every 3 months on day 15`
+    #[must_use]
+    pub fn parse(schedule: &str) -> Option<Self> {
+        let lowered = schedule.trim().to_lowercase();
+
+        let (repeat, day_of_month) = match lowered.split_once(" on day ") {
+            Some((repeat, day)) => {
+                let day = day.trim().parse::<u32>().ok()?;
+                if !(1..=31).contains(&day) {
+                    return None;
+                }
+                (repeat.trim(), Some(day))
+            }
+            None => (lowered.as_str(), None),
+        };
+
+        let words = repeat.split_whitespace().collect::<Vec<&str>>();
+
+        let (unit, interval) = match words.as_slice() {
+            [unit] if unit.ends_with("ly") => (RecurringUnit::from_s(unit)?, 1),
+            ["every", unit] => (RecurringUnit::from_s(unit)?, 1),
+            ["every", interval, unit] => (RecurringUnit::from_s(unit)?, interval.parse().ok()?),
+            _ => return None,
+        };
+
+        if interval == 0 || (day_of_month.is_some() && unit != RecurringUnit::Month) {
+            return None;
+        }
+
+        Some(RecurringSchedule {
+            unit,
+            interval,
+            day_of_month,
+        })
+    }
+
+    /// Returns the date of the occurrence at the given position counting from the start date.
+    /// Days that do not exist in a month are moved to the last day of that month
+    fn get_occurrence(&self, start_date: NaiveDate, position: u32) -> Option<NaiveDate> {
+        let steps = position.checked_mul(self.interval)?;
+
+        match self.unit {
+            RecurringUnit::Day => start_date.checked_add_days(Days::new(u64::from(steps))),
+            RecurringUnit::Week => start_date.checked_add_days(Days::new(u64::from(steps) * 7)),
+            RecurringUnit::Month => {
+                let month_start = start_date
+                    .with_day(1)?
+                    .checked_add_months(Months::new(steps))?;
+                let day = self.day_of_month.unwrap_or(start_date.day());
+                Some(clamp_to_month(month_start, day))
+            }
+            RecurringUnit::Year => {
+                let month_start = start_date
+                    .with_day(1)?
+                    .checked_add_months(Months::new(steps.checked_mul(12)?))?;
+                Some(clamp_to_month(month_start, start_date.day()))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RecurringSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.interval == 1 {
+            write!(f, "{}", self.unit.every_one())?;
+        } else {
+            write!(f, "every {} {}s", self.interval, self.unit.to_str())?;
+        }
+
+        if let Some(day) = self.day_of_month {
+            write!(f, " on day {day}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the given day of the month of `month_start` or the last day of the month if it's shorter
+fn clamp_to_month(month_start: NaiveDate, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|day| month_start.with_day(day))
+        .unwrap_or(month_start)
+}
+
+/// A tx template that gets posted as a new tx every time it's due
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringTx {
+    pub recurring_id: i32,
+    pub details: String,
+    pub tx_method: String,
+    pub amount: Cent,
+    pub tx_type: String,
+    pub tags: String,
+    pub schedule: RecurringSchedule,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub last_posted: Option<NaiveDate>,
+    pub paused: bool,
+}
+
+impl RecurringTx {
+    /// Returns every date the tx is due at that is after the last posted date, up to and including `today`.
+    /// Nothing is due while the tx is paused
+    #[must_use]
+    pub fn get_due_dates(&self, today: NaiveDate) -> Vec<NaiveDate> {
+        let mut due_dates = Vec::new();
+
+        if self.paused {
+            return due_dates;
+        }
+
+        for date in self.occurrences() {
+            if date > today {
+                break;
+            }
+            due_dates.push(date);
+        }
+
+        due_dates
+    }
+
+    /// The next date this tx will be posted at
+    #[must_use]
+    pub fn get_next_date(&self) -> Option<NaiveDate> {
+        self.occurrences().next()
+    }
+
+    /// All the occurrences that have not been posted yet within the start and the end date
+    fn occurrences(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        (0..)
+            .map_while(|position| self.schedule.get_occurrence(self.start_date, position))
+            .take_while(|date| {
+                date.year() <= i32::from(MAX_YEAR) && self.end_date.is_none_or(|end| *date <= end)
+            })
+            .filter(|date| {
+                *date >= self.start_date && self.last_posted.is_none_or(|last| *date > last)
+            })
+    }
+
+    /// Returns the data to show on the Recurring page
+    #[must_use]
+    pub fn to_vec(&self, total_posted: usize) -> Vec<String> {
+        let next_date = match self.get_next_date() {
+            Some(date) => date.to_string(),
+            None => String::from("Ended"),
+        };
+
+        let status = if self.paused { "Paused" } else { "Active" };
+
+        vec![
+            self.details.clone(),
+            self.tx_method.clone(),
+            self.amount.to_string(),
+            self.tx_type.clone(),
+            self.tags.clone(),
+            self.schedule.to_string(),
+            next_date,
+            total_posted.to_string(),
+            status.to_string(),
+        ]
+    }
+}
+
+/// Saves a new recurring tx. Returns the id it was saved with
+pub fn add_recurring_tx(recurring_tx: &RecurringTx, conn: &mut Connection) -> Result<i32> {
+    let sp = conn.savepoint()?;
+
+    sp.execute(
+        "INSERT INTO recurring_txs (details, tx_method, amount, tx_type, tags, schedule, start_date, end_date, last_posted, paused)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        (
+            &recurring_tx.details,
+            &recurring_tx.tx_method,
+            recurring_tx.amount,
+            &recurring_tx.tx_type,
+            &recurring_tx.tags,
+            recurring_tx.schedule.to_string(),
+            recurring_tx.start_date.to_string(),
+            recurring_tx.end_date.map(|date| date.to_string()),
+            recurring_tx.last_posted.map(|date| date.to_string()),
+            recurring_tx.paused,
+        ),
+    )?;
+
+    let recurring_id = sp.last_insert_rowid() as i32;
+
+    sp.commit()?;
+    Ok(recurring_id)
+}
+
+/// Replaces the saved data of a recurring tx. The txs that it posted earlier are not changed
+pub fn update_recurring_tx(recurring_tx: &RecurringTx, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    let updated = sp.execute(
+        "UPDATE recurring_txs SET details = ?1, tx_method = ?2, amount = ?3, tx_type = ?4, tags = ?5,
+        schedule = ?6, start_date = ?7, end_date = ?8, last_posted = ?9, paused = ?10 WHERE recurring_id = ?11",
+        (
+            &recurring_tx.details,
+            &recurring_tx.tx_method,
+            recurring_tx.amount,
+            &recurring_tx.tx_type,
+            &recurring_tx.tags,
+            recurring_tx.schedule.to_string(),
+            recurring_tx.start_date.to_string(),
+            recurring_tx.end_date.map(|date| date.to_string()),
+            recurring_tx.last_posted.map(|date| date.to_string()),
+            recurring_tx.paused,
+            recurring_tx.recurring_id,
+        ),
+    )?;
+
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    sp.commit()
+}
+
+/// Pauses or resumes a recurring tx. Occurrences that were due while it was paused are skipped
+/// so resuming does not post them all at once
+pub fn set_recurring_paused(
+    recurring_id: i32,
+    paused: bool,
+    today: NaiveDate,
+    conn: &mut Connection,
+) -> Result<()> {
+    let sp = conn.savepoint()?;
+
+    sp.execute(
+        "UPDATE recurring_txs SET paused = ?1 WHERE recurring_id = ?2",
+        (paused, recurring_id),
+    )?;
+
+    if !paused {
+        let yesterday = today.pred_opt().unwrap_or(today).to_string();
+        sp.execute(
+            "UPDATE recurring_txs SET last_posted = ?1 WHERE recurring_id = ?2 AND start_date <= ?1
+            AND (last_posted IS NULL OR last_posted < ?1)",
+            (&yesterday, recurring_id),
+        )?;
+    }
+
+    sp.commit()
+}
+
+/// Deletes a recurring tx. The txs that it posted earlier are kept
+pub fn delete_recurring_tx(recurring_id: i32, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    sp.execute(
+        "DELETE FROM recurring_txs WHERE recurring_id = ?1",
+        [recurring_id],
+    )?;
+    sp.commit()
+}
+
+/// Links a tx to the recurring tx that posted it and moves the last posted date of the recurring tx
+pub fn link_recurring_tx(
+    recurring_id: i32,
+    id_num: i32,
+    date: NaiveDate,
+    sp: &Savepoint,
+) -> Result<()> {
+    sp.execute(
+        "INSERT INTO recurring_tx_links (recurring_id, id_num, date) VALUES (?1, ?2, ?3)",
+        (recurring_id, id_num, date.to_string()),
+    )?;
+
+    sp.execute(
+        "UPDATE recurring_txs SET last_posted = ?1 WHERE recurring_id = ?2",
+        (date.to_string(), recurring_id),
+    )?;
+
+    Ok(())
+}
+
+/// Returns the `id_num` of every tx the recurring tx has posted that still exists
+pub fn get_recurring_tx_ids(recurring_id: i32, conn: &Connection) -> Vec<i32> {
+    let mut statement = conn
+        .prepare(
+            "SELECT recurring_tx_links.id_num FROM recurring_tx_links
+            INNER JOIN tx_all ON tx_all.id_num = recurring_tx_links.id_num
+            WHERE recurring_id = ? ORDER BY recurring_tx_links.date, recurring_tx_links.id_num",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([recurring_id], |row| row.get(0))
+        .expect("could not get recurring tx links")
+        .flatten()
+        .collect()
+}
+
+/// Returns the recurring tx that posted the given tx if any
+pub fn get_tx_recurring_id(id_num: i32, conn: &Connection) -> Option<i32> {
+    conn.query_row(
+        "SELECT recurring_id FROM recurring_tx_links WHERE id_num = ?",
+        [id_num],
+        |row| row.get(0),
+    )
+    .optional()
+    .expect("could not get recurring tx link")
+}
+
+/// Returns the number of recurring txs that use the tx method, including transfers from or to it
+pub fn get_recurring_tx_method_usage(method: &str, conn: &Connection) -> Result<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM recurring_txs WHERE tx_method = ?1
+        OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+        OR substr(tx_method, -(length(?1) + 4)) = ' to ' || ?1",
+        [method],
+        |row| row.get(0),
+    )
+}
+
+/// Dates of recurring txs are saved as YYYY-MM-DD
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").expect("invalid saved date")
+}
+
+/// Returns every saved recurring tx in the order they were added
+pub fn get_all_recurring_txs(conn: &Connection) -> Vec<RecurringTx> {
+    let mut statement = conn
+        .prepare(
+            "SELECT recurring_id, details, tx_method, amount, tx_type, tags, schedule, start_date,
+            end_date, last_posted, paused FROM recurring_txs ORDER BY recurring_id",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| {
+            let schedule: String = row.get(6)?;
+            let end_date: Option<String> = row.get(8)?;
+            let last_posted: Option<String> = row.get(9)?;
+
+            Ok(RecurringTx {
+                recurring_id: row.get(0)?,
+                details: row.get(1)?,
+                tx_method: row.get(2)?,
+                amount: row.get(3)?,
+                tx_type: row.get(4)?,
+                tags: row.get(5)?,
+                schedule: RecurringSchedule::parse(&schedule).expect("invalid saved schedule"),
+                start_date: parse_date(&row.get::<_, String>(7)?),
+                end_date: end_date.as_deref().map(parse_date),
+                last_posted: last_posted.as_deref().map(parse_date),
+                paused: row.get(10)?,
+            })
+        })
+        .expect("could not get recurring txs")
+        .flatten()
+        .collect()
+}
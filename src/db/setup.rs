@@ -1,5 +1,12 @@
+use chrono::{Datelike, Local};
 use rusqlite::{Connection, Result, Savepoint};
 
+use crate::db::{
+    add_activity_tag_moves, add_archived_column, add_budgets, add_counterparties, add_goals,
+    add_holdings, add_import_profiles, add_recurring_txs, add_tx_method_kind, add_tx_splits,
+    add_tx_status, quote_columns, quote_identifier,
+};
+
 pub const MONTHS: [&str; 12] = [
     "January",
     "February",
@@ -15,34 +22,38 @@ pub const MONTHS: [&str; 12] = [
     "December",
 ];
 
-pub const YEARS: [&str; 16] = [
-    "2022", "2023", "2024", "2025", "2026", "2027", "2028", "2029", "2030", "2031", "2032", "2033",
-    "2034", "2035", "2036", "2037",
-];
+/// The earliest year a transaction can be dated at
+pub const MIN_YEAR: u16 = 1900;
+
+/// The latest year a transaction can be dated at
+pub const MAX_YEAR: u16 = 2199;
 
 pub const MODES: [&str; 3] = ["Monthly", "Yearly", "All Time"];
 
+/// The schema version of the DB that gets saved in `PRAGMA user_version`.
+/// Must match the version of the last entry in `MIGRATIONS`
+pub const DB_VERSION: i32 = 18;
+
+/// The currency every tx method and the base currency start with
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+/// Every kind a tx method can be
+pub const TX_METHOD_KINDS: [&str; 2] = ["Asset", "Liability"];
+
+/// The kind every tx method starts with
+pub const DEFAULT_TX_METHOD_KIND: &str = "Asset";
+
+/// Tx methods of this kind hold money that is owed, like a credit card or a loan.
+/// Their balance goes negative as the debt grows
+pub const LIABILITY_TX_METHOD_KIND: &str = "Liability";
+
 /// Creates the db that is used by this app
 pub fn create_db(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
     // add a save point to reverse commits if failed
     let sp = conn.savepoint()?;
 
     // tx_all table. Will contain all tx data
-    // Amount should have been REAL, old mistake, can't be bothered to migrate to REAL at this point
-    // Same for date column
-    // Also not too frequently searched by amount field anyway. Only when searching txs. Not a priority
-    sp.execute(
-        "CREATE TABLE tx_all (
-        date TEXT,
-        details TEXT,
-        tx_method TEXT,
-        amount TEXT,
-        tx_type TEXT,
-        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
-        tags TEXT
-    );",
-        [],
-    )?;
+    create_tx_table(&sp)?;
 
     create_balances_table(tx_methods, &sp)?;
 
@@ -54,7 +65,39 @@ pub fn create_db(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
 
     create_missing_indexes(&sp)?;
 
-    sp.execute("CREATE UNIQUE INDEX all_tx_id_IDX ON tx_all (id_num);", [])?;
+    create_tx_methods_table(tx_methods, &sp)?;
+
+    add_archived_column(&sp)?;
+
+    create_exchange_rates_table(&sp)?;
+
+    create_settings_table(&sp)?;
+
+    create_tags_tables(&sp)?;
+
+    add_tx_splits(&sp)?;
+
+    add_recurring_txs(&sp)?;
+
+    add_budgets(&sp)?;
+
+    add_goals(&sp)?;
+
+    add_tx_status(&sp)?;
+
+    add_tx_method_kind(&sp)?;
+
+    add_counterparties(&sp)?;
+
+    add_holdings(&sp)?;
+
+    add_import_profiles(&sp)?;
+
+    add_activity_tag_moves(&sp)?;
+
+    // the ledger starts with the current year only. More years get added as txs are added outside the range
+    let current_year = Local::now().year();
+    create_year_range_table(current_year, current_year, &sp)?;
 
     sp.execute(
         "CREATE UNIQUE INDEX changes_all_id_IDX ON changes_all (id_num);",
@@ -65,14 +108,10 @@ pub fn create_db(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
         [],
     )?;
 
-    // fill up balance_all table with total year * 12 + 1 rows with 0 balance
-    let zero_values = vec!["0.00"; tx_methods.len()];
+    // fill up balance_all table with 12 rows for the current year + 1 row for the final balance with 0 balance
+    let zero_values = vec!["0"; tx_methods.len()];
 
-    let highlighted_tx_methods = tx_methods
-        .iter()
-        .map(|method| format!("\"{method}\""))
-        .collect::<Vec<String>>()
-        .join(",");
+    let highlighted_tx_methods = quote_columns(tx_methods);
 
     let query = format!(
         "INSERT INTO balance_all ({}) VALUES ({})",
@@ -80,24 +119,44 @@ pub fn create_db(tx_methods: &[String], conn: &mut Connection) -> Result<()> {
         zero_values.join(",")
     );
 
-    for _i in YEARS {
-        for _a in 0..MONTHS.len() {
-            sp.execute(&query, [])?;
-        }
+    for _a in 0..=MONTHS.len() {
+        sp.execute(&query, [])?;
     }
 
-    sp.execute(&query, [])?;
+    sp.pragma_update(None, "user_version", DB_VERSION)?;
+
     sp.commit()?;
     Ok(())
 }
 
+/// creates the `tx_all` table of the DB along with its indexes. Amount is saved in cents
+pub fn create_tx_table(sp: &Savepoint) -> Result<()> {
+    // Date should have been a DATE, old mistake, can't be bothered to migrate at this point
+    sp.execute(
+        "CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount INTEGER,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tags TEXT
+    );",
+        [],
+    )?;
+
+    sp.execute("CREATE UNIQUE INDEX all_tx_id_IDX ON tx_all (id_num);", [])?;
+
+    Ok(())
+}
+
 /// creates the `balance_all` table of the DB
 pub fn create_balances_table(tx_methods: &[String], sp: &Savepoint) -> Result<()> {
-    // balance_all table. Will contain tx methods as columns and their balances.
+    // balance_all table. Will contain tx methods as columns and their balances in cents.
     // each row represents 1 month.
     let tx_methods_str = tx_methods
         .iter()
-        .map(|method| format!(r#""{method}" REAL DEFAULT 0.00"#))
+        .map(|method| format!("{} INTEGER DEFAULT 0", quote_identifier(method)))
         .collect::<Vec<String>>()
         .join(",");
 
@@ -118,7 +177,7 @@ pub fn create_changes_table(tx_methods: &[String], sp: &Savepoint) -> Result<()>
     // changes_all column. Will contain all balance changes with up and down arrows
     let columns = tx_methods
         .iter()
-        .map(|column_name| format!(r#""{column_name}" TEXT DEFAULT 0.00"#))
+        .map(|column_name| format!("{} TEXT DEFAULT 0.00", quote_identifier(column_name)))
         .collect::<Vec<String>>()
         .join(",");
 
@@ -136,6 +195,25 @@ pub fn create_changes_table(tx_methods: &[String], sp: &Savepoint) -> Result<()>
     Ok(())
 }
 
+/// creates the `year_range` table of the DB. Contains a single row with the first and the last year
+/// that `balance_all` has rows for
+pub fn create_year_range_table(start_year: i32, end_year: i32, sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE year_range (
+        start_year INTEGER NOT NULL,
+        end_year INTEGER NOT NULL
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "INSERT INTO year_range (start_year, end_year) VALUES (?1, ?2)",
+        [start_year, end_year],
+    )?;
+
+    Ok(())
+}
+
 pub fn create_activities_table(sp: &Savepoint) -> Result<()> {
     sp.execute(
         "CREATE TABLE activities (
@@ -196,3 +274,274 @@ pub fn create_missing_indexes(sp: &Savepoint) -> Result<()> {
 
     Ok(())
 }
+
+/// creates the `tx_methods` table that keeps the currency of each tx method.
+/// All given methods start with the default currency
+pub fn create_tx_methods_table(tx_methods: &[String], sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_methods (
+        name TEXT NOT NULL PRIMARY KEY,
+        currency TEXT NOT NULL
+    );",
+        [],
+    )?;
+
+    for method in tx_methods {
+        sp.execute(
+            "INSERT INTO tx_methods (name, currency) VALUES (?1, ?2)",
+            [method, DEFAULT_CURRENCY],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// creates the `exchange_rates` table. A row means 1 `from_currency` = `rate` `to_currency`
+pub fn create_exchange_rates_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE exchange_rates (
+        from_currency TEXT NOT NULL,
+        to_currency TEXT NOT NULL,
+        rate REAL NOT NULL,
+        PRIMARY KEY (from_currency, to_currency)
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `tags` table with every unique tag and the `tx_tags` table that links txs to their tags
+pub fn create_tags_tables(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tags (
+        tag_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE TABLE tx_tags (
+        id_num INTEGER NOT NULL,
+        tag_id INTEGER NOT NULL,
+        PRIMARY KEY (id_num, tag_id),
+        CONSTRAINT tx_tags_tx_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE,
+        CONSTRAINT tx_tags_tag_FK FOREIGN KEY (tag_id) REFERENCES tags(tag_id) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+
+    sp.execute("CREATE INDEX tx_tags_tag_id_idx ON tx_tags(tag_id);", [])?;
+
+    Ok(())
+}
+
+/// creates the `tx_splits` table that contains the split lines of txs that are split across multiple tags
+pub fn create_tx_splits_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_splits (
+        split_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        id_num INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        note TEXT NOT NULL DEFAULT '',
+        CONSTRAINT tx_splits_tx_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX tx_splits_id_num_idx ON tx_splits(id_num);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `recurring_txs` table with the recurring tx templates and the `recurring_tx_links` table
+/// that links the posted txs to the template that posted them
+pub fn create_recurring_tables(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE recurring_txs (
+        recurring_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        details TEXT NOT NULL,
+        tx_method TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        tx_type TEXT NOT NULL,
+        tags TEXT NOT NULL,
+        schedule TEXT NOT NULL,
+        start_date TEXT NOT NULL,
+        end_date TEXT,
+        last_posted TEXT,
+        paused INTEGER NOT NULL DEFAULT 0
+    );",
+        [],
+    )?;
+
+    // The link is kept when the tx gets deleted so restoring the tx with the same id_num links it back
+    sp.execute(
+        "CREATE TABLE recurring_tx_links (
+        recurring_id INTEGER NOT NULL,
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        date TEXT NOT NULL,
+        CONSTRAINT recurring_tx_links_FK FOREIGN KEY (recurring_id) REFERENCES recurring_txs(recurring_id) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX recurring_tx_links_recurring_id_idx ON recurring_tx_links(recurring_id);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `budgets` table. Each tag can have one budget per period
+pub fn create_budgets_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE budgets (
+        budget_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tag TEXT NOT NULL,
+        period TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        rollover INTEGER NOT NULL DEFAULT 0,
+        start_date TEXT NOT NULL,
+        CONSTRAINT budgets_tag_period_UN UNIQUE (tag, period)
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `goals` table. A goal tracks either a tx method or a tag
+pub fn create_goals_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE goals (
+        goal_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        target INTEGER NOT NULL,
+        target_date TEXT NOT NULL,
+        tx_method TEXT,
+        tag TEXT,
+        CONSTRAINT goals_source_CK CHECK ((tx_method IS NULL) != (tag IS NULL))
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `tx_counterparties` table that links debt txs to the person they are with.
+/// The link is kept when the tx gets deleted so restoring the tx with the same id_num links it back
+pub fn create_counterparties_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE tx_counterparties (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        counterparty TEXT NOT NULL
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE INDEX tx_counterparties_counterparty_idx ON tx_counterparties(counterparty);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `holdings` table with the investments of each tx method and the `prices` table
+/// with the price history of each symbol
+pub fn create_holdings_tables(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE holdings (
+        holding_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tx_method TEXT NOT NULL,
+        symbol TEXT NOT NULL,
+        quantity REAL NOT NULL,
+        cost_basis INTEGER NOT NULL,
+        CONSTRAINT holdings_method_symbol_UN UNIQUE (tx_method, symbol)
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "CREATE TABLE prices (
+        symbol TEXT NOT NULL,
+        date TEXT NOT NULL,
+        price INTEGER NOT NULL,
+        CONSTRAINT prices_PK PRIMARY KEY (symbol, date)
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `import_profiles` table with the column mapping of each bank statement.
+/// A statement has either a single amount column or separate debit and credit columns
+pub fn create_import_profiles_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE import_profiles (
+        name TEXT NOT NULL PRIMARY KEY,
+        delimiter TEXT NOT NULL,
+        has_header INTEGER NOT NULL,
+        date_column INTEGER NOT NULL,
+        date_format TEXT NOT NULL,
+        details_column INTEGER NOT NULL,
+        amount_column INTEGER,
+        debit_column INTEGER,
+        credit_column INTEGER,
+        decimal_separator TEXT NOT NULL,
+        tx_method TEXT,
+        tx_method_column INTEGER,
+        CONSTRAINT import_profiles_amount_CK CHECK (
+            (amount_column IS NULL) = (debit_column IS NOT NULL AND credit_column IS NOT NULL)
+        ),
+        CONSTRAINT import_profiles_method_CK CHECK ((tx_method IS NULL) != (tx_method_column IS NULL))
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `activity_tag_moves` table with the budgets and goals moved to another tag by a tag
+/// activity so undo and redo can move them back
+pub fn create_activity_tag_moves_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE activity_tag_moves (
+        activity_num INTEGER NOT NULL,
+        moved_table TEXT NOT NULL,
+        row_id INTEGER NOT NULL,
+        old_tag TEXT NOT NULL,
+        new_tag TEXT NOT NULL,
+        insertion_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        CONSTRAINT activity_tag_moves_FK FOREIGN KEY (activity_num) REFERENCES activities(activity_num) ON DELETE CASCADE
+    );",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// creates the `settings` table with the default base currency
+pub fn create_settings_table(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "CREATE TABLE settings (
+        name TEXT NOT NULL PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+        [],
+    )?;
+
+    sp.execute(
+        "INSERT INTO settings (name, value) VALUES ('base_currency', ?1)",
+        [DEFAULT_CURRENCY],
+    )?;
+
+    Ok(())
+}
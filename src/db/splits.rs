@@ -0,0 +1,151 @@
+use rusqlite::{Connection, Result, Savepoint};
+use std::collections::HashMap;
+
+use crate::utility::Cent;
+
+/// Separates the tag, the amount and the note of a split line. Example: `Food | 30.00 | Milk`
+pub const SPLIT_FIELD_SEPARATOR: char = '|';
+
+/// Separates the split lines of a tx. Example: `Food | 30.00; Home | 20.00 | Soap`
+pub const SPLIT_LINE_SEPARATOR: char = ';';
+
+/// A part of the tx amount that belongs to a single tag
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxSplit {
+    pub tag: String,
+    pub amount: Cent,
+    pub note: String,
+}
+
+/// Parses split lines written as `Tag | Amount | Note` separated by `;`. The note is optional.
+/// Returns `None` if any of the lines is not valid
+#[must_use]
+pub fn parse_splits(splits: &str) -> Option<Vec<TxSplit>> {
+    let mut all_splits = Vec::new();
+
+    for line in splits.split(SPLIT_LINE_SEPARATOR).map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields = line
+            .splitn(3, SPLIT_FIELD_SEPARATOR)
+            .map(str::trim)
+            .collect::<Vec<&str>>();
+
+        if fields.len() < 2 || fields[0].is_empty() || fields[0].contains(',') {
+            return None;
+        }
+
+        all_splits.push(TxSplit {
+            tag: fields[0].to_string(),
+            amount: fields[1].parse().ok()?,
+            note: fields.get(2).unwrap_or(&"").to_string(),
+        });
+    }
+
+    Some(all_splits)
+}
+
+/// Turns the splits into the text form that `parse_splits` accepts
+#[must_use]
+pub fn splits_to_text(splits: &[TxSplit]) -> String {
+    splits
+        .iter()
+        .map(|split| {
+            if split.note.is_empty() {
+                format!("{} {SPLIT_FIELD_SEPARATOR} {}", split.tag, split.amount)
+            } else {
+                format!(
+                    "{} {SPLIT_FIELD_SEPARATOR} {} {SPLIT_FIELD_SEPARATOR} {}",
+                    split.tag, split.amount, split.note
+                )
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(&format!("{SPLIT_LINE_SEPARATOR} "))
+}
+
+/// Returns the unique tags of the splits as comma separated tags
+#[must_use]
+pub fn get_split_tags(splits: &[TxSplit]) -> String {
+    let mut tags: Vec<&str> = Vec::new();
+
+    for split in splits {
+        if !tags.contains(&split.tag.as_str()) {
+            tags.push(&split.tag);
+        }
+    }
+
+    tags.join(", ")
+}
+
+/// Replaces all split lines of the tx with the given ones
+pub fn set_tx_splits(id_num: i32, splits: &[TxSplit], sp: &Savepoint) -> Result<()> {
+    sp.execute("DELETE FROM tx_splits WHERE id_num = ?1", [id_num])?;
+
+    for split in splits {
+        sp.execute(
+            "INSERT INTO tx_splits (id_num, tag, amount, note) VALUES (?1, ?2, ?3, ?4)",
+            (id_num, &split.tag, split.amount, &split.note),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Same as `set_tx_splits` but the splits are given in their text form
+pub fn save_tx_splits(id_num: i32, splits: &str, conn: &mut Connection) -> Result<()> {
+    let splits = parse_splits(splits).unwrap_or_default();
+
+    let sp = conn.savepoint()?;
+    set_tx_splits(id_num, &splits, &sp)?;
+    sp.commit()
+}
+
+/// Returns the split lines of the tx in the order they were added
+pub fn get_tx_splits(id_num: i32, conn: &Connection) -> Vec<TxSplit> {
+    let mut statement = conn
+        .prepare("SELECT tag, amount, note FROM tx_splits WHERE id_num = ? ORDER BY split_id")
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([id_num], |row| {
+            Ok(TxSplit {
+                tag: row.get(0)?,
+                amount: row.get(1)?,
+                note: row.get(2)?,
+            })
+        })
+        .expect("could not get tx splits")
+        .flatten()
+        .collect()
+}
+
+/// Returns the split lines of every tx that has any, with the `id_num` of the tx as the key
+pub fn get_all_tx_splits(conn: &Connection) -> HashMap<i32, Vec<TxSplit>> {
+    let mut statement = conn
+        .prepare("SELECT id_num, tag, amount, note FROM tx_splits ORDER BY split_id")
+        .expect("could not prepare statement");
+
+    let mut tx_splits: HashMap<i32, Vec<TxSplit>> = HashMap::new();
+
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                TxSplit {
+                    tag: row.get(1)?,
+                    amount: row.get(2)?,
+                    note: row.get(3)?,
+                },
+            ))
+        })
+        .expect("could not get tx splits");
+
+    for (id_num, split) in rows.flatten() {
+        tx_splits.entry(id_num).or_default().push(split);
+    }
+
+    tx_splits
+}
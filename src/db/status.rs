@@ -0,0 +1,94 @@
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Result};
+use std::collections::HashMap;
+
+use crate::db::{get_method_change, QueryBuilder};
+use crate::utility::Cent;
+
+/// Every status a tx can have, in the order they are stepped through on the Search page
+pub const TX_STATUSES: [&str; 3] = ["Pending", "Cleared", "Reconciled"];
+
+/// The statuses a tx can be given from the Add TX page. Reconciled is only set by `reconcile_txs`
+pub const SETTABLE_TX_STATUSES: [&str; 2] = ["Pending", "Cleared"];
+
+/// The status of txs that were added without one. Txs saved before statuses existed also get this one
+pub const DEFAULT_TX_STATUS: &str = "Cleared";
+
+/// Txs with this status have not shown up on the bank statement yet
+pub const PENDING_TX_STATUS: &str = "Pending";
+
+/// Txs with this status were matched against a bank statement and can not be edited
+pub const RECONCILED_TX_STATUS: &str = "Reconciled";
+
+/// Saves the status of a tx. An empty status is saved as the default one
+pub fn set_tx_status(id_num: i32, status: &str, conn: &Connection) -> Result<()> {
+    let status = if status.is_empty() {
+        DEFAULT_TX_STATUS
+    } else {
+        status
+    };
+
+    conn.execute(
+        "UPDATE tx_all SET status = ?1 WHERE id_num = ?2",
+        (status, id_num),
+    )?;
+    Ok(())
+}
+
+/// Returns the saved status of a tx
+pub fn get_saved_tx_status(id_num: i32, conn: &Connection) -> Result<String> {
+    conn.query_row(
+        "SELECT status FROM tx_all WHERE id_num = ?1",
+        [id_num],
+        |row| row.get(0),
+    )
+}
+
+/// Returns how much the pending txs changed the balance of each tx method. Only the txs up to
+/// and including the given date and `id_num` are counted. All pending txs are counted if none is given.
+/// Subtracting these from a balance gives the cleared balance
+pub fn get_pending_changes(
+    until: Option<(&str, i32)>,
+    conn: &Connection,
+) -> Result<HashMap<String, Cent>> {
+    let mut query = QueryBuilder::select("tx_method, amount, tx_type", "tx_all")
+        .where_cond("status = ?", [PENDING_TX_STATUS.to_string()]);
+
+    if let Some((date, id_num)) = until {
+        query = query.where_cond(
+            "date < ? OR (date = ? AND id_num <= ?)",
+            [
+                Value::from(date.to_string()),
+                Value::from(date.to_string()),
+                Value::from(id_num),
+            ],
+        );
+    }
+
+    let (query, values) = query.build();
+
+    let mut statement = conn.prepare(&query)?;
+    let mut rows = statement.query(params_from_iter(values))?;
+
+    let mut pending_changes: HashMap<String, Cent> = HashMap::new();
+
+    while let Some(row) = rows.next()? {
+        let tx_method: String = row.get(0)?;
+        let amount: Cent = row.get(1)?;
+        let tx_type: String = row.get(2)?;
+
+        // A transfer changes both of its tx methods
+        let methods = if tx_type == "Transfer" {
+            tx_method.split(" to ").collect::<Vec<&str>>()
+        } else {
+            vec![tx_method.as_str()]
+        };
+
+        for method in methods {
+            *pending_changes.entry(method.to_string()).or_default() +=
+                get_method_change(method, &tx_method, &tx_type, amount);
+        }
+    }
+
+    Ok(pending_changes)
+}
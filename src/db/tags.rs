@@ -0,0 +1,404 @@
+use rusqlite::{params_from_iter, Connection, Result, Savepoint};
+
+use crate::db::{
+    get_split_tags, parse_splits, rename_budget_tags, rename_goal_tags, set_tx_splits,
+    splits_to_text, QueryBuilder, SPLIT_FIELD_SEPARATOR, SPLIT_LINE_SEPARATOR,
+};
+use crate::outputs::TagError;
+use crate::page_handler::ActivityType;
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, get_all_tags, get_tx_id_num, is_in_tag_subtree,
+    split_tags, Cent, CurrencyConverter,
+};
+
+/// Separates a parent tag from its child tag. Example: `Food:Groceries`
+pub const TAG_SEPARATOR: char = ':';
+
+/// `moved_table` of a budget moved by a tag activity
+pub const BUDGETS_TABLE: &str = "budgets";
+
+/// `moved_table` of a goal moved by a tag activity
+pub const GOALS_TABLE: &str = "goals";
+
+/// A budget or a goal moved to another tag by renaming or merging tags
+pub struct TagMove {
+    /// Either `BUDGETS_TABLE` or `GOALS_TABLE`
+    pub moved_table: String,
+    /// The `budget_id` or the `goal_id`
+    pub row_id: i32,
+    pub old_tag: String,
+    pub new_tag: String,
+}
+
+/// How a tag is used across all txs
+pub struct TagUsage {
+    pub name: String,
+    pub tx_count: usize,
+    /// Income total of the txs in the base currency
+    pub income: Cent,
+    /// Expense total of the txs in the base currency
+    pub expense: Cent,
+}
+
+impl TagUsage {
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.tx_count.to_string(),
+            self.income.to_string(),
+            self.expense.to_string(),
+        ]
+    }
+}
+
+/// Links the tx to each of the given comma separated tags. Tags that do not exist yet get created
+pub fn save_tx_tags(id_num: i64, tags: &str, sp: &Savepoint) -> Result<()> {
+    for tag in split_tags(tags) {
+        sp.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", [&tag])?;
+        sp.execute(
+            "INSERT OR IGNORE INTO tx_tags (id_num, tag_id) SELECT ?1, tag_id FROM tags WHERE name = ?2",
+            (id_num, &tag),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Replaces all tags of the tx with the given comma separated tags
+pub fn set_tx_tags(id_num: i32, tags: &str, sp: &Savepoint) -> Result<()> {
+    QueryBuilder::update("tx_all")
+        .set("tags", tags.to_string())
+        .where_cond("id_num = ?", [id_num])
+        .execute(sp)?;
+
+    sp.execute("DELETE FROM tx_tags WHERE id_num = ?1", [id_num])?;
+    save_tx_tags(id_num.into(), tags, sp)
+}
+
+/// Returns the usage of every tag that is used by at least one tx, ordered by the tag name
+pub fn get_tag_usage(conn: &Connection) -> Result<Vec<TagUsage>> {
+    let converter = CurrencyConverter::new(conn);
+
+    let mut statement = conn.prepare(
+        "SELECT tags.name, tx_all.tx_method, tx_all.amount, tx_all.tx_type FROM tx_tags
+        JOIN tags ON tags.tag_id = tx_tags.tag_id
+        JOIN tx_all ON tx_all.id_num = tx_tags.id_num ORDER BY tags.name",
+    )?;
+    let mut rows = statement.query([])?;
+
+    let mut all_usage: Vec<TagUsage> = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(0)?;
+        let tx_method: String = row.get(1)?;
+        let amount: Cent = row.get(2)?;
+        let tx_type: String = row.get(3)?;
+
+        if all_usage.last().map(|usage| &usage.name) != Some(&name) {
+            all_usage.push(TagUsage {
+                name,
+                tx_count: 0,
+                income: Cent::default(),
+                expense: Cent::default(),
+            });
+        }
+
+        let usage = all_usage.last_mut().unwrap();
+        usage.tx_count += 1;
+
+        match tx_type.as_str() {
+            "Income" => usage.income += converter.to_base(amount, &tx_method),
+            "Expense" => usage.expense += converter.to_base(amount, &tx_method),
+            _ => {}
+        }
+    }
+
+    Ok(all_usage)
+}
+
+/// Trims the tag and checks that it can be saved as a tag
+pub fn check_tag_name(tag: &str) -> Result<String, TagError> {
+    let tag = tag.trim();
+
+    if tag.is_empty() || tag.contains([',', SPLIT_FIELD_SEPARATOR, SPLIT_LINE_SEPARATOR]) {
+        return Err(TagError::InvalidName(tag.to_string()));
+    }
+
+    Ok(tag.to_string())
+}
+
+fn check_tag_exists(tag: &str, all_tags: &[String]) -> Result<(), TagError> {
+    if all_tags.iter().any(|t| t == tag) {
+        Ok(())
+    } else {
+        Err(TagError::NotFound(tag.to_string()))
+    }
+}
+
+/// Returns each replaced tag with the tag it gets replaced with. Children of a replaced tag move to
+/// the new tag with it unless the new tag is one of them.
+/// Example: renaming `Food` to `Meals` also renames `Food:Groceries` to `Meals:Groceries`
+fn get_tag_changes(
+    old_tags: &[String],
+    new_tag: Option<&str>,
+    conn: &Connection,
+) -> Result<Vec<(String, Option<String>)>> {
+    let mut tag_changes = old_tags
+        .iter()
+        .map(|tag| (tag.clone(), new_tag.map(ToString::to_string)))
+        .collect::<Vec<(String, Option<String>)>>();
+
+    let Some(new_tag) = new_tag else {
+        return Ok(tag_changes);
+    };
+
+    // Budgets and goals can be on a tag that no tx uses
+    let mut statement = conn.prepare(
+        "SELECT name FROM tags UNION SELECT tag FROM budgets
+        UNION SELECT tag FROM goals WHERE tag IS NOT NULL ORDER BY 1",
+    )?;
+    let known_tags = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    for old_tag in old_tags {
+        if is_in_tag_subtree(new_tag, old_tag) {
+            continue;
+        }
+
+        for tag in &known_tags {
+            if tag == old_tag
+                || !is_in_tag_subtree(tag, old_tag)
+                || tag_changes.iter().any(|(changed, _)| changed == tag)
+            {
+                continue;
+            }
+
+            let child = format!("{new_tag}{}", &tag[old_tag.len()..]);
+            tag_changes.push((tag.clone(), Some(child)));
+        }
+    }
+
+    Ok(tag_changes)
+}
+
+/// Saves the budgets and goals moved by a tag activity with it
+fn save_tag_moves(activity_num: i32, tag_moves: &[TagMove], sp: &Savepoint) -> Result<()> {
+    for tag_move in tag_moves {
+        sp.execute(
+            "INSERT INTO activity_tag_moves (activity_num, moved_table, row_id, old_tag, new_tag)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            (
+                activity_num,
+                &tag_move.moved_table,
+                tag_move.row_id,
+                &tag_move.old_tag,
+                &tag_move.new_tag,
+            ),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns the budgets and goals moved by a tag activity in the order they were moved
+pub fn get_tag_moves(activity_num: i32, conn: &Connection) -> Result<Vec<TagMove>> {
+    let mut statement = conn.prepare(
+        "SELECT moved_table, row_id, old_tag, new_tag FROM activity_tag_moves
+        WHERE activity_num = ?1 ORDER BY insertion_id",
+    )?;
+
+    let rows = statement.query_map([activity_num], |row| {
+        Ok(TagMove {
+            moved_table: row.get(0)?,
+            row_id: row.get(1)?,
+            old_tag: row.get(2)?,
+            new_tag: row.get(3)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
+/// Replaces each old tag with its new tag in every tx that uses any of them and moves their budgets
+/// and goals to it. The tags without a new tag are removed. Split lines of a removed tag are moved
+/// to Unknown as their amount still needs a tag. All changed txs are saved in a single activity,
+/// each tx after the change followed by the tx before it, along with the moved budgets and goals.
+/// Returns the amount of changed txs
+fn replace_tags(
+    tag_changes: &[(String, Option<String>)],
+    activity_type: ActivityType,
+    conn: &mut Connection,
+) -> Result<usize, TagError> {
+    let sp = conn.savepoint().map_err(TagError::UpdateFailed)?;
+
+    let old_tags = tag_changes
+        .iter()
+        .map(|(old_tag, _)| old_tag.clone())
+        .collect::<Vec<String>>();
+
+    let replacement_of = |tag: &str| {
+        tag_changes
+            .iter()
+            .find(|(old_tag, _)| old_tag == tag)
+            .map(|(_, new_tag)| new_tag.as_deref())
+    };
+
+    let query = format!(
+        "SELECT DISTINCT tx_tags.id_num FROM tx_tags JOIN tags ON tags.tag_id = tx_tags.tag_id
+        WHERE tags.name IN ({}) ORDER BY tx_tags.id_num",
+        vec!["?"; old_tags.len()].join(", ")
+    );
+
+    let id_nums = {
+        let mut statement = sp.prepare(&query).map_err(TagError::UpdateFailed)?;
+        statement
+            .query_map(params_from_iter(&old_tags), |row| row.get(0))
+            .and_then(Iterator::collect::<Result<Vec<i32>>>)
+            .map_err(TagError::UpdateFailed)?
+    };
+
+    let mut changed_txs = Vec::new();
+
+    for id_num in id_nums {
+        let old_tx = get_tx_id_num(id_num, &sp);
+
+        if let Some(mut splits) = parse_splits(&old_tx[7]).filter(|splits| !splits.is_empty()) {
+            for split in &mut splits {
+                if let Some(new_tag) = replacement_of(&split.tag) {
+                    split.tag = new_tag.unwrap_or("Unknown").to_string();
+                }
+            }
+
+            let tags = get_split_tags(&splits);
+
+            set_tx_tags(id_num, &tags, &sp).map_err(TagError::UpdateFailed)?;
+            set_tx_splits(id_num, &splits, &sp).map_err(TagError::UpdateFailed)?;
+
+            let mut new_tx = old_tx.clone();
+            new_tx[5] = tags;
+            new_tx[7] = splits_to_text(&splits);
+            changed_txs.push((new_tx, old_tx));
+            continue;
+        }
+
+        let mut tags: Vec<String> = Vec::new();
+
+        for tag in split_tags(&old_tx[5]) {
+            let tag = match replacement_of(&tag) {
+                None => tag,
+                Some(Some(new_tag)) => new_tag.to_string(),
+                Some(None) => continue,
+            };
+
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        // Same as the Add TX page, a tx without any tag is tagged as Unknown
+        let tags = if tags.is_empty() {
+            String::from("Unknown")
+        } else {
+            tags.join(", ")
+        };
+
+        if tags == old_tx[5] {
+            continue;
+        }
+
+        set_tx_tags(id_num, &tags, &sp).map_err(TagError::UpdateFailed)?;
+
+        let mut new_tx = old_tx.clone();
+        new_tx[5] = tags;
+        changed_txs.push((new_tx, old_tx));
+    }
+
+    let renamed_tags = tag_changes
+        .iter()
+        .filter_map(|(old_tag, new_tag)| Some((old_tag.clone(), new_tag.clone()?)))
+        .collect::<Vec<(String, String)>>();
+
+    let mut tag_moves = rename_budget_tags(&renamed_tags, &sp).map_err(TagError::UpdateFailed)?;
+    tag_moves.extend(rename_goal_tags(&renamed_tags, &sp).map_err(TagError::UpdateFailed)?);
+
+    sp.execute(
+        "DELETE FROM tags WHERE tag_id NOT IN (SELECT tag_id FROM tx_tags)",
+        [],
+    )
+    .map_err(TagError::UpdateFailed)?;
+
+    if !changed_txs.is_empty() || !tag_moves.is_empty() {
+        let activity_num = add_new_activity(activity_type, &sp);
+
+        for (new_tx, old_tx) in &changed_txs {
+            add_new_activity_tx(new_tx, activity_num, &sp);
+            add_new_activity_tx(old_tx, activity_num, &sp);
+        }
+
+        save_tag_moves(activity_num, &tag_moves, &sp).map_err(TagError::UpdateFailed)?;
+    }
+
+    sp.commit().map_err(TagError::UpdateFailed)?;
+    Ok(changed_txs.len())
+}
+
+/// Renames a tag and its children in every tx that uses them. Renaming to an existing tag merges the two.
+/// Returns the amount of changed txs
+pub fn rename_tag(old_tag: &str, new_tag: &str, conn: &mut Connection) -> Result<usize, TagError> {
+    let new_tag = check_tag_name(new_tag)?;
+    check_tag_exists(old_tag, &get_all_tags(conn))?;
+
+    if old_tag == new_tag {
+        return Err(TagError::SameTag);
+    }
+
+    let tag_changes = get_tag_changes(&[old_tag.to_string()], Some(&new_tag), conn)
+        .map_err(TagError::UpdateFailed)?;
+
+    replace_tags(
+        &tag_changes,
+        ActivityType::RenameTag(Some(old_tag.to_string()), Some(new_tag.clone())),
+        conn,
+    )
+}
+
+/// Replaces all given tags with `to_tag` in every tx that uses any of them. Their children move under `to_tag`.
+/// Returns the amount of changed txs
+pub fn merge_tags(
+    from_tags: &[String],
+    to_tag: &str,
+    conn: &mut Connection,
+) -> Result<usize, TagError> {
+    let to_tag = check_tag_name(to_tag)?;
+    let all_tags = get_all_tags(conn);
+
+    for tag in from_tags {
+        check_tag_exists(tag, &all_tags)?;
+    }
+
+    if from_tags.contains(&to_tag) {
+        return Err(TagError::SameTag);
+    }
+
+    let tag_changes =
+        get_tag_changes(from_tags, Some(&to_tag), conn).map_err(TagError::UpdateFailed)?;
+
+    replace_tags(
+        &tag_changes,
+        ActivityType::MergeTags(Some(from_tags.join(", ")), Some(to_tag.clone())),
+        conn,
+    )
+}
+
+/// Removes a tag from every tx that uses it. Returns the amount of changed txs
+pub fn remove_tag(tag: &str, conn: &mut Connection) -> Result<usize, TagError> {
+    check_tag_exists(tag, &get_all_tags(conn))?;
+
+    replace_tags(
+        &[(tag.to_string(), None)],
+        ActivityType::RemoveTag(Some(tag.to_string())),
+        conn,
+    )
+}
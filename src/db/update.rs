@@ -1,10 +1,25 @@
 use rusqlite::{Connection, Result, Savepoint};
+use std::collections::HashMap;
 
 use crate::db::{
-    create_activities_table, create_activity_txs_table, create_balances_table,
-    create_changes_table, create_missing_indexes,
+    create_activities_table, create_activity_tag_moves_table, create_activity_txs_table,
+    create_balances_table, create_budgets_table, create_changes_table, create_counterparties_table,
+    create_exchange_rates_table, create_goals_table, create_holdings_tables,
+    create_import_profiles_table, create_missing_indexes, create_recurring_tables,
+    create_settings_table, create_tags_tables, create_tx_methods_table, create_tx_splits_table,
+    create_tx_table, create_year_range_table, get_goal_tx_method_usage,
+    get_holding_tx_method_usage, get_recurring_tx_method_usage, quote_columns, quote_identifier,
+    recalculate_ledger, rename_goal_tx_method, rename_holding_tx_method,
+    rename_import_profile_tx_method, save_tx_tags, QueryBuilder, DEFAULT_CURRENCY, MONTHS,
+    TX_METHOD_KINDS,
+};
+use crate::outputs::TxMethodError;
+use crate::page_handler::ActivityType;
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, get_active_tx_methods, get_all_tx_methods,
+    get_last_balance_id, get_tx_id_num, get_tx_method_currencies, get_tx_method_usage,
+    get_year_range,
 };
-use crate::utility::get_all_tx_methods;
 
 /// adds new tx methods as columns on `balance_all` and `changes_all` tables. Gets called after
 /// successful handling of 'J' from the app
@@ -13,49 +28,55 @@ pub fn add_new_tx_methods(tx_methods: &[String], conn: &mut Connection) -> Resul
     let sp = conn.savepoint()?;
 
     for i in tx_methods {
-        let query = format!(r#"ALTER TABLE balance_all ADD COLUMN "{i}" REAL DEFAULT 0.00"#);
+        let query = format!(
+            "ALTER TABLE balance_all ADD COLUMN {} INTEGER DEFAULT 0",
+            quote_identifier(i)
+        );
         sp.execute(&query, [])?;
     }
 
     for i in tx_methods {
-        let query = format!(r#"ALTER TABLE changes_all ADD COLUMN "{i}" TEXT DEFAULT 0.00"#);
+        let query = format!(
+            "ALTER TABLE changes_all ADD COLUMN {} TEXT DEFAULT 0.00",
+            quote_identifier(i)
+        );
         sp.execute(&query, [])?;
     }
+
+    for i in tx_methods {
+        sp.execute(
+            "INSERT INTO tx_methods (name, currency) VALUES (?1, ?2)",
+            [i, DEFAULT_CURRENCY],
+        )?;
+    }
     sp.commit()?;
     Ok(())
 }
 
-/// Adds the tags column inside the database. Used when the old database without the tags column is detected
-pub fn add_tags_column(conn: &mut Connection) -> Result<()> {
-    let sp = conn.savepoint()?;
+/// Adds the tags column inside the database. Migration version 1
+pub fn add_tags_column(sp: &Savepoint) -> Result<()> {
     sp.execute("ALTER TABLE tx_all ADD tags TEXT DEFAULT Unknown;", [])?;
-    sp.commit()?;
     Ok(())
 }
 
-/// Migrates existing database's `balance_all` column's data type from TEXT to REAL
-pub fn update_balance_type(conn: &mut Connection) -> Result<()> {
-    let all_methods = get_all_tx_methods(conn);
+/// Migrates existing database's `balance_all` column's data type from TEXT to REAL. Migration version 2
+pub fn update_balance_type(sp: &Savepoint) -> Result<()> {
+    let all_methods = get_all_tx_methods(sp);
 
-    let sp = conn.savepoint()?;
-    let old_last_balance = get_last_balance(&sp, &all_methods);
+    let old_last_balance = get_last_balance(sp, &all_methods);
 
     // rename table
     let query = "ALTER TABLE balance_all RENAME TO balance_all_old";
     sp.execute(query, [])?;
 
     // create the new updated balance_all table
-    create_balances_table(&all_methods, &sp)?;
+    create_balances_table(&all_methods, sp)?;
 
-    let columns = all_methods
-        .iter()
-        .map(|column_name| format!(r#""{column_name}""#))
-        .collect::<Vec<String>>()
-        .join(",");
+    let columns = quote_columns(&all_methods);
 
     let values = all_methods
         .iter()
-        .map(|method| format!(r#"CAST("{method}" as REAL)"#))
+        .map(|method| format!("CAST({} as REAL)", quote_identifier(method)))
         .collect::<Vec<_>>()
         .join(",");
 
@@ -74,11 +95,7 @@ pub fn update_balance_type(conn: &mut Connection) -> Result<()> {
     // fill up `balance_all` table with total year * 12 + 1 rows with 0 balance for all columns
     let zero_values = vec!["0.00"; all_methods.len()];
 
-    let highlighted_tx_methods = all_methods
-        .iter()
-        .map(|method| format!("\"{method}\""))
-        .collect::<Vec<String>>()
-        .join(",");
+    let highlighted_tx_methods = quote_columns(&all_methods);
 
     let query = format!(
         "INSERT INTO balance_all ({}) VALUES ({})",
@@ -95,7 +112,7 @@ pub fn update_balance_type(conn: &mut Connection) -> Result<()> {
     let new_values = old_last_balance
         .iter()
         .enumerate()
-        .map(|(index, data)| format!(r#""{}" = {data}"#, all_methods[index]))
+        .map(|(index, data)| format!("{} = {data}", quote_identifier(&all_methods[index])))
         .collect::<Vec<_>>()
         .join(",");
 
@@ -104,23 +121,186 @@ pub fn update_balance_type(conn: &mut Connection) -> Result<()> {
 
     let new_values = all_methods
         .iter()
-        .map(|data| format!(r#""{data}" = 0.00"#,))
+        .map(|data| format!("{} = 0.00", quote_identifier(data)))
         .collect::<Vec<_>>()
         .join(",");
 
     let query = format!("UPDATE balance_all SET {new_values} WHERE id_num = 49",);
 
     sp.execute(&query, [])?;
-    sp.commit()?;
     Ok(())
 }
 
+/// Migrates `tx_all` amount and `balance_all` balances from TEXT/REAL to INTEGER cents. Migration version 5.
+/// The migration runner keeps foreign keys off and legacy alter table on so `changes_all`
+/// is neither cascade deleted nor pointed to the renamed table
+pub fn migrate_to_cents(sp: &Savepoint) -> Result<()> {
+    let all_methods = get_all_tx_methods(sp);
+
+    sp.execute("ALTER TABLE tx_all RENAME TO tx_all_old", [])?;
+    sp.execute("DROP INDEX IF EXISTS all_tx_id_IDX", [])?;
+    sp.execute("DROP INDEX IF EXISTS tx_all_date_idx", [])?;
+
+    create_tx_table(sp)?;
+    sp.execute("CREATE INDEX tx_all_date_idx ON tx_all(date);", [])?;
+
+    sp.execute(
+        "INSERT INTO tx_all (date, details, tx_method, amount, tx_type, id_num, tags)
+        SELECT date, details, tx_method, CAST(ROUND(CAST(amount AS REAL) * 100) AS INTEGER), tx_type, id_num, tags
+        FROM tx_all_old",
+        [],
+    )?;
+    sp.execute("DROP TABLE tx_all_old", [])?;
+
+    // balance_all has nothing referencing it, so renaming is fine here
+    sp.execute("ALTER TABLE balance_all RENAME TO balance_all_old", [])?;
+    sp.execute("DROP INDEX IF EXISTS balance_all_id_num_IDX", [])?;
+
+    create_balances_table(&all_methods, sp)?;
+
+    let columns = quote_columns(&all_methods);
+
+    let values = all_methods
+        .iter()
+        .map(|method| {
+            format!(
+                "CAST(ROUND(CAST({} AS REAL) * 100) AS INTEGER)",
+                quote_identifier(method)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let query = format!(
+        "INSERT INTO balance_all (id_num, {columns}) SELECT id_num, {values} FROM balance_all_old"
+    );
+
+    sp.execute(&query, [])?;
+    sp.execute("DROP TABLE balance_all_old", [])?;
+    sp.execute(
+        "CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Creates the tables for tx method currencies, exchange rates and the base currency.
+/// Every existing tx method gets the default currency. Migration version 6
+pub fn migrate_to_currencies(sp: &Savepoint) -> Result<()> {
+    let all_methods = get_all_tx_methods(sp);
+
+    create_tx_methods_table(&all_methods, sp)?;
+    create_exchange_rates_table(sp)?;
+    create_settings_table(sp)?;
+
+    Ok(())
+}
+
+/// Adds the archived flag to the `tx_methods` table. All existing methods start unarchived. Migration version 7
+pub fn add_archived_column(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "ALTER TABLE tx_methods ADD COLUMN archived INTEGER NOT NULL DEFAULT 0",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Moves the comma separated tags of every tx to the `tags` and `tx_tags` tables. Migration version 8
+pub fn migrate_to_tags(sp: &Savepoint) -> Result<()> {
+    create_tags_tables(sp)?;
+
+    let mut statement = sp.prepare("SELECT id_num, tags FROM tx_all")?;
+    let all_tags = statement
+        .query_map([], |row| {
+            let tags: Option<String> = row.get(1)?;
+            Ok((row.get::<_, i64>(0)?, tags.unwrap_or_default()))
+        })?
+        .collect::<Result<Vec<(i64, String)>>>()?;
+
+    for (id_num, tags) in all_tags {
+        save_tx_tags(id_num, &tags, sp)?;
+    }
+
+    Ok(())
+}
+
+/// Adds the `tx_splits` table and saves the split lines of activity txs so they can be restored.
+/// Migration version 9
+pub fn add_tx_splits(sp: &Savepoint) -> Result<()> {
+    create_tx_splits_table(sp)?;
+
+    sp.execute(
+        "ALTER TABLE activity_txs ADD COLUMN splits TEXT NOT NULL DEFAULT ''",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Adds the tables of the recurring txs. Migration version 10
+pub fn add_recurring_txs(sp: &Savepoint) -> Result<()> {
+    create_recurring_tables(sp)
+}
+
+/// Adds the `budgets` table. Migration version 11
+pub fn add_budgets(sp: &Savepoint) -> Result<()> {
+    create_budgets_table(sp)
+}
+
+/// Adds the `goals` table. Migration version 12
+pub fn add_goals(sp: &Savepoint) -> Result<()> {
+    create_goals_table(sp)
+}
+
+/// Adds the status column to `tx_all` and `activity_txs`. Every existing tx starts as cleared.
+/// Migration version 13
+pub fn add_tx_status(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "ALTER TABLE tx_all ADD COLUMN status TEXT NOT NULL DEFAULT 'Cleared'",
+        [],
+    )?;
+    sp.execute(
+        "ALTER TABLE activity_txs ADD COLUMN status TEXT NOT NULL DEFAULT 'Cleared'",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Adds the kind of the tx methods. Every existing method starts as an asset. Migration version 14
+pub fn add_tx_method_kind(sp: &Savepoint) -> Result<()> {
+    sp.execute(
+        "ALTER TABLE tx_methods ADD COLUMN kind TEXT NOT NULL DEFAULT 'Asset'",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Adds the `tx_counterparties` table. Migration version 15
+pub fn add_counterparties(sp: &Savepoint) -> Result<()> {
+    create_counterparties_table(sp)
+}
+
+/// Adds the `holdings` and `prices` tables. Migration version 16
+pub fn add_holdings(sp: &Savepoint) -> Result<()> {
+    create_holdings_tables(sp)
+}
+
+/// Adds the `import_profiles` table. Migration version 17
+pub fn add_import_profiles(sp: &Savepoint) -> Result<()> {
+    create_import_profiles_table(sp)
+}
+
+/// Adds the `activity_tag_moves` table. Migration version 18
+pub fn add_activity_tag_moves(sp: &Savepoint) -> Result<()> {
+    create_activity_tag_moves_table(sp)
+}
+
 /// return the last balance from the db
-fn get_last_balance(sp: &Savepoint, all_methods: &Vec<String>) -> Vec<String> {
-    let mut query =
-        format!("SELECT {all_methods:?} FROM balance_all ORDER BY id_num DESC LIMIT 1",);
-    query = query.replace('[', "");
-    query = query.replace(']', "");
+fn get_last_balance(sp: &Savepoint, all_methods: &[String]) -> Vec<String> {
+    let query = format!(
+        "SELECT {} FROM balance_all ORDER BY id_num DESC LIMIT 1",
+        quote_columns(all_methods)
+    );
 
     let final_balance = sp.query_row(&query, [], |row| {
         let mut final_data: Vec<String> = Vec::new();
@@ -136,31 +316,149 @@ fn get_last_balance(sp: &Savepoint, all_methods: &Vec<String>) -> Vec<String> {
 /// Updates the DB with the new tx method name
 pub fn rename_column(old_name: &str, new_name: &str, conn: &mut Connection) -> Result<()> {
     let sp = conn.savepoint()?;
-    let query = format!(r#"ALTER TABLE balance_all RENAME COLUMN "{old_name}" TO "{new_name}""#);
+    let (old_column, new_column) = (quote_identifier(old_name), quote_identifier(new_name));
+
+    let query = format!("ALTER TABLE balance_all RENAME COLUMN {old_column} TO {new_column}");
     sp.execute(&query, [])?;
 
-    let query = format!(r#"ALTER TABLE changes_all RENAME COLUMN "{old_name}" TO "{new_name}""#);
+    let query = format!("ALTER TABLE changes_all RENAME COLUMN {old_column} TO {new_column}");
     sp.execute(&query, [])?;
 
+    sp.execute(
+        "UPDATE tx_methods SET name = ?1 WHERE name = ?2",
+        [new_name, old_name],
+    )?;
+
+    replace_tx_method(old_name, new_name, &sp)?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Replaces the tx method of every tx that uses the old name with the new name
+fn replace_tx_method(old_name: &str, new_name: &str, sp: &Savepoint) -> Result<()> {
     // Follows 3 cases
     // 1. old_name == new_name. Replace old name with the new name
     // 2. If the tx method = old_name to tx_method. Replace the old name part but keep to tx_method
     // 3. If the tx method = tx_method to old_name. Replace the old name part but keep tx_method to
     // last 2 are used for transfer tx
-    let query = format!(
-        r#"UPDATE tx_all SET tx_method =
+    // recurring txs are updated the same way so they keep posting to the same tx method
+    // and goals keep tracking the balance of the renamed tx method. Holdings and import profiles
+    // move along with it
+    for table in ["tx_all", "recurring_txs"] {
+        let query = format!(
+            "UPDATE {table} SET tx_method =
             CASE
-                WHEN tx_method = "{old_name}" THEN "{new_name}"
-                WHEN tx_method LIKE "{old_name} %" THEN REPLACE(tx_method, "{old_name}", "{new_name}")
-                WHEN tx_method LIKE "% {old_name}" THEN REPLACE(tx_method, " {old_name}", " {new_name}")
+                WHEN tx_method = ?1 THEN ?2
+                WHEN substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+                    THEN ?2 || substr(tx_method, length(?1) + 1)
+                WHEN substr(tx_method, -(length(?1) + 4)) = ' to ' || ?1
+                    THEN substr(tx_method, 1, length(tx_method) - length(?1)) || ?2
                 ELSE tx_method
             END
-        WHERE tx_method LIKE "%{old_name}%""#
+        WHERE tx_method LIKE '%' || ?1 || '%'"
+        );
+        sp.execute(&query, [old_name, new_name])?;
+    }
+
+    rename_goal_tx_method(old_name, new_name, sp)?;
+    rename_holding_tx_method(old_name, new_name, sp)?;
+    rename_import_profile_tx_method(old_name, new_name, sp)
+}
+
+/// Moves every tx of a tx method to another one and deletes the merged method.
+/// Transfers between the two methods would become a transfer to itself, so they are deleted
+/// along with recurring transfers between them. The merge is saved as an activity along with the deleted transfers.
+/// `balance_all` and `changes_all` are rebuilt afterwards. Returns the amount of deleted transfers
+pub fn merge_tx_methods(
+    from_method: &str,
+    to_method: &str,
+    conn: &mut Connection,
+) -> Result<usize, TxMethodError> {
+    let sp = conn.savepoint().map_err(TxMethodError::UpdateFailed)?;
+
+    let all_methods = get_all_tx_methods(&sp);
+
+    for method in [from_method, to_method] {
+        if !all_methods.contains(&method.to_string()) {
+            return Err(TxMethodError::NotFound(method.to_string()));
+        }
+    }
+
+    if from_method == to_method {
+        return Err(TxMethodError::SameMethod);
+    }
+
+    let currencies: HashMap<String, String> = get_tx_method_currencies(&sp).into_iter().collect();
+
+    if currencies[from_method] != currencies[to_method] {
+        return Err(TxMethodError::CurrencyMismatch(
+            currencies[from_method].clone(),
+            currencies[to_method].clone(),
+        ));
+    }
+
+    if get_active_tx_methods(&sp) == [from_method] {
+        return Err(TxMethodError::NoActiveMethod);
+    }
+
+    let transfer_ids = {
+        let mut statement = sp
+            .prepare(
+                "SELECT id_num FROM tx_all WHERE tx_method = ?1 || ' to ' || ?2 OR tx_method = ?2 || ' to ' || ?1
+                ORDER BY id_num",
+            )
+            .map_err(TxMethodError::UpdateFailed)?;
+        statement
+            .query_map([from_method, to_method], |row| row.get(0))
+            .and_then(Iterator::collect::<Result<Vec<i32>>>)
+            .map_err(TxMethodError::UpdateFailed)?
+    };
+
+    let deleted_transfers = transfer_ids
+        .iter()
+        .map(|id_num| get_tx_id_num(*id_num, &sp))
+        .collect::<Vec<Vec<String>>>();
+
+    sp.execute(
+        "DELETE FROM tx_all WHERE tx_method = ?1 || ' to ' || ?2 OR tx_method = ?2 || ' to ' || ?1",
+        [from_method, to_method],
+    )
+    .map_err(TxMethodError::UpdateFailed)?;
+
+    sp.execute(
+        "DELETE FROM recurring_txs WHERE tx_method = ?1 || ' to ' || ?2 OR tx_method = ?2 || ' to ' || ?1",
+        [from_method, to_method],
+    )
+    .map_err(TxMethodError::UpdateFailed)?;
+
+    replace_tx_method(from_method, to_method, &sp).map_err(TxMethodError::UpdateFailed)?;
+
+    for table in ["balance_all", "changes_all"] {
+        let query = format!(
+            "ALTER TABLE {table} DROP COLUMN {}",
+            quote_identifier(from_method)
+        );
+        sp.execute(&query, [])
+            .map_err(TxMethodError::UpdateFailed)?;
+    }
+
+    sp.execute("DELETE FROM tx_methods WHERE name = ?1", [from_method])
+        .map_err(TxMethodError::UpdateFailed)?;
+
+    recalculate_ledger(&sp).map_err(TxMethodError::UpdateFailed)?;
+
+    let activity_num = add_new_activity(
+        ActivityType::MergeTxMethods(Some(from_method.to_string()), Some(to_method.to_string())),
+        &sp,
     );
-    sp.execute(&query, [])?;
 
-    sp.commit()?;
-    Ok(())
+    for tx in &deleted_transfers {
+        add_new_activity_tx(tx, activity_num, &sp);
+    }
+
+    sp.commit().map_err(TxMethodError::UpdateFailed)?;
+    Ok(deleted_transfers.len())
 }
 
 /// repositions tx method positions in the db
@@ -176,11 +474,7 @@ pub fn reposition_column(tx_methods: &[String], conn: &mut Connection) -> Result
     create_balances_table(tx_methods, &sp)?;
     create_changes_table(tx_methods, &sp)?;
 
-    let columns = tx_methods
-        .iter()
-        .map(|column_name| format!(r#""{column_name}""#,))
-        .collect::<Vec<String>>()
-        .join(",");
+    let columns = quote_columns(tx_methods);
 
     let query = format!(
         "INSERT INTO balance_all (id_num, {columns}) SELECT id_num, {columns} FROM balance_all_old"
@@ -200,15 +494,205 @@ pub fn reposition_column(tx_methods: &[String], conn: &mut Connection) -> Result
     Ok(())
 }
 
-/// Create new tables to migrate to the new form of database to include activities
-pub fn migrate_to_activities(conn: &mut Connection) -> Result<()> {
-    let sp = conn.savepoint()?;
+/// Deletes a tx method along with its `balance_all` and `changes_all` columns.
+/// Refuses to delete if any tx, recurring tx, goal or holding still uses the method as those must be reassigned first
+pub fn delete_tx_method(method: &str, conn: &mut Connection) -> Result<(), TxMethodError> {
+    let sp = conn.savepoint().map_err(TxMethodError::UpdateFailed)?;
+
+    if !get_all_tx_methods(&sp).contains(&method.to_string()) {
+        return Err(TxMethodError::NotFound(method.to_string()));
+    }
+
+    let total_txs = get_tx_method_usage(method, &sp).map_err(TxMethodError::UpdateFailed)?;
+
+    if total_txs > 0 {
+        return Err(TxMethodError::InUse(method.to_string(), total_txs));
+    }
+
+    let total_recurring =
+        get_recurring_tx_method_usage(method, &sp).map_err(TxMethodError::UpdateFailed)?;
+
+    if total_recurring > 0 {
+        return Err(TxMethodError::UsedByRecurring(
+            method.to_string(),
+            total_recurring,
+        ));
+    }
+
+    let total_goals = get_goal_tx_method_usage(method, &sp).map_err(TxMethodError::UpdateFailed)?;
+
+    if total_goals > 0 {
+        return Err(TxMethodError::UsedByGoal(method.to_string(), total_goals));
+    }
+
+    let total_holdings =
+        get_holding_tx_method_usage(method, &sp).map_err(TxMethodError::UpdateFailed)?;
+
+    if total_holdings > 0 {
+        return Err(TxMethodError::UsedByHolding(
+            method.to_string(),
+            total_holdings,
+        ));
+    }
+
+    if get_active_tx_methods(&sp) == [method] {
+        return Err(TxMethodError::NoActiveMethod);
+    }
+
+    // Without any tx the method has no balance or changes left to keep
+    for table in ["balance_all", "changes_all"] {
+        let query = format!(
+            "ALTER TABLE {table} DROP COLUMN {}",
+            quote_identifier(method)
+        );
+        sp.execute(&query, [])
+            .map_err(TxMethodError::UpdateFailed)?;
+    }
 
-    create_activities_table(&sp)?;
-    create_activity_txs_table(&sp)?;
-    create_missing_indexes(&sp)?;
+    sp.execute("DELETE FROM tx_methods WHERE name = ?1", [method])
+        .map_err(TxMethodError::UpdateFailed)?;
 
+    sp.commit().map_err(TxMethodError::UpdateFailed)
+}
+
+/// Archives or unarchives a tx method. Archived methods are hidden from the Home and Add TX page
+/// along with the stepper and autofill, but their txs stay in search and activities
+pub fn set_tx_method_archived(
+    method: &str,
+    archived: bool,
+    conn: &mut Connection,
+) -> Result<(), TxMethodError> {
+    let sp = conn.savepoint().map_err(TxMethodError::UpdateFailed)?;
+
+    if !get_all_tx_methods(&sp).contains(&method.to_string()) {
+        return Err(TxMethodError::NotFound(method.to_string()));
+    }
+
+    if archived && get_active_tx_methods(&sp) == [method] {
+        return Err(TxMethodError::NoActiveMethod);
+    }
+
+    sp.execute(
+        "UPDATE tx_methods SET archived = ?1 WHERE name = ?2",
+        (archived, method),
+    )
+    .map_err(TxMethodError::UpdateFailed)?;
+
+    sp.commit().map_err(TxMethodError::UpdateFailed)
+}
+
+/// Sets whether a tx method is an asset or a liability. The saved balances are kept as they are
+pub fn set_tx_method_kind(
+    method: &str,
+    kind: &str,
+    conn: &mut Connection,
+) -> Result<(), TxMethodError> {
+    let sp = conn.savepoint().map_err(TxMethodError::UpdateFailed)?;
+
+    if !get_all_tx_methods(&sp).contains(&method.to_string()) {
+        return Err(TxMethodError::NotFound(method.to_string()));
+    }
+
+    if !TX_METHOD_KINDS.contains(&kind) {
+        return Err(TxMethodError::InvalidKind(kind.to_string()));
+    }
+
+    sp.execute(
+        "UPDATE tx_methods SET kind = ?1 WHERE name = ?2",
+        (kind, method),
+    )
+    .map_err(TxMethodError::UpdateFailed)?;
+
+    sp.commit().map_err(TxMethodError::UpdateFailed)
+}
+
+/// Create new tables to migrate to the new form of database to include activities. Migration version 3
+pub fn migrate_to_activities(sp: &Savepoint) -> Result<()> {
+    create_activities_table(sp)?;
+    create_activity_txs_table(sp)?;
+    create_missing_indexes(sp)?;
+
+    Ok(())
+}
+
+/// Create the `year_range` table for databases that were created with the fixed 2022 to 2037 window.
+/// Migration version 4
+pub fn migrate_to_year_range(sp: &Savepoint) -> Result<()> {
+    let last_balance_id = get_last_balance_id(sp)?;
+
+    // the old db always started at 2022 and had total year * 12 + 1 rows
+    let end_year = 2022 + (last_balance_id - 1) / 12 - 1;
+    create_year_range_table(2022, end_year, sp)?;
+
+    Ok(())
+}
+
+/// Makes sure the given year is inside the year range of the DB. Gets called on app startup
+pub fn extend_year_range(year: i32, conn: &mut Connection) -> Result<()> {
+    let sp = conn.savepoint()?;
+    add_missing_years(year, &sp)?;
     sp.commit()?;
+    Ok(())
+}
+
+/// Adds 12 rows to `balance_all` for each missing year between the current year range and the given year.
+/// Does nothing if the year is already inside the range.
+pub fn add_missing_years(year: i32, sp: &Savepoint) -> Result<()> {
+    let (start_year, end_year) = get_year_range(sp)?;
+
+    if (start_year..=end_year).contains(&year) {
+        return Ok(());
+    }
+
+    let all_methods = get_all_tx_methods(sp);
+    let last_balance_id = get_last_balance_id(sp)?;
+
+    let columns = quote_columns(&all_methods);
+
+    let zero_values = vec!["0"; all_methods.len()].join(",");
+
+    let zero_query =
+        format!("INSERT INTO balance_all (id_num, {columns}) VALUES (?1, {zero_values})");
+
+    if year > end_year {
+        let new_rows = (year - end_year) * MONTHS.len() as i32;
+        let new_last_id = last_balance_id + new_rows;
+
+        // move the absolute final balance to the new last row
+        let query = format!(
+            "INSERT INTO balance_all (id_num, {columns}) SELECT ?1, {columns} FROM balance_all WHERE id_num = ?2"
+        );
+        sp.execute(&query, [new_last_id, last_balance_id])?;
+
+        // the old final balance row becomes January of the first new year
+        let mut reset_query = QueryBuilder::update("balance_all");
+        for method in &all_methods {
+            reset_query = reset_query.set(method, 0);
+        }
+
+        reset_query
+            .where_cond("id_num = ?", [last_balance_id])
+            .execute(sp)?;
+
+        for id_num in last_balance_id + 1..new_last_id {
+            sp.execute(&zero_query, [id_num])?;
+        }
+
+        sp.execute("UPDATE year_range SET end_year = ?1", [year])?;
+    } else {
+        let new_rows = (start_year - year) * MONTHS.len() as i32;
+
+        // shift every existing row forward to make room at the start. Done in 2 steps through
+        // negative values so the ids never collide midway
+        sp.execute("UPDATE balance_all SET id_num = -(id_num + ?1)", [new_rows])?;
+        sp.execute("UPDATE balance_all SET id_num = -id_num", [])?;
+
+        for id_num in 1..=new_rows {
+            sp.execute(&zero_query, [id_num])?;
+        }
+
+        sp.execute("UPDATE year_range SET start_year = ?1", [year])?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,50 @@
+use rusqlite::Connection;
+
+use crate::db::{get_all_counterparties, get_counterparty_history, Counterparty, DebtItem};
+
+/// Contains every counterparty with their outstanding balance along with the history of the debt txs
+/// with them
+pub struct DebtData {
+    counterparties: Vec<(Counterparty, Vec<DebtItem>)>,
+}
+
+impl DebtData {
+    pub fn new(conn: &Connection) -> Self {
+        let counterparties = get_all_counterparties(conn)
+            .expect("could not get the counterparties")
+            .into_iter()
+            .map(|counterparty| {
+                let history = get_counterparty_history(&counterparty.name, conn)
+                    .expect("could not get the counterparty history");
+                (counterparty, history)
+            })
+            .collect();
+
+        DebtData { counterparties }
+    }
+
+    /// Convert all counterparties to a Vector where each value of the vector is the data of a person
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.counterparties
+            .iter()
+            .map(|(counterparty, _)| counterparty.to_vec())
+            .collect()
+    }
+
+    /// Returns the debt txs with the counterparty at the index from the oldest to the newest
+    pub fn get_history(&self, index: usize) -> Vec<Vec<String>> {
+        self.counterparties[index]
+            .1
+            .iter()
+            .map(DebtItem::to_vec)
+            .collect()
+    }
+
+    pub fn get_counterparty(&self, index: usize) -> &Counterparty {
+        &self.counterparties[index].0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counterparties.is_empty()
+    }
+}
@@ -0,0 +1,126 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::debt_page::DebtData;
+use crate::page_handler::{TableData, BACKGROUND, BLUE, HEADER, RED, SELECTED, TEXT};
+use crate::utility::{main_block, styled_block};
+
+/// Shows every person money was lent to or borrowed from along with the history of the selected one
+#[cfg(not(tarpaulin_include))]
+pub fn debt_ui(f: &mut Frame, debt_data: &DebtData, table_data: &mut TableData) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let mut table_name = "People".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("People: {}", table_data.items.len());
+    }
+
+    let header_cells = ["Name", "Outstanding", "Status"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().enumerate().map(|(index, item)| {
+        let cells = item.iter().enumerate().map(|(cell_index, c)| {
+            if cell_index == 1 {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+
+        // Money owed to the user is shown in blue and money the user owes in red
+        let outstanding = debt_data.get_counterparty(index).outstanding.value();
+
+        let style = if outstanding > 0 {
+            Style::default().bg(BACKGROUND).fg(BLUE)
+        } else if outstanding < 0 {
+            Style::default().bg(BACKGROUND).fg(RED)
+        } else {
+            Style::default().bg(BACKGROUND).fg(TEXT)
+        };
+
+        Row::new(cells).height(1).bottom_margin(0).style(style)
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    if table_data.state.selected().is_some() {
+        table_area = table_area
+            .highlight_symbol(">> ")
+            .row_highlight_style(Style::default().bg(SELECTED));
+    }
+
+    let (history_name, history) = match table_data.state.selected() {
+        Some(index) => (
+            format!("History: {}", debt_data.get_counterparty(index).name),
+            debt_data.get_history(index),
+        ),
+        None => ("History: Select a person".to_string(), Vec::new()),
+    };
+
+    let history_header_cells = ["Date", "Details", "TX Method", "Type", "Amount", "Open"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let history_header = Row::new(history_header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let history_rows = history.iter().map(|item| {
+        let cells = item.iter().enumerate().map(|(index, c)| {
+            if matches!(index, 4 | 5) {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+    });
+
+    let history_area = Table::new(
+        history_rows,
+        [
+            Constraint::Percentage(12),
+            Constraint::Percentage(34),
+            Constraint::Percentage(16),
+            Constraint::Percentage(12),
+            Constraint::Percentage(13),
+            Constraint::Percentage(13),
+        ],
+    )
+    .header(history_header)
+    .block(styled_block(&history_name));
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+    f.render_widget(history_area, chunks[1]);
+}
@@ -0,0 +1,5 @@
+mod debt_data;
+mod debt_ui;
+
+pub use debt_data::DebtData;
+pub use debt_ui::debt_ui;
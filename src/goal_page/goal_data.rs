@@ -0,0 +1,140 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+
+use crate::db::{get_all_goals, get_tag_savings, Goal, GoalSource};
+use crate::utility::{get_all_tx_methods, get_last_balances, Cent};
+
+/// The amount saved for a goal and how much more is needed to reach it
+pub struct GoalStatus {
+    pub goal: Goal,
+    pub saved: Cent,
+}
+
+impl GoalStatus {
+    /// The amount left to save. 0 once the goal is reached
+    #[must_use]
+    pub fn remaining(&self) -> Cent {
+        (self.goal.target - self.saved).max(Cent::default())
+    }
+
+    /// How much of the target has been saved. Can go above 100
+    #[must_use]
+    pub fn progress_percentage(&self) -> f64 {
+        if self.goal.target <= Cent::default() {
+            return 100.0;
+        }
+        ((self.saved.as_f64() / self.goal.target.as_f64()) * 100.0).max(0.0)
+    }
+
+    #[must_use]
+    pub fn is_reached(&self) -> bool {
+        self.saved >= self.goal.target
+    }
+
+    /// The amount of months left to save in, counting both the current and the target month.
+    /// 0 if the target date has passed
+    #[must_use]
+    pub fn months_left(&self, today: NaiveDate) -> i32 {
+        if self.goal.target_date < today {
+            return 0;
+        }
+
+        let target_date = self.goal.target_date;
+        (target_date.year() - today.year()) * 12 + target_date.month() as i32 - today.month() as i32
+            + 1
+    }
+
+    /// The amount that needs to be saved every month to reach the goal on time.
+    /// Once the target date has passed, the whole remaining amount is needed
+    #[must_use]
+    pub fn monthly_needed(&self, today: NaiveDate) -> Cent {
+        self.remaining() / i64::from(self.months_left(today).max(1))
+    }
+
+    fn status(&self, today: NaiveDate) -> &'static str {
+        if self.is_reached() {
+            "Reached"
+        } else if self.goal.target_date < today {
+            "Overdue"
+        } else {
+            "Saving"
+        }
+    }
+
+    fn to_vec(&self, today: NaiveDate) -> Vec<String> {
+        vec![
+            self.goal.name.clone(),
+            format!("{}: {}", self.goal.source.kind(), self.goal.source.name()),
+            self.goal.target.to_string(),
+            self.saved.to_string(),
+            self.remaining().to_string(),
+            format!("{:.2}", self.progress_percentage()),
+            self.goal.target_date.to_string(),
+            self.months_left(today).to_string(),
+            self.monthly_needed(today).to_string(),
+            self.status(today).to_string(),
+        ]
+    }
+}
+
+/// Contains every goal along with the amount saved for it. Tx method goals use the current balance
+/// of the method while tag goals use the total of the txs with the tag
+pub struct GoalData {
+    goals: Vec<GoalStatus>,
+    today: NaiveDate,
+}
+
+impl GoalData {
+    pub fn new(today: NaiveDate, conn: &Connection) -> Self {
+        let tx_methods = get_all_tx_methods(conn);
+        let last_balances = get_last_balances(conn);
+
+        let goals = get_all_goals(conn)
+            .into_iter()
+            .map(|goal| {
+                let saved = match &goal.source {
+                    GoalSource::TxMethod(method) => tx_methods
+                        .iter()
+                        .position(|name| name == method)
+                        .map(|index| last_balances[index].parse().unwrap())
+                        .unwrap_or_default(),
+                    GoalSource::Tag(tag) => {
+                        get_tag_savings(tag, conn).expect("could not get the tag savings")
+                    }
+                };
+
+                GoalStatus { goal, saved }
+            })
+            .collect();
+
+        GoalData { goals, today }
+    }
+
+    /// Convert all goals to a Vector where each value of the vector is the data of a goal
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.goals
+            .iter()
+            .map(|status| status.to_vec(self.today))
+            .collect()
+    }
+
+    pub fn get_goal(&self, index: usize) -> &GoalStatus {
+        &self.goals[index]
+    }
+
+    /// Returns the goal with the given id if it exists
+    pub fn find_goal(&self, goal_id: i32) -> Option<&Goal> {
+        self.goals
+            .iter()
+            .map(|status| &status.goal)
+            .find(|goal| goal.goal_id == goal_id)
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.today
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.goals.is_empty()
+    }
+}
@@ -0,0 +1,134 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Gauge, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::goal_page::GoalData;
+use crate::page_handler::{TableData, BACKGROUND, BOX, HEADER, SELECTED, TEXT};
+use crate::utility::{main_block, styled_block};
+
+/// Shows all goals along with the amount saved and the monthly amount needed to reach them
+#[cfg(not(tarpaulin_include))]
+pub fn goal_ui(f: &mut Frame, goal_data: &GoalData, table_data: &mut TableData) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let mut table_name = "Goals".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("Goals: {}", table_data.items.len());
+    }
+
+    let header_cells = [
+        "Name",
+        "Tracks",
+        "Target",
+        "Saved",
+        "Remaining",
+        "Progress %",
+        "Target Date",
+        "Months Left",
+        "Monthly Needed",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().map(|item| {
+        let cells = item.iter().enumerate().map(|(index, c)| {
+            // Only the amounts get commas. The name, the tracked item and the date can contain numbers
+            if matches!(index, 2 | 3 | 4 | 8) {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(14),
+            Constraint::Percentage(16),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+            Constraint::Percentage(9),
+            Constraint::Percentage(8),
+            Constraint::Percentage(9),
+            Constraint::Percentage(7),
+            Constraint::Percentage(11),
+            Constraint::Percentage(8),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    if table_data.state.selected().is_some() {
+        table_area = table_area
+            .highlight_symbol(">> ")
+            .row_highlight_style(Style::default().bg(SELECTED));
+    }
+
+    // The progress of the selected goal
+    let gauge = if let Some(index) = table_data.state.selected() {
+        let status = goal_data.get_goal(index);
+        let today = goal_data.today();
+
+        let saved = format!(
+            "Saved {} of {}",
+            status.saved.to_string().separate_with_commas(),
+            status.goal.target.to_string().separate_with_commas(),
+        );
+
+        let label = if status.is_reached() {
+            format!("{saved}. Goal reached")
+        } else if status.months_left(today) == 0 {
+            format!(
+                "{saved}. {} is still needed after the target date",
+                status.remaining().to_string().separate_with_commas(),
+            )
+        } else {
+            format!(
+                "{saved}. Save {} per month for {} months to reach it by {}",
+                status
+                    .monthly_needed(today)
+                    .to_string()
+                    .separate_with_commas(),
+                status.months_left(today),
+                status.goal.target_date
+            )
+        };
+
+        Gauge::default()
+            .block(styled_block(&status.goal.name))
+            .gauge_style(Style::default().fg(BOX).bg(BACKGROUND))
+            .ratio((status.progress_percentage() / 100.0).clamp(0.0, 1.0))
+            .label(label)
+    } else {
+        Gauge::default()
+            .block(styled_block("Progress"))
+            .gauge_style(Style::default().fg(BOX).bg(BACKGROUND))
+            .ratio(0.0)
+            .label("Select a goal to see its progress")
+    };
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+    f.render_widget(gauge, chunks[1]);
+}
@@ -0,0 +1,5 @@
+mod goal_data;
+mod goal_ui;
+
+pub use goal_data::{GoalData, GoalStatus};
+pub use goal_ui::goal_ui;
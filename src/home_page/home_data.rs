@@ -1,13 +1,18 @@
+use chrono::NaiveDate;
 use rusqlite::{Connection, Result as sqlResult};
 use std::collections::HashMap;
 
+use crate::db::{get_pending_changes, MarketValues};
 use crate::tx_handler::delete_tx;
-use crate::utility::{get_all_changes, get_all_tx_methods, get_all_txs, get_last_balances};
+use crate::utility::{
+    get_all_changes, get_all_tx_methods, get_all_txs, get_last_balances, get_liability_tx_methods,
+    reverse_date_format, Cent, CurrencyConverter,
+};
 
 /// This struct stores the transaction data, balance, changes and the id num
 /// Data storing format is:
 ///
-/// `all_tx` : `[[date, details, tx_method, amount, tx_type, tags],]`
+/// `all_tx` : `[[date, details, tx_method, amount, tx_type, tags, status],]`
 ///
 /// `all_balance`: `[["123.00", "123.00"],]`
 ///
@@ -50,36 +55,154 @@ impl TransactionData {
         self.all_tx.clone()
     }
 
+    /// returns the ID Numbers of all the txs in the same order as the txs
+    pub fn get_id_nums(&self) -> Vec<String> {
+        self.all_id_num.clone()
+    }
+
     pub fn is_tx_empty(&self) -> bool {
         self.all_tx.is_empty()
     }
 
     /// returns all the balance data for the given index. Index is of the
-    /// Home Table's selected index
-    pub fn get_balance(&self, index: usize) -> Vec<String> {
+    /// Home Table's selected index. The total is the liquid balance converted to the base currency
+    pub fn get_balance(
+        &self,
+        index: usize,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Vec<String> {
         let mut balance_data = vec!["Balance".to_string()];
-        let mut total_balance = 0.0;
-        for i in &self.all_balance[index] {
-            let num_balance = i.parse::<f64>().unwrap();
-            total_balance += num_balance;
-            balance_data.push(format!("{num_balance:.2}"));
+        let all_tx_methods = get_all_tx_methods(conn);
+        let liability_methods = get_liability_tx_methods(conn);
+        let mut total_balance = Cent::default();
+        for (i, method) in self.all_balance[index].iter().zip(all_tx_methods.iter()) {
+            let num_balance = i.parse::<Cent>().unwrap();
+            if !liability_methods.contains(method) {
+                total_balance += converter.to_base(num_balance, method);
+            }
+            balance_data.push(num_balance.to_string());
         }
-        balance_data.push(format!("{total_balance:.2}"));
+        balance_data.push(total_balance.to_string());
         balance_data
     }
 
     /// returns the absolute final balance that is found after all transactions were counted for.
-    /// The value is saved in the DB at the final row
-    pub fn get_last_balance(&self, conn: &Connection) -> Vec<String> {
+    /// The value is saved in the DB at the final row. The total is the liquid balance converted
+    /// to the base currency
+    pub fn get_last_balance(
+        &self,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Vec<String> {
         let mut balance_data = vec!["Balance".to_string()];
+        let all_tx_methods = get_all_tx_methods(conn);
+        let liability_methods = get_liability_tx_methods(conn);
         let db_data = get_last_balances(conn);
-        let mut total_balance = 0.0;
-        for i in &db_data {
-            let num_balance = i.parse::<f64>().unwrap();
-            total_balance += num_balance;
-            balance_data.push(format!("{num_balance:.2}",));
+        let mut total_balance = Cent::default();
+        for (i, method) in db_data.iter().zip(all_tx_methods.iter()) {
+            let num_balance = i.parse::<Cent>().unwrap();
+            if !liability_methods.contains(method) {
+                total_balance += converter.to_base(num_balance, method);
+            }
+            balance_data.push(num_balance.to_string());
+        }
+        balance_data.push(total_balance.to_string());
+        balance_data
+    }
+
+    /// returns the market value of all holdings on the date of the given index or with the latest
+    /// prices if none is selected. Converted to the base currency. None if there are no holdings
+    pub fn get_market_value(
+        &self,
+        index: Option<usize>,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Option<Cent> {
+        let market_values = MarketValues::new(conn);
+
+        if market_values.is_empty() {
+            return None;
+        }
+
+        let date = index
+            .map(|index| NaiveDate::parse_from_str(&self.all_tx[index][0], "%d-%m-%Y").unwrap());
+
+        Some(market_values.total_value(date, converter))
+    }
+
+    /// returns the net worth for the given index or after all txs if none is selected. Assets and
+    /// the market value of the holdings are added and the debt of the liabilities is subtracted.
+    /// Converted to the base currency
+    pub fn get_net_worth(
+        &self,
+        index: Option<usize>,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Cent {
+        let all_tx_methods = get_all_tx_methods(conn);
+
+        let balances = match index {
+            Some(index) => self.all_balance[index].clone(),
+            None => get_last_balances(conn),
+        };
+
+        // A liability balance is already negative while there is debt on it
+        balances
+            .iter()
+            .zip(all_tx_methods.iter())
+            .map(|(balance, method)| converter.to_base(balance.parse().unwrap(), method))
+            .sum::<Cent>()
+            + self
+                .get_market_value(index, converter, conn)
+                .unwrap_or_default()
+    }
+
+    /// returns the balance without the pending txs for the given index or the absolute final
+    /// balance without any pending tx if none is selected. The total is converted to the base currency
+    pub fn get_cleared_balance(
+        &self,
+        index: Option<usize>,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Vec<String> {
+        let mut balance_data = vec!["Cleared".to_string()];
+
+        let balance = match index {
+            Some(index) => self.get_balance(index, converter, conn),
+            None => self.get_last_balance(converter, conn),
+        };
+
+        // Only the pending txs up to the selected tx have changed its balance
+        let until = index.map(|index| {
+            (
+                reverse_date_format(self.all_tx[index][0].clone()),
+                self.get_id_num(index),
+            )
+        });
+
+        let pending_changes = get_pending_changes(
+            until
+                .as_ref()
+                .map(|(date, id_num)| (date.as_str(), *id_num)),
+            conn,
+        )
+        .expect("could not get the pending txs");
+
+        let all_tx_methods = get_all_tx_methods(conn);
+        let liability_methods = get_liability_tx_methods(conn);
+        let mut total_balance = Cent::default();
+
+        // The first value is the row name and the last one is the total
+        for (balance, method) in balance[1..].iter().zip(all_tx_methods.iter()) {
+            let cleared_balance = balance.parse::<Cent>().unwrap()
+                - pending_changes.get(method).copied().unwrap_or_default();
+            if !liability_methods.contains(method) {
+                total_balance += converter.to_base(cleared_balance, method);
+            }
+            balance_data.push(cleared_balance.to_string());
         }
-        balance_data.push(format!("{total_balance:.2}",));
+        balance_data.push(total_balance.to_string());
         balance_data
     }
 
@@ -106,7 +229,12 @@ impl TransactionData {
 
     /// returns total incomes for the selected month by going through all the tx saved in the struct
     // Computes the total income and returns it as a vector of strings.
-    pub fn get_total_income(&self, current_index: Option<usize>, conn: &Connection) -> Vec<String> {
+    pub fn get_total_income(
+        &self,
+        current_index: Option<usize>,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Vec<String> {
         // Initialize the output vector with the title "Income".
         let mut final_income = vec!["Income".to_string()];
         let mut income_data = HashMap::new();
@@ -114,7 +242,7 @@ impl TransactionData {
         // Get all transaction methods from the database and set 0 as the default value
         let all_tx_methods = get_all_tx_methods(conn);
         for method in &all_tx_methods {
-            income_data.insert(method, 0.0);
+            income_data.insert(method, Cent::default());
         }
 
         // Compute the stopping index based on the current index, if present.
@@ -124,14 +252,14 @@ impl TransactionData {
         }
 
         // Iterate over all transactions and accumulate the total income.
-        let mut total_income = 0.0_f64;
+        let mut total_income = Cent::default();
         for tx in &self.all_tx {
             let tx_type = &tx[4];
 
             if tx_type == "Income" {
                 let method = &tx[2];
-                let amount = tx[3].parse::<f64>().unwrap();
-                total_income += amount;
+                let amount = tx[3].parse::<Cent>().unwrap();
+                total_income += converter.to_base(amount, method);
                 *income_data.get_mut(method).unwrap() += amount;
             }
 
@@ -144,11 +272,11 @@ impl TransactionData {
         }
 
         for i in &all_tx_methods {
-            final_income.push(format!("{:.2}", income_data[i]));
+            final_income.push(income_data[i].to_string());
         }
 
         // Add the computed total income to the output vector.
-        final_income.push(format!("{total_income:.2}"));
+        final_income.push(total_income.to_string());
         final_income
     }
 
@@ -157,6 +285,7 @@ impl TransactionData {
     pub fn get_total_expense(
         &self,
         current_index: Option<usize>,
+        converter: &CurrencyConverter,
         conn: &Connection,
     ) -> Vec<String> {
         // Initialize the output vector with the title "Expense".
@@ -166,7 +295,7 @@ impl TransactionData {
         // Get all transaction methods from the database and set 0 as the default value
         let all_tx_methods = get_all_tx_methods(conn);
         for method in &all_tx_methods {
-            expense_data.insert(method, 0.0);
+            expense_data.insert(method, Cent::default());
         }
 
         // Compute the stopping index based on the current index, if present.
@@ -176,14 +305,14 @@ impl TransactionData {
         }
 
         // Iterate over all transactions and accumulate the total expense.
-        let mut total_expense = 0.0_f64;
+        let mut total_expense = Cent::default();
         for tx in &self.all_tx {
             let tx_type = &tx[4];
 
             if tx_type == "Expense" {
                 let method = &tx[2];
-                let amount = tx[3].parse::<f64>().unwrap();
-                total_expense += amount;
+                let amount = tx[3].parse::<Cent>().unwrap();
+                total_expense += converter.to_base(amount, method);
                 *expense_data.get_mut(method).unwrap() += amount;
             }
 
@@ -196,11 +325,11 @@ impl TransactionData {
         }
 
         for i in &all_tx_methods {
-            final_expense.push(format!("{:.2}", expense_data[i]));
+            final_expense.push(expense_data[i].to_string());
         }
 
         // Add the computed total expense to the output vector.
-        final_expense.push(format!("{total_expense:.2}"));
+        final_expense.push(total_expense.to_string());
         final_expense
     }
 
@@ -212,6 +341,7 @@ impl TransactionData {
     pub fn get_daily_expense(
         &self,
         current_index: Option<usize>,
+        converter: &CurrencyConverter,
         conn: &Connection,
     ) -> Vec<String> {
         let all_tx_methods = get_all_tx_methods(conn);
@@ -226,11 +356,11 @@ impl TransactionData {
         };
 
         let mut expense_data = HashMap::new();
-        let mut total_expense = 0.0_f64;
+        let mut total_expense = Cent::default();
 
         // Get all transaction methods, set 0 as the default value
         for method in &all_tx_methods {
-            expense_data.insert(method, 0.0);
+            expense_data.insert(method, Cent::default());
         }
 
         let ongoing_date = self.all_tx[index][0].clone();
@@ -250,8 +380,8 @@ impl TransactionData {
                     break;
                 }
 
-                let parsed_amount = amount.parse::<f64>().unwrap();
-                total_expense += parsed_amount;
+                let parsed_amount = amount.parse::<Cent>().unwrap();
+                total_expense += converter.to_base(parsed_amount, tx_method);
                 *expense_data.get_mut(tx_method).unwrap() += parsed_amount;
             }
 
@@ -263,16 +393,21 @@ impl TransactionData {
         }
 
         for i in &all_tx_methods {
-            final_expense.push(format!("{:.2}", expense_data[i]));
+            final_expense.push(expense_data[i].to_string());
         }
 
         // Add the computed total expense to the output vector.
-        final_expense.push(format!("{total_expense:.2}"));
+        final_expense.push(total_expense.to_string());
         final_expense
     }
 
     /// Returns total income accumulated till the current scrolling table index on the ongoing date
-    pub fn get_daily_income(&self, current_index: Option<usize>, conn: &Connection) -> Vec<String> {
+    pub fn get_daily_income(
+        &self,
+        current_index: Option<usize>,
+        converter: &CurrencyConverter,
+        conn: &Connection,
+    ) -> Vec<String> {
         let all_tx_methods = get_all_tx_methods(conn);
         let mut final_income: Vec<String> = vec!["Daily Income".to_string()];
 
@@ -284,11 +419,11 @@ impl TransactionData {
         };
 
         let mut income_data = HashMap::new();
-        let mut total_income = 0.0_f64;
+        let mut total_income = Cent::default();
 
         // Get all transaction methods and set 0 as the default value
         for method in &all_tx_methods {
-            income_data.insert(method, 0.0);
+            income_data.insert(method, Cent::default());
         }
 
         let ongoing_date = self.all_tx[index][0].clone();
@@ -307,8 +442,8 @@ impl TransactionData {
                     break;
                 }
 
-                let parsed_amount = amount.parse::<f64>().unwrap();
-                total_income += parsed_amount;
+                let parsed_amount = amount.parse::<Cent>().unwrap();
+                total_income += converter.to_base(parsed_amount, tx_method);
                 *income_data.get_mut(tx_method).unwrap() += parsed_amount;
             }
 
@@ -319,11 +454,11 @@ impl TransactionData {
         }
 
         for i in &all_tx_methods {
-            final_income.push(format!("{:.2}", income_data[i]));
+            final_income.push(income_data[i].to_string());
         }
 
         // Add the computed total expense to the output vector.
-        final_income.push(format!("{total_income:.2}"));
+        final_income.push(total_income.to_string());
         final_income
     }
 }
@@ -8,10 +8,14 @@ use thousands::Separable;
 use crate::page_handler::{
     HomeRow, HomeTab, IndexedData, TableData, BACKGROUND, BLUE, BOX, HEADER, RED, SELECTED, TEXT,
 };
-use crate::utility::{create_tab, get_all_tx_methods, main_block, styled_block};
+use crate::utility::{
+    create_tab, get_all_tx_methods, is_expense_like, is_income_like, main_block, styled_block,
+    Cent, CurrencyConverter,
+};
 
-pub const BALANCE_BOLD: [&str; 7] = [
+pub const BALANCE_BOLD: [&str; 8] = [
     "Balance",
+    "Cleared",
     "Changes",
     "Total",
     "Income",
@@ -29,6 +33,8 @@ pub fn home_ui(
     years: &IndexedData,
     table: &mut TableData,
     balance: &mut [Vec<String>],
+    net_worth: Cent,
+    market_value: Option<Cent>,
     current_tab: &HomeTab,
     width_data: &mut [Constraint],
     balance_load: &mut [f64],
@@ -53,6 +59,8 @@ pub fn home_ui(
     conn: &Connection,
 ) {
     let all_methods = get_all_tx_methods(conn);
+    let converter = CurrencyConverter::new(conn);
+    let total_header = converter.total_header();
     let size = f.area();
 
     // Used to highlight Changes on Balance section of Home Page
@@ -66,9 +74,17 @@ pub fn home_ui(
     }
 
     // Transaction widget's top row/header to highlight what each data will mean
-    let header_cells = ["Date", "Details", "TX Method", "Amount", "Type", "Tags"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+    let header_cells = [
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Tags",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
 
     let header = Row::new(header_cells)
         .style(Style::default().bg(HEADER))
@@ -121,11 +137,12 @@ pub fn home_ui(
         rows,
         [
             Constraint::Percentage(10),
-            Constraint::Percentage(37),
-            Constraint::Percentage(13),
+            Constraint::Percentage(33),
             Constraint::Percentage(13),
+            Constraint::Percentage(12),
             Constraint::Percentage(8),
-            Constraint::Percentage(18),
+            Constraint::Percentage(14),
+            Constraint::Percentage(10),
         ],
     )
     .header(header)
@@ -231,6 +248,7 @@ pub fn home_ui(
             let c = if row_type != HomeRow::TopRow
                 && ![
                     "Balance",
+                    "Cleared",
                     "Changes",
                     "Income",
                     "Expense",
@@ -373,7 +391,10 @@ pub fn home_ui(
                 Cell::from(c).style(Style::default().fg(BLUE))
             } else if c.contains('↓') {
                 Cell::from(c).style(Style::default().fg(RED))
-            } else if all_methods.contains(&c) || BALANCE_BOLD.contains(&c.as_str()) {
+            } else if all_methods.contains(&c)
+                || BALANCE_BOLD.contains(&c.as_str())
+                || c == total_header
+            {
                 Cell::from(c).style(Style::default().add_modifier(Modifier::BOLD))
             } else {
                 Cell::from(c)
@@ -387,8 +408,27 @@ pub fn home_ui(
 
     // use the acquired width data to allocated spaces
     // between columns on Balance widget.
+    // The Total column only has the liquid balance, the net worth also counts the liabilities
+    // and the market value of the holdings
+    let mut balance_name = match market_value {
+        Some(market_value) => format!(
+            "Balance | Market Value: {} | Net Worth: {}",
+            market_value.to_string().separate_with_commas(),
+            net_worth.to_string().separate_with_commas()
+        ),
+        None => format!(
+            "Balance | Net Worth: {}",
+            net_worth.to_string().separate_with_commas()
+        ),
+    };
+
+    // The totals leave out the tx methods without an exchange rate
+    if let Some(note) = converter.missing_rates_note() {
+        balance_name = format!("{balance_name} | {note}");
+    }
+
     let balance_area = Table::new(bal_data, width_data.to_owned())
-        .block(styled_block("Balance"))
+        .block(styled_block(&balance_name))
         .style(Style::default().fg(BOX));
 
     match current_tab {
@@ -407,9 +447,9 @@ pub fn home_ui(
         HomeTab::Table => {
             if let Some(a) = table.state.selected() {
                 table_area = table_area.highlight_symbol(">> ");
-                if table.items[a][4] == "Expense" {
+                if is_expense_like(&table.items[a][4]) {
                     table_area = table_area.row_highlight_style(selected_style_expense);
-                } else if table.items[a][4] == "Income" {
+                } else if is_income_like(&table.items[a][4]) {
                     table_area = table_area.row_highlight_style(selected_style_income);
                 } else if table.items[a][4] == "Transfer" {
                     table_area = table_area.row_highlight_style(Style::default().bg(SELECTED));
@@ -14,7 +14,14 @@ pub fn activity_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
             KeyCode::Char('h') => handler.do_help_popup(),
             KeyCode::Char('z') => handler.go_summary(),
             KeyCode::Char('w') => handler.go_search(),
+            KeyCode::Char('o') => handler.go_recurring(),
+            KeyCode::Char('b') => handler.go_budget(),
+            KeyCode::Char('g') => handler.go_goal(),
+            KeyCode::Char('k') => handler.go_reconcile(),
+            KeyCode::Char('i') => handler.go_debt(),
+            KeyCode::Char('T') => handler.go_tag(),
             KeyCode::Char('v') => handler.show_activity_tx_details(),
+            KeyCode::Char('e') => handler.activity_restore_tx(),
             KeyCode::Right => handler.handle_right_arrow(),
             KeyCode::Left => handler.handle_left_arrow(),
             KeyCode::Up => handler.handle_up_arrow(),
@@ -20,12 +20,14 @@ pub fn add_tx_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
                 KeyCode::Char('w') => handler.go_search(),
                 KeyCode::Char('c') => handler.clear_input(),
                 KeyCode::Char('y') => handler.go_activity(),
+                KeyCode::Char('o') => handler.go_recurring(),
+                KeyCode::Char('b') => handler.go_budget(),
+                KeyCode::Char('g') => handler.go_goal(),
+                KeyCode::Char('k') => handler.go_reconcile(),
+                KeyCode::Char('i') => handler.go_debt(),
+                KeyCode::Char('T') => handler.go_tag(),
                 KeyCode::Enter => handler.select_date_field(),
-                KeyCode::Char(c) => {
-                    if c.is_numeric() {
-                        handler.handle_number_press();
-                    }
-                }
+                KeyCode::Char(c) if c.is_numeric() => handler.handle_number_press(),
                 _ => {}
             },
             _ => match handler.key.code {
@@ -41,6 +43,8 @@ pub fn add_tx_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
                     TxTab::Amount => handler.handle_amount(),
                     TxTab::TxType => handler.handle_tx_type(),
                     TxTab::Tags => handler.handle_tags(),
+                    TxTab::Splits => handler.handle_splits(),
+                    TxTab::Status => handler.handle_status(),
                     TxTab::Nothing => {}
                 },
             },
@@ -0,0 +1,47 @@
+use crossterm::event::KeyCode;
+
+use crate::key_checker::InputKeyHandler;
+use crate::outputs::HandlingOutput;
+use crate::page_handler::PopupState;
+
+/// Tracks the keys of the Budget page and calls relevant function based on it
+#[cfg(not(tarpaulin_include))]
+pub fn budget_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
+    match handler.popup {
+        PopupState::Nothing => match handler.key.code {
+            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
+            KeyCode::Char('f') => handler.go_home(),
+            KeyCode::Char('a') => handler.go_add_tx(),
+            KeyCode::Char('r') => handler.go_chart(),
+            KeyCode::Char('h') => handler.do_help_popup(),
+            KeyCode::Char('z') => handler.go_summary(),
+            KeyCode::Char('w') => handler.go_search(),
+            KeyCode::Char('y') => handler.go_activity(),
+            KeyCode::Char('o') => handler.go_recurring(),
+            KeyCode::Char('g') => handler.go_goal(),
+            KeyCode::Char('k') => handler.go_reconcile(),
+            KeyCode::Char('i') => handler.go_debt(),
+            KeyCode::Char('T') => handler.go_tag(),
+            KeyCode::Char('n') => return Some(HandlingOutput::EditBudget(None)),
+            KeyCode::Char('e') => return handler.budget_edit(),
+            KeyCode::Char('d') => handler.do_deletion_popup(),
+            KeyCode::Right => handler.handle_right_arrow(),
+            KeyCode::Left => handler.handle_left_arrow(),
+            KeyCode::Up => handler.handle_up_arrow(),
+            KeyCode::Down => handler.handle_down_arrow(),
+            _ => {}
+        },
+        PopupState::TxDeletion => match handler.key.code {
+            KeyCode::Left | KeyCode::Right | KeyCode::Enter => handler.handle_deletion_popup(),
+            _ => {}
+        },
+        PopupState::BudgetHelp => match handler.key.code {
+            KeyCode::Up => handler.popup_scroll_up(),
+            KeyCode::Down => handler.popup_scroll_down(),
+            _ => handler.do_empty_popup(),
+        },
+        _ => handler.do_empty_popup(),
+    }
+
+    None
+}
@@ -17,11 +17,20 @@ pub fn chart_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
             KeyCode::Char('r') => handler.do_chart_hidden_mode(),
             KeyCode::Char('w') => handler.go_search(),
             KeyCode::Char('y') => handler.go_activity(),
+            KeyCode::Char('o') => handler.go_recurring(),
+            KeyCode::Char('b') => handler.go_budget(),
+            KeyCode::Char('g') => handler.go_goal(),
+            KeyCode::Char('k') => handler.go_reconcile(),
+            KeyCode::Char('i') => handler.go_debt(),
+            KeyCode::Char('T') => handler.go_tag(),
             KeyCode::Right => handler.handle_right_arrow(),
             KeyCode::Left => handler.handle_left_arrow(),
             KeyCode::Up => handler.handle_up_arrow(),
             KeyCode::Down => handler.handle_down_arrow(),
             KeyCode::Char(' ') => handler.switch_chart_tx_method_activation(),
+            KeyCode::Char('t') => handler.change_chart_tag(),
+            KeyCode::Char('l') => handler.change_chart_goal(),
+            KeyCode::Char('m') => handler.switch_chart_market_value(),
             _ => {}
         },
         PopupState::ChartHelp => match handler.key.code {
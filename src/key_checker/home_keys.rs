@@ -13,15 +13,25 @@ pub fn home_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
             KeyCode::Char('a') => handler.go_add_tx(),
             KeyCode::Char('r') => handler.go_chart(),
             KeyCode::Char('j') => return Some(HandlingOutput::TakeUserInput),
+            KeyCode::Char('p') => return Some(HandlingOutput::ExportTxs(None)),
             KeyCode::Char('h') => handler.do_help_popup(),
             KeyCode::Char('z') => handler.go_summary(),
             KeyCode::Char('w') => handler.go_search(),
             KeyCode::Char('e') => handler.home_edit_tx(),
             KeyCode::Char('d') => handler.do_deletion_popup(),
             KeyCode::Char('y') => handler.go_activity(),
+            KeyCode::Char('o') => handler.go_recurring(),
+            KeyCode::Char('b') => handler.go_budget(),
+            KeyCode::Char('g') => handler.go_goal(),
+            KeyCode::Char('k') => handler.go_reconcile(),
+            KeyCode::Char('i') => handler.go_debt(),
+            KeyCode::Char('T') => handler.go_tag(),
             KeyCode::Char(',') => handler.switch_tx_index_up(),
             KeyCode::Char('.') => handler.switch_tx_index_down(),
             KeyCode::Char('v') => handler.show_home_tx_details(),
+            KeyCode::Char('c') => handler.switch_cleared_balance(),
+            KeyCode::Char('u') => handler.home_undo(),
+            KeyCode::Char('U') => handler.home_redo(),
             KeyCode::Right => handler.handle_right_arrow(),
             KeyCode::Left => handler.handle_left_arrow(),
             KeyCode::Up => handler.handle_up_arrow(),
@@ -32,7 +42,7 @@ pub fn home_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
             KeyCode::Left | KeyCode::Right | KeyCode::Enter => handler.handle_deletion_popup(),
             _ => {}
         },
-        PopupState::HomeHelp => match handler.key.code {
+        PopupState::HomeHelp | PopupState::BudgetWarning(_) => match handler.key.code {
             KeyCode::Up => handler.popup_scroll_up(),
             KeyCode::Down => handler.popup_scroll_down(),
             _ => handler.do_empty_popup(),
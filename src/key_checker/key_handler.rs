@@ -1,21 +1,34 @@
+use chrono::{Datelike, Local, NaiveDate};
 use crossterm::event::{KeyCode, KeyEvent};
 use rusqlite::Connection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::activity_page::ActivityData;
+use crate::budget_page::BudgetData;
 use crate::chart_page::ChartData;
+use crate::db::{
+    check_tx_editable, delete_budget, delete_goal, delete_recurring_tx, get_all_goals,
+    get_tx_splits, reconcile_txs, remove_tag, set_recurring_paused, Reconciliation, TAG_SEPARATOR,
+};
+use crate::debt_page::DebtData;
+use crate::goal_page::GoalData;
 use crate::home_page::TransactionData;
 use crate::outputs::TxType;
-use crate::outputs::{HandlingOutput, TxUpdateError, VerifyingOutput};
+use crate::outputs::{HandlingOutput, HistoryError, TxUpdateError, VerifyingOutput};
 use crate::page_handler::{
-    ActivityTab, ActivityType, ChartTab, CurrentUi, DateType, DeletionStatus, HomeTab, IndexedData,
-    PopupState, SortingType, SummaryTab, TableData, TxTab,
+    ActivityTab, ActivityType, BudgetTab, ChartTab, CurrentUi, DateType, DeletionStatus, HomeTab,
+    IndexedData, PopupState, SortingType, SummaryTab, TableData, TxTab,
 };
+use crate::reconcile_page::ReconcileData;
+use crate::recurring_page::RecurringData;
 use crate::summary_page::SummaryData;
-use crate::tx_handler::TxData;
+use crate::tag_page::TagData;
+use crate::tx_handler::{redo_activity, restore_deleted_tx, revert_edit, undo_activity, TxData};
 use crate::utility::{
-    add_new_activity, add_new_activity_tx, get_all_tx_methods, get_all_tx_methods_cumulative,
-    get_empty_changes, sort_table_data, switch_tx_index,
+    add_new_activity, add_new_activity_tx, get_active_tx_methods, get_all_tags, get_all_tx_methods,
+    get_all_tx_methods_cumulative, get_empty_changes, get_parent_tags, get_tx_id_num,
+    get_year_range, hide_archived_columns, hide_collapsed_tags, sort_table_data, switch_tx_index,
+    CurrencyConverter,
 };
 
 /// Stores all the data that is required to handle
@@ -56,10 +69,31 @@ pub struct InputKeyHandler<'a> {
     activity_tab: &'a mut ActivityTab,
     activity_data: &'a mut ActivityData,
     activity_table: &'a mut TableData,
+    recurring_data: &'a mut RecurringData,
+    recurring_table: &'a mut TableData,
+    budget_months: &'a mut IndexedData,
+    budget_years: &'a mut IndexedData,
+    budget_tab: &'a mut BudgetTab,
+    budget_data: &'a mut BudgetData,
+    budget_table: &'a mut TableData,
+    goal_data: &'a mut GoalData,
+    goal_table: &'a mut TableData,
+    reconciliation: &'a mut Option<Reconciliation>,
+    reconcile_data: &'a mut ReconcileData,
+    reconcile_table: &'a mut TableData,
+    debt_data: &'a mut DebtData,
+    debt_table: &'a mut TableData,
+    tag_data: &'a mut TagData,
+    tag_table: &'a mut TableData,
     total_tags: usize,
     chart_index: &'a mut Option<f64>,
     chart_hidden_mode: &'a mut bool,
     summary_hidden_mode: &'a mut bool,
+    summary_collapsed_tags: &'a mut HashSet<String>,
+    chart_tag: &'a mut Option<String>,
+    chart_goal: &'a mut Option<i32>,
+    chart_market_value: &'a mut bool,
+    cleared_balance: &'a mut bool,
     deletion_status: &'a mut DeletionStatus,
     ongoing_balance: &'a mut Vec<String>,
     ongoing_changes: &'a mut Vec<String>,
@@ -110,9 +144,30 @@ impl<'a> InputKeyHandler<'a> {
         activity_tab: &'a mut ActivityTab,
         activity_data: &'a mut ActivityData,
         activity_table: &'a mut TableData,
+        recurring_data: &'a mut RecurringData,
+        recurring_table: &'a mut TableData,
+        budget_months: &'a mut IndexedData,
+        budget_years: &'a mut IndexedData,
+        budget_tab: &'a mut BudgetTab,
+        budget_data: &'a mut BudgetData,
+        budget_table: &'a mut TableData,
+        goal_data: &'a mut GoalData,
+        goal_table: &'a mut TableData,
+        reconciliation: &'a mut Option<Reconciliation>,
+        reconcile_data: &'a mut ReconcileData,
+        reconcile_table: &'a mut TableData,
+        debt_data: &'a mut DebtData,
+        debt_table: &'a mut TableData,
+        tag_data: &'a mut TagData,
+        tag_table: &'a mut TableData,
         chart_index: &'a mut Option<f64>,
         chart_hidden_mode: &'a mut bool,
         summary_hidden_mode: &'a mut bool,
+        summary_collapsed_tags: &'a mut HashSet<String>,
+        chart_tag: &'a mut Option<String>,
+        chart_goal: &'a mut Option<i32>,
+        chart_market_value: &'a mut bool,
+        cleared_balance: &'a mut bool,
         deletion_status: &'a mut DeletionStatus,
         ongoing_balance: &'a mut Vec<String>,
         ongoing_changes: &'a mut Vec<String>,
@@ -125,9 +180,11 @@ impl<'a> InputKeyHandler<'a> {
         max_popup_scroll: &'a mut usize,
         conn: &'a mut Connection,
     ) -> InputKeyHandler<'a> {
-        let total_tags = summary_data
-            .get_table_data(summary_modes, summary_months.index, summary_years.index)
-            .len();
+        let total_tags = hide_collapsed_tags(
+            summary_data.get_table_data(summary_modes, summary_months.index, summary_years.index),
+            summary_collapsed_tags,
+        )
+        .len();
         InputKeyHandler {
             key,
             page,
@@ -163,10 +220,31 @@ impl<'a> InputKeyHandler<'a> {
             activity_tab,
             activity_data,
             activity_table,
+            recurring_data,
+            recurring_table,
+            budget_months,
+            budget_years,
+            budget_tab,
+            budget_data,
+            budget_table,
+            goal_data,
+            goal_table,
+            reconciliation,
+            reconcile_data,
+            reconcile_table,
+            debt_data,
+            debt_table,
+            tag_data,
+            tag_table,
             total_tags,
             chart_index,
             chart_hidden_mode,
             summary_hidden_mode,
+            summary_collapsed_tags,
+            chart_tag,
+            chart_goal,
+            chart_market_value,
+            cleared_balance,
             deletion_status,
             ongoing_balance,
             ongoing_changes,
@@ -251,6 +329,48 @@ impl<'a> InputKeyHandler<'a> {
         *self.page = CurrentUi::Activity;
     }
 
+    /// Moves the interface to the Recurring page with the latest recurring tx data
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_recurring(&mut self) {
+        *self.page = CurrentUi::Recurring;
+        self.reload_recurring_table();
+    }
+
+    /// Moves the interface to the Budget page with the latest budget data
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_budget(&mut self) {
+        *self.page = CurrentUi::Budget;
+        self.reload_budget_table();
+    }
+
+    /// Moves the interface to the Goals page with the latest goal progress
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_goal(&mut self) {
+        *self.page = CurrentUi::Goal;
+        self.reload_goal_table();
+    }
+
+    /// Moves the interface to the Reconcile page with the latest unreconciled txs
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_reconcile(&mut self) {
+        *self.page = CurrentUi::Reconcile;
+        self.reload_reconcile_table();
+    }
+
+    /// Moves the interface to the Debts page with the latest outstanding balances
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_debt(&mut self) {
+        *self.page = CurrentUi::Debt;
+        self.reload_debt_table();
+    }
+
+    /// Moves the interface to the Tags page with the latest tag usage
+    #[cfg(not(tarpaulin_include))]
+    pub fn go_tag(&mut self) {
+        *self.page = CurrentUi::Tag;
+        self.reload_tag_table();
+    }
+
     /// Turns on help popup
     #[cfg(not(tarpaulin_include))]
     pub fn do_help_popup(&mut self) {
@@ -261,6 +381,12 @@ impl<'a> InputKeyHandler<'a> {
             CurrentUi::Summary => *self.popup = PopupState::SummaryHelp,
             CurrentUi::Search => *self.popup = PopupState::SearchHelp,
             CurrentUi::Activity => *self.popup = PopupState::ActivityHelp,
+            CurrentUi::Recurring => *self.popup = PopupState::RecurringHelp,
+            CurrentUi::Budget => *self.popup = PopupState::BudgetHelp,
+            CurrentUi::Goal => *self.popup = PopupState::GoalHelp,
+            CurrentUi::Reconcile => *self.popup = PopupState::ReconcileHelp,
+            CurrentUi::Debt => *self.popup = PopupState::DebtHelp,
+            CurrentUi::Tag => *self.popup = PopupState::TagHelp,
             CurrentUi::Initial => {}
         }
     }
@@ -269,15 +395,23 @@ impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
     pub fn do_deletion_popup(&mut self) {
         match self.page {
-            CurrentUi::Home => {
-                if self.table.state.selected().is_some() {
-                    *self.popup = PopupState::TxDeletion;
-                }
+            CurrentUi::Home if self.table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
             }
-            CurrentUi::Search => {
-                if self.search_table.state.selected().is_some() {
-                    *self.popup = PopupState::TxDeletion;
-                }
+            CurrentUi::Search if self.search_table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
+            }
+            CurrentUi::Recurring if self.recurring_table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
+            }
+            CurrentUi::Budget if self.budget_table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
+            }
+            CurrentUi::Goal if self.goal_table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
+            }
+            CurrentUi::Tag if self.tag_table.state.selected().is_some() => {
+                *self.popup = PopupState::TxDeletion;
             }
             _ => {}
         }
@@ -353,9 +487,33 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Exports the txs of the current search result. Nothing to export until a search finds txs
+    #[cfg(not(tarpaulin_include))]
+    pub fn search_export_txs(&mut self) -> Option<HandlingOutput> {
+        if self.search_txs.is_tx_empty() {
+            self.search_data
+                .add_tx_status("Export: Search for transactions to export first".to_string());
+            return None;
+        }
+
+        Some(HandlingOutput::ExportTxs(Some(
+            self.search_txs.get_id_nums(),
+        )))
+    }
+
     /// Adds new tx and reloads home and chart data
     #[cfg(not(tarpaulin_include))]
     pub fn add_tx(&mut self) {
+        let tx_date = NaiveDate::parse_from_str(self.add_tx_data.get_all_texts()[0], "%Y-%m-%d");
+        let is_expense = self.add_tx_data.get_all_texts()[5]
+            .to_lowercase()
+            .starts_with('e');
+
+        let exceeded_before = match tx_date {
+            Ok(date) if is_expense => self.get_exceeded_budget_ids(date),
+            _ => Vec::new(),
+        };
+
         let status = self.add_tx_data.add_tx(self.conn);
 
         match status {
@@ -363,16 +521,80 @@ impl<'a> InputKeyHandler<'a> {
                 self.go_home_reset();
                 // we just added a new tx, select the month tab again + reload the data of balance and table widgets to get updated data
                 *self.home_tab = HomeTab::Months;
+                self.reload_year_lists();
                 self.reload_home_table();
                 self.reload_chart_data();
                 self.reload_summary_data();
                 self.reset_search_data();
                 self.reload_activity_table();
+
+                if let (Ok(date), true) = (tx_date, is_expense) {
+                    self.check_exceeded_budgets(date, &exceeded_before);
+                }
             }
             Err(e) => self.add_tx_data.add_tx_status(e),
         }
     }
 
+    /// Returns the budgets that are over their limit in the month of the given date
+    #[cfg(not(tarpaulin_include))]
+    fn get_budgets_of_month(&self, date: NaiveDate) -> Option<BudgetData> {
+        let (start_year, end_year) = get_year_range(self.conn).ok()?;
+
+        if date.year() < start_year || date.year() > end_year {
+            return None;
+        }
+
+        Some(BudgetData::new(
+            date.month0() as usize,
+            (date.year() - start_year) as usize,
+            self.summary_data,
+            self.conn,
+        ))
+    }
+
+    /// Returns the ids of the budgets that are over their limit in the month of the given date
+    #[cfg(not(tarpaulin_include))]
+    fn get_exceeded_budget_ids(&self, date: NaiveDate) -> Vec<i32> {
+        self.get_budgets_of_month(date)
+            .map(|budget_data| {
+                budget_data
+                    .get_exceeded_budgets()
+                    .iter()
+                    .map(|status| status.budget.budget_id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Shows a warning popup if any budget of the month of the given date went over its limit
+    /// that was not over it before the tx was added
+    #[cfg(not(tarpaulin_include))]
+    fn check_exceeded_budgets(&mut self, date: NaiveDate, exceeded_before: &[i32]) {
+        let Some(budget_data) = self.get_budgets_of_month(date) else {
+            return;
+        };
+
+        let warnings: Vec<String> = budget_data
+            .get_exceeded_budgets()
+            .into_iter()
+            .filter(|status| !exceeded_before.contains(&status.budget.budget_id))
+            .map(|status| {
+                format!(
+                    "{} ({}): Spent {} of {}",
+                    status.budget.tag,
+                    status.budget.period.to_str(),
+                    status.spent,
+                    status.available()
+                )
+            })
+            .collect();
+
+        if !warnings.is_empty() {
+            *self.popup = PopupState::BudgetWarning(warnings.join("\n"));
+        }
+    }
+
     /// Based on transaction Selected, opens Add Tx page and
     /// allocates the data of the tx to the input boxes
     #[cfg(not(tarpaulin_include))]
@@ -382,6 +604,11 @@ impl<'a> InputKeyHandler<'a> {
             let target_id_num = self.all_tx_data.get_id_num(a);
             let tx_type = &target_data[4];
 
+            if let Err(err) = check_tx_editable(target_id_num, self.conn) {
+                *self.popup = PopupState::HistoryFailed(err.to_string());
+                return;
+            }
+
             // based on what kind of transaction is selected, passes the tx data to the struct
             // and change the current interface
             if tx_type != "Transfer" {
@@ -413,6 +640,8 @@ impl<'a> InputKeyHandler<'a> {
                 );
                 *self.page = CurrentUi::AddTx;
             }
+            self.add_tx_data.load_splits(self.conn);
+            self.add_tx_data.load_status(self.conn);
             self.add_tx_data.add_tx_status(
                 "Info: Entering Transaction edit mode. Press C to reset.".to_string(),
             );
@@ -424,9 +653,13 @@ impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
     pub fn home_delete_tx(&mut self) {
         if let Some(index) = self.table.state.selected() {
-            let mut tx_data = self.all_tx_data.get_tx(index).to_owned();
             let id_num = self.all_tx_data.get_id_num(index);
-            tx_data.push(id_num.to_string());
+            let tx_data = get_tx_id_num(id_num, self.conn);
+
+            if let Err(err) = check_tx_editable(id_num, self.conn) {
+                *self.popup = PopupState::DeleteFailed(err.to_string());
+                return;
+            }
 
             let status = self.all_tx_data.del_tx(index, self.conn);
             match status {
@@ -469,6 +702,8 @@ impl<'a> InputKeyHandler<'a> {
                     KeyCode::Char('4') => *self.add_tx_tab = TxTab::FromMethod,
                     KeyCode::Char('5') => *self.add_tx_tab = TxTab::Amount,
                     KeyCode::Char('6') => *self.add_tx_tab = TxTab::Tags,
+                    KeyCode::Char('7') => *self.add_tx_tab = TxTab::Splits,
+                    KeyCode::Char('8') => *self.add_tx_tab = TxTab::Status,
                     _ => {}
                 },
                 TxType::Transfer => match self.key.code {
@@ -479,6 +714,7 @@ impl<'a> InputKeyHandler<'a> {
                     KeyCode::Char('5') => *self.add_tx_tab = TxTab::ToMethod,
                     KeyCode::Char('6') => *self.add_tx_tab = TxTab::Amount,
                     KeyCode::Char('7') => *self.add_tx_tab = TxTab::Tags,
+                    KeyCode::Char('8') => *self.add_tx_tab = TxTab::Status,
                     _ => {}
                 },
             },
@@ -490,6 +726,7 @@ impl<'a> InputKeyHandler<'a> {
                     KeyCode::Char('4') => *self.search_tab = TxTab::FromMethod,
                     KeyCode::Char('5') => *self.search_tab = TxTab::Amount,
                     KeyCode::Char('6') => *self.search_tab = TxTab::Tags,
+                    KeyCode::Char('7') => *self.search_tab = TxTab::Status,
                     _ => {}
                 },
                 TxType::Transfer => match self.key.code {
@@ -500,6 +737,7 @@ impl<'a> InputKeyHandler<'a> {
                     KeyCode::Char('5') => *self.search_tab = TxTab::ToMethod,
                     KeyCode::Char('6') => *self.search_tab = TxTab::Amount,
                     KeyCode::Char('7') => *self.search_tab = TxTab::Tags,
+                    KeyCode::Char('8') => *self.search_tab = TxTab::Status,
                     _ => {}
                 },
             },
@@ -581,7 +819,24 @@ impl<'a> InputKeyHandler<'a> {
                 }
                 ActivityTab::List => {}
             },
-            CurrentUi::Initial => {}
+            CurrentUi::Budget => match self.budget_tab {
+                BudgetTab::Years => {
+                    self.budget_months.set_index_zero();
+                    self.budget_years.previous();
+                    self.reload_budget_table();
+                }
+                BudgetTab::Months => {
+                    self.budget_months.previous();
+                    self.reload_budget_table();
+                }
+                BudgetTab::List => {}
+            },
+            CurrentUi::Initial
+            | CurrentUi::Recurring
+            | CurrentUi::Goal
+            | CurrentUi::Reconcile
+            | CurrentUi::Debt
+            | CurrentUi::Tag => {}
         }
     }
 
@@ -653,7 +908,24 @@ impl<'a> InputKeyHandler<'a> {
                 }
                 ActivityTab::List => {}
             },
-            CurrentUi::Initial => {}
+            CurrentUi::Budget => match self.budget_tab {
+                BudgetTab::Years => {
+                    self.budget_months.set_index_zero();
+                    self.budget_years.next();
+                    self.reload_budget_table();
+                }
+                BudgetTab::Months => {
+                    self.budget_months.next();
+                    self.reload_budget_table();
+                }
+                BudgetTab::List => {}
+            },
+            CurrentUi::Initial
+            | CurrentUi::Recurring
+            | CurrentUi::Goal
+            | CurrentUi::Reconcile
+            | CurrentUi::Debt
+            | CurrentUi::Tag => {}
         }
     }
 
@@ -667,6 +939,12 @@ impl<'a> InputKeyHandler<'a> {
             CurrentUi::Chart => self.do_chart_up(),
             CurrentUi::Search => self.do_search_up(),
             CurrentUi::Activity => self.do_activity_up(),
+            CurrentUi::Recurring => self.do_recurring_up(),
+            CurrentUi::Budget => self.do_budget_up(),
+            CurrentUi::Goal => self.do_goal_up(),
+            CurrentUi::Reconcile => self.do_reconcile_up(),
+            CurrentUi::Debt => self.do_debt_up(),
+            CurrentUi::Tag => self.do_tag_up(),
             CurrentUi::Initial => {}
         }
         self.check_autofill();
@@ -682,6 +960,12 @@ impl<'a> InputKeyHandler<'a> {
             CurrentUi::Chart => self.do_chart_down(),
             CurrentUi::Search => self.do_search_down(),
             CurrentUi::Activity => self.do_activity_down(),
+            CurrentUi::Recurring => self.do_recurring_down(),
+            CurrentUi::Budget => self.do_budget_down(),
+            CurrentUi::Goal => self.do_goal_down(),
+            CurrentUi::Reconcile => self.do_reconcile_down(),
+            CurrentUi::Debt => self.do_debt_down(),
+            CurrentUi::Tag => self.do_tag_down(),
             CurrentUi::Initial => {}
         }
         self.check_autofill();
@@ -758,6 +1042,24 @@ impl<'a> InputKeyHandler<'a> {
         self.check_autofill();
     }
 
+    /// Checks and verifies splits field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_splits(&mut self) {
+        if let CurrentUi::AddTx = self.page {
+            self.check_add_tx_splits();
+        }
+    }
+
+    /// Checks and verifies status field
+    #[cfg(not(tarpaulin_include))]
+    pub fn handle_status(&mut self) {
+        match self.page {
+            CurrentUi::AddTx => self.check_add_tx_status(),
+            CurrentUi::Search => self.check_search_status(),
+            _ => {}
+        }
+    }
+
     /// Resets all input boxes on Add Tx and Transfer page
     #[cfg(not(tarpaulin_include))]
     pub fn clear_input(&mut self) {
@@ -822,6 +1124,111 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Collapses or expands the child tags of the selected parent tag on the Summary page table
+    #[cfg(not(tarpaulin_include))]
+    pub fn toggle_summary_tag(&mut self) {
+        if let SummaryTab::Table = self.summary_tab {
+            if let Some(index) = self.summary_table.state.selected() {
+                let tag_name = self.summary_table.items[index][0].clone();
+
+                let all_tags = self.summary_data.get_table_data(
+                    self.summary_modes,
+                    self.summary_months.index,
+                    self.summary_years.index,
+                );
+
+                let has_children = all_tags
+                    .iter()
+                    .any(|row| get_parent_tags(&row[0]).contains(&tag_name));
+
+                if !has_children {
+                    return;
+                }
+
+                if !self.summary_collapsed_tags.remove(&tag_name) {
+                    self.summary_collapsed_tags.insert(tag_name.clone());
+                }
+
+                let summary_table = sort_table_data(
+                    hide_collapsed_tags(all_tags, self.summary_collapsed_tags),
+                    self.summary_sort,
+                );
+                self.total_tags = summary_table.len();
+                *self.summary_table = TableData::new(summary_table);
+
+                let new_index = self
+                    .summary_table
+                    .items
+                    .iter()
+                    .position(|row| row[0] == tag_name);
+                self.summary_table.state.select(new_index);
+            }
+        }
+    }
+
+    /// Filters the chart to the next tag and its child tags. Goes back to all txs after the last tag
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_chart_tag(&mut self) {
+        let mut all_tags: Vec<String> = Vec::new();
+
+        for tag in get_all_tags(self.conn) {
+            for parent in get_parent_tags(&tag) {
+                if !all_tags.contains(&parent) {
+                    all_tags.push(parent);
+                }
+            }
+
+            if !all_tags.contains(&tag) {
+                all_tags.push(tag);
+            }
+        }
+
+        all_tags.sort_by(|a, b| a.split(TAG_SEPARATOR).cmp(b.split(TAG_SEPARATOR)));
+
+        let next_index = match self.chart_tag {
+            Some(tag) => all_tags
+                .iter()
+                .position(|t| t == tag)
+                .map(|index| index + 1),
+            None => Some(0),
+        };
+
+        *self.chart_tag = next_index.and_then(|index| all_tags.get(index).cloned());
+        self.reload_chart_index();
+    }
+
+    /// Draws the target of the next goal as a line on the chart. Goes back to no line after the last goal
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_chart_goal(&mut self) {
+        let all_goals = get_all_goals(self.conn);
+
+        let next_index = match self.chart_goal {
+            Some(goal_id) => all_goals
+                .iter()
+                .position(|goal| goal.goal_id == *goal_id)
+                .map(|index| index + 1),
+            None => Some(0),
+        };
+
+        *self.chart_goal =
+            next_index.and_then(|index| all_goals.get(index).map(|goal| goal.goal_id));
+        self.reload_chart_index();
+    }
+
+    /// Shows or hides the market value of the holdings of each tx method on the chart
+    #[cfg(not(tarpaulin_include))]
+    pub fn switch_chart_market_value(&mut self) {
+        *self.chart_market_value = !*self.chart_market_value;
+        self.reload_chart_index();
+    }
+
+    /// Switches the Home page Balance row between the total balance and the cleared balance
+    #[cfg(not(tarpaulin_include))]
+    pub fn switch_cleared_balance(&mut self) {
+        *self.cleared_balance = !*self.cleared_balance;
+        self.reload_home_balance_data();
+    }
+
     /// Handle keypress when deletion popup is turned on
     #[cfg(not(tarpaulin_include))]
     pub fn handle_deletion_popup(&mut self) {
@@ -839,6 +1246,22 @@ impl<'a> InputKeyHandler<'a> {
                         self.search_delete_tx();
                         *self.popup = PopupState::Nothing;
                     }
+                    CurrentUi::Recurring => {
+                        *self.popup = PopupState::Nothing;
+                        self.recurring_delete_tx();
+                    }
+                    CurrentUi::Budget => {
+                        *self.popup = PopupState::Nothing;
+                        self.budget_delete();
+                    }
+                    CurrentUi::Goal => {
+                        *self.popup = PopupState::Nothing;
+                        self.goal_delete();
+                    }
+                    CurrentUi::Tag => {
+                        *self.popup = PopupState::Nothing;
+                        self.tag_remove();
+                    }
                     _ => {}
                 },
                 DeletionStatus::No => *self.popup = PopupState::Nothing,
@@ -858,8 +1281,15 @@ impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
     pub fn search_edit_tx(&mut self) {
         if let Some(a) = self.search_table.state.selected() {
-            let target_data = &self.search_txs.get_tx(a);
             let target_id_num = self.search_txs.get_id_num(a);
+
+            if let Err(err) = check_tx_editable(target_id_num, self.conn) {
+                *self.popup = PopupState::HistoryFailed(err.to_string());
+                return;
+            }
+
+            // The search result of a split tx may only have the amount of some of its split lines
+            let target_data = &get_tx_id_num(target_id_num, self.conn);
             let tx_type = &target_data[4];
 
             // based on what kind of transaction is selected, passes the tx data to the struct
@@ -893,6 +1323,8 @@ impl<'a> InputKeyHandler<'a> {
                 );
                 *self.page = CurrentUi::AddTx;
             }
+            self.add_tx_data.load_splits(self.conn);
+            self.add_tx_data.load_status(self.conn);
             self.reload_add_tx_balance_load();
         }
     }
@@ -901,6 +1333,11 @@ impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
     pub fn search_delete_tx(&mut self) {
         if let Some(index) = self.search_table.state.selected() {
+            if let Err(err) = check_tx_editable(self.search_txs.get_id_num(index), self.conn) {
+                *self.popup = PopupState::DeleteFailed(err.to_string());
+                return;
+            }
+
             let status = self.search_txs.del_tx(index, self.conn);
             match status {
                 Ok(()) => {
@@ -918,6 +1355,256 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Reverses the last tx change from the Home page
+    #[cfg(not(tarpaulin_include))]
+    pub fn home_undo(&mut self) {
+        let status = undo_activity(self.conn);
+        self.handle_history_status(status);
+    }
+
+    /// Applies the last undone tx change again from the Home page
+    #[cfg(not(tarpaulin_include))]
+    pub fn home_redo(&mut self) {
+        let status = redo_activity(self.conn);
+        self.handle_history_status(status);
+    }
+
+    /// Restores the deleted tx or reverts the edited tx of the selected activity on the Activity page
+    #[cfg(not(tarpaulin_include))]
+    pub fn activity_restore_tx(&mut self) {
+        let Some(index) = self.activity_table.state.selected() else {
+            return;
+        };
+
+        let activity = &self.activity_data.activities[index];
+        let activity_num = activity.activity_num();
+
+        let status = if let ActivityType::EditTX(_) = activity.activity_type() {
+            revert_edit(activity_num, self.conn)
+        } else {
+            restore_deleted_tx(activity_num, self.conn).map(|_| ())
+        };
+
+        match status {
+            Ok(()) => {
+                self.reload_year_lists();
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reset_search_data();
+                self.reload_activity_table();
+                self.activity_table.state.select(Some(index));
+            }
+            Err(err) => *self.popup = PopupState::HistoryFailed(err.to_string()),
+        }
+    }
+
+    /// Pauses the selected recurring tx on the Recurring page or resumes it if it's paused
+    #[cfg(not(tarpaulin_include))]
+    pub fn recurring_toggle_pause(&mut self) {
+        let Some(index) = self.recurring_table.state.selected() else {
+            return;
+        };
+
+        let recurring_tx = self.recurring_data.get_recurring_tx(index);
+        let today = Local::now().date_naive();
+
+        match set_recurring_paused(
+            recurring_tx.recurring_id,
+            !recurring_tx.paused,
+            today,
+            self.conn,
+        ) {
+            Ok(()) => {
+                self.reload_recurring_table();
+                self.recurring_table.state.select(Some(index));
+            }
+            Err(err) => *self.popup = PopupState::RecurringStatus(err.to_string()),
+        }
+    }
+
+    /// Leaves the UI to edit the selected recurring tx on the Recurring page
+    #[cfg(not(tarpaulin_include))]
+    pub fn recurring_edit_tx(&mut self) -> Option<HandlingOutput> {
+        let index = self.recurring_table.state.selected()?;
+        let recurring_id = self.recurring_data.get_recurring_tx(index).recurring_id;
+
+        Some(HandlingOutput::EditRecurringTx(Some(recurring_id)))
+    }
+
+    /// Deletes the selected recurring tx on the Recurring page. The txs it posted are kept
+    #[cfg(not(tarpaulin_include))]
+    fn recurring_delete_tx(&mut self) {
+        let Some(index) = self.recurring_table.state.selected() else {
+            return;
+        };
+
+        let recurring_id = self.recurring_data.get_recurring_tx(index).recurring_id;
+
+        match delete_recurring_tx(recurring_id, self.conn) {
+            Ok(()) => self.reload_recurring_table(),
+            Err(err) => *self.popup = PopupState::DeleteFailed(err.to_string()),
+        }
+    }
+
+    /// Leaves the UI to edit the selected budget on the Budget page
+    #[cfg(not(tarpaulin_include))]
+    pub fn budget_edit(&mut self) -> Option<HandlingOutput> {
+        let index = self.budget_table.state.selected()?;
+        let budget_id = self.budget_data.get_budget(index).budget.budget_id;
+
+        Some(HandlingOutput::EditBudget(Some(budget_id)))
+    }
+
+    /// Deletes the selected budget on the Budget page
+    #[cfg(not(tarpaulin_include))]
+    fn budget_delete(&mut self) {
+        let Some(index) = self.budget_table.state.selected() else {
+            return;
+        };
+
+        let budget_id = self.budget_data.get_budget(index).budget.budget_id;
+
+        match delete_budget(budget_id, self.conn) {
+            Ok(()) => {
+                self.reload_budget_table();
+                *self.budget_tab = BudgetTab::Years;
+            }
+            Err(err) => *self.popup = PopupState::DeleteFailed(err.to_string()),
+        }
+    }
+
+    /// Leaves the UI to edit the selected goal on the Goals page
+    #[cfg(not(tarpaulin_include))]
+    pub fn goal_edit(&mut self) -> Option<HandlingOutput> {
+        let index = self.goal_table.state.selected()?;
+        let goal_id = self.goal_data.get_goal(index).goal.goal_id;
+
+        Some(HandlingOutput::EditGoal(Some(goal_id)))
+    }
+
+    /// Deletes the selected goal on the Goals page
+    #[cfg(not(tarpaulin_include))]
+    fn goal_delete(&mut self) {
+        let Some(index) = self.goal_table.state.selected() else {
+            return;
+        };
+
+        let goal_id = self.goal_data.get_goal(index).goal.goal_id;
+
+        match delete_goal(goal_id, self.conn) {
+            Ok(()) => {
+                if *self.chart_goal == Some(goal_id) {
+                    *self.chart_goal = None;
+                }
+                self.reload_goal_table();
+            }
+            Err(err) => *self.popup = PopupState::DeleteFailed(err.to_string()),
+        }
+    }
+
+    /// Leaves the UI to rename the selected tag on the Tags page
+    #[cfg(not(tarpaulin_include))]
+    pub fn tag_rename(&mut self) -> Option<HandlingOutput> {
+        let index = self.tag_table.state.selected()?;
+        let tag = self.tag_data.get_tag(index).name.clone();
+
+        Some(HandlingOutput::RenameTag(tag))
+    }
+
+    /// Leaves the UI to merge the selected tag on the Tags page into another tag
+    #[cfg(not(tarpaulin_include))]
+    pub fn tag_merge(&mut self) -> Option<HandlingOutput> {
+        let index = self.tag_table.state.selected()?;
+        let tag = self.tag_data.get_tag(index).name.clone();
+
+        Some(HandlingOutput::MergeTag(tag))
+    }
+
+    /// Removes the selected tag on the Tags page from every tx that uses it
+    #[cfg(not(tarpaulin_include))]
+    fn tag_remove(&mut self) {
+        let Some(index) = self.tag_table.state.selected() else {
+            return;
+        };
+
+        let tag = self.tag_data.get_tag(index).name.clone();
+
+        match remove_tag(&tag, self.conn) {
+            Ok(_) => {
+                if self.chart_tag.as_ref() == Some(&tag) {
+                    *self.chart_tag = None;
+                }
+                self.reload_tag_table();
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reset_search_data();
+                self.reload_activity_table();
+            }
+            Err(err) => *self.popup = PopupState::DeleteFailed(err.to_string()),
+        }
+    }
+
+    /// Ticks the selected tx on the Reconcile page or unticks it if it's already ticked
+    #[cfg(not(tarpaulin_include))]
+    pub fn reconcile_toggle_tick(&mut self) {
+        let Some(index) = self.reconcile_table.state.selected() else {
+            return;
+        };
+
+        self.reconcile_data.toggle_tick(index);
+        self.reconcile_table.items = self.reconcile_data.get_txs();
+    }
+
+    /// Reconciles the ticked txs if the statement balance has been reached and ends the reconciliation
+    #[cfg(not(tarpaulin_include))]
+    pub fn reconcile_save(&mut self) {
+        let Some(reconciliation) = self.reconciliation.as_ref() else {
+            return;
+        };
+
+        match reconcile_txs(
+            reconciliation,
+            &self.reconcile_data.ticked_id_nums(),
+            self.conn,
+        ) {
+            Ok(()) => {
+                *self.reconciliation = None;
+                self.reload_reconcile_table();
+                self.reload_home_table();
+                self.reload_activity_table();
+            }
+            Err(err) => *self.popup = PopupState::HistoryFailed(err.to_string()),
+        }
+    }
+
+    /// Ends the ongoing reconciliation without reconciling any tx
+    #[cfg(not(tarpaulin_include))]
+    pub fn reconcile_cancel(&mut self) {
+        *self.reconciliation = None;
+        self.reload_reconcile_table();
+    }
+
+    /// Reloads all tx related data after an undo or redo or shows the error popup
+    #[cfg(not(tarpaulin_include))]
+    fn handle_history_status(&mut self, status: Result<(), HistoryError>) {
+        match status {
+            Ok(()) => {
+                self.reload_year_lists();
+                self.reload_home_table();
+                self.reload_chart_data();
+                self.reload_summary_data();
+                self.reset_search_data();
+                self.reload_activity_table();
+
+                *self.table.state.selected_mut() = None;
+                *self.home_tab = HomeTab::Months;
+            }
+            Err(err) => *self.popup = PopupState::HistoryFailed(err.to_string()),
+        }
+    }
+
     #[cfg(not(tarpaulin_include))]
     pub fn switch_tx_index_up(&mut self) {
         if let Some(index) = self.table.state.selected() {
@@ -937,6 +1624,14 @@ impl<'a> InputKeyHandler<'a> {
             let selected_tx_id = self.all_tx_data.get_id_num(index);
             let previous_tx_id = self.all_tx_data.get_id_num(index - 1);
 
+            // Swapping deletes and adds both txs again so reconciled txs stay where they are
+            if let Err(err) = check_tx_editable(selected_tx_id, self.conn)
+                .and_then(|()| check_tx_editable(previous_tx_id, self.conn))
+            {
+                *self.popup = PopupState::HistoryFailed(err.to_string());
+                return;
+            }
+
             switch_tx_index(
                 selected_tx_id,
                 previous_tx_id,
@@ -970,6 +1665,14 @@ impl<'a> InputKeyHandler<'a> {
             let selected_tx_id = self.all_tx_data.get_id_num(index);
             let next_tx_id = self.all_tx_data.get_id_num(index + 1);
 
+            // Swapping deletes and adds both txs again so reconciled txs stay where they are
+            if let Err(err) = check_tx_editable(selected_tx_id, self.conn)
+                .and_then(|()| check_tx_editable(next_tx_id, self.conn))
+            {
+                *self.popup = PopupState::HistoryFailed(err.to_string());
+                return;
+            }
+
             switch_tx_index(selected_tx_id, next_tx_id, selected_tx, next_tx, self.conn);
 
             self.reload_home_table();
@@ -983,9 +1686,23 @@ impl<'a> InputKeyHandler<'a> {
     pub fn show_home_tx_details(&mut self) {
         if let Some(index) = self.table.state.selected() {
             let selected_tx = self.all_tx_data.get_tx(index);
-            let tx_details = &selected_tx[1];
+            let mut popup_text = selected_tx[1].to_string();
+
+            let splits = get_tx_splits(self.all_tx_data.get_id_num(index), self.conn);
+
+            if !splits.is_empty() {
+                popup_text += "\n\nSplits:";
 
-            *self.popup = PopupState::ShowDetails(tx_details.to_string());
+                for split in splits {
+                    popup_text += &format!("\n{}: {}", split.tag, split.amount);
+
+                    if !split.note.is_empty() {
+                        popup_text += &format!(" ({})", split.note);
+                    }
+                }
+            }
+
+            *self.popup = PopupState::ShowDetails(popup_text);
         }
     }
 
@@ -1490,6 +2207,47 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Handle key inputs for the Splits field on the Add Tx page
+    #[cfg(not(tarpaulin_include))]
+    fn check_add_tx_splits(&mut self) {
+        match self.key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                let status = self.add_tx_data.check_splits();
+                self.add_tx_data.add_tx_status(status.to_string());
+                match status {
+                    VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
+                        *self.add_tx_tab = TxTab::Nothing;
+                        self.reload_add_tx_balance_data();
+                    }
+                    VerifyingOutput::NotAccepted(_) => {}
+                }
+            }
+            KeyCode::Backspace => self.add_tx_data.edit_splits(None),
+            KeyCode::Char(a) => self.add_tx_data.edit_splits(Some(a)),
+            _ => {}
+        }
+    }
+
+    /// Handle key inputs for the Status field on the Add Tx page
+    #[cfg(not(tarpaulin_include))]
+    fn check_add_tx_status(&mut self) {
+        match self.key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                let status = self.add_tx_data.check_status(false);
+                self.add_tx_data.add_tx_status(status.to_string());
+                match status {
+                    VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
+                        *self.add_tx_tab = TxTab::Nothing;
+                    }
+                    VerifyingOutput::NotAccepted(_) => {}
+                }
+            }
+            KeyCode::Backspace => self.add_tx_data.edit_status(None),
+            KeyCode::Char(a) => self.add_tx_data.edit_status(Some(a)),
+            _ => {}
+        }
+    }
+
     /// Handle key inputs for the Date field on the Search page
     #[cfg(not(tarpaulin_include))]
     fn check_search_date(&mut self) {
@@ -1683,6 +2441,26 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    /// Handle key inputs for the Status field on the Search page
+    #[cfg(not(tarpaulin_include))]
+    fn check_search_status(&mut self) {
+        match self.key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                let status = self.search_data.check_status(true);
+                self.search_data.add_tx_status(status.to_string());
+                match status {
+                    VerifyingOutput::Accepted(_) | VerifyingOutput::Nothing(_) => {
+                        *self.search_tab = TxTab::Nothing;
+                    }
+                    VerifyingOutput::NotAccepted(_) => {}
+                }
+            }
+            KeyCode::Backspace => self.search_data.edit_status(None),
+            KeyCode::Char(a) => self.search_data.edit_status(Some(a)),
+            _ => {}
+        }
+    }
+
     /// Reload Home page's table data by fetching from the DB
     #[cfg(not(tarpaulin_include))]
     fn reload_home_table(&mut self) {
@@ -1692,13 +2470,26 @@ impl<'a> InputKeyHandler<'a> {
         self.reload_home_balance_data();
     }
 
+    /// Reload the year lists of all pages as the year range may have grown after adding a tx
+    #[cfg(not(tarpaulin_include))]
+    fn reload_year_lists(&mut self) {
+        self.home_years.reload_yearly(self.conn);
+        self.chart_years.reload_yearly(self.conn);
+        self.summary_years.reload_yearly(self.conn);
+        self.activity_years.reload_yearly(self.conn);
+        self.budget_years.reload_yearly(self.conn);
+    }
+
     /// Reset summary table data by recreating it from gathered Summary Data
     #[cfg(not(tarpaulin_include))]
     fn reload_summary(&mut self) {
-        let summary_table = self.summary_data.get_table_data(
-            self.summary_modes,
-            self.summary_months.index,
-            self.summary_years.index,
+        let summary_table = hide_collapsed_tags(
+            self.summary_data.get_table_data(
+                self.summary_modes,
+                self.summary_months.index,
+                self.summary_years.index,
+            ),
+            self.summary_collapsed_tags,
         );
         self.total_tags = summary_table.len();
         *self.summary_table = TableData::new(summary_table);
@@ -1734,7 +2525,7 @@ impl<'a> InputKeyHandler<'a> {
     #[cfg(not(tarpaulin_include))]
     fn reload_home_balance_load(&mut self) {
         // 0 for all methods + 1 more for the total balance column
-        let balance_data = vec![String::from("0.0"); get_all_tx_methods(self.conn).len() + 1];
+        let balance_data = vec![String::from("0.0"); get_active_tx_methods(self.conn).len() + 1];
         self.ongoing_balance.clone_from(&balance_data);
         *self.ongoing_changes = vec![String::from("0.0"); balance_data.len()];
         self.ongoing_expense.clone_from(&balance_data.clone());
@@ -1755,6 +2546,53 @@ impl<'a> InputKeyHandler<'a> {
         *self.activity_table = TableData::new(self.activity_data.get_txs());
     }
 
+    /// Reload recurring tx data by fetching from the DB
+    #[cfg(not(tarpaulin_include))]
+    fn reload_recurring_table(&mut self) {
+        *self.recurring_data = RecurringData::new(self.conn);
+        *self.recurring_table = TableData::new(self.recurring_data.get_txs());
+    }
+
+    /// Reload budget data of the selected month from the Summary data
+    #[cfg(not(tarpaulin_include))]
+    fn reload_budget_table(&mut self) {
+        *self.budget_data = BudgetData::new(
+            self.budget_months.index,
+            self.budget_years.index,
+            self.summary_data,
+            self.conn,
+        );
+        *self.budget_table = TableData::new(self.budget_data.get_txs());
+    }
+
+    /// Reload goal data with the latest balances and txs
+    #[cfg(not(tarpaulin_include))]
+    fn reload_goal_table(&mut self) {
+        *self.goal_data = GoalData::new(Local::now().date_naive(), self.conn);
+        *self.goal_table = TableData::new(self.goal_data.get_txs());
+    }
+
+    /// Reload the unreconciled txs of the ongoing reconciliation. Removes all ticks
+    #[cfg(not(tarpaulin_include))]
+    fn reload_reconcile_table(&mut self) {
+        *self.reconcile_data = ReconcileData::new(self.reconciliation.as_ref(), self.conn);
+        *self.reconcile_table = TableData::new(self.reconcile_data.get_txs());
+    }
+
+    /// Reload every counterparty with the latest debt txs
+    #[cfg(not(tarpaulin_include))]
+    fn reload_debt_table(&mut self) {
+        *self.debt_data = DebtData::new(self.conn);
+        *self.debt_table = TableData::new(self.debt_data.get_txs());
+    }
+
+    /// Reload every tag with the latest usage and totals
+    #[cfg(not(tarpaulin_include))]
+    fn reload_tag_table(&mut self) {
+        *self.tag_data = TagData::new(self.conn);
+        *self.tag_table = TableData::new(self.tag_data.get_txs());
+    }
+
     /// Move the cursor for text fields to the correct position, if it's misplaced
     #[cfg(not(tarpaulin_include))]
     fn go_correct_index(&mut self) {
@@ -1774,6 +2612,7 @@ impl<'a> InputKeyHandler<'a> {
             TxTab::Amount => self.add_tx_data.do_amount_up(false, self.conn),
             TxTab::TxType => self.add_tx_data.do_tx_type_up(),
             TxTab::Tags => self.add_tx_data.do_tags_up(self.conn),
+            TxTab::Status => self.add_tx_data.do_status_up(false),
             _ => Ok(()),
         };
 
@@ -1791,6 +2630,7 @@ impl<'a> InputKeyHandler<'a> {
             TxTab::Amount => self.add_tx_data.do_amount_down(false, self.conn),
             TxTab::TxType => self.add_tx_data.do_tx_type_down(),
             TxTab::Tags => self.add_tx_data.do_tags_down(self.conn),
+            TxTab::Status => self.add_tx_data.do_status_down(false),
             _ => Ok(()),
         };
 
@@ -1808,6 +2648,7 @@ impl<'a> InputKeyHandler<'a> {
             TxTab::Amount => self.search_data.do_amount_up(true, self.conn),
             TxTab::TxType => self.search_data.do_tx_type_up(),
             TxTab::Tags => self.search_data.do_tags_up(self.conn),
+            TxTab::Status => self.search_data.do_status_up(true),
             TxTab::Nothing => {
                 if self.search_table.state.selected() == Some(0) {
                     self.search_table
@@ -1818,7 +2659,7 @@ impl<'a> InputKeyHandler<'a> {
                 }
                 Ok(())
             }
-            TxTab::Details => Ok(()),
+            TxTab::Details | TxTab::Splits => Ok(()),
         };
 
         if let Err(e) = status {
@@ -1835,6 +2676,7 @@ impl<'a> InputKeyHandler<'a> {
             TxTab::Amount => self.search_data.do_amount_down(true, self.conn),
             TxTab::TxType => self.search_data.do_tx_type_down(),
             TxTab::Tags => self.search_data.do_tags_down(self.conn),
+            TxTab::Status => self.search_data.do_status_down(true),
             TxTab::Nothing => {
                 if self.search_table.state.selected() == Some(self.search_table.items.len() - 1) {
                     self.search_table.state.select(Some(0));
@@ -1843,7 +2685,7 @@ impl<'a> InputKeyHandler<'a> {
                 }
                 Ok(())
             }
-            TxTab::Details => Ok(()),
+            TxTab::Details | TxTab::Splits => Ok(()),
         };
 
         if let Err(e) = status {
@@ -1904,6 +2746,188 @@ impl<'a> InputKeyHandler<'a> {
         }
     }
 
+    #[cfg(not(tarpaulin_include))]
+    fn do_recurring_up(&mut self) {
+        if self.recurring_data.is_empty() {
+            return;
+        }
+
+        if self.recurring_table.state.selected() == Some(0) {
+            self.recurring_table.state.select(None);
+        } else {
+            self.recurring_table.previous();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_recurring_down(&mut self) {
+        if self.recurring_data.is_empty() {
+            return;
+        }
+
+        if self.recurring_table.state.selected() == Some(self.recurring_table.items.len() - 1) {
+            self.recurring_table.state.select(None);
+        } else {
+            self.recurring_table.next();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_goal_up(&mut self) {
+        if self.goal_data.is_empty() {
+            return;
+        }
+
+        if self.goal_table.state.selected() == Some(0) {
+            self.goal_table.state.select(None);
+        } else {
+            self.goal_table.previous();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_goal_down(&mut self) {
+        if self.goal_data.is_empty() {
+            return;
+        }
+
+        if self.goal_table.state.selected() == Some(self.goal_table.items.len() - 1) {
+            self.goal_table.state.select(None);
+        } else {
+            self.goal_table.next();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_reconcile_up(&mut self) {
+        if self.reconcile_data.is_empty() {
+            return;
+        }
+
+        if self.reconcile_table.state.selected() == Some(0) {
+            self.reconcile_table.state.select(None);
+        } else {
+            self.reconcile_table.previous();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_reconcile_down(&mut self) {
+        if self.reconcile_data.is_empty() {
+            return;
+        }
+
+        if self.reconcile_table.state.selected() == Some(self.reconcile_table.items.len() - 1) {
+            self.reconcile_table.state.select(None);
+        } else {
+            self.reconcile_table.next();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_debt_up(&mut self) {
+        if self.debt_data.is_empty() {
+            return;
+        }
+
+        if self.debt_table.state.selected() == Some(0) {
+            self.debt_table.state.select(None);
+        } else {
+            self.debt_table.previous();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_debt_down(&mut self) {
+        if self.debt_data.is_empty() {
+            return;
+        }
+
+        if self.debt_table.state.selected() == Some(self.debt_table.items.len() - 1) {
+            self.debt_table.state.select(None);
+        } else {
+            self.debt_table.next();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_tag_up(&mut self) {
+        if self.tag_data.is_empty() {
+            return;
+        }
+
+        if self.tag_table.state.selected() == Some(0) {
+            self.tag_table.state.select(None);
+        } else {
+            self.tag_table.previous();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_tag_down(&mut self) {
+        if self.tag_data.is_empty() {
+            return;
+        }
+
+        if self.tag_table.state.selected() == Some(self.tag_table.items.len() - 1) {
+            self.tag_table.state.select(None);
+        } else {
+            self.tag_table.next();
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_budget_up(&mut self) {
+        match self.budget_tab {
+            BudgetTab::Years => {
+                if self.budget_data.is_empty() {
+                    *self.budget_tab = self.budget_tab.change_tab_down();
+                } else {
+                    *self.budget_tab = self.budget_tab.change_tab_up();
+                    self.budget_table
+                        .state
+                        .select(Some(self.budget_table.items.len() - 1));
+                }
+            }
+            BudgetTab::Months => {
+                *self.budget_tab = self.budget_tab.change_tab_up();
+            }
+            BudgetTab::List => {
+                if self.budget_table.state.selected() == Some(0) {
+                    self.budget_table.state.select(None);
+                    *self.budget_tab = self.budget_tab.change_tab_up();
+                } else {
+                    self.budget_table.previous();
+                }
+            }
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn do_budget_down(&mut self) {
+        match self.budget_tab {
+            BudgetTab::Years => {
+                *self.budget_tab = self.budget_tab.change_tab_down();
+            }
+            BudgetTab::Months => {
+                if self.budget_data.is_empty() {
+                    *self.budget_tab = self.budget_tab.change_tab_up();
+                } else {
+                    *self.budget_tab = self.budget_tab.change_tab_down();
+                    self.budget_table.state.select(Some(0));
+                }
+            }
+            BudgetTab::List => {
+                if self.budget_table.state.selected() == Some(self.budget_table.items.len() - 1) {
+                    *self.budget_tab = self.budget_tab.change_tab_down();
+                    self.budget_table.state.select(None);
+                } else {
+                    self.budget_table.next();
+                }
+            }
+        }
+    }
+
     #[cfg(not(tarpaulin_include))]
     fn check_autofill(&mut self) {
         match self.page {
@@ -1925,49 +2949,69 @@ impl<'a> InputKeyHandler<'a> {
     fn reload_home_balance_data(&mut self) {
         let mut balance_data = vec![vec![String::new()]];
         balance_data[0].extend(get_all_tx_methods(self.conn));
-        balance_data[0].extend(vec!["Total".to_string()]);
+        let converter = CurrencyConverter::new(self.conn);
+        balance_data[0].push(converter.total_header());
 
         let current_table_index = self.table.state.selected();
 
         match current_table_index {
             // pass out the current index to get the necessary balance & changes data
             Some(a) => {
-                balance_data.push(self.all_tx_data.get_balance(a));
+                if *self.cleared_balance {
+                    balance_data.push(self.all_tx_data.get_cleared_balance(
+                        Some(a),
+                        &converter,
+                        self.conn,
+                    ));
+                } else {
+                    balance_data.push(self.all_tx_data.get_balance(a, &converter, self.conn));
+                }
                 balance_data.push(self.all_tx_data.get_changes(a));
             }
             // if none selected, get empty changes + the absolute final balance
             None => {
-                balance_data.push(self.all_tx_data.get_last_balance(self.conn));
+                if *self.cleared_balance {
+                    balance_data.push(
+                        self.all_tx_data
+                            .get_cleared_balance(None, &converter, self.conn),
+                    );
+                } else {
+                    balance_data.push(self.all_tx_data.get_last_balance(&converter, self.conn));
+                }
                 balance_data.push(get_empty_changes(self.conn));
             }
         }
 
         // total income, total expense, daily income, daily expense data based on the selected index.
-        balance_data.push(
-            self.all_tx_data
-                .get_total_income(current_table_index, self.conn),
-        );
-        balance_data.push(
-            self.all_tx_data
-                .get_total_expense(current_table_index, self.conn),
-        );
-        balance_data.push(
-            self.all_tx_data
-                .get_daily_income(current_table_index, self.conn),
-        );
-        balance_data.push(
-            self.all_tx_data
-                .get_daily_expense(current_table_index, self.conn),
-        );
+        balance_data.push(self.all_tx_data.get_total_income(
+            current_table_index,
+            &converter,
+            self.conn,
+        ));
+        balance_data.push(self.all_tx_data.get_total_expense(
+            current_table_index,
+            &converter,
+            self.conn,
+        ));
+        balance_data.push(self.all_tx_data.get_daily_income(
+            current_table_index,
+            &converter,
+            self.conn,
+        ));
+        balance_data.push(self.all_tx_data.get_daily_expense(
+            current_table_index,
+            &converter,
+            self.conn,
+        ));
 
-        *self.balance_data = balance_data;
+        *self.balance_data = hide_archived_columns(balance_data, self.conn);
     }
 
     /// Force add tx page's balance load to start from 0.0
     #[cfg(not(tarpaulin_include))]
     fn reload_add_tx_balance_load(&mut self) {
         // 0 for all methods + 1 more for the total balance column
-        let ongoing_data = vec![String::from("0.0"); get_all_tx_methods(self.conn).len() + 1];
+        let ongoing_data = vec![String::from("0.0"); get_active_tx_methods(self.conn).len() + 1];
         self.ongoing_balance.clone_from(&ongoing_data);
         *self.ongoing_changes = vec![String::from("0.0"); ongoing_data.len()];
         self.reload_add_tx_balance_data();
@@ -1978,19 +3022,20 @@ impl<'a> InputKeyHandler<'a> {
     fn reload_add_tx_balance_data(&mut self) {
         let mut balance_data = vec![vec![String::new()]];
         balance_data[0].extend(get_all_tx_methods(self.conn));
-        balance_data[0].extend(vec!["Total".to_string()]);
+        let converter = CurrencyConverter::new(self.conn);
+        balance_data[0].push(converter.total_header());
 
         let current_table_index = self.table.state.selected();
 
         let (current_balance, current_changes) = match current_table_index {
             // pass out the current index to get the necessary balance & changes data
             Some(a) => (
-                self.all_tx_data.get_balance(a),
+                self.all_tx_data.get_balance(a, &converter, self.conn),
                 self.all_tx_data.get_changes(a),
             ),
             // if none selected, get empty changes + the absolute final balance
             None => (
-                self.all_tx_data.get_last_balance(self.conn),
+                self.all_tx_data.get_last_balance(&converter, self.conn),
                 get_empty_changes(self.conn),
             ),
         };
@@ -2002,6 +3047,6 @@ impl<'a> InputKeyHandler<'a> {
         ));
         balance_data.push(self.add_tx_data.generate_changes_section(self.conn));
 
-        *self.balance_data = balance_data;
+        *self.balance_data = hide_archived_columns(balance_data, self.conn);
     }
 }
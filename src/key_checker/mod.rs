@@ -1,17 +1,29 @@
 mod activity_keys;
 mod add_tx_keys;
+mod budget_keys;
 mod chart_keys;
+mod debt_keys;
+mod goal_keys;
 mod home_keys;
 mod initial_keys;
 mod key_handler;
+mod reconcile_keys;
+mod recurring_keys;
 mod search_keys;
 mod summary_keys;
+mod tag_keys;
 
 pub use activity_keys::activity_keys;
 pub use add_tx_keys::add_tx_keys;
+pub use budget_keys::budget_keys;
 pub use chart_keys::chart_keys;
+pub use debt_keys::debt_keys;
+pub use goal_keys::goal_keys;
 pub use home_keys::home_keys;
 pub use initial_keys::initial_keys;
 pub use key_handler::InputKeyHandler;
+pub use reconcile_keys::reconcile_keys;
+pub use recurring_keys::recurring_keys;
 pub use search_keys::search_keys;
 pub use summary_keys::summary_keys;
+pub use tag_keys::tag_keys;
@@ -0,0 +1,42 @@
+use crossterm::event::KeyCode;
+
+use crate::key_checker::InputKeyHandler;
+use crate::outputs::HandlingOutput;
+use crate::page_handler::PopupState;
+
+/// Tracks the keys of the Reconcile page and calls relevant function based on it
+#[cfg(not(tarpaulin_include))]
+pub fn reconcile_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
+    match handler.popup {
+        PopupState::Nothing => match handler.key.code {
+            KeyCode::Char('q') => return Some(HandlingOutput::QuitUi),
+            KeyCode::Char('f') => handler.go_home(),
+            KeyCode::Char('a') => handler.go_add_tx(),
+            KeyCode::Char('r') => handler.go_chart(),
+            KeyCode::Char('h') => handler.do_help_popup(),
+            KeyCode::Char('z') => handler.go_summary(),
+            KeyCode::Char('w') => handler.go_search(),
+            KeyCode::Char('y') => handler.go_activity(),
+            KeyCode::Char('o') => handler.go_recurring(),
+            KeyCode::Char('b') => handler.go_budget(),
+            KeyCode::Char('g') => handler.go_goal(),
+            KeyCode::Char('i') => handler.go_debt(),
+            KeyCode::Char('T') => handler.go_tag(),
+            KeyCode::Char('n') => return Some(HandlingOutput::StartReconciliation),
+            KeyCode::Char(' ') | KeyCode::Enter => handler.reconcile_toggle_tick(),
+            KeyCode::Char('s') => handler.reconcile_save(),
+            KeyCode::Char('x') => handler.reconcile_cancel(),
+            KeyCode::Up => handler.handle_up_arrow(),
+            KeyCode::Down => handler.handle_down_arrow(),
+            _ => {}
+        },
+        PopupState::ReconcileHelp => match handler.key.code {
+            KeyCode::Up => handler.popup_scroll_up(),
+            KeyCode::Down => handler.popup_scroll_down(),
+            _ => handler.do_empty_popup(),
+        },
+        _ => handler.do_empty_popup(),
+    }
+
+    None
+}
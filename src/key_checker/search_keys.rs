@@ -20,17 +20,20 @@ pub fn search_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
                 KeyCode::Char('s') => handler.search_tx(),
                 KeyCode::Char('c') => handler.clear_input(),
                 KeyCode::Char('x') => handler.change_search_date_type(),
+                KeyCode::Char('p') => return handler.search_export_txs(),
                 KeyCode::Char('e') => handler.search_edit_tx(),
                 KeyCode::Char('d') => handler.do_deletion_popup(),
                 KeyCode::Char('y') => handler.go_activity(),
+                KeyCode::Char('o') => handler.go_recurring(),
+                KeyCode::Char('b') => handler.go_budget(),
+                KeyCode::Char('g') => handler.go_goal(),
+                KeyCode::Char('k') => handler.go_reconcile(),
+                KeyCode::Char('i') => handler.go_debt(),
+                KeyCode::Char('T') => handler.go_tag(),
                 KeyCode::Up => handler.handle_up_arrow(),
                 KeyCode::Down => handler.handle_down_arrow(),
                 KeyCode::Enter => handler.select_date_field(),
-                KeyCode::Char(c) => {
-                    if c.is_numeric() {
-                        handler.handle_number_press();
-                    }
-                }
+                KeyCode::Char(c) if c.is_numeric() => handler.handle_number_press(),
                 _ => {}
             },
             _ => match handler.key.code {
@@ -46,7 +49,8 @@ pub fn search_keys(handler: &mut InputKeyHandler) -> Option<HandlingOutput> {
                     TxTab::Amount => handler.handle_amount(),
                     TxTab::TxType => handler.handle_tx_type(),
                     TxTab::Tags => handler.handle_tags(),
-                    TxTab::Nothing => {}
+                    TxTab::Status => handler.handle_status(),
+                    TxTab::Splits | TxTab::Nothing => {}
                 },
             },
         },
@@ -1,14 +1,20 @@
 mod activity_page;
 mod add_tx_page;
+pub mod budget_page;
 pub mod chart_page;
 pub mod db;
+pub mod debt_page;
+pub mod goal_page;
 pub mod home_page;
 mod initial_page;
 mod key_checker;
 pub mod outputs;
 pub mod page_handler;
 mod popup_page;
+pub mod reconcile_page;
+mod recurring_page;
 mod search_page;
 pub mod summary_page;
+pub mod tag_page;
 pub mod tx_handler;
 pub mod utility;
@@ -1,6 +1,6 @@
 use dirs::data_local_dir;
-use rex_tui::page_handler::initialize_app;
-use std::env::{current_dir, set_current_dir};
+use rex_tui::page_handler::{initialize_app, run_export_command};
+use std::env::{args, current_dir, set_current_dir};
 use std::fs;
 
 fn main() {
@@ -17,6 +17,17 @@ fn main() {
         set_current_dir(&working_path).unwrap();
 
         working_path.push("data.sqlite");
+
+        // `rex export ...` writes a CSV file without opening the interface
+        let args = args().collect::<Vec<String>>();
+        if args.get(1).is_some_and(|arg| arg == "export") {
+            if let Err(e) = run_export_command(&args[2..], &working_path, &original_dir) {
+                println!("{e}");
+                std::process::exit(1);
+            }
+            return;
+        }
+
         if initialize_app(&working_path, &original_dir).is_err() {
             std::process::exit(1);
         }
@@ -51,6 +51,11 @@ pub enum CheckingError {
     EmptyAmount,
     EmptyTxType,
     SameTxMethod,
+    SplitOnTransfer,
+    SplitMismatch,
+    CurrencyMismatch(String, String),
+    InvalidStatus,
+    DebtTxType,
 }
 
 impl Display for CheckingError {
@@ -65,6 +70,25 @@ impl Display for CheckingError {
                 f,
                 "Tx Method: From and To methods cannot be the same for Transfer"
             ),
+            CheckingError::SplitOnTransfer => {
+                write!(f, "Splits: Transfer transactions cannot be split")
+            }
+            CheckingError::SplitMismatch => write!(
+                f,
+                "Splits: Split amounts must add up to the transaction amount"
+            ),
+            CheckingError::CurrencyMismatch(from_currency, to_currency) => write!(
+                f,
+                "Tx Method: Cannot transfer between a {from_currency} and a {to_currency} TX Method"
+            ),
+            CheckingError::InvalidStatus => write!(
+                f,
+                "Status: Status must be Pending or Cleared. Reconcile the transaction from the Reconcile page"
+            ),
+            CheckingError::DebtTxType => write!(
+                f,
+                "Tx Type: Lend, Borrow and Repay transactions can only be added from the Debts page"
+            ),
         }
     }
 }
@@ -77,6 +101,7 @@ pub enum SteppingError {
     InvalidTxMethod,
     InvalidAmount,
     InvalidTxType,
+    InvalidTxStatus,
     InvalidTags,
     UnknownBValue,
 }
@@ -98,6 +123,9 @@ impl Display for SteppingError {
             SteppingError::InvalidTxType => {
                 write!(f, "Tx Type: Failed to step due to invalid tx type")
             }
+            SteppingError::InvalidTxStatus => {
+                write!(f, "Tx Status: Failed to step due to invalid status")
+            }
             SteppingError::InvalidTags => {
                 write!(f, "Tags: Failed to step as the tag does not exists")
             }
@@ -138,3 +166,401 @@ impl Display for TxUpdateError {
         }
     }
 }
+
+#[derive(Debug, PartialEq)]
+pub enum CentParsingError {
+    InvalidAmount(String),
+}
+
+impl Display for CentParsingError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            CentParsingError::InvalidAmount(amount) => {
+                write!(f, "Amount: {amount} is not a valid amount")
+            }
+        }
+    }
+}
+
+impl Error for CentParsingError {}
+
+#[derive(Debug)]
+pub enum RateImportError {
+    ReadFailed(ioError),
+    InvalidLine(usize, String),
+    SavingFailed(sqlError),
+}
+
+impl Display for RateImportError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            RateImportError::ReadFailed(e) => {
+                write!(f, "Failed to read the exchange rate file. Error: {e}")
+            }
+            RateImportError::InvalidLine(line_num, line) => write!(
+                f,
+                "Line {line_num}: '{line}' is not valid. Expected format: from currency,to currency,rate. Example: EUR,USD,1.08"
+            ),
+            RateImportError::SavingFailed(e) => {
+                write!(f, "Failed to save the exchange rates. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for RateImportError {}
+
+#[derive(Debug)]
+pub enum PriceImportError {
+    ReadFailed(ioError),
+    InvalidLine(usize, String),
+    SavingFailed(sqlError),
+}
+
+impl Display for PriceImportError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            PriceImportError::ReadFailed(e) => {
+                write!(f, "Failed to read the price file. Error: {e}")
+            }
+            PriceImportError::InvalidLine(line_num, line) => write!(
+                f,
+                "Line {line_num}: '{line}' is not valid. Expected format: symbol,date,price. Example: AAPL,2024-05-01,170.50"
+            ),
+            PriceImportError::SavingFailed(e) => {
+                write!(f, "Failed to save the prices. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for PriceImportError {}
+
+#[derive(Debug)]
+pub enum ExportError {
+    InvalidArgument(String),
+    InvalidColumn(String),
+    InvalidDelimiter(String),
+    InvalidDateFormat(String),
+    InvalidDate(String),
+    QueryFailed(sqlError),
+    WriteFailed(ioError),
+}
+
+impl Display for ExportError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ExportError::InvalidArgument(arg) => write!(
+                f,
+                "Invalid or missing argument: {arg}. Example: rex export --from 2024-01-01 --to 2024-12-31 --output txs.csv"
+            ),
+            ExportError::InvalidColumn(column) => write!(
+                f,
+                "{column} is not a valid column. Columns: date, details, method, amount, type, tags, id, balance"
+            ),
+            ExportError::InvalidDelimiter(delimiter) => write!(
+                f,
+                "{delimiter} is not a valid delimiter. The delimiter must be a single character that is not a quote"
+            ),
+            ExportError::InvalidDateFormat(format) => {
+                write!(f, "{format} is not a valid date format. Example: %Y-%m-%d")
+            }
+            ExportError::InvalidDate(date) => {
+                write!(f, "{date} is not a valid date. Example: 2024-05-01")
+            }
+            ExportError::QueryFailed(e) => {
+                write!(f, "Failed to get the transactions. Error: {e}")
+            }
+            ExportError::WriteFailed(e) => {
+                write!(f, "Failed to write the export file. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+#[derive(Debug)]
+pub enum ImportError {
+    ReadFailed(ioError),
+    InvalidProfile(String),
+    InvalidRow(usize, String),
+    NoTransactions,
+    SavingFailed(sqlError),
+}
+
+impl Display for ImportError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ImportError::ReadFailed(e) => {
+                write!(f, "Failed to read the statement file. Error: {e}")
+            }
+            ImportError::InvalidProfile(reason) => {
+                write!(f, "The import profile is not valid. {reason}")
+            }
+            ImportError::InvalidRow(line_num, reason) => {
+                write!(f, "Line {line_num} is not valid. {reason}")
+            }
+            ImportError::NoTransactions => {
+                write!(f, "The statement file does not have any transactions")
+            }
+            ImportError::SavingFailed(e) => {
+                write!(f, "Failed to save the transactions. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    NewerVersion(i32, i32),
+    BackupFailed(sqlError),
+    MigrationFailed(i32, sqlError),
+}
+
+impl Display for MigrationError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            MigrationError::NewerVersion(found, supported) => write!(
+                f,
+                "The database is from a newer version of the app (schema version {found}). This version supports up to schema version {supported}. Update the app to open it."
+            ),
+            MigrationError::BackupFailed(e) => {
+                write!(f, "Failed to create a database backup before updating. Error: {e}")
+            }
+            MigrationError::MigrationFailed(version, e) => write!(
+                f,
+                "Database updating to schema version {version} failed. Commits reversed. Error: {e}"
+            ),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+#[derive(Debug)]
+pub enum TxMethodError {
+    NotFound(String),
+    InUse(String, i64),
+    UsedByRecurring(String, i64),
+    UsedByGoal(String, i64),
+    UsedByHolding(String, i64),
+    NoActiveMethod,
+    SameMethod,
+    CurrencyMismatch(String, String),
+    InvalidKind(String),
+    UpdateFailed(sqlError),
+}
+
+impl Display for TxMethodError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TxMethodError::NotFound(method) => {
+                write!(f, "Transaction Method {method} does not exist")
+            }
+            TxMethodError::InUse(method, total) => write!(
+                f,
+                "{total} transactions still use {method}. Reassign them to a different Transaction Method first"
+            ),
+            TxMethodError::UsedByRecurring(method, total) => write!(
+                f,
+                "{total} recurring transactions still use {method}. Edit or delete them from the Recurring page first"
+            ),
+            TxMethodError::UsedByGoal(method, total) => write!(
+                f,
+                "{total} goals still track {method}. Edit or delete them from the Goals page first"
+            ),
+            TxMethodError::UsedByHolding(method, total) => write!(
+                f,
+                "{method} still has {total} holdings. Set their quantity to 0 to remove them first"
+            ),
+            TxMethodError::NoActiveMethod => write!(
+                f,
+                "At least one Transaction Method must stay active"
+            ),
+            TxMethodError::SameMethod => {
+                write!(f, "A Transaction Method cannot be merged into itself")
+            }
+            TxMethodError::CurrencyMismatch(from_currency, to_currency) => write!(
+                f,
+                "Transaction Methods with different currencies cannot be merged ({from_currency} and {to_currency})"
+            ),
+            TxMethodError::InvalidKind(kind) => write!(
+                f,
+                "{kind} is not a valid Transaction Method kind. Values: Asset/Liability"
+            ),
+            TxMethodError::UpdateFailed(e) => {
+                write!(f, "Failed to update the Transaction Method. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for TxMethodError {}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    NothingToUndo,
+    NothingToRedo,
+    TxNotFound(i32),
+    TxExists(i32),
+    TxReconciled(i32),
+    UnknownTxMethod(String),
+    NotRestorable,
+    UpdateFailed(sqlError),
+}
+
+impl Display for HistoryError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            HistoryError::NothingToUndo => write!(f, "There is no activity left to undo"),
+            HistoryError::NothingToRedo => write!(f, "There is no undone activity to redo"),
+            HistoryError::TxNotFound(id_num) => write!(
+                f,
+                "Transaction with ID {id_num} no longer exists. The activity cannot be reversed"
+            ),
+            HistoryError::TxExists(id_num) => write!(
+                f,
+                "A transaction with ID {id_num} already exists. The activity cannot be reversed"
+            ),
+            HistoryError::TxReconciled(id_num) => write!(
+                f,
+                "Transaction with ID {id_num} is reconciled. Undo the reconciliation before reversing the activity"
+            ),
+            HistoryError::UnknownTxMethod(method) => write!(
+                f,
+                "Transaction Method {method} no longer exists. The activity cannot be reversed"
+            ),
+            HistoryError::NotRestorable => write!(
+                f,
+                "Only deleted transactions can be restored and only edits can be reverted"
+            ),
+            HistoryError::UpdateFailed(e) => {
+                write!(
+                    f,
+                    "Something went wrong while updating transactions. Error: {e}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for HistoryError {}
+
+#[derive(Debug)]
+pub enum TagError {
+    NotFound(String),
+    InvalidName(String),
+    SameTag,
+    UpdateFailed(sqlError),
+}
+
+impl Display for TagError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            TagError::NotFound(tag) => write!(f, "No transaction uses the tag {tag}"),
+            TagError::InvalidName(tag) => write!(
+                f,
+                "\"{tag}\" is not a valid tag name. Tags cannot be empty or contain , | or ;"
+            ),
+            TagError::SameTag => write!(f, "A tag cannot be renamed or merged into itself"),
+            TagError::UpdateFailed(e) => {
+                write!(f, "Failed to update the tags. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for TagError {}
+
+#[derive(Debug)]
+pub enum RecurringError {
+    PostFailed(String, sqlError),
+}
+
+impl Display for RecurringError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            RecurringError::PostFailed(details, e) => write!(
+                f,
+                "Failed to post the recurring transaction \"{details}\". Pause or edit it from the Recurring page. Error: {e}"
+            ),
+        }
+    }
+}
+
+impl Error for RecurringError {}
+
+#[derive(Debug)]
+pub enum ReconcileError {
+    Locked(i32),
+    NotBalanced(String),
+    UpdateFailed(sqlError),
+}
+
+impl Display for ReconcileError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            ReconcileError::Locked(id_num) => write!(
+                f,
+                "Transaction with ID {id_num} is reconciled and cannot be changed. Undo the reconciliation to change it"
+            ),
+            ReconcileError::NotBalanced(difference) => write!(
+                f,
+                "The statement balance is still {difference} away from the reconciled balance"
+            ),
+            ReconcileError::UpdateFailed(e) => {
+                write!(f, "Failed to reconcile the transactions. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for ReconcileError {}
+
+#[derive(Debug)]
+pub enum CounterpartyError {
+    EmptyName,
+    InvalidAmount,
+    NothingOwed(String),
+    RepayTooLarge(String, String),
+    UpdateFailed(sqlError),
+}
+
+impl Display for CounterpartyError {
+    #[cfg(not(tarpaulin_include))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result {
+        match self {
+            CounterpartyError::EmptyName => write!(f, "The name of the person cannot be empty"),
+            CounterpartyError::InvalidAmount => {
+                write!(f, "The amount must be more than 0")
+            }
+            CounterpartyError::NothingOwed(name) => write!(
+                f,
+                "Nothing is owed between you and {name}. There is nothing to repay"
+            ),
+            CounterpartyError::RepayTooLarge(name, outstanding) => write!(
+                f,
+                "Only {outstanding} is outstanding with {name}. The repayment cannot be more than that"
+            ),
+            CounterpartyError::UpdateFailed(e) => {
+                write!(f, "Failed to save the debt transaction. Error: {e}")
+            }
+        }
+    }
+}
+
+impl Error for CounterpartyError {}
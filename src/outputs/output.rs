@@ -1,9 +1,22 @@
 use std::fmt;
 
+use crate::db::{MAX_YEAR, MIN_YEAR};
+
 pub enum HandlingOutput {
     QuitUi,
     TakeUserInput,
     PrintNewUpdate,
+    EditRecurringTx(Option<i32>),
+    EditBudget(Option<i32>),
+    EditGoal(Option<i32>),
+    StartReconciliation,
+    AddDebtTx,
+    /// The tag to rename
+    RenameTag(String),
+    /// The tag to merge into another tag
+    MergeTag(String),
+    /// The id nums of the search result to export. None to export a date range
+    ExportTxs(Option<Vec<String>>),
 }
 
 #[derive(PartialEq, Debug)]
@@ -32,6 +45,8 @@ pub enum AType {
     Amount,
     TxType,
     Tags,
+    Splits,
+    Status,
 }
 
 impl fmt::Display for AType {
@@ -43,6 +58,8 @@ impl fmt::Display for AType {
             AType::Amount => write!(f, "Amount"),
             AType::TxType => write!(f, "Tx Type"),
             AType::Tags => write!(f, "Tags"),
+            AType::Splits => write!(f, "Splits"),
+            AType::Status => write!(f, "Tx Status"),
         }
     }
 }
@@ -61,9 +78,12 @@ pub enum NAType {
     AmountBelowZero,
     InvalidTxMethod,
     InvalidTxType,
+    InvalidTxStatus,
     ParsingError(AType),
     InvalidBValue,
     NonExistingTag,
+    InvalidSplit,
+    SplitMismatch,
 }
 
 impl fmt::Display for NAType {
@@ -83,7 +103,7 @@ impl fmt::Display for NAType {
                 f,
                 "Date: Day length not acceptable. Example Date: 2022-05-01"
             ),
-            NAType::YearTooBig => write!(f, "Date: Year must be between 2022-2037"),
+            NAType::YearTooBig => write!(f, "Date: Year must be between {MIN_YEAR}-{MAX_YEAR}"),
             NAType::MonthTooBig => write!(f, "Date: Month must be between 01-12"),
             NAType::DayTooBig => write!(f, "Date: Day must be between 01-31"),
             NAType::NonExistingDate => {
@@ -93,7 +113,11 @@ impl fmt::Display for NAType {
             NAType::InvalidTxMethod => write!(f, "TX Method: Transaction Method not found"),
             NAType::InvalidTxType => write!(
                 f,
-                "TX Type: Transaction Type not acceptable. Values: Expense/Income/E/I"
+                "TX Type: Transaction Type not acceptable. Values: Expense/Income/Opening Balance/E/I/O"
+            ),
+            NAType::InvalidTxStatus => write!(
+                f,
+                "TX Status: Status not acceptable. Values: Pending/Cleared/Reconciled/P/C/R"
             ),
             NAType::ParsingError(error) => {
                 write!(f, "{error}: Error acquired while validating input")
@@ -103,6 +127,14 @@ impl fmt::Display for NAType {
                 "Amount: TX Method cannot be empty. Value of B cannot be determined"
             ),
             NAType::NonExistingTag => write!(f, "Tags: Non-existing tags cannot be accepted"),
+            NAType::InvalidSplit => write!(
+                f,
+                "Splits: Each split must be written as Tag | Amount | Note separated by ;"
+            ),
+            NAType::SplitMismatch => write!(
+                f,
+                "Splits: Split amounts must add up to the transaction amount"
+            ),
         }
     }
 }
@@ -131,10 +163,10 @@ impl fmt::Display for ComparisonType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ComparisonType::Equal => write!(f, "amount ="),
-            ComparisonType::BiggerThan => write!(f, "CAST(amount AS REAL) >"),
-            ComparisonType::SmallerThan => write!(f, "CAST(amount AS REAL) <"),
-            ComparisonType::EqualOrBigger => write!(f, "CAST(amount AS REAL) >="),
-            ComparisonType::EqualOrSmaller => write!(f, "CAST(amount AS REAL) <="),
+            ComparisonType::BiggerThan => write!(f, "amount >"),
+            ComparisonType::SmallerThan => write!(f, "amount <"),
+            ComparisonType::EqualOrBigger => write!(f, "amount >="),
+            ComparisonType::EqualOrSmaller => write!(f, "amount <="),
         }
     }
 }
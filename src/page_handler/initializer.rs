@@ -1,20 +1,32 @@
 use atty::Stream;
+use chrono::{Datelike, Local};
 use rusqlite::Connection;
 use std::env::set_current_dir;
 use std::error::Error;
 use std::fs::{self, File};
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use crate::db::{add_new_tx_methods, rename_column, reposition_column};
+use crate::db::{
+    add_budget, add_goal, add_new_tx_methods, add_opening_balances, add_recurring_tx,
+    delete_tx_method, export_txs, extend_year_range, import_exchange_rates, import_prices,
+    import_txs, merge_tags, merge_tx_methods, parse_export_args, rebuild_ledger, rename_column,
+    rename_tag, reposition_column, set_base_currency, set_exchange_rates, set_holding, set_prices,
+    set_tx_method_archived, set_tx_method_currency, set_tx_method_kind, update_budget, update_goal,
+    update_recurring_tx, verify_ledger,
+};
 use crate::initial_page::check_version;
 use crate::outputs::HandlingOutput;
 use crate::page_handler::{start_app, ResetType, UserInputType};
+use crate::tx_handler::{add_debt_tx, post_due_recurring_txs};
 use crate::utility::{
     check_n_create_db, check_old_sql, create_backup_location_file, create_change_location_file,
     delete_backup_db, delete_location_change, enter_tui_interface, exit_tui_interface,
+    flush_output, get_budget_data, get_debt_tx_data, get_export_data, get_goal_data,
+    get_reconcile_data, get_recurring_tx_data, get_tag_merge_data, get_tag_rename_data,
     is_location_changed, save_backup_db, start_taking_input, start_terminal, start_timer,
+    take_input,
 };
 
 /// Initialize the tui loop
@@ -55,16 +67,32 @@ pub fn initialize_app(
     // initiates migration if old database is detected.
     check_old_sql(&mut conn);
 
+    // the year range must contain the current year so the pages can start at it
+    extend_year_range(Local::now().year(), &mut conn)?;
+
+    // add every recurring tx that became due since the last time the app was opened
+    let mut recurring_status = post_recurring_txs(&mut conn);
+
+    // the reconciliation that is ongoing on the Reconcile page. Kept while the UI is left for user inputs
+    let mut reconciliation = None;
+
     loop {
         let mut terminal = enter_tui_interface()?;
-        let result = start_app(&mut terminal, &new_version_available, &mut conn);
+        let result = start_app(
+            &mut terminal,
+            &new_version_available,
+            &mut recurring_status,
+            &mut reconciliation,
+            &mut conn,
+        );
         exit_tui_interface()?;
 
         match result {
             Ok(output) => match output {
                 HandlingOutput::TakeUserInput => match start_taking_input(&conn) {
-                    UserInputType::AddNewTxMethod(tx_methods) => {
-                        let status = add_new_tx_methods(&tx_methods, &mut conn);
+                    UserInputType::AddNewTxMethod(tx_methods, opening_balances) => {
+                        let status = add_new_tx_methods(&tx_methods, &mut conn)
+                            .and_then(|()| add_opening_balances(&opening_balances, &mut conn));
                         match status {
                             Ok(()) => start_timer("Added Transaction Methods Successfully."),
                             Err(e) => {
@@ -148,7 +176,283 @@ pub fn initialize_app(
                             }
                         }
                     }
-                    UserInputType::InvalidInput => unreachable!()
+                    UserInputType::SetTxMethodCurrency(currency_data) => {
+                        let method = &currency_data[0];
+                        let currency = &currency_data[1];
+
+                        match set_tx_method_currency(method, currency, &mut conn) {
+                            Ok(()) => start_timer("Tx Method currency set successfully."),
+                            Err(e) => {
+                                println!("Error while setting tx method currency. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetTxMethodKind(kind_data) => {
+                        let method = &kind_data[0];
+                        let kind = &kind_data[1];
+
+                        match set_tx_method_kind(method, kind, &mut conn) {
+                            Ok(()) => start_timer("Tx Method kind set successfully."),
+                            Err(e) => {
+                                println!("Error while setting tx method kind. Error: {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetHolding(holding) => {
+                        match set_holding(&holding, &mut conn) {
+                            Ok(()) if holding.quantity == 0.0 => {
+                                start_timer("Holding removed successfully.");
+                            }
+                            Ok(()) => start_timer("Holding saved successfully."),
+                            Err(e) => {
+                                println!("Error while saving holding. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetPrices(prices) => match set_prices(&prices, &mut conn) {
+                        Ok(()) => start_timer("Prices saved successfully."),
+                        Err(e) => {
+                            println!("Error while saving prices. Error: {e:?}");
+                            start_timer("");
+                        }
+                    },
+                    UserInputType::ImportPrices(file_path) => {
+                        match import_prices(&file_path, &mut conn) {
+                            Ok(total) => start_timer(format!("Imported {total} prices successfully.")),
+                            Err(e) => {
+                                println!("Error while importing prices. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::ImportTxs(profile, file_path) => {
+                        match import_txs(&file_path, &profile, &mut conn) {
+                            Ok(total) => start_timer(format!("Imported {total} transactions successfully.")),
+                            Err(e) => {
+                                println!("Error while importing transactions. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetBaseCurrency(currency) => {
+                        match set_base_currency(&currency, &mut conn) {
+                            Ok(()) => start_timer("Base currency set successfully."),
+                            Err(e) => {
+                                println!("Error while setting base currency. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::SetExchangeRates(exchange_rates) => {
+                        match set_exchange_rates(&exchange_rates, &mut conn) {
+                            Ok(()) => start_timer("Exchange rates saved successfully."),
+                            Err(e) => {
+                                println!("Error while saving exchange rates. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::ImportExchangeRates(file_path) => {
+                        match import_exchange_rates(&file_path, &mut conn) {
+                            Ok(total) => start_timer(format!("Imported {total} exchange rates successfully.")),
+                            Err(e) => {
+                                println!("Error while importing exchange rates. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::DeleteTxMethod(method) => {
+                        match delete_tx_method(&method, &mut conn) {
+                            Ok(()) => start_timer("Tx Method deleted successfully."),
+                            Err(e) => {
+                                println!("Error while deleting tx method. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::ArchiveTxMethod(method, archived) => {
+                        match set_tx_method_archived(&method, archived, &mut conn) {
+                            Ok(()) if archived => start_timer("Tx Method archived successfully."),
+                            Ok(()) => start_timer("Tx Method unarchived successfully."),
+                            Err(e) => {
+                                println!("Error while archiving tx method. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::MergeTxMethod(merge_data) => {
+                        let from_method = &merge_data[0];
+                        let to_method = &merge_data[1];
+
+                        match merge_tx_methods(from_method, to_method, &mut conn) {
+                            Ok(0) => start_timer("Tx Methods merged successfully."),
+                            Ok(deleted) => start_timer(format!("Tx Methods merged successfully. Deleted {deleted} transfers between them.")),
+                            Err(e) => {
+                                println!("Error while merging tx methods. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::VerifyLedger => {
+                        match verify_ledger(&conn) {
+                            Ok(differences) if differences.is_empty() => start_timer("Balances and changes match the transactions."),
+                            Ok(differences) => {
+                                println!("Found {} differences. Nothing was changed.\n", differences.len());
+                                for difference in differences {
+                                    println!("- {difference}");
+                                }
+                                print!("\nPress Enter to go back: ");
+                                flush_output(&std::io::stdout());
+                                take_input();
+                            }
+                            Err(e) => {
+                                println!("Error while verifying balances. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::RebuildLedger => {
+                        match rebuild_ledger(&mut conn) {
+                            Ok(()) => start_timer("Balances and changes rebuilt successfully."),
+                            Err(e) => {
+                                println!("Error while rebuilding balances. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::RenameTag(_)
+                    | UserInputType::MergeTags(..)
+                    | UserInputType::SaveRecurringTx(_)
+                    | UserInputType::SaveBudget(_)
+                    | UserInputType::SaveGoal(_)
+                    | UserInputType::StartReconciliation(_)
+                    | UserInputType::AddDebtTx(_)
+                    | UserInputType::ExportTxs(_)
+                    | UserInputType::InvalidInput => unreachable!()
+                },
+                HandlingOutput::EditRecurringTx(recurring_id) => match get_recurring_tx_data(recurring_id, &conn) {
+                    UserInputType::SaveRecurringTx(recurring_tx) => {
+                        let status = if recurring_id.is_some() {
+                            update_recurring_tx(&recurring_tx, &mut conn)
+                        } else {
+                            add_recurring_tx(&recurring_tx, &mut conn).map(|_| ())
+                        };
+
+                        match status {
+                            Ok(()) => {
+                                recurring_status = post_recurring_txs(&mut conn);
+                                start_timer("Recurring transaction saved successfully.");
+                            }
+                            Err(e) => {
+                                println!("Error while saving recurring transaction. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::EditBudget(budget_id) => match get_budget_data(budget_id, &conn) {
+                    UserInputType::SaveBudget(budget) => {
+                        let status = if budget_id.is_some() {
+                            update_budget(&budget, &mut conn)
+                        } else {
+                            add_budget(&budget, &mut conn).map(|_| ())
+                        };
+
+                        match status {
+                            Ok(()) => start_timer("Budget saved successfully."),
+                            Err(e) => {
+                                println!("Error while saving budget. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::EditGoal(goal_id) => match get_goal_data(goal_id, &conn) {
+                    UserInputType::SaveGoal(goal) => {
+                        let status = if goal_id.is_some() {
+                            update_goal(&goal, &mut conn)
+                        } else {
+                            add_goal(&goal, &mut conn).map(|_| ())
+                        };
+
+                        match status {
+                            Ok(()) => start_timer("Goal saved successfully."),
+                            Err(e) => {
+                                println!("Error while saving goal. Error: {e:?}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::StartReconciliation => match get_reconcile_data(&conn) {
+                    UserInputType::StartReconciliation(new_reconciliation) => {
+                        reconciliation = Some(new_reconciliation);
+                        start_timer("Reconciliation started. Press K to tick the transactions on the Reconcile page.");
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::AddDebtTx => match get_debt_tx_data(&conn) {
+                    UserInputType::AddDebtTx(debt_tx) => match add_debt_tx(&debt_tx, &mut conn) {
+                        Ok(_) => start_timer("Debt transaction added successfully."),
+                        Err(e) => {
+                            println!("{e}");
+                            start_timer("");
+                        }
+                    },
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::RenameTag(tag) => match get_tag_rename_data(&tag, &conn) {
+                    UserInputType::RenameTag(rename_data) => {
+                        match rename_tag(&rename_data[0], &rename_data[1], &mut conn) {
+                            Ok(total) => start_timer(format!("Tag renamed successfully in {total} transactions.")),
+                            Err(e) => {
+                                println!("Error while renaming tag. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::MergeTag(tag) => match get_tag_merge_data(&tag, &conn) {
+                    UserInputType::MergeTags(from_tags, to_tag) => {
+                        match merge_tags(&from_tags, &to_tag, &mut conn) {
+                            Ok(total) => start_timer(format!("Tags merged successfully in {total} transactions.")),
+                            Err(e) => {
+                                println!("Error while merging tags. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
+                },
+                HandlingOutput::ExportTxs(tx_ids) => match get_export_data(tx_ids, &conn) {
+                    UserInputType::ExportTxs(export) => {
+                        match export_txs(&export.source, &export.options, &export.output, &conn) {
+                            Ok(total) => start_timer(format!(
+                                "Exported {total} transactions to {}.",
+                                export.output.to_string_lossy()
+                            )),
+                            Err(e) => {
+                                println!("Error while exporting transactions. {e}");
+                                start_timer("");
+                            }
+                        }
+                    }
+                    UserInputType::CancelledOperation => start_timer("Operation Cancelled."),
+                    _ => unreachable!(),
                 },
                 HandlingOutput::QuitUi => {
                     save_backup_db(&db_path, original_db_path);
@@ -165,3 +469,60 @@ pub fn initialize_app(
 
     Ok(())
 }
+
+/// Runs `rex export` without opening the TUI. A relative output location is relative to the folder
+/// the command was run from
+#[cfg(not(tarpaulin_include))]
+pub fn run_export_command(
+    args: &[String],
+    original_db_path: &PathBuf,
+    original_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let mut export = parse_export_args(args)?;
+
+    if export.output.is_relative() {
+        export.output = original_dir.join(&export.output);
+    }
+
+    let db_path = if let Some(mut location) = is_location_changed(original_db_path) {
+        location.push("data.sqlite");
+        location
+    } else {
+        original_db_path.clone()
+    };
+
+    if !db_path.is_file() {
+        return Err(format!("No data was found at {}", db_path.to_string_lossy()).into());
+    }
+
+    let mut conn = Connection::open(&db_path)?;
+
+    check_old_sql(&mut conn);
+
+    let total = export_txs(&export.source, &export.options, &export.output, &conn)?;
+
+    println!(
+        "Exported {total} transactions to {}",
+        export.output.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// Posts all due recurring txs. Returns the text to show in a popup if anything was posted or posting failed
+#[cfg(not(tarpaulin_include))]
+fn post_recurring_txs(conn: &mut Connection) -> Option<String> {
+    match post_due_recurring_txs(Local::now().date_naive(), conn) {
+        Ok(posted_txs) if posted_txs.is_empty() => None,
+        Ok(posted_txs) => {
+            let mut status = format!("Added {} recurring transactions\n", posted_txs.len());
+
+            for tx in posted_txs {
+                status.push_str(&format!("\n{} {} {} {}", tx[0], tx[1], tx[3], tx[4]));
+            }
+
+            Some(status)
+        }
+        Err(e) => Some(e.to_string()),
+    }
+}
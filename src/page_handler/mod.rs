@@ -2,6 +2,6 @@ mod initializer;
 mod ui_handler;
 mod ui_state;
 
-pub use initializer::initialize_app;
+pub use initializer::{initialize_app, run_export_command};
 pub use ui_handler::*;
 pub use ui_state::*;
@@ -1,3 +1,4 @@
+use chrono::Local;
 use crossterm::event::poll;
 use crossterm::event::{self, Event, KeyEventKind};
 use ratatui::backend::Backend;
@@ -5,29 +6,38 @@ use ratatui::layout::Constraint;
 use ratatui::style::Color;
 use ratatui::Terminal;
 use rusqlite::Connection;
+use std::collections::HashSet;
 use std::time::Duration;
 
 use crate::activity_page::activity_ui;
 use crate::activity_page::ActivityData;
 use crate::add_tx_page::add_tx_ui;
+use crate::budget_page::{budget_ui, BudgetData};
 use crate::chart_page::{chart_ui, ChartData};
+use crate::db::Reconciliation;
+use crate::debt_page::{debt_ui, DebtData};
+use crate::goal_page::{goal_ui, GoalData};
 use crate::home_page::home_ui;
 use crate::home_page::TransactionData;
 use crate::initial_page::initial_ui;
 use crate::key_checker::{
-    activity_keys, add_tx_keys, chart_keys, home_keys, initial_keys, search_keys, summary_keys,
+    activity_keys, add_tx_keys, budget_keys, chart_keys, debt_keys, goal_keys, home_keys,
+    initial_keys, reconcile_keys, recurring_keys, search_keys, summary_keys, tag_keys,
     InputKeyHandler,
 };
 use crate::outputs::{HandlingOutput, UiHandlingError};
 use crate::page_handler::{
-    ActivityTab, ChartTab, CurrentUi, DateType, DeletionStatus, HomeTab, IndexedData, PopupState,
-    SortingType, SummaryTab, TableData, TxTab,
+    ActivityTab, BudgetTab, ChartTab, CurrentUi, DateType, DeletionStatus, HomeTab, IndexedData,
+    PopupState, SortingType, SummaryTab, TableData, TxTab,
 };
 use crate::popup_page::PopupData;
+use crate::reconcile_page::{reconcile_ui, ReconcileData};
+use crate::recurring_page::{recurring_ui, RecurringData};
 use crate::search_page::search_ui;
 use crate::summary_page::{summary_ui, SummaryData};
+use crate::tag_page::{tag_ui, TagData};
 use crate::tx_handler::TxData;
-use crate::utility::{get_all_tx_methods, get_all_tx_methods_cumulative};
+use crate::utility::{get_active_tx_methods, get_all_tx_methods_cumulative, CurrencyConverter};
 
 pub const BACKGROUND: Color = Color::Rgb(245, 245, 255);
 pub const TEXT: Color = Color::Rgb(153, 78, 236);
@@ -39,11 +49,14 @@ pub const RED: Color = Color::Rgb(255, 51, 51);
 pub const BLUE: Color = Color::Rgb(51, 51, 255);
 pub const GRAY: Color = Color::Rgb(128, 128, 128);
 
-/// Starts the interface and run the app
+/// Starts the interface and run the app. `recurring_status` is shown in a popup once if there is no new version.
+/// `reconciliation` is the ongoing reconciliation of the Reconcile page
 #[cfg(not(tarpaulin_include))]
 pub fn start_app<B: Backend>(
     terminal: &mut Terminal<B>,
     new_version_data: &Option<Vec<String>>,
+    recurring_status: &mut Option<String>,
+    reconciliation: &mut Option<Reconciliation>,
     conn: &mut Connection,
 ) -> Result<HandlingOutput, UiHandlingError> {
     // Setting up some default values. Let's go through all of them
@@ -51,11 +64,11 @@ pub fn start_app<B: Backend>(
     // contains the home page month list that is indexed
     let mut home_months = IndexedData::new_monthly();
     // contains the home page year list that is indexed
-    let mut home_years = IndexedData::new_yearly();
+    let mut home_years = IndexedData::new_yearly(conn);
     // contains the chart page month list that is indexed
     let mut chart_months = IndexedData::new_monthly();
     // contains the chart page year list that is indexed
-    let mut chart_years = IndexedData::new_yearly();
+    let mut chart_years = IndexedData::new_yearly(conn);
     // contains the chart page mode selection list that is indexed
     let mut chart_modes = IndexedData::new_modes();
     // contains the chart page tx method selection list that is indexed
@@ -64,13 +77,17 @@ pub fn start_app<B: Backend>(
     // contains the summary page month list that is indexed
     let mut summary_months = IndexedData::new_monthly();
     // contains the summary page year list that is indexed
-    let mut summary_years = IndexedData::new_yearly();
+    let mut summary_years = IndexedData::new_yearly(conn);
     // contains the summary page mode selection list that is indexed
     let mut summary_modes = IndexedData::new_modes();
     // contains the Activity page month list that is indexed
-    let mut activity_years = IndexedData::new_yearly();
+    let mut activity_years = IndexedData::new_yearly(conn);
     // contains the Activity page month list that is indexed
     let mut activity_months = IndexedData::new_monthly();
+    // contains the Budget page year list that is indexed
+    let mut budget_years = IndexedData::new_yearly(conn);
+    // contains the Budget page month list that is indexed
+    let mut budget_months = IndexedData::new_monthly();
 
     // the selected widget on the Home Page. Default set to the month selection
     let mut home_tab = HomeTab::Months;
@@ -95,6 +112,8 @@ pub fn start_app<B: Backend>(
     // stores current popup status
     let mut popup_state = if let Some(data) = new_version_data {
         PopupState::NewUpdate(data.to_owned())
+    } else if let Some(status) = recurring_status.take() {
+        PopupState::RecurringStatus(status)
     } else {
         PopupState::Nothing
     };
@@ -111,6 +130,8 @@ pub fn start_app<B: Backend>(
     let mut search_date_type = DateType::Exact;
     // Store the current selected widget on Activity page
     let mut activity_tab = ActivityTab::Years;
+    // Store the current selected widget on Budget page
+    let mut budget_tab = BudgetTab::Years;
 
     // Holds the data that will be/are inserted into the Add Tx page's input fields
     let mut add_tx_data = TxData::new();
@@ -136,6 +157,37 @@ pub fn start_app<B: Backend>(
     // data for the Activity Page's table
     let mut activity_table = TableData::new(activity_data.get_txs());
 
+    // Stores all recurring txs for the Recurring page
+    let mut recurring_data = RecurringData::new(conn);
+    // data for the Recurring Page's table
+    let mut recurring_table = TableData::new(recurring_data.get_txs());
+
+    // Stores the budgets of the selected month for the Budget page
+    let mut budget_data =
+        BudgetData::new(budget_months.index, budget_years.index, &summary_data, conn);
+    // data for the Budget Page's table
+    let mut budget_table = TableData::new(budget_data.get_txs());
+
+    // Stores all goals along with their progress for the Goals page
+    let mut goal_data = GoalData::new(Local::now().date_naive(), conn);
+    // data for the Goals Page's table
+    let mut goal_table = TableData::new(goal_data.get_txs());
+
+    // Stores the txs that can be ticked for the ongoing reconciliation
+    let mut reconcile_data = ReconcileData::new(reconciliation.as_ref(), conn);
+    // data for the Reconcile Page's table
+    let mut reconcile_table = TableData::new(reconcile_data.get_txs());
+
+    // Stores every counterparty along with their debt txs for the Debts page
+    let mut debt_data = DebtData::new(conn);
+    // data for the Debts Page's table
+    let mut debt_table = TableData::new(debt_data.get_txs());
+
+    // Stores every tag along with its usage for the Tags page
+    let mut tag_data = TagData::new(conn);
+    // data for the Tags Page's table
+    let mut tag_table = TableData::new(tag_data.get_txs());
+
     // the initial page REX loading index
     let mut starter_index = 0;
 
@@ -148,11 +200,26 @@ pub fn start_app<B: Backend>(
     // Whether the summary is in hidden mode
     let mut summary_hidden_mode = false;
 
+    // Parent tags whose child tags are hidden on the Summary page table
+    let mut summary_collapsed_tags: HashSet<String> = HashSet::new();
+
+    // The tag the chart is filtered to. Txs of its child tags are also shown
+    let mut chart_tag: Option<String> = None;
+
+    // The goal whose target is drawn as a line on the chart
+    let mut chart_goal: Option<i32> = None;
+
+    // Whether the chart also shows the market value of the holdings of each tx method
+    let mut chart_market_value = false;
+
+    // Whether the Home page Balance row shows the cleared balance instead of the total balance
+    let mut cleared_balance = false;
+
     // The initial popup when deleting tx will start on Yes value
     let mut deletion_status: DeletionStatus = DeletionStatus::Yes;
 
     // The current balance that is being shown on the home tab Balance column. Will change every loop util the actual balance is reached
-    let mut balance_load = vec![0.0; get_all_tx_methods(conn).len() + 1];
+    let mut balance_load = vec![0.0; get_active_tx_methods(conn).len() + 1];
     // The balance shown in the UI before the current actual balance that is being shown in the UI
     // If went from row 2 to row 3, this will contain the balance or row 2 to calculate the difference
     // we have to animate/load progressively
@@ -202,7 +269,7 @@ pub fn start_app<B: Backend>(
     let mut balance_data = Vec::new();
     // Home and add tx page balance section's column space
     let mut width_data = Vec::new();
-    let total_columns = get_all_tx_methods(conn).len() + 2;
+    let total_columns = get_active_tx_methods(conn).len() + 2;
     let width_percent = (100 / total_columns) as u16;
 
     // save the % of space each column should take in the Balance section based on the total
@@ -224,36 +291,46 @@ pub fn start_app<B: Backend>(
         terminal
             .draw(|f| {
                 match page {
-                    CurrentUi::Home => home_ui(
-                        f,
-                        to_reset,
-                        &home_months,
-                        &home_years,
-                        &mut table,
-                        &mut balance_data,
-                        &home_tab,
-                        &mut width_data,
-                        &mut balance_load,
-                        &mut ongoing_balance,
-                        &mut last_balance,
-                        &mut changes_load,
-                        &mut ongoing_changes,
-                        &mut last_changes,
-                        &mut income_load,
-                        &mut ongoing_income,
-                        &mut last_income,
-                        &mut expense_load,
-                        &mut ongoing_expense,
-                        &mut last_expense,
-                        &mut daily_income_load,
-                        &mut daily_ongoing_income,
-                        &mut daily_last_income,
-                        &mut daily_expense_load,
-                        &mut daily_ongoing_expense,
-                        &mut daily_last_expense,
-                        &mut load_percentage,
-                        conn,
-                    ),
+                    CurrentUi::Home => {
+                        let converter = CurrencyConverter::new(conn);
+                        let net_worth =
+                            all_tx_data.get_net_worth(table.state.selected(), &converter, conn);
+                        let market_value =
+                            all_tx_data.get_market_value(table.state.selected(), &converter, conn);
+
+                        home_ui(
+                            f,
+                            to_reset,
+                            &home_months,
+                            &home_years,
+                            &mut table,
+                            &mut balance_data,
+                            net_worth,
+                            market_value,
+                            &home_tab,
+                            &mut width_data,
+                            &mut balance_load,
+                            &mut ongoing_balance,
+                            &mut last_balance,
+                            &mut changes_load,
+                            &mut ongoing_changes,
+                            &mut last_changes,
+                            &mut income_load,
+                            &mut ongoing_income,
+                            &mut last_income,
+                            &mut expense_load,
+                            &mut ongoing_expense,
+                            &mut last_expense,
+                            &mut daily_income_load,
+                            &mut daily_ongoing_income,
+                            &mut daily_last_income,
+                            &mut daily_expense_load,
+                            &mut daily_ongoing_expense,
+                            &mut daily_last_expense,
+                            &mut load_percentage,
+                            conn,
+                        );
+                    }
 
                     CurrentUi::AddTx => add_tx_ui(
                         f,
@@ -285,6 +362,9 @@ pub fn start_app<B: Backend>(
                         chart_hidden_mode,
                         &mut chart_index,
                         &chart_activated_methods,
+                        chart_tag.as_deref(),
+                        chart_goal.and_then(|goal_id| goal_data.find_goal(goal_id)),
+                        chart_market_value,
                         conn,
                     ),
 
@@ -298,6 +378,7 @@ pub fn start_app<B: Backend>(
                         &summary_tab,
                         summary_hidden_mode,
                         &summary_sort,
+                        &summary_collapsed_tags,
                         conn,
                     ),
                     CurrentUi::Search => search_ui(
@@ -315,6 +396,19 @@ pub fn start_app<B: Backend>(
                         &activity_data,
                         &mut activity_table,
                     ),
+                    CurrentUi::Recurring => recurring_ui(f, &mut recurring_table),
+                    CurrentUi::Budget => budget_ui(
+                        f,
+                        &budget_months,
+                        &budget_years,
+                        &budget_tab,
+                        &budget_data,
+                        &mut budget_table,
+                    ),
+                    CurrentUi::Goal => goal_ui(f, &goal_data, &mut goal_table),
+                    CurrentUi::Reconcile => reconcile_ui(f, &reconcile_data, &mut reconcile_table),
+                    CurrentUi::Debt => debt_ui(f, &debt_data, &mut debt_table),
+                    CurrentUi::Tag => tag_ui(f, &tag_data, &mut tag_table),
                 }
                 popup_data.create_popup(
                     f,
@@ -328,20 +422,20 @@ pub fn start_app<B: Backend>(
 
         // Based on the UI status, either start polling for key press or continue the loop
         match page {
-            CurrentUi::Initial => {
-                // Initial page will loop indefinitely to animate the text
-                if !poll(Duration::from_millis(40)).map_err(UiHandlingError::PollingError)? {
-                    starter_index = (starter_index + 1) % 27;
-                    continue;
-                }
+            // Initial page will loop indefinitely to animate the text
+            CurrentUi::Initial
+                if !poll(Duration::from_millis(40)).map_err(UiHandlingError::PollingError)? =>
+            {
+                starter_index = (starter_index + 1) % 27;
+                continue;
             }
-            CurrentUi::Chart => {
-                // If chart animation has ended, start polling
+            // If chart animation has ended, start polling
+            CurrentUi::Chart
                 if chart_index.is_some()
-                    && !poll(Duration::from_millis(2)).map_err(UiHandlingError::PollingError)?
-                {
-                    continue;
-                }
+                    && !poll(Duration::from_millis(2))
+                        .map_err(UiHandlingError::PollingError)? =>
+            {
+                continue;
             }
             CurrentUi::Home | CurrentUi::AddTx => {
                 // If balance loading hasn't ended yet, continue the loop
@@ -401,9 +495,30 @@ pub fn start_app<B: Backend>(
                 &mut activity_tab,
                 &mut activity_data,
                 &mut activity_table,
+                &mut recurring_data,
+                &mut recurring_table,
+                &mut budget_months,
+                &mut budget_years,
+                &mut budget_tab,
+                &mut budget_data,
+                &mut budget_table,
+                &mut goal_data,
+                &mut goal_table,
+                reconciliation,
+                &mut reconcile_data,
+                &mut reconcile_table,
+                &mut debt_data,
+                &mut debt_table,
+                &mut tag_data,
+                &mut tag_table,
                 &mut chart_index,
                 &mut chart_hidden_mode,
                 &mut summary_hidden_mode,
+                &mut summary_collapsed_tags,
+                &mut chart_tag,
+                &mut chart_goal,
+                &mut chart_market_value,
+                &mut cleared_balance,
                 &mut deletion_status,
                 &mut ongoing_balance,
                 &mut ongoing_changes,
@@ -425,6 +540,12 @@ pub fn start_app<B: Backend>(
                 CurrentUi::Summary => summary_keys(&mut handler),
                 CurrentUi::Search => search_keys(&mut handler),
                 CurrentUi::Activity => activity_keys(&mut handler),
+                CurrentUi::Recurring => recurring_keys(&mut handler),
+                CurrentUi::Budget => budget_keys(&mut handler),
+                CurrentUi::Goal => goal_keys(&mut handler),
+                CurrentUi::Reconcile => reconcile_keys(&mut handler),
+                CurrentUi::Debt => debt_keys(&mut handler),
+                CurrentUi::Tag => tag_keys(&mut handler),
             };
 
             // If there is a status it means it needs to be handled outside the UI
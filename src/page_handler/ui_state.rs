@@ -4,8 +4,11 @@ use ratatui::widgets::TableState;
 use rusqlite::Connection;
 use std::path::PathBuf;
 
-use crate::db::{MODES, MONTHS, YEARS};
-use crate::utility::{get_all_tx_methods, get_all_tx_methods_cumulative};
+use crate::db::{
+    Budget, ExportCommand, Goal, Holding, ImportProfile, NewDebtTx, OpeningBalance, Price,
+    Reconciliation, RecurringTx, MODES, MONTHS,
+};
+use crate::utility::{get_all_tx_methods, get_all_tx_methods_cumulative, get_all_years};
 
 /// The struct stores all transaction data for the Transaction widget
 /// and creates an index to keep track of which transactions row is selected
@@ -64,7 +67,6 @@ impl TableData {
 /// It is used for keeping track of the Months and Years current index.
 ///
 /// titles: `["January", "February",]`
-
 pub struct IndexedData {
     pub titles: Vec<String>,
     pub index: usize,
@@ -79,10 +81,18 @@ impl IndexedData {
         }
     }
 
-    pub fn new_yearly() -> Self {
-        let year_index = Local::now().year() as usize - 2022;
+    /// Creates the year list from the year range of the db. Selects the current year
+    /// or the last year if the current year is not in the range
+    pub fn new_yearly(conn: &Connection) -> Self {
+        let titles = get_all_years(conn);
+        let current_year = Local::now().year().to_string();
+        let year_index = titles
+            .iter()
+            .position(|year| year == &current_year)
+            .unwrap_or(titles.len() - 1);
+
         IndexedData {
-            titles: YEARS.into_iter().map(ToString::to_string).collect(),
+            titles,
             index: year_index,
         }
     }
@@ -125,6 +135,18 @@ impl IndexedData {
     pub fn set_index_zero(&mut self) {
         self.index = 0;
     }
+
+    /// Reloads the year list from the db while keeping the same year selected.
+    /// Used after the year range may have grown
+    pub fn reload_yearly(&mut self, conn: &Connection) {
+        let selected_year = self.titles[self.index].clone();
+        self.titles = get_all_years(conn);
+        self.index = self
+            .titles
+            .iter()
+            .position(|year| year == &selected_year)
+            .unwrap_or(0);
+    }
 }
 
 /// The enum is used to keep track of which tab is currently set at active
@@ -172,6 +194,8 @@ pub enum TxTab {
     Amount,
     TxType,
     Tags,
+    Splits,
+    Status,
     Nothing,
 }
 
@@ -185,6 +209,12 @@ pub enum CurrentUi {
     Summary,
     Search,
     Activity,
+    Recurring,
+    Budget,
+    Goal,
+    Reconcile,
+    Debt,
+    Tag,
 }
 
 /// Indicates which popup is currently on and is being shown in the screen
@@ -196,7 +226,16 @@ pub enum PopupState {
     SummaryHelp,
     SearchHelp,
     ActivityHelp,
+    RecurringHelp,
+    RecurringStatus(String),
+    BudgetHelp,
+    BudgetWarning(String),
+    GoalHelp,
+    ReconcileHelp,
+    DebtHelp,
+    TagHelp,
     DeleteFailed(String),
+    HistoryFailed(String),
     TxDeletion,
     ShowDetails(String),
     Nothing,
@@ -363,13 +402,35 @@ impl SummaryTab {
 }
 
 pub enum UserInputType {
-    AddNewTxMethod(Vec<String>),
+    AddNewTxMethod(Vec<String>, Vec<OpeningBalance>),
     RenameTxMethod(Vec<String>),
     RepositionTxMethod(Vec<String>),
     SetNewLocation(PathBuf),
     CancelledOperation,
     ResetData(ResetType),
     BackupDBPath(Vec<PathBuf>),
+    SetTxMethodCurrency(Vec<String>),
+    SetBaseCurrency(String),
+    SetExchangeRates(Vec<(String, String, f64)>),
+    ImportExchangeRates(PathBuf),
+    DeleteTxMethod(String),
+    ArchiveTxMethod(String, bool),
+    MergeTxMethod(Vec<String>),
+    VerifyLedger,
+    RebuildLedger,
+    SetTxMethodKind(Vec<String>),
+    SetHolding(Holding),
+    SetPrices(Vec<Price>),
+    ImportPrices(PathBuf),
+    ImportTxs(ImportProfile, PathBuf),
+    RenameTag(Vec<String>),
+    MergeTags(Vec<String>, String),
+    SaveRecurringTx(RecurringTx),
+    SaveBudget(Budget),
+    SaveGoal(Goal),
+    StartReconciliation(Reconciliation),
+    AddDebtTx(NewDebtTx),
+    ExportTxs(ExportCommand),
     InvalidInput,
 }
 
@@ -382,11 +443,25 @@ impl UserInputType {
     #[cfg(not(tarpaulin_include))]
     pub fn from_string(input: &str) -> Self {
         match input {
-            "1" => UserInputType::AddNewTxMethod(Vec::new()),
+            "1" => UserInputType::AddNewTxMethod(Vec::new(), Vec::new()),
             "2" => UserInputType::RenameTxMethod(Vec::new()),
             "3" => UserInputType::RepositionTxMethod(Vec::new()),
             "4" => UserInputType::SetNewLocation(PathBuf::new()),
             "5" => UserInputType::BackupDBPath(Vec::new()),
+            "6" => UserInputType::SetTxMethodCurrency(Vec::new()),
+            "7" => UserInputType::SetBaseCurrency(String::new()),
+            "8" => UserInputType::SetExchangeRates(Vec::new()),
+            "9" => UserInputType::ImportExchangeRates(PathBuf::new()),
+            "10" => UserInputType::DeleteTxMethod(String::new()),
+            "11" => UserInputType::ArchiveTxMethod(String::new(), false),
+            "12" => UserInputType::MergeTxMethod(Vec::new()),
+            "13" => UserInputType::VerifyLedger,
+            "14" => UserInputType::RebuildLedger,
+            "15" => UserInputType::SetTxMethodKind(Vec::new()),
+            "16" => UserInputType::SetHolding(Holding::default()),
+            "17" => UserInputType::SetPrices(Vec::new()),
+            "18" => UserInputType::ImportPrices(PathBuf::new()),
+            "19" => UserInputType::ImportTxs(ImportProfile::default(), PathBuf::new()),
             "cancel" => UserInputType::CancelledOperation,
             _ => UserInputType::InvalidInput,
         }
@@ -456,7 +531,8 @@ pub enum HomeRow {
 impl HomeRow {
     #[cfg(not(tarpaulin_include))]
     pub fn get_row(data: &[String]) -> Self {
-        if data[0] == "Balance" {
+        // The Balance row shows either the total or the cleared balance
+        if data[0] == "Balance" || data[0] == "Cleared" {
             HomeRow::Balance
         } else if data[0] == "Changes" {
             HomeRow::Changes
@@ -500,12 +576,48 @@ impl ActivityTab {
     }
 }
 
+pub enum BudgetTab {
+    Years,
+    Months,
+    List,
+}
+
+impl BudgetTab {
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_tab_up(&mut self) -> Self {
+        match &self {
+            BudgetTab::Years => BudgetTab::List,
+            BudgetTab::Months => BudgetTab::Years,
+            BudgetTab::List => BudgetTab::Months,
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    pub fn change_tab_down(&mut self) -> Self {
+        match &self {
+            BudgetTab::List => BudgetTab::Years,
+            BudgetTab::Years => BudgetTab::Months,
+            BudgetTab::Months => BudgetTab::List,
+        }
+    }
+}
+
 pub enum ActivityType {
     NewTX,
     EditTX(Option<i32>),
     DeleteTX(Option<i32>),
     IDNumSwap(Option<i32>, Option<i32>),
     SearchTX(Option<u8>),
+    Undo(Option<String>),
+    Redo(Option<String>),
+    RestoreTX(Option<i32>),
+    RevertEdit(Option<i32>),
+    RenameTag(Option<String>, Option<String>),
+    MergeTags(Option<String>, Option<String>),
+    RemoveTag(Option<String>),
+    Reconcile(Option<String>, Option<String>, Option<String>),
+    Import(Option<String>, Option<usize>),
+    MergeTxMethods(Option<String>, Option<String>),
 }
 
 impl ActivityType {
@@ -516,6 +628,16 @@ impl ActivityType {
             "Delete TX" => Self::DeleteTX(None),
             "TX Position Swap" => Self::IDNumSwap(None, None),
             "Search TX" => Self::SearchTX(None),
+            "Undo" => Self::Undo(None),
+            "Redo" => Self::Redo(None),
+            "Restore TX" => Self::RestoreTX(None),
+            "Revert Edit" => Self::RevertEdit(None),
+            "Rename Tag" => Self::RenameTag(None, None),
+            "Merge Tags" => Self::MergeTags(None, None),
+            "Remove Tag" => Self::RemoveTag(None),
+            "Reconcile" => Self::Reconcile(None, None, None),
+            "Import" => Self::Import(None, None),
+            "Merge TX Methods" => Self::MergeTxMethods(None, None),
             _ => unreachable!(),
         }
     }
@@ -537,6 +659,44 @@ impl ActivityType {
                     String::from("Transactions were searched with multiple fields")
                 }
             }
+            Self::Undo(details) => format!("Undone: {}", details.as_ref().unwrap()),
+            Self::Redo(details) => format!("Redone: {}", details.as_ref().unwrap()),
+            Self::RestoreTX(id) => {
+                format!("A deleted transaction was restored with ID {}", id.unwrap())
+            }
+            Self::RevertEdit(id) => {
+                format!("A transaction edit was reverted with ID {}", id.unwrap())
+            }
+            Self::RenameTag(old_tag, new_tag) => format!(
+                "Tag {} was renamed to {}",
+                old_tag.as_ref().unwrap(),
+                new_tag.as_ref().unwrap()
+            ),
+            Self::MergeTags(from_tags, to_tag) => format!(
+                "Tags {} were merged into {}",
+                from_tags.as_ref().unwrap(),
+                to_tag.as_ref().unwrap()
+            ),
+            Self::RemoveTag(tag) => format!(
+                "Tag {} was removed from all transactions",
+                tag.as_ref().unwrap()
+            ),
+            Self::Reconcile(method, date, balance) => format!(
+                "{} was reconciled up to {} with statement balance {}",
+                method.as_ref().unwrap(),
+                date.as_ref().unwrap(),
+                balance.as_ref().unwrap()
+            ),
+            Self::Import(profile, total) => format!(
+                "{} transactions were imported with the {} profile",
+                total.unwrap(),
+                profile.as_ref().unwrap()
+            ),
+            Self::MergeTxMethods(from_method, to_method) => format!(
+                "TX Method {} was merged into {}",
+                from_method.as_ref().unwrap(),
+                to_method.as_ref().unwrap()
+            ),
         }
     }
 
@@ -547,6 +707,16 @@ impl ActivityType {
             Self::DeleteTX(_) => String::from("Delete TX"),
             Self::IDNumSwap(_, _) => String::from("TX Position Swap"),
             Self::SearchTX(_) => String::from("Search TX"),
+            Self::Undo(_) => String::from("Undo"),
+            Self::Redo(_) => String::from("Redo"),
+            Self::RestoreTX(_) => String::from("Restore TX"),
+            Self::RevertEdit(_) => String::from("Revert Edit"),
+            Self::RenameTag(_, _) => String::from("Rename Tag"),
+            Self::MergeTags(_, _) => String::from("Merge Tags"),
+            Self::RemoveTag(_) => String::from("Remove Tag"),
+            Self::Reconcile(..) => String::from("Reconcile"),
+            Self::Import(..) => String::from("Import"),
+            Self::MergeTxMethods(..) => String::from("Merge TX Methods"),
         }
     }
 }
@@ -9,6 +9,12 @@ pub const R: &str = "R: Chart Page";
 pub const Z: &str = "Z: Summary Page";
 pub const Y: &str = "Y: Activity Page";
 pub const W: &str = "W: Search Page";
+pub const O: &str = "O: Recurring Page";
+pub const B: &str = "B: Budget Page";
+pub const G: &str = "G: Goals Page";
+pub const K: &str = "K: Reconcile Page";
+pub const I: &str = "I: Debts Page";
+pub const T: &str = "T: Tags Page";
 pub const Q: &str = "Q: Quit";
 pub const H: &str = "H: Show help";
 pub const V: &str = "V: Show selected transaction details";
@@ -45,8 +51,17 @@ impl<'a> PopupData<'a> {
             PopupState::ChartHelp => self.get_chart_help_text(),
             PopupState::SummaryHelp => self.get_summary_help_text(),
             PopupState::DeleteFailed(err) => self.get_delete_failed_text(err),
+            PopupState::HistoryFailed(err) => self.get_history_failed_text(err),
             PopupState::SearchHelp => self.get_search_help_text(),
             PopupState::ActivityHelp => self.get_activity_help_text(),
+            PopupState::RecurringHelp => self.get_recurring_help_text(),
+            PopupState::RecurringStatus(status) => self.get_recurring_status_text(status),
+            PopupState::BudgetHelp => self.get_budget_help_text(),
+            PopupState::BudgetWarning(warning) => self.get_budget_warning_text(warning),
+            PopupState::GoalHelp => self.get_goal_help_text(),
+            PopupState::ReconcileHelp => self.get_reconcile_help_text(),
+            PopupState::DebtHelp => self.get_debt_help_text(),
+            PopupState::TagHelp => self.get_tag_help_text(),
             PopupState::ShowDetails(details) => {
                 self.get_transaction_details_text(details.to_string())
             }
@@ -91,6 +106,8 @@ On Transfer transaction there will be one additional field pushing Tags to the k
 3: TX Method    Example: Cash, Bank, Card
 4: Amount       Example: 1000, 100+50, b - 100
 6: Tags         Example: Food, Car. Add a Comma for a new tag
+7: Splits       Example: Food | 30 | Milk; Home | 20. Not available on Transfer
+8: TX Status    Example: Pending/Cleared/P/C
 
 S: Save the inputted data as a Transaction
 Enter: Submit field and continue. Also selects the first field if nothing is selected
@@ -107,6 +124,12 @@ m: On amount field 'm' is considered as 1,000,000 or a million
 Calculation: Amount field supports simple calculation with +, -, *, /
 Tags: This field can be treated as the category of this transaction.
 Empty tags field gets replaced with Unknown. Separate more than 1 tags with a comma
+Use : to put a tag under a parent tag. Example: Food:Groceries
+Splits: Divides the amount across multiple tags. Each split is Tag | Amount | Note with an optional note.
+Separate more than 1 splits with a ;. The split amounts must add up to the amount. An empty amount
+gets filled with their total. A split transaction takes its tags from the splits and the Summary
+and Search count only the split amount of each tag
+TX Status: Pending transactions have not reached the bank yet. Empty status gets saved as Cleared
 
 Example amount: 100 + b, b + b, 5 * b, 1.2k + 1m
 
@@ -115,6 +138,12 @@ Example amount: 100 + b, b + b, 5 * b, 1.2k + 1m
 {Z}
 {Y}
 {W}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
 {H}
 {Q}
 "
@@ -131,6 +160,9 @@ Following are the supported keys here
 
 R: Hides the top widgets for full chart view
 Space: Enable/Disable tx method from the chart
+T: Show only the txs of the next tag and its child tags. Goes back to all txs after the last tag
+L: Draw the target of the next goal as a line. Goes back to no line after the last goal
+M: Show or hide the market value of the holdings of each active tx method
 
 Arrow Up/Down: Cycle widgets
 Arrow Left/Right: Move value of the widget
@@ -140,6 +172,12 @@ Arrow Left/Right: Move value of the widget
 {Z}
 {Y}
 {W}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
 {H}
 {Q}
 "
@@ -152,21 +190,33 @@ Arrow Left/Right: Move value of the widget
         format!(
             "This page shows various information based on all transactions \
             and is for tracking incomes and expenses based on tags \
-            Transfer Transaction are not shown here
+            Transfer Transaction are not shown here. \
+            Parent tags such as Food of Food:Groceries show the subtotal of all tags under them
 
 Following are the supported keys here
 
 X: Sorts table by Tag, Total Income or Total Expense
 Z: Hides the top widgets for full view
+C: Collapse or expand the child tags of the selected parent tag
+Enter: Search the transactions of the selected tag and its child tags
 
 Arrow Up/Down: Cycle widgets/table value
 Arrow Left/Right: Move value of the widget
 
+The Holdings table shows the unrealized gain or loss of each investment holding with the latest prices. \
+Holdings and prices can be added with J on the Home page.
+
 {F}
 {A}
 {R}
 {Y}
 {W}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
 {H}
 {Q}
 "
@@ -183,6 +233,10 @@ E: Edit the selected transaction on the table
 D: Delete the selected transaction on the table
 ,: Swaps the location of the selected transaction with the transaction above it
 .: Swaps the location of the selected transaction with the transaction below it
+U: Undo the last added, edited, deleted or swapped transaction
+Shift + U: Redo the last undone change
+C: Switch the Balance row between the total and the cleared balance
+P: Export the transactions within a date range to a CSV file
 {V}
 
 Arrow Up/Down: Cycle widgets/table value
@@ -190,11 +244,31 @@ Arrow Left/Right: Move value of the widget
 
 Swapping transaction location will only work if they are on the same date. 
 
+Undo and Redo can be used multiple times and are recorded on the Activity page. \
+Making a new change after undoing clears the changes that can be redone.
+
+Each Transaction Method column is in its own currency. The Total column is converted to the base currency. \
+Currencies and exchange rates can be set with J. Methods without an exchange rate are left out of the Total.
+
+The cleared balance leaves out the Pending transactions that have not reached the bank yet.
+
+Transaction Methods can be set as liabilities with J. The Total column only counts the assets as the liquid balance \
+while the Net Worth on the Balance title also subtracts the debt of the liabilities.
+
+Investment holdings and their prices can be added with J. Their Market Value is shown on the Balance title \
+with the prices of the selected transaction date and is counted in the Net Worth.
+
 {A}
 {R}
 {Z}
 {Y}
 {W}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
 {H}
 {Q}
 ")
@@ -206,6 +280,12 @@ Swapping transaction location will only work if they are on the same date.
         err.to_string()
     }
 
+    #[cfg(not(tarpaulin_include))]
+    fn get_history_failed_text(&mut self, err: &str) -> String {
+        self.set_title("Action Failed");
+        err.to_string()
+    }
+
     #[cfg(not(tarpaulin_include))]
     fn get_search_help_text(&mut self) -> String {
         self.set_title("Help");
@@ -219,12 +299,14 @@ Swapping transaction location will only work if they are on the same date.
 3: TX Method    Example: Cash, Bank, Card
 4: Amount       Example: 1000, 100+50, b - 100
 6: Tags         Example: Food, Car. Add a Comma for a new tag
+7: TX Status    Example: Pending/Cleared/Reconciled/P/C/R. Key 8 on Transfer
 
 Fields: Minimum 1 field must be filled to search for transactions. \
                     Fill up multiple fields for better accuracy
 
 S: Search for transactions with the given data
 X: Cycle date type for searching with exact date, month based or year based
+P: Export the found transactions to a CSV file
 Enter: Submit field and continue. Also selects the first field if nothing is selected
 Esc: Stop editing field
 Tab: Accept Autocompletion. Pressing again will remove the autocompleted value
@@ -242,7 +324,8 @@ Example amount: 100 + b, b + b, 5 * b, 1.2k + 5m
 Details Field: If details field is filled up, it will try to find transactions \
                 that matches the given input. It doesn't have to be an exact match
 Tags Field: If tags field is filled up with more than 1 tags, it will match all transactions \
-                that has any one of the tags.
+                that has any one of the tags. Split transactions show and compare only the amount \
+                of the splits with the searched tags.
 Amount Field: Amount field supports '>' '<' '>=' '<=' highlighting amount \
                 Bigger than, Smaller than, Bigger or equal, Smaller or equal respectively. \
                 No symbol will mean exact amount match. 
@@ -254,6 +337,12 @@ Example amount : <1000, >=10000
 {R}
 {Z}
 {Y}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
 {H}
 {Q}
 "
@@ -269,6 +358,92 @@ Example amount : <1000, >=10000
 Following are the supported keys here
 
 {V}
+E: Restore the selected deleted transaction or revert the selected transaction edit
+
+A restored transaction keeps its old ID if it is still free, otherwise it gets a new ID. \
+Restoring and reverting are recorded as activities and can be undone from the Home page.
+
+Arrow Up/Down: Cycle widgets
+Arrow Left/Right: Move value of the widget
+
+{F}
+{A}
+{R}
+{Z}
+{W}
+{O}
+{B}
+{G}
+{K}
+{I}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    fn get_recurring_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the recurring transactions. \
+            A recurring transaction is added as a new transaction every time it is due \
+            when the app starts, starting at the start date and stopping after the end date.
+
+Following are the supported keys here
+
+N: Add a new recurring transaction
+E: Edit the selected recurring transaction
+P: Pause or resume the selected recurring transaction
+D: Delete the selected recurring transaction
+
+Schedule examples: daily, weekly, monthly, yearly, every 2 weeks, monthly on day 15, every 3 months on day 31
+A day that does not exist in a month is moved to the last day of that month.
+
+Nothing is added while a recurring transaction is paused and the dates that were missed while paused are skipped. \
+Deleting a recurring transaction keeps the transactions it has added.
+
+Arrow Up/Down: Cycle the table
+
+{F}
+{A}
+{R}
+{Z}
+{Y}
+{W}
+{B}
+{G}
+{K}
+{I}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_recurring_status_text(&mut self, status: &str) -> String {
+        self.set_title("Recurring Transactions");
+        status.to_string()
+    }
+
+    fn get_budget_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the budgets of the selected month and how much of them has been spent. \
+            A budget of a parent tag such as Food also counts the expenses of its child tags such as Food:Groceries. \
+            Spending is calculated in the base currency.
+
+Following are the supported keys here
+
+N: Add a new budget
+E: Edit the selected budget
+D: Delete the selected budget
+
+A monthly budget resets every month and a yearly budget every year. \
+With rollover, the unspent amount of a period gets added to the next one, starting from the start month of the budget. \
+Adding an expense that takes a budget over its limit shows a warning on the Home page.
 
 Arrow Up/Down: Cycle widgets
 Arrow Left/Right: Move value of the widget
@@ -277,7 +452,164 @@ Arrow Left/Right: Move value of the widget
 {A}
 {R}
 {Z}
+{Y}
+{W}
+{O}
+{G}
+{K}
+{I}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_budget_warning_text(&mut self, warning: &str) -> String {
+        self.set_title("Budget Exceeded");
+        format!("The new transaction took these budgets over their limit\n\n{warning}")
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_goal_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the savings goals and how close they are to their target. \
+            A goal tracks either the balance of a tx method or the total of the transactions of a tag and its child tags. \
+            For a tag, income and transfers add to the total while expenses take from it, calculated in the base currency.
+
+Following are the supported keys here
+
+N: Add a new goal
+E: Edit the selected goal
+D: Delete the selected goal
+
+Monthly Needed is the amount to save every month, including the current one, to reach the target by the target date. \
+Press L on the Chart page to draw the target of a goal as a line.
+
+Arrow Up/Down: Cycle the table
+
+{F}
+{A}
+{R}
+{Z}
+{Y}
+{W}
+{O}
+{B}
+{K}
+{I}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_reconcile_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page matches the transactions of a tx method against a bank statement. \
+            Start a reconciliation with the tx method, the statement date and the closing balance of the statement, \
+            then tick the transactions that show up on the statement until the difference reaches 0.
+
+Following are the supported keys here
+
+N: Start a new reconciliation
+Space/Enter: Tick or untick the selected transaction
+S: Reconcile the ticked transactions. Only works once the difference is 0
+X: Stop the ongoing reconciliation without saving
+
+The reconciled balance counts only the already reconciled transactions up to the statement date. \
+Reconciled transactions cannot be edited and the reconciliation is saved on the Activity page. \
+Undo on the Home page reverts the last reconciliation.
+
+Arrow Up/Down: Cycle the table
+
+{F}
+{A}
+{R}
+{Z}
+{Y}
+{W}
+{O}
+{B}
+{G}
+{I}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_debt_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows the people money was lent to or borrowed from and how much is still outstanding with each. \
+            Lending takes the money out of the tx method and borrowing adds it, the same way an expense and an income would, \
+            but neither is counted as one.
+
+Following are the supported keys here
+
+N: Add a new Lend, Borrow or Repay transaction
+
+A repayment goes in the direction of what is outstanding with the person and settles their oldest open items first. \
+The Open column of the history shows how much of each item is not settled yet. \
+The transactions show up on the Home page where they can be edited or deleted like any other transaction.
+
+Arrow Up/Down: Cycle the table
+
+{F}
+{A}
+{R}
+{Z}
+{Y}
+{W}
+{O}
+{B}
+{G}
+{K}
+{T}
+{H}
+{Q}
+"
+        )
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    fn get_tag_help_text(&mut self) -> String {
+        self.set_title("Help");
+        format!(
+            "This page shows every tag along with the amount of transactions using it \
+            and the income and expense total of those transactions, calculated in the base currency.
+
+Following are the supported keys here
+
+E: Rename the selected tag
+M: Merge the selected tag into another tag
+D: Remove the selected tag from every transaction
+
+Renaming or merging also moves the budgets and goals of the tag. \
+Transactions left without any tag get the Unknown tag. \
+Every change is saved on the Activity page and can be undone from the Home page.
+
+Arrow Up/Down: Cycle the table
+
+{F}
+{A}
+{R}
+{Z}
+{Y}
 {W}
+{O}
+{B}
+{G}
+{K}
+{I}
 {H}
 {Q}
 "
@@ -0,0 +1,5 @@
+mod reconcile_data;
+mod reconcile_ui;
+
+pub use reconcile_data::ReconcileData;
+pub use reconcile_ui::reconcile_ui;
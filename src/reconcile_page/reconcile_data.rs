@@ -0,0 +1,118 @@
+use rusqlite::Connection;
+
+use crate::db::{get_reconciled_balance, get_unreconciled_txs, ReconcileTx, Reconciliation};
+use crate::utility::{reverse_date_format, Cent};
+
+/// The ongoing reconciliation along with the txs that can still be reconciled and the ones ticked so far
+pub struct ReconcileData {
+    reconciliation: Option<Reconciliation>,
+    /// The balance at the statement date counting only the already reconciled txs
+    reconciled_balance: Cent,
+    txs: Vec<ReconcileTx>,
+    ticked: Vec<bool>,
+}
+
+impl ReconcileData {
+    pub fn new(reconciliation: Option<&Reconciliation>, conn: &Connection) -> Self {
+        let Some(reconciliation) = reconciliation else {
+            return ReconcileData {
+                reconciliation: None,
+                reconciled_balance: Cent::default(),
+                txs: Vec::new(),
+                ticked: Vec::new(),
+            };
+        };
+
+        let reconciled_balance = get_reconciled_balance(reconciliation, conn)
+            .expect("could not get the reconciled balance");
+        let txs = get_unreconciled_txs(
+            &reconciliation.tx_method,
+            reconciliation.statement_date,
+            conn,
+        )
+        .expect("could not get the unreconciled txs");
+
+        ReconcileData {
+            reconciliation: Some(reconciliation.clone()),
+            reconciled_balance,
+            ticked: vec![false; txs.len()],
+            txs,
+        }
+    }
+
+    /// Convert all txs to a Vector where each value of the vector is the data of a tx
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.txs
+            .iter()
+            .zip(self.ticked.iter())
+            .map(|(tx, ticked)| {
+                vec![
+                    if *ticked { "[x]" } else { "[ ]" }.to_string(),
+                    reverse_date_format(tx.date.clone()),
+                    tx.details.clone(),
+                    tx.tx_type.clone(),
+                    tx.change.to_string(),
+                    tx.status.clone(),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn get_reconciliation(&self) -> Option<&Reconciliation> {
+        self.reconciliation.as_ref()
+    }
+
+    /// Ticks the tx if it's not ticked, otherwise unticks it
+    pub fn toggle_tick(&mut self, index: usize) {
+        if let Some(ticked) = self.ticked.get_mut(index) {
+            *ticked = !*ticked;
+        }
+    }
+
+    pub fn is_ticked(&self, index: usize) -> bool {
+        self.ticked[index]
+    }
+
+    /// The `id_num` of every ticked tx
+    pub fn ticked_id_nums(&self) -> Vec<i32> {
+        self.txs
+            .iter()
+            .zip(self.ticked.iter())
+            .filter(|(_, ticked)| **ticked)
+            .map(|(tx, _)| tx.id_num)
+            .collect()
+    }
+
+    pub fn reconciled_balance(&self) -> Cent {
+        self.reconciled_balance
+    }
+
+    /// The total change of the ticked txs on the tx method
+    pub fn ticked_total(&self) -> Cent {
+        self.txs
+            .iter()
+            .zip(self.ticked.iter())
+            .filter(|(_, ticked)| **ticked)
+            .map(|(tx, _)| tx.change)
+            .sum()
+    }
+
+    /// How far the statement balance is from the reconciled balance with the ticked txs
+    pub fn difference(&self) -> Cent {
+        match &self.reconciliation {
+            Some(reconciliation) => {
+                reconciliation.statement_balance - self.reconciled_balance - self.ticked_total()
+            }
+            None => Cent::default(),
+        }
+    }
+
+    /// Whether the ticked txs can be reconciled
+    pub fn is_balanced(&self) -> bool {
+        self.reconciliation.is_some() && self.difference().is_zero()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+}
@@ -0,0 +1,127 @@
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::page_handler::{TableData, BACKGROUND, HEADER, RED, SELECTED, TEXT};
+use crate::reconcile_page::ReconcileData;
+use crate::utility::{main_block, styled_block};
+
+/// Shows the ongoing reconciliation and the txs that can be ticked to reach the statement balance
+#[cfg(not(tarpaulin_include))]
+pub fn reconcile_ui(f: &mut Frame, reconcile_data: &ReconcileData, table_data: &mut TableData) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let Some(reconciliation) = reconcile_data.get_reconciliation() else {
+        let info = Paragraph::new(
+            "No reconciliation is ongoing. Press N to start reconciling a Transaction Method",
+        )
+        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .block(styled_block("Reconcile"))
+        .alignment(Alignment::Left);
+
+        f.render_widget(info, chunks[0]);
+        return;
+    };
+
+    let difference = reconcile_data.difference();
+
+    let summary_text = format!(
+        "Statement balance: {} | Reconciled balance: {} | Ticked: {} | Difference: {}",
+        reconciliation
+            .statement_balance
+            .to_string()
+            .separate_with_commas(),
+        reconcile_data
+            .reconciled_balance()
+            .to_string()
+            .separate_with_commas(),
+        reconcile_data
+            .ticked_total()
+            .to_string()
+            .separate_with_commas(),
+        difference.to_string().separate_with_commas(),
+    );
+
+    // The difference is highlighted until the ticked txs can be reconciled
+    let summary_style = if reconcile_data.is_balanced() {
+        Style::default().bg(BACKGROUND).fg(TEXT)
+    } else {
+        Style::default().bg(BACKGROUND).fg(RED)
+    };
+
+    let summary_name = format!(
+        "Reconciling {} up to {}",
+        reconciliation.tx_method, reconciliation.statement_date
+    );
+
+    let summary = Paragraph::new(summary_text)
+        .style(summary_style)
+        .block(styled_block(&summary_name))
+        .alignment(Alignment::Left);
+
+    let mut table_name = "Transactions".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("Transactions: {}", table_data.items.len());
+    }
+
+    let header_cells = ["", "Date", "Details", "Type", "Amount", "TX Status"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().enumerate().map(|(index, item)| {
+        let cells = item.iter().enumerate().map(|(cell_index, c)| {
+            if cell_index == 4 {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+
+        let style = if reconcile_data.is_ticked(index) {
+            Style::default().bg(BACKGROUND).fg(HEADER)
+        } else {
+            Style::default().bg(BACKGROUND).fg(TEXT)
+        };
+
+        Row::new(cells).height(1).bottom_margin(0).style(style)
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(5),
+            Constraint::Percentage(12),
+            Constraint::Percentage(43),
+            Constraint::Percentage(12),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    if table_data.state.selected().is_some() {
+        table_area = table_area
+            .highlight_symbol(">> ")
+            .row_highlight_style(Style::default().bg(SELECTED));
+    }
+
+    f.render_widget(summary, chunks[0]);
+    f.render_stateful_widget(table_area, chunks[1], &mut table_data.state);
+}
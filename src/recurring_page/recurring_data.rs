@@ -0,0 +1,41 @@
+use rusqlite::Connection;
+
+use crate::db::{get_all_recurring_txs, get_recurring_tx_ids, RecurringTx};
+
+/// Stores all recurring txs along with the amount of txs each of them has posted
+pub struct RecurringData {
+    recurring_txs: Vec<RecurringTx>,
+    total_posted: Vec<usize>,
+}
+
+impl RecurringData {
+    pub fn new(conn: &Connection) -> Self {
+        let recurring_txs = get_all_recurring_txs(conn);
+        let total_posted = recurring_txs
+            .iter()
+            .map(|recurring_tx| get_recurring_tx_ids(recurring_tx.recurring_id, conn).len())
+            .collect();
+
+        RecurringData {
+            recurring_txs,
+            total_posted,
+        }
+    }
+
+    /// Convert all recurring txs to a Vector where each value of the vector is the data of a recurring tx
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.recurring_txs
+            .iter()
+            .zip(&self.total_posted)
+            .map(|(recurring_tx, total_posted)| recurring_tx.to_vec(*total_posted))
+            .collect()
+    }
+
+    pub fn get_recurring_tx(&self, index: usize) -> &RecurringTx {
+        &self.recurring_txs[index]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recurring_txs.is_empty()
+    }
+}
@@ -0,0 +1,87 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::page_handler::{TableData, BACKGROUND, HEADER, SELECTED, TEXT};
+use crate::utility::{main_block, styled_block};
+
+/// Shows all recurring txs in a table
+#[cfg(not(tarpaulin_include))]
+pub fn recurring_ui(f: &mut Frame, table_data: &mut TableData) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0)])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let mut table_name = "Recurring Transactions".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("Recurring Transactions: {}", table_data.items.len());
+    }
+
+    let header_cells = [
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Tags",
+        "Schedule",
+        "Next",
+        "Posted",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().map(|item| {
+        let cells = item.iter().enumerate().map(|(index, c)| {
+            // Only the amount gets commas. The next date and the schedule can contain numbers
+            if index == 2 {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(20),
+            Constraint::Percentage(13),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+            Constraint::Percentage(15),
+            Constraint::Percentage(9),
+            Constraint::Percentage(6),
+            Constraint::Percentage(7),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    if table_data.state.selected().is_some() {
+        table_area = table_area
+            .highlight_symbol(">> ")
+            .row_highlight_style(Style::default().bg(SELECTED));
+    }
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+}
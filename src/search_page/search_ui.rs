@@ -10,7 +10,7 @@ use crate::page_handler::{
     DateType, TableData, TxTab, BACKGROUND, BLUE, GRAY, HEADER, RED, SELECTED, TEXT,
 };
 use crate::tx_handler::TxData;
-use crate::utility::{main_block, styled_block};
+use crate::utility::{is_expense_like, is_income_like, main_block, styled_block};
 
 #[cfg(not(tarpaulin_include))]
 pub fn search_ui(
@@ -24,7 +24,7 @@ pub fn search_ui(
 
     use ratatui::layout::Position;
     let status_data = search_data.get_tx_status();
-    // Contains date, details, from method, to method, amount, tx type, tags.
+    // Contains date, details, from method, to method, amount, tx type, tags, autofill, splits and tx status.
     // Except to method, rest will be used for the widgets
     let input_data = search_data.get_all_texts();
     // The index of the cursor position
@@ -63,9 +63,17 @@ pub fn search_ui(
         table_name = format!("Transactions: {}", search_table.items.len());
     }
 
-    let header_cells = ["Date", "Details", "TX Method", "Amount", "Type", "Tags"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+    let header_cells = [
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Tags",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
 
     let header = Row::new(header_cells)
         .style(Style::default().bg(HEADER))
@@ -88,6 +96,12 @@ pub fn search_ui(
         ])
         .split(size);
 
+    // the details chunk also contains the tx status input box
+    let details_chunk = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
+        .split(chunks[1]);
+
     // based on the tx type divide the first chunk into 5 or 6 parts horizontally
     // this chunk contains the input boxes take takes input
     let input_chunk = {
@@ -123,11 +137,12 @@ pub fn search_ui(
         rows,
         [
             Constraint::Percentage(10),
-            Constraint::Percentage(37),
-            Constraint::Percentage(13),
+            Constraint::Percentage(33),
             Constraint::Percentage(13),
+            Constraint::Percentage(12),
             Constraint::Percentage(8),
-            Constraint::Percentage(18),
+            Constraint::Percentage(14),
+            Constraint::Percentage(10),
         ],
     )
     .header(header)
@@ -173,6 +188,8 @@ pub fn search_ui(
 
     let mut tags_text = Line::from(format!("{} ", input_data[6]));
 
+    let tx_status_text = Line::from(format!("{} ", input_data[9]));
+
     match search_tab {
         TxTab::Details => {
             details_text = Line::from(vec![
@@ -242,6 +259,11 @@ pub fn search_ui(
         .block(styled_block("Tags"))
         .alignment(Alignment::Left);
 
+    let tx_status_sec = Paragraph::new(tx_status_text)
+        .style(Style::default().bg(BACKGROUND).fg(TEXT))
+        .block(styled_block("TX Status"))
+        .alignment(Alignment::Left);
+
     // We will be adding a cursor based on which tab is selected + the selected index.
     // This was created utilizing the tui-rs example named user_input.rs
     match search_tab {
@@ -250,8 +272,12 @@ pub fn search_ui(
             y: input_chunk[0].y + 1,
         }),
         TxTab::Details => f.set_cursor_position(Position {
-            x: chunks[1].x + current_index as u16 + 1,
-            y: chunks[1].y + 1,
+            x: details_chunk[0].x + current_index as u16 + 1,
+            y: details_chunk[0].y + 1,
+        }),
+        TxTab::Status => f.set_cursor_position(Position {
+            x: details_chunk[1].x + current_index as u16 + 1,
+            y: details_chunk[1].y + 1,
         }),
         TxTab::TxType => f.set_cursor_position(Position {
             x: input_chunk[1].x + current_index as u16 + 1,
@@ -296,9 +322,9 @@ pub fn search_ui(
 
     if let Some(a) = search_table.state.selected() {
         table_area = table_area.highlight_symbol(">> ");
-        if search_table.items[a][4] == "Expense" {
+        if is_expense_like(&search_table.items[a][4]) {
             table_area = table_area.row_highlight_style(selected_style_expense);
-        } else if search_table.items[a][4] == "Income" {
+        } else if is_income_like(&search_table.items[a][4]) {
             table_area = table_area.row_highlight_style(selected_style_income);
         } else if search_table.items[a][4] == "Transfer" {
             table_area = table_area.row_highlight_style(Style::default().bg(SELECTED));
@@ -312,7 +338,8 @@ pub fn search_ui(
     }
 
     // render the previously generated data into an interface
-    f.render_widget(details_sec, chunks[1]);
+    f.render_widget(details_sec, details_chunk[0]);
+    f.render_widget(tx_status_sec, details_chunk[1]);
     f.render_widget(status_sec, chunks[2]);
     f.render_widget(date_sec, input_chunk[0]);
     f.render_widget(tx_type_sec, input_chunk[1]);
@@ -1,79 +1,187 @@
 use rusqlite::Connection;
 use std::collections::HashMap;
 
-use crate::db::{MONTHS, YEARS};
-use crate::page_handler::IndexedData;
-use crate::utility::{get_all_tx_methods, get_all_txs};
+use crate::db::{get_all_tx_splits, MarketValues, TxSplit, MONTHS};
+use crate::page_handler::{IndexedData, SortingType};
+use crate::utility::{
+    get_all_tx_methods, get_all_tx_tags, get_all_txs, get_all_years, get_parent_tags,
+    sort_table_data, Cent, CurrencyConverter,
+};
 
 type MyVec = Vec<Vec<String>>;
 type MyTuple = (
-    f64,
-    f64,
-    (f64, String, String),
-    (f64, String, String),
-    f64,
-    f64,
+    Cent,
+    Cent,
+    (Cent, String, String),
+    (Cent, String, String),
+    Cent,
+    Cent,
 );
 
+/// Returns the gain or loss as a percentage of the cost basis
+fn gain_percentage(gain: Cent, cost_basis: Cent) -> String {
+    if cost_basis.is_zero() {
+        "0.00".to_string()
+    } else {
+        format!("{:.2}", gain.as_f64() / cost_basis.as_f64() * 100.0)
+    }
+}
+
+/// Creates the Summary rows of every holding with the latest prices and a total row in the base currency
+fn get_holding_rows(market_values: &MarketValues, converter: &CurrencyConverter) -> MyVec {
+    let mut rows = Vec::new();
+
+    if market_values.is_empty() {
+        return rows;
+    }
+
+    let mut total_cost = Cent::default();
+    let mut total_value = Cent::default();
+
+    for holding in market_values.holdings() {
+        let price = market_values.price_on(&holding.symbol, None);
+        let value = market_values.holding_value(holding, None);
+        let gain = value - holding.cost_basis;
+
+        total_cost += converter.to_base(holding.cost_basis, &holding.tx_method);
+        total_value += converter.to_base(value, &holding.tx_method);
+
+        rows.push(vec![
+            holding.symbol.clone(),
+            holding.tx_method.clone(),
+            holding.quantity.to_string(),
+            holding.cost_basis.to_string(),
+            price.map_or_else(|| "-".to_string(), |price| price.to_string()),
+            value.to_string(),
+            gain.to_string(),
+            gain_percentage(gain, holding.cost_basis),
+        ]);
+    }
+
+    let total_gain = total_value - total_cost;
+
+    rows.push(vec![
+        format!("Total ({})", converter.base_currency()),
+        String::new(),
+        String::new(),
+        total_cost.to_string(),
+        String::new(),
+        total_value.to_string(),
+        total_gain.to_string(),
+        gain_percentage(total_gain, total_cost),
+    ]);
+
+    rows
+}
+
 /// Contains the necessary information to construct the Summary Page highlighting
-/// tag based expense and income information, biggest expense and income
+/// tag based expense and income information, biggest expense and income.
+/// All totals are converted to the base currency while the per method data stays in the method's currency
 pub struct SummaryData {
+    /// Month index with the txs of the month. The `id_num` of the tx is at the end of each tx
     all_txs: HashMap<i32, Vec<Vec<String>>>,
+    tx_tags: HashMap<i32, Vec<String>>,
+    tx_splits: HashMap<i32, Vec<TxSplit>>,
+    years: Vec<String>,
+    /// `[symbol, method, quantity, cost basis, price, market value, gain/loss, gain %]` of each holding
+    /// with a total row in the base currency at the end. Empty if there are no holdings
+    holdings: MyVec,
+    converter: CurrencyConverter,
 }
 
 impl SummaryData {
     /// Goes through all transactions to collect data for the summary
     pub fn new(conn: &Connection) -> Self {
         let mut all_txs = HashMap::new();
+        let years = get_all_years(conn);
 
-        for x in 0..YEARS.len() {
+        for x in 0..years.len() {
             for i in 0..MONTHS.len() {
                 let target_id = i as i32 + (x as i32 * 12);
-                let (txs, ..) = get_all_txs(conn, i, x);
+                let (mut txs, _, id_nums) = get_all_txs(conn, i, x);
+                for (tx, id_num) in txs.iter_mut().zip(id_nums) {
+                    tx.push(id_num);
+                }
                 all_txs.insert(target_id, txs);
             }
         }
-        SummaryData { all_txs }
+        let converter = CurrencyConverter::new(conn);
+
+        SummaryData {
+            all_txs,
+            tx_tags: get_all_tx_tags(conn),
+            tx_splits: get_all_tx_splits(conn),
+            years,
+            holdings: get_holding_rows(&MarketValues::new(conn), &converter),
+            converter,
+        }
+    }
+
+    /// Returns the unrealized gain and loss of each holding valued with the latest prices
+    #[must_use]
+    pub fn get_holdings(&self) -> MyVec {
+        self.holdings.clone()
+    }
+
+    /// Returns the tags of the given tx
+    fn get_tx_tags(&self, tx_data: &[String]) -> Vec<&str> {
+        let id_num: i32 = tx_data[7].parse().unwrap();
+
+        self.tx_tags
+            .get(&id_num)
+            .map(|tags| tags.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// The currency all totals are converted to
+    pub fn base_currency(&self) -> &str {
+        self.converter.base_currency()
+    }
+
+    /// A note about the currencies left out of the totals. None if every currency has a rate
+    pub fn missing_rates_note(&self) -> Option<String> {
+        self.converter.missing_rates_note()
     }
 
     /// Iters through the given transactions to collect earning and expense data
     fn get_data(
         &self,
         txs: &Vec<Vec<String>>,
-        method_earning: &mut HashMap<String, f64>,
-        method_expense: &mut HashMap<String, f64>,
+        method_earning: &mut HashMap<String, Cent>,
+        method_expense: &mut HashMap<String, Cent>,
     ) -> MyTuple {
-        let mut total_income: f64 = 0.0;
-        let mut total_expense: f64 = 0.0;
+        let mut total_income = Cent::default();
+        let mut total_expense = Cent::default();
 
-        let mut biggest_earning = (0.0, String::new(), String::new());
-        let mut biggest_expense = (0.0, String::new(), String::new());
+        let mut biggest_earning = (Cent::default(), String::new(), String::new());
+        let mut biggest_expense = (Cent::default(), String::new(), String::new());
 
-        let mut monthly_earning = 0.0;
-        let mut monthly_expense = 0.0;
+        let mut monthly_earning = Cent::default();
+        let mut monthly_expense = Cent::default();
 
         for tx in txs {
             let tx_date = &tx[0];
             let tx_method = &tx[2];
-            let tx_amount: f64 = tx[3].parse().unwrap();
+            let tx_amount: Cent = tx[3].parse().unwrap();
+            let base_amount = self.converter.to_base(tx_amount, tx_method);
             let tx_type = &tx[4];
 
             match tx_type.as_str() {
                 "Income" => {
-                    if tx_amount > biggest_earning.0 {
-                        biggest_earning = (tx_amount, tx_method.to_string(), tx_date.to_string());
+                    if base_amount > biggest_earning.0 {
+                        biggest_earning = (base_amount, tx_method.to_string(), tx_date.to_string());
                     };
-                    total_income += tx_amount;
-                    monthly_earning += tx_amount;
+                    total_income += base_amount;
+                    monthly_earning += base_amount;
 
                     *method_earning.get_mut(tx_method).unwrap() += tx_amount;
                 }
                 "Expense" => {
-                    if tx_amount > biggest_expense.0 {
-                        biggest_expense = (tx_amount, tx_method.to_string(), tx_date.to_string());
+                    if base_amount > biggest_expense.0 {
+                        biggest_expense = (base_amount, tx_method.to_string(), tx_date.to_string());
                     };
-                    total_expense += tx_amount;
-                    monthly_expense += tx_amount;
+                    total_expense += base_amount;
+                    monthly_expense += base_amount;
 
                     *method_expense.get_mut(tx_method).unwrap() += tx_amount;
                 }
@@ -91,127 +199,135 @@ impl SummaryData {
         )
     }
 
+    /// Adds the amount of the tx to the subtotal of each of its tags and every parent of them.
+    /// A subtotal gets the amount once, no matter how many tags of the same tree the tx has.
+    /// The totals get the amount once for every tag of the tx.
+    /// A split tx only adds the amount of each split line to the tag of the line
+    fn add_tag_amounts(
+        &self,
+        tx_data: &[String],
+        totals: &mut (Cent, Cent),
+        subtotals: &mut HashMap<String, (Cent, Cent)>,
+    ) {
+        let tx_amount = self
+            .converter
+            .to_base(tx_data[3].parse().unwrap(), &tx_data[2]);
+        let is_income = match tx_data[4].as_str() {
+            "Income" => true,
+            "Expense" => false,
+            _ => return,
+        };
+
+        let id_num: i32 = tx_data[7].parse().unwrap();
+
+        if let Some(splits) = self.tx_splits.get(&id_num) {
+            for split in splits {
+                let split_amount = self.converter.to_base(split.amount, &tx_data[2]);
+
+                for tag in get_parent_tags(&split.tag)
+                    .into_iter()
+                    .chain([split.tag.clone()])
+                {
+                    let subtotal = subtotals.entry(tag).or_default();
+
+                    if is_income {
+                        subtotal.0 += split_amount;
+                    } else {
+                        subtotal.1 += split_amount;
+                    }
+                }
+
+                if is_income {
+                    totals.0 += split_amount;
+                } else {
+                    totals.1 += split_amount;
+                }
+            }
+            return;
+        }
+
+        let mut tag_tree: Vec<String> = Vec::new();
+
+        for tag in self.get_tx_tags(tx_data) {
+            if is_income {
+                totals.0 += tx_amount;
+            } else {
+                totals.1 += tx_amount;
+            }
+
+            for tree_tag in get_parent_tags(tag).into_iter().chain([tag.to_string()]) {
+                if !tag_tree.contains(&tree_tag) {
+                    tag_tree.push(tree_tag);
+                }
+            }
+        }
+
+        // gather data by loop through each tag. If tag exists, add with the value, if not insert it
+        for tag in tag_tree {
+            let subtotal = subtotals.entry(tag).or_default();
+
+            if is_income {
+                subtotal.0 += tx_amount;
+            } else {
+                subtotal.1 += tx_amount;
+            }
+        }
+    }
+
     /// Returns a vector that will be used to creating table in the Summary UI
-    /// The vector contains tags and their income and expense data
+    /// The vector contains tags and their income and expense data. Parent tags such as `Food` of
+    /// `Food:Groceries` get a row with the subtotal of all txs under them
     pub fn get_table_data(
         &self,
         mode: &IndexedData,
         month: usize,
         year: usize,
     ) -> Vec<Vec<String>> {
-        let mut income_tags = HashMap::new();
-        let mut expense_tags = HashMap::new();
-        match mode.index {
-            // 0 = monthly mode. Select the data only of the given month year
-            0 => {
-                let target_id = month as i32 + (year as i32 * 12);
+        let mut totals = (Cent::default(), Cent::default());
+        let mut subtotals = HashMap::new();
 
-                for tx_data in &self.all_txs[&target_id] {
-                    let tx_amount: f64 = tx_data[3].parse().unwrap();
-                    let tx_type = &tx_data[4];
-                    let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
-
-                    // gather data by loop through each tx. If tag exists, add with the value, if not insert it
-                    match tx_type.as_str() {
-                        "Income" => {
-                            for tag in tx_tags {
-                                if income_tags.contains_key(tag) {
-                                    *income_tags.get_mut(tag).unwrap() += tx_amount;
-                                } else {
-                                    income_tags.insert(tag, tx_amount);
-                                }
-                            }
-                        }
-                        "Expense" => {
-                            for tag in tx_tags {
-                                if expense_tags.contains_key(tag) {
-                                    *expense_tags.get_mut(tag).unwrap() += tx_amount;
-                                } else {
-                                    expense_tags.insert(tag, tx_amount);
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
+        let target_ids = match mode.index {
+            // 0 = monthly mode. Select the data only of the given month year
+            0 => vec![month as i32 + (year as i32 * 12)],
             // 1 = yearly mode. Select the data of all months of the given year
-            1 => {
-                for i in 0..MONTHS.len() {
-                    let target_id = i as i32 + (year as i32 * 12);
+            1 => (0..MONTHS.len())
+                .map(|i| i as i32 + (year as i32 * 12))
+                .collect(),
+            //  * 2 = all time mode. Select every single data
+            2 => (0..self.years.len())
+                .flat_map(|x| (0..MONTHS.len()).map(move |i| i as i32 + (x as i32 * 12)))
+                .collect(),
+            _ => Vec::new(),
+        };
 
-                    for tx_data in &self.all_txs[&target_id] {
-                        let tx_amount: f64 = tx_data[3].parse().unwrap();
-                        let tx_type = &tx_data[4];
-                        let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
-
-                        // gather data by loop through each tx. If tag exists, add with the value, if not insert it
-                        match tx_type.as_str() {
-                            "Income" => {
-                                for tag in tx_tags {
-                                    if income_tags.contains_key(tag) {
-                                        *income_tags.get_mut(tag).unwrap() += tx_amount;
-                                    } else {
-                                        income_tags.insert(tag, tx_amount);
-                                    }
-                                }
-                            }
-                            "Expense" => {
-                                for tag in tx_tags {
-                                    if expense_tags.contains_key(tag) {
-                                        *expense_tags.get_mut(tag).unwrap() += tx_amount;
-                                    } else {
-                                        expense_tags.insert(tag, tx_amount);
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
+        for target_id in target_ids {
+            for tx_data in &self.all_txs[&target_id] {
+                self.add_tag_amounts(tx_data, &mut totals, &mut subtotals);
             }
-            //  * 2 = all time mode. Select every single data
-            2 => {
-                for x in 0..YEARS.len() {
-                    for i in 0..MONTHS.len() {
-                        let target_id = i as i32 + (x as i32 * 12);
+        }
 
-                        for tx_data in &self.all_txs[&target_id] {
-                            let tx_amount: f64 = tx_data[3].parse().unwrap();
-                            let tx_type = &tx_data[4];
-                            let tx_tags = tx_data[5].split(", ").collect::<Vec<&str>>();
-
-                            // gather data by loop through each tx. If tag exists, add with the value, if not insert it
-                            match tx_type.as_str() {
-                                "Income" => {
-                                    for tag in tx_tags {
-                                        if income_tags.contains_key(tag) {
-                                            *income_tags.get_mut(tag).unwrap() += tx_amount;
-                                        } else {
-                                            income_tags.insert(tag, tx_amount);
-                                        }
-                                    }
-                                }
-                                "Expense" => {
-                                    for tag in tx_tags {
-                                        if expense_tags.contains_key(tag) {
-                                            *expense_tags.get_mut(tag).unwrap() += tx_amount;
-                                        } else {
-                                            expense_tags.insert(tag, tx_amount);
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
+        let table_data = self.generate_table_data(totals, &subtotals);
+        sort_table_data(table_data, &SortingType::ByTags)
+    }
+
+    /// Returns the expense of every tag and parent tag in the base currency for the given month
+    /// index of the given year index. Used by the budgets to find how much was spent
+    pub fn get_tag_expenses(&self, month: usize, year: usize) -> HashMap<String, Cent> {
+        let mut totals = (Cent::default(), Cent::default());
+        let mut subtotals = HashMap::new();
+
+        let target_id = month as i32 + (year as i32 * 12);
+
+        if let Some(txs) = self.all_txs.get(&target_id) {
+            for tx_data in txs {
+                self.add_tag_amounts(tx_data, &mut totals, &mut subtotals);
             }
-            _ => {}
         }
-        let mut table_data = self.generate_table_data(&income_tags, &expense_tags);
-        table_data.sort();
-        table_data
+
+        subtotals
+            .into_iter()
+            .map(|(tag, (_, expense))| (tag, expense))
+            .collect()
     }
 
     /// Returns a vector that will be used to highlight points such as largest transaction,
@@ -224,28 +340,28 @@ impl SummaryData {
         conn: &Connection,
     ) -> (MyVec, MyVec, MyVec, MyVec, MyVec) {
         let all_methods = get_all_tx_methods(conn);
-        let mut total_income: f64 = 0.0;
-        let mut total_expense: f64 = 0.0;
+        let mut total_income = Cent::default();
+        let mut total_expense = Cent::default();
 
         // (Amount, Method, date)
-        let mut biggest_earning = (0.0, String::from("-"), String::from("-"));
-        let mut biggest_expense = (0.0, String::from("-"), String::from("-"));
+        let mut biggest_earning = (Cent::default(), String::from("-"), String::from("-"));
+        let mut biggest_expense = (Cent::default(), String::from("-"), String::from("-"));
 
-        let mut largest_monthly_earning = 0.0;
-        let mut largest_monthly_expense = 0.0;
+        let mut largest_monthly_earning = Cent::default();
+        let mut largest_monthly_expense = Cent::default();
 
         // (Amount, Method, date)
-        let mut peak_earning = (0.0, String::from("-"));
-        let mut peak_expense = (0.0, String::from("-"));
-        let mut total_month_checked = 0.0;
+        let mut peak_earning = (Cent::default(), String::from("-"));
+        let mut peak_expense = (Cent::default(), String::from("-"));
+        let mut total_month_checked = 0;
 
         // {Method Name, Amount}
         let mut method_earning = HashMap::new();
         let mut method_expense = HashMap::new();
 
         for method in &all_methods {
-            method_earning.insert(method.to_string(), 0.0);
-            method_expense.insert(method.to_string(), 0.0);
+            method_earning.insert(method.to_string(), Cent::default());
+            method_expense.insert(method.to_string(), Cent::default());
         }
 
         match mode.index {
@@ -253,7 +369,7 @@ impl SummaryData {
                 let target_id = month as i32 + (year as i32 * 12);
                 let tx_data = &self.all_txs[&target_id];
                 if !tx_data.is_empty() {
-                    total_month_checked += 1.0;
+                    total_month_checked += 1;
                 }
 
                 self.update_tx_data(
@@ -277,7 +393,7 @@ impl SummaryData {
                     let target_id = i as i32 + (year as i32 * 12);
                     let tx_data = &self.all_txs[&target_id];
                     if !tx_data.is_empty() {
-                        total_month_checked += 1.0;
+                        total_month_checked += 1;
                     }
 
                     self.update_tx_data(
@@ -298,12 +414,12 @@ impl SummaryData {
                 }
             }
             2 => {
-                for x in 0..YEARS.len() {
+                for x in 0..self.years.len() {
                     for i in 0..MONTHS.len() {
                         let target_id = i as i32 + (x as i32 * 12);
                         let tx_data = &self.all_txs[&target_id];
                         if !tx_data.is_empty() {
-                            total_month_checked += 1.0;
+                            total_month_checked += 1;
                         }
 
                         self.update_tx_data(
@@ -330,48 +446,57 @@ impl SummaryData {
         let (income_percentage, expense_percentage) =
             self.get_percentages(total_income, total_expense);
 
-        let average_income = if total_income != 0.0 {
-            total_income / total_month_checked
+        let average_income = if total_income.is_zero() {
+            Cent::default()
         } else {
-            0.0
+            total_income / total_month_checked
         };
 
-        let average_expense = if total_income != 0.0 {
-            total_expense / total_month_checked
+        let average_expense = if total_income.is_zero() {
+            Cent::default()
         } else {
-            0.0
+            total_expense / total_month_checked
         };
 
         let mut method_data = Vec::new();
 
         for method in &all_methods {
-            let earning_percentage = if method_earning[method] != 0.0 {
-                format!("{:.2}%", (method_earning[method] / total_income) * 100.0)
-            } else {
+            let base_earning = self.converter.to_base(method_earning[method], method);
+            let base_expense = self.converter.to_base(method_expense[method], method);
+
+            let earning_percentage = if base_earning.is_zero() {
                 format!("{:.2}", 0.0)
+            } else {
+                format!(
+                    "{:.2}%",
+                    (base_earning.as_f64() / total_income.as_f64()) * 100.0
+                )
             };
 
-            let expense_percentage = if method_expense[method] != 0.0 {
-                format!("{:.2}%", (method_expense[method] / total_expense) * 100.0)
-            } else {
+            let expense_percentage = if base_expense.is_zero() {
                 format!("{:.2}", 0.0)
+            } else {
+                format!(
+                    "{:.2}%",
+                    (base_expense.as_f64() / total_expense.as_f64()) * 100.0
+                )
             };
 
-            let average_earning = if method_earning[method] != 0.0 {
-                format!("{:.2}", method_earning[method] / total_month_checked)
+            let average_earning = if method_earning[method].is_zero() {
+                Cent::default().to_string()
             } else {
-                format!("{:.2}", 0.0)
+                (method_earning[method] / total_month_checked).to_string()
             };
 
-            let average_expense = if method_expense[method] != 0.0 {
-                format!("{:.2}", method_expense[method] / total_month_checked)
+            let average_expense = if method_expense[method].is_zero() {
+                Cent::default().to_string()
             } else {
-                format!("{:.2}", 0.0)
+                (method_expense[method] / total_month_checked).to_string()
             };
             method_data.push(vec![
                 method.to_string(),
-                format!("{:.2}", method_earning[method]),
-                format!("{:.2}", method_expense[method]),
+                method_earning[method].to_string(),
+                method_expense[method].to_string(),
                 earning_percentage,
                 expense_percentage,
                 average_earning,
@@ -382,17 +507,17 @@ impl SummaryData {
         let summary_data_1 = vec![
             vec![
                 String::from("Total Income"),
-                format!("{:.2}", total_income),
+                total_income.to_string(),
                 income_percentage,
             ],
             vec![
                 String::from("Total Expense"),
-                format!("{:.2}", total_expense),
+                total_expense.to_string(),
                 expense_percentage,
             ],
             vec![
                 String::from("Net"),
-                format!("{:.2}", total_income - total_expense),
+                (total_income - total_expense).to_string(),
                 String::from("-"),
             ],
         ];
@@ -400,12 +525,12 @@ impl SummaryData {
         let summary_data_2 = vec![
             vec![
                 String::from("Average Income"),
-                format!("{:.2}", average_income),
+                average_income.to_string(),
                 String::from("-"),
             ],
             vec![
                 String::from("Average Expense"),
-                format!("{:.2}", average_expense),
+                average_expense.to_string(),
                 String::from("-"),
             ],
         ];
@@ -414,13 +539,13 @@ impl SummaryData {
             vec![
                 String::from("Largest Income"),
                 biggest_earning.2,
-                format!("{:.2}", biggest_earning.0),
+                biggest_earning.0.to_string(),
                 biggest_earning.1,
             ],
             vec![
                 String::from("Largest Expense"),
                 biggest_expense.2,
-                format!("{:.2}", biggest_expense.0),
+                biggest_expense.0.to_string(),
                 biggest_expense.1,
             ],
             vec![
@@ -435,13 +560,13 @@ impl SummaryData {
             vec![
                 String::from("Peak Earning"),
                 peak_earning.1,
-                format!("{:.2}", peak_earning.0),
+                peak_earning.0.to_string(),
                 String::from("-"),
             ],
             vec![
                 String::from("Peak Expense"),
                 peak_expense.1,
-                format!("{:.2}", peak_expense.0),
+                peak_expense.0.to_string(),
                 String::from("-"),
             ],
         ];
@@ -459,16 +584,16 @@ impl SummaryData {
     fn update_tx_data(
         &self,
         tx_data: &Vec<Vec<String>>,
-        total_income: &mut f64,
-        total_expense: &mut f64,
-        biggest_earning: &mut (f64, String, String),
-        biggest_expense: &mut (f64, String, String),
-        largest_monthly_earning: &mut f64,
-        largest_monthly_expense: &mut f64,
-        peak_earning: &mut (f64, String),
-        peak_expense: &mut (f64, String),
-        method_earning: &mut HashMap<String, f64>,
-        method_expense: &mut HashMap<String, f64>,
+        total_income: &mut Cent,
+        total_expense: &mut Cent,
+        biggest_earning: &mut (Cent, String, String),
+        biggest_expense: &mut (Cent, String, String),
+        largest_monthly_earning: &mut Cent,
+        largest_monthly_expense: &mut Cent,
+        peak_earning: &mut (Cent, String),
+        peak_expense: &mut (Cent, String),
+        method_earning: &mut HashMap<String, Cent>,
+        method_expense: &mut HashMap<String, Cent>,
         month: usize,
         year: usize,
     ) {
@@ -501,7 +626,7 @@ impl SummaryData {
             }
             *peak_earning = (
                 *largest_monthly_earning,
-                format!("{string_month}-{}", YEARS[year]),
+                format!("{string_month}-{}", self.years[year]),
             );
         }
 
@@ -514,78 +639,48 @@ impl SummaryData {
             }
             *peak_expense = (
                 *largest_monthly_expense,
-                format!("{string_month}-{}", YEARS[year]),
+                format!("{string_month}-{}", self.years[year]),
             );
         }
     }
 
-    /// Generates a vector to be used as table data from tag list
+    /// Generates a vector to be used as table data from the subtotal of each tag.
+    /// The percentages are calculated from the totals of the tags without the parent subtotals
     fn generate_table_data(
         &self,
-        income_tags: &HashMap<&str, f64>,
-        expense_tags: &HashMap<&str, f64>,
+        (total_income, total_expense): (Cent, Cent),
+        subtotals: &HashMap<String, (Cent, Cent)>,
     ) -> Vec<Vec<String>> {
-        let mut to_return = Vec::new();
-        let mut total_income = 0.0;
-        let mut total_expense = 0.0;
-
-        for (key, value) in income_tags {
-            let mut to_push = vec![(*key).to_string(), format!("{:.2}", value)];
-            total_income += value;
-
-            // if the same tag already exists on expense, get that value as well
-            if expense_tags.contains_key(key) {
-                to_push.push(format!("{:.2}", expense_tags[key]));
-                total_expense += expense_tags[key];
-            } else {
-                to_push.push(format!("{:.2}", 0.0));
-            }
-            to_return.push(to_push);
-        }
-
-        for (key, value) in expense_tags {
-            // gather data only from the tags that didn't exist on Income tag list
-            if !income_tags.contains_key(key) {
-                to_return.push(vec![
-                    (*key).to_string(),
-                    format!("{:.2}", 0.0),
-                    format!("{:.2}", value),
-                ]);
-                total_expense += value;
-            }
-        }
-        // we got the income and expense data earlier. Now need to loop again
-        // to gather the % data
-        for x in to_return.iter_mut() {
-            let income_percentage = if &x[1] != "0.00" {
-                let income = &x[1].parse::<f64>().unwrap();
-                format!("{:.2}", ((income / total_income) * 100.0))
-            } else {
+        let get_percentage = |value: Cent, total: Cent| {
+            if value.is_zero() {
                 format!("{:.2}", 0.0)
-            };
-
-            let expense_percentage = if &x[2] != "0.00" {
-                let expense = &x[2].parse::<f64>().unwrap();
-                format!("{:.2}", ((expense / total_expense) * 100.0))
             } else {
-                format!("{:.2}", 0.0)
-            };
-
-            x.push(income_percentage);
-            x.push(expense_percentage);
-        }
+                format!("{:.2}", ((value.as_f64() / total.as_f64()) * 100.0))
+            }
+        };
 
-        to_return
+        subtotals
+            .iter()
+            .map(|(tag, (income, expense))| {
+                vec![
+                    tag.to_string(),
+                    income.to_string(),
+                    expense.to_string(),
+                    get_percentage(*income, total_income),
+                    get_percentage(*expense, total_expense),
+                ]
+            })
+            .collect()
     }
 
     /// Takes 2 numbers and returns how much % are each of them
-    fn get_percentages(&self, value1: f64, value2: f64) -> (String, String) {
-        if value1 == 0.0 && value2 == 0.0 {
+    fn get_percentages(&self, value1: Cent, value2: Cent) -> (String, String) {
+        if value1.is_zero() && value2.is_zero() {
             return (String::from("0.00"), String::from("0.00"));
         }
-        let total = value1 + value2;
-        let percentage1 = (value1 / total) * 100.0;
-        let percentage2 = (value2 / total) * 100.0;
+        let total = (value1 + value2).as_f64();
+        let percentage1 = (value1.as_f64() / total) * 100.0;
+        let percentage2 = (value2.as_f64() / total) * 100.0;
         (format!("{percentage1:.2}%",), format!("{percentage2:.2}%",))
     }
 }
@@ -3,17 +3,18 @@ use ratatui::style::{Modifier, Style};
 use ratatui::widgets::{Cell, Row, Table};
 use ratatui::Frame;
 use rusqlite::Connection;
+use std::collections::HashSet;
 use thousands::Separable;
 
 use crate::page_handler::{
     IndexedData, SortingType, SummaryTab, TableData, BACKGROUND, BOX, HEADER, SELECTED, TEXT,
 };
 use crate::summary_page::SummaryData;
-use crate::utility::{create_tab, get_all_tx_methods, main_block, styled_block};
+use crate::utility::{create_tab, get_all_tx_methods, get_parent_tags, main_block, styled_block};
 
 /// The function draws the Summary page of the interface.
 #[cfg(not(tarpaulin_include))]
-pub fn summary_ui(
+pub fn summary_ui<S: ::std::hash::BuildHasher>(
     f: &mut Frame,
     months: &IndexedData,
     years: &IndexedData,
@@ -23,6 +24,7 @@ pub fn summary_ui(
     current_page: &SummaryTab,
     summary_hidden_mode: bool,
     summary_sort: &SortingType,
+    collapsed_tags: &HashSet<String, S>,
     conn: &Connection,
 ) {
     let (summary_data_1, summary_data_2, summary_data_3, summary_data_4, method_data) =
@@ -33,9 +35,20 @@ pub fn summary_ui(
     let mut summary_table_3 = TableData::new(summary_data_3);
     let mut summary_table_4 = TableData::new(summary_data_4);
     let mut method_table = TableData::new(method_data);
+    let mut holding_table = TableData::new(summary_data.get_holdings());
 
     let size = f.area();
 
+    // every total on this page is converted to the base currency
+    let mut tags_title = format!("Tags ({})", summary_data.base_currency());
+    let mut totals_title = format!("Totals ({})", summary_data.base_currency());
+
+    // The totals leave out the tx methods without an exchange rate
+    if let Some(note) = summary_data.missing_rates_note() {
+        tags_title = format!("{tags_title} | {note}");
+        totals_title = format!("{totals_title} | {note}");
+    }
+
     let tag_header = if let SortingType::ByTags = summary_sort {
         "Tags ↓"
     } else {
@@ -86,14 +99,39 @@ pub fn summary_ui(
         .height(1)
         .bottom_margin(0);
 
+    let holding_header_cells = [
+        "Symbol",
+        "Method",
+        "Quantity",
+        "Cost Basis",
+        "Price",
+        "Market Value",
+        "Gain/Loss",
+        "Gain %",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let holding_header = Row::new(holding_header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
     let method_len = get_all_tx_methods(conn).len() as u16;
 
+    // The holdings table goes right below the method table and takes no space without any holding
+    let holding_len = if holding_table.items.is_empty() {
+        0
+    } else {
+        holding_table.items.len() as u16 + 3
+    };
+
     let mut main_layout = Layout::default().direction(Direction::Vertical).margin(2);
     let mut summary_layout = Layout::default().direction(Direction::Horizontal);
 
     if summary_hidden_mode {
         main_layout = main_layout.constraints([
-            Constraint::Length(method_len + 3),
+            Constraint::Length(method_len + 3 + holding_len),
             Constraint::Length(9),
             Constraint::Min(0),
         ]);
@@ -106,7 +144,7 @@ pub fn summary_ui(
                     Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(3),
-                    Constraint::Length(method_len + 3),
+                    Constraint::Length(method_len + 3 + holding_len),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
@@ -117,7 +155,7 @@ pub fn summary_ui(
                 main_layout = main_layout.constraints([
                     Constraint::Length(3),
                     Constraint::Length(3),
-                    Constraint::Length(method_len + 3),
+                    Constraint::Length(method_len + 3 + holding_len),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
@@ -127,7 +165,7 @@ pub fn summary_ui(
             2 => {
                 main_layout = main_layout.constraints([
                     Constraint::Length(3),
-                    Constraint::Length(method_len + 3),
+                    Constraint::Length(method_len + 3 + holding_len),
                     Constraint::Length(9),
                     Constraint::Min(0),
                 ]);
@@ -145,6 +183,15 @@ pub fn summary_ui(
         summary_layout.split(chunks[4 - mode_selection.index])
     };
 
+    let method_chunk = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(method_len + 3), Constraint::Min(0)])
+        .split(if summary_hidden_mode {
+            chunks[0]
+        } else {
+            chunks[3 - mode_selection.index]
+        });
+
     let left_summary = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
@@ -163,10 +210,25 @@ pub fn summary_ui(
 
     let mut mode_selection_tab = create_tab(mode_selection, "Modes");
 
+    let parent_tags = table_data
+        .items
+        .iter()
+        .flat_map(|item| get_parent_tags(&item[0]))
+        .collect::<HashSet<String>>();
+
     // Goes through all tags provided and creates row for the table
     let rows = table_data.items.iter().map(|item| {
         let height = 1;
-        let cells = item.iter().map(|c| Cell::from(c.separate_with_commas()));
+        let cells = item.iter().enumerate().map(|(j, c)| {
+            // Parent tags are marked with whether their child tags are collapsed
+            if j == 0 && collapsed_tags.contains(c) {
+                Cell::from(format!("▸ {c}"))
+            } else if j == 0 && parent_tags.contains(c) {
+                Cell::from(format!("▾ {c}"))
+            } else {
+                Cell::from(c.separate_with_commas())
+            }
+        });
         Row::new(cells)
             .height(height as u16)
             .bottom_margin(0)
@@ -248,6 +310,21 @@ pub fn summary_ui(
             .style(Style::default().fg(TEXT))
     });
 
+    let holding_rows = holding_table.items.iter().map(|item| {
+        let height = 1;
+        let cells = item.iter().enumerate().map(|(j, c)| {
+            let mut cell = Cell::from(c.separate_with_commas());
+            if j == 0 {
+                cell = cell.style(Style::default().fg(TEXT).add_modifier(Modifier::BOLD));
+            }
+            cell
+        });
+        Row::new(cells)
+            .height(height as u16)
+            .bottom_margin(0)
+            .style(Style::default().fg(TEXT))
+    });
+
     let mut table_area = Table::new(
         rows,
         [
@@ -259,7 +336,7 @@ pub fn summary_ui(
         ],
     )
     .header(header)
-    .block(styled_block("Tags"))
+    .block(styled_block(&tags_title))
     .style(Style::default().fg(BOX));
 
     let summary_area_1 = Table::new(
@@ -270,7 +347,7 @@ pub fn summary_ui(
             Constraint::Percentage(33),
         ],
     )
-    .block(styled_block(""))
+    .block(styled_block(&totals_title))
     .style(Style::default().fg(BOX));
 
     let summary_area_2 = Table::new(
@@ -324,6 +401,11 @@ pub fn summary_ui(
     .block(styled_block(""))
     .style(Style::default().fg(BOX));
 
+    let holding_area = Table::new(holding_rows, [Constraint::Percentage(12); 8])
+        .header(holding_header)
+        .block(styled_block("Holdings"))
+        .style(Style::default().fg(BOX));
+
     match current_page {
         // previously added a black block to year and month widget if a value is not selected
         // Now we will turn that black block into green if a value is selected
@@ -354,13 +436,17 @@ pub fn summary_ui(
         }
     }
 
+    if !holding_table.items.is_empty() {
+        f.render_stateful_widget(holding_area, method_chunk[1], &mut holding_table.state);
+    }
+
     if summary_hidden_mode {
         f.render_stateful_widget(summary_area_1, left_summary[0], &mut summary_table_1.state);
         f.render_stateful_widget(summary_area_2, left_summary[1], &mut summary_table_2.state);
         f.render_stateful_widget(summary_area_3, right_summary[0], &mut summary_table_3.state);
         f.render_stateful_widget(summary_area_4, right_summary[1], &mut summary_table_4.state);
         f.render_stateful_widget(table_area, chunks[2], &mut table_data.state);
-        f.render_stateful_widget(method_area, chunks[0], &mut method_table.state);
+        f.render_stateful_widget(method_area, method_chunk[0], &mut method_table.state);
     } else {
         f.render_widget(mode_selection_tab, chunks[0]);
         f.render_stateful_widget(summary_area_1, left_summary[0], &mut summary_table_1.state);
@@ -373,16 +459,16 @@ pub fn summary_ui(
                 f.render_widget(year_tab, chunks[1]);
                 f.render_widget(month_tab, chunks[2]);
                 f.render_stateful_widget(table_area, chunks[5], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[3], &mut method_table.state);
+                f.render_stateful_widget(method_area, method_chunk[0], &mut method_table.state);
             }
             1 => {
                 f.render_widget(year_tab, chunks[1]);
                 f.render_stateful_widget(table_area, chunks[4], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[2], &mut method_table.state);
+                f.render_stateful_widget(method_area, method_chunk[0], &mut method_table.state);
             }
             2 => {
                 f.render_stateful_widget(table_area, chunks[3], &mut table_data.state);
-                f.render_stateful_widget(method_area, chunks[1], &mut method_table.state);
+                f.render_stateful_widget(method_area, method_chunk[0], &mut method_table.state);
             }
             _ => {}
         }
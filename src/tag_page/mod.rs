@@ -0,0 +1,5 @@
+mod tag_data;
+mod tag_ui;
+
+pub use tag_data::TagData;
+pub use tag_ui::tag_ui;
@@ -0,0 +1,44 @@
+use rusqlite::Connection;
+
+use crate::db::{get_tag_usage, TagUsage};
+use crate::utility::CurrencyConverter;
+
+/// Contains every tag that is used by at least one tx along with how much it is used
+pub struct TagData {
+    tags: Vec<TagUsage>,
+    converter: CurrencyConverter,
+}
+
+impl TagData {
+    pub fn new(conn: &Connection) -> Self {
+        let tags = get_tag_usage(conn).expect("could not get the tag usage");
+
+        TagData {
+            tags,
+            converter: CurrencyConverter::new(conn),
+        }
+    }
+
+    /// Convert all tags to a Vector where each value of the vector is the data of a tag
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.tags.iter().map(TagUsage::to_vec).collect()
+    }
+
+    pub fn get_tag(&self, index: usize) -> &TagUsage {
+        &self.tags[index]
+    }
+
+    /// The currency the income and expense totals are converted to
+    pub fn base_currency(&self) -> &str {
+        self.converter.base_currency()
+    }
+
+    /// A note about the currencies left out of the totals. None if every currency has a rate
+    pub fn missing_rates_note(&self) -> Option<String> {
+        self.converter.missing_rates_note()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
@@ -0,0 +1,82 @@
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Row, Table};
+use ratatui::Frame;
+use thousands::Separable;
+
+use crate::page_handler::{TableData, BACKGROUND, HEADER, SELECTED, TEXT};
+use crate::tag_page::TagData;
+use crate::utility::{main_block, styled_block};
+
+/// Shows every tag along with the amount of txs using it and their income and expense totals
+#[cfg(not(tarpaulin_include))]
+pub fn tag_ui(f: &mut Frame, tag_data: &TagData, table_data: &mut TableData) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Min(0)])
+        .split(size);
+
+    f.render_widget(main_block(), size);
+
+    let mut table_name = "Tags".to_string();
+
+    if !table_data.items.is_empty() {
+        table_name = format!("Tags: {}", table_data.items.len());
+    }
+
+    // The totals leave out the tx methods without an exchange rate
+    if let Some(note) = tag_data.missing_rates_note() {
+        table_name = format!("{table_name} | {note}");
+    }
+
+    let income_header = format!("Income ({})", tag_data.base_currency());
+    let expense_header = format!("Expense ({})", tag_data.base_currency());
+
+    let headers = ["Tag", "Transactions", &income_header, &expense_header];
+
+    let header_cells = headers
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(BACKGROUND)));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(HEADER))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table_data.items.iter().map(|item| {
+        let cells = item.iter().enumerate().map(|(index, c)| {
+            if matches!(index, 2 | 3) {
+                Cell::from(c.separate_with_commas())
+            } else {
+                Cell::from(c.to_string())
+            }
+        });
+        Row::new(cells)
+            .height(1)
+            .bottom_margin(0)
+            .style(Style::default().bg(BACKGROUND).fg(TEXT))
+    });
+
+    let mut table_area = Table::new(
+        rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ],
+    )
+    .header(header)
+    .block(styled_block(&table_name));
+
+    if table_data.state.selected().is_some() {
+        table_area = table_area
+            .highlight_symbol(">> ")
+            .row_highlight_style(Style::default().bg(SELECTED));
+    }
+
+    f.render_stateful_widget(table_area, chunks[0], &mut table_data.state);
+}
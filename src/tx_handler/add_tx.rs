@@ -1,9 +1,10 @@
-use rusqlite::{Connection, Result as sqlResult};
+use rusqlite::{params, Connection, Result as sqlResult, Savepoint};
 use std::collections::HashMap;
 
+use crate::db::{add_missing_years, save_tx_tags, QueryBuilder};
 use crate::utility::{
     get_all_tx_methods, get_last_balance_id, get_last_balances, get_last_time_balance,
-    get_last_tx_id,
+    get_last_tx_id, get_year_range, is_expense_like, is_income_like, Cent,
 };
 
 /// Adds a transaction to the database with the given info. The flow of this goes like this:
@@ -24,7 +25,22 @@ pub fn add_tx(
 ) -> sqlResult<()> {
     // create a connection and a savepoint
     let sp = conn.savepoint()?;
+    add_tx_in_savepoint(date, details, tx_method, amount, tx_type, tags, id_num, &sp)?;
+    sp.commit()
+}
 
+/// Adds a transaction the same way as `add_tx` inside the given savepoint. Used when multiple txs
+/// must either all be added or none of them
+pub fn add_tx_in_savepoint(
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    id_num: Option<&str>,
+    sp: &Savepoint,
+) -> sqlResult<()> {
     // the process goes through 4 parts
     // Add the tx itself in the db
     // calculate the amount to add/subtract from the balance_all table
@@ -33,23 +49,39 @@ pub fn add_tx(
 
     // if Some(id) means it's a transaction editing
     // else it's a normal transaction
+    let int_amount = amount
+        .parse::<Cent>()
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
     if let Some(id) = id_num {
         let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, id_num, tags) VALUES (?, ?, ?, ?, ?, ?, ?)"#;
-        sp.execute(query, [date, details, tx_method, amount, tx_type, id, tags])?;
+        sp.execute(
+            query,
+            params![date, details, tx_method, int_amount, tx_type, id, tags],
+        )?;
     } else {
         let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, tags) VALUES (?, ?, ?, ?, ?, ?)"#;
-        sp.execute(query, [date, details, tx_method, amount, tx_type, tags])?;
+        sp.execute(
+            query,
+            params![date, details, tx_method, int_amount, tx_type, tags],
+        )?;
     }
 
-    // 2025-05-10
+    // tx_all keeps the tags as they were written to show them. tx_tags is used for everything else
+    save_tx_tags(sp.last_insert_rowid(), tags, sp)?;
+
+    let split_date = date.split('-').collect::<Vec<&str>>();
+    let tx_year = split_date[0].parse::<i32>().unwrap();
+
+    // create the balance rows for the tx year if it's outside of the current year range
+    add_missing_years(tx_year, sp)?;
+    let (start_year, _) = get_year_range(sp)?;
+
+    // 2025-05-10 with the year range starting at 2022
     // take 2025 and subtract 2022 = 3, means the year number 3
     // take 05 -> 5 -> 5th month. 5 + (3 * 12) =  the row of this month's balance on balance_all table
     // we are not subtracting 1 from month because balance_all table starts at 1
-    let split_date = date.split('-').collect::<Vec<&str>>();
-    let (year, month) = (
-        split_date[0].parse::<i32>().unwrap() - 2022,
-        split_date[1].parse::<i32>().unwrap(),
-    );
+    let (year, month) = (tx_year - start_year, split_date[1].parse::<i32>().unwrap());
 
     let mut from_method = String::new();
     let mut to_method = String::new();
@@ -64,11 +96,11 @@ pub fn add_tx(
 
     // This is necessary for the foreign key field in the changes_all table
     // and must align with the latest transaction id_num
-    let mut last_id = get_last_tx_id(&sp)?;
+    let mut last_id = get_last_tx_id(sp)?;
     if let Some(id) = id_num {
         last_id = id.parse().unwrap();
     }
-    let last_balance_id = get_last_balance_id(&sp)?;
+    let last_balance_id = get_last_balance_id(sp)?;
 
     // we have to get these following data to push to the database
     // new_balance_data: the working month balance after the transaction
@@ -78,14 +110,12 @@ pub fn add_tx(
     let mut new_changes_data = Vec::new();
     let mut last_balance_data = HashMap::new();
 
-    let all_tx_methods = get_all_tx_methods(&sp);
-    let last_balance = get_last_balances(&sp);
+    let all_tx_methods = get_all_tx_methods(sp);
+    let last_balance = get_last_balances(sp);
 
     // Retrieve the current month's balance for each transaction method.
     let mut current_month_balance =
-        get_last_time_balance(month as usize, year as usize, &all_tx_methods, &sp);
-
-    let int_amount = amount.parse::<f64>().unwrap();
+        get_last_time_balance(month as usize, year as usize, &all_tx_methods, sp);
 
     // Update the current month's balance based on the transaction type.
     match tx_type {
@@ -97,13 +127,13 @@ pub fn add_tx(
             *current_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
             *current_month_balance.get_mut(&to_method).unwrap() = new_balance_to;
         }
-        "Expense" => {
+        _ if is_expense_like(tx_type) => {
             let new_balance = current_month_balance[tx_method] - int_amount;
 
             // Update the current month's balance for the relevant method.
             *current_month_balance.get_mut(tx_method).unwrap() = new_balance;
         }
-        "Income" => {
+        _ if is_income_like(tx_type) => {
             let new_balance = current_month_balance[tx_method] + int_amount;
             // Update the current month's balance for the relevant method.
             *current_month_balance.get_mut(tx_method).unwrap() = new_balance;
@@ -114,83 +144,66 @@ pub fn add_tx(
     // Add the current month's balances to the new balance data vector.
     // It's done this way to match the tx method location
     for i in &all_tx_methods {
-        new_balance_data.push(format!("{:.2}", current_month_balance[i]));
+        new_balance_data.push(current_month_balance[i]);
     }
 
-    //
     for i in 0..all_tx_methods.len() {
         // the variable to keep track whether any changes were made to the tx method
-        let current_last_balance = last_balance[i].parse::<f64>().unwrap();
-        let mut current_change = format!("{:.2}", 0.0);
+        let current_last_balance = last_balance[i].parse::<Cent>().unwrap();
+        let mut current_change = Cent::default().to_string();
 
         // add the proper values and changes based on the tx type
         if tx_type == "Transfer" && all_tx_methods[i] == from_method {
-            current_change = format!("↓{:.2}", &int_amount);
+            current_change = format!("↓{int_amount}");
 
             let edited_balance = current_last_balance - int_amount;
-            last_balance_data.insert(&from_method, format!("{edited_balance:.2}"));
+            last_balance_data.insert(&from_method, edited_balance);
         } else if tx_type == "Transfer" && all_tx_methods[i] == to_method {
-            current_change = format!("↑{:.2}", &int_amount);
+            current_change = format!("↑{int_amount}");
 
             let edited_balance = current_last_balance + int_amount;
-            last_balance_data.insert(&to_method, format!("{edited_balance:.2}"));
+            last_balance_data.insert(&to_method, edited_balance);
         } else if tx_type != "Transfer" && all_tx_methods[i] == tx_method {
-            if tx_type == "Expense" {
-                current_change = format!("↓{:.2}", &int_amount);
+            if is_expense_like(tx_type) {
+                current_change = format!("↓{int_amount}");
 
                 let edited_balance = current_last_balance - int_amount;
-                last_balance_data.insert(&all_tx_methods[i], format!("{edited_balance:.2}"));
-            } else if tx_type == "Income" {
-                current_change = format!("↑{:.2}", &int_amount);
+                last_balance_data.insert(&all_tx_methods[i], edited_balance);
+            } else if is_income_like(tx_type) {
+                current_change = format!("↑{int_amount}");
 
                 let edited_balance = current_last_balance + int_amount;
-                last_balance_data.insert(&all_tx_methods[i], format!("{edited_balance:.2}"));
+                last_balance_data.insert(&all_tx_methods[i], edited_balance);
             }
         }
         new_changes_data.push(current_change);
     }
 
-    let set_values = all_tx_methods
-        .iter()
-        .zip(new_balance_data.iter())
-        .map(|(method, value)| format!(r#""{method}" = "{value}""#,))
-        .collect::<Vec<_>>()
-        .join(", ");
-
-    let balance_query =
-        format!("UPDATE balance_all SET {set_values} WHERE id_num = {target_id_num}",);
-
-    let last_balance_query: String = if tx_type == "Transfer" {
-        format!(
-            r#"UPDATE balance_all SET "{from_method}" = "{}", "{to_method}" = "{}" WHERE id_num = {}"#,
-            last_balance_data[&from_method], last_balance_data[&to_method], last_balance_id
-        )
+    let mut balance_query = QueryBuilder::update("balance_all");
+    for (method, value) in all_tx_methods.iter().zip(new_balance_data) {
+        balance_query = balance_query.set(method, value);
+    }
+    let balance_query = balance_query.where_cond("id_num = ?", [target_id_num]);
+
+    let last_balance_query = if tx_type == "Transfer" {
+        QueryBuilder::update("balance_all")
+            .set(&from_method, last_balance_data[&from_method])
+            .set(&to_method, last_balance_data[&to_method])
     } else {
-        format!(
-            r#"UPDATE balance_all SET "{tx_method}" = "{}" WHERE id_num = {}"#,
-            last_balance_data[&tx_method.to_string()],
-            last_balance_id
-        )
-    };
-
-    let changes_query = format!(
-        "INSERT INTO changes_all (id_num, date, {}) VALUES ({}, ?, {})",
-        all_tx_methods
-            .iter()
-            .map(|s| format!(r#""{s}""#,))
-            .collect::<Vec<_>>()
-            .join(", "),
-        last_id,
-        new_changes_data
-            .iter()
-            .map(|s| format!(r#""{s}""#,))
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-
-    sp.execute(&balance_query, [])?;
-    sp.execute(&last_balance_query, [])?;
-    sp.execute(&changes_query, [date])?;
-    sp.commit()?;
+        QueryBuilder::update("balance_all")
+            .set(tx_method, last_balance_data[&tx_method.to_string()])
+    }
+    .where_cond("id_num = ?", [last_balance_id]);
+
+    let mut changes_query = QueryBuilder::insert("changes_all")
+        .set("id_num", last_id)
+        .set("date", date.to_string());
+    for (method, change) in all_tx_methods.iter().zip(new_changes_data) {
+        changes_query = changes_query.set(method, change);
+    }
+
+    balance_query.execute(sp)?;
+    last_balance_query.execute(sp)?;
+    changes_query.execute(sp)?;
     Ok(())
 }
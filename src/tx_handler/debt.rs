@@ -0,0 +1,85 @@
+use rusqlite::Connection;
+
+use crate::db::{
+    get_counterparty_outstanding, link_counterparty, DebtAction, NewDebtTx, BORROW_TX_TYPE,
+    LEND_TX_TYPE, REPAY_IN_TX_TYPE, REPAY_OUT_TX_TYPE,
+};
+use crate::outputs::CounterpartyError;
+use crate::page_handler::ActivityType;
+use crate::tx_handler::add_tx_in_savepoint;
+use crate::utility::{add_new_activity, add_new_activity_tx, get_last_tx, get_last_tx_id};
+
+/// Adds the debt tx, links it to the counterparty and saves it as an activity so it can be undone.
+/// All three are saved in one savepoint. A repayment goes in the direction of what is outstanding and cannot be more than that.
+/// Returns the added tx
+pub fn add_debt_tx(
+    debt_tx: &NewDebtTx,
+    conn: &mut Connection,
+) -> Result<Vec<String>, CounterpartyError> {
+    let counterparty = debt_tx.counterparty.trim();
+
+    if counterparty.is_empty() {
+        return Err(CounterpartyError::EmptyName);
+    }
+
+    if debt_tx.amount.value() <= 0 {
+        return Err(CounterpartyError::InvalidAmount);
+    }
+
+    let tx_type = match debt_tx.action {
+        DebtAction::Lend => LEND_TX_TYPE,
+        DebtAction::Borrow => BORROW_TX_TYPE,
+        DebtAction::Repay => {
+            let outstanding = get_counterparty_outstanding(counterparty, conn)
+                .map_err(CounterpartyError::UpdateFailed)?;
+
+            if outstanding.is_zero() {
+                return Err(CounterpartyError::NothingOwed(counterparty.to_string()));
+            }
+
+            if debt_tx.amount > outstanding.abs() {
+                return Err(CounterpartyError::RepayTooLarge(
+                    counterparty.to_string(),
+                    outstanding.abs().to_string(),
+                ));
+            }
+
+            if outstanding.value() > 0 {
+                REPAY_IN_TX_TYPE
+            } else {
+                REPAY_OUT_TX_TYPE
+            }
+        }
+    };
+
+    let details = if debt_tx.details.trim().is_empty() {
+        format!("{tx_type} {counterparty}")
+    } else {
+        debt_tx.details.trim().to_string()
+    };
+
+    let sp = conn.savepoint().map_err(CounterpartyError::UpdateFailed)?;
+
+    add_tx_in_savepoint(
+        &debt_tx.date.to_string(),
+        &details,
+        &debt_tx.tx_method,
+        &debt_tx.amount.to_string(),
+        tx_type,
+        "Unknown",
+        None,
+        &sp,
+    )
+    .map_err(CounterpartyError::UpdateFailed)?;
+
+    let id_num = get_last_tx_id(&sp).map_err(CounterpartyError::UpdateFailed)?;
+    link_counterparty(id_num, counterparty, &sp).map_err(CounterpartyError::UpdateFailed)?;
+
+    let last_tx = get_last_tx(&sp);
+    let activity_num = add_new_activity(ActivityType::NewTX, &sp);
+    add_new_activity_tx(&last_tx, activity_num, &sp);
+
+    sp.commit().map_err(CounterpartyError::UpdateFailed)?;
+
+    Ok(last_tx)
+}
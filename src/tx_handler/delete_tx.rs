@@ -1,35 +1,47 @@
-use rusqlite::{Connection, Result as sqlResult};
+use rusqlite::{Connection, Result as sqlResult, Savepoint};
 
-use crate::utility::{get_all_tx_methods, get_last_balance_id, get_last_balances};
+use crate::db::{quote_columns, QueryBuilder};
+use crate::utility::{
+    get_all_tx_methods, get_last_balance_id, get_last_balances, get_year_range, is_expense_like,
+    is_income_like, Cent,
+};
 
 /// Updates the absolute final balance, balance data and deletes the selected transaction.
 /// Foreign key cascade takes care of the Changes data in the database.
 pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
     let sp = conn.savepoint()?;
+    delete_tx_in_savepoint(id_num, &sp)?;
+    sp.commit()
+}
 
-    let tx_methods = get_all_tx_methods(&sp);
+/// Deletes a transaction the same way as `delete_tx` inside the given savepoint. Used when multiple
+/// txs must either all be changed or none of them
+pub fn delete_tx_in_savepoint(id_num: i32, sp: &Savepoint) -> sqlResult<()> {
+    let tx_methods = get_all_tx_methods(sp);
 
     // contains the data of the final row data before the tx gets deleted
-    let last_balance = get_last_balances(&sp);
-    let last_balance_id = get_last_balance_id(&sp)?;
+    let last_balance = get_last_balances(sp);
+    let last_balance_id = get_last_balance_id(sp)?;
+    let (start_year, _) = get_year_range(sp)?;
 
     // will contain data of the updated final balance row data
     let mut final_last_balance = Vec::new();
 
     // get the deletion tx data
-    let query = format!("SELECT * FROM tx_all Where id_num = {id_num}",);
-    let data = sp.query_row(&query, [], |row| {
-        let final_data: Vec<String> = vec![row.get(0)?, row.get(2)?, row.get(3)?, row.get(4)?];
-        Ok(final_data)
-    })?;
+    let (data, amount) =
+        sp.query_row("SELECT * FROM tx_all WHERE id_num = ?", [id_num], |row| {
+            let final_data: Vec<String> = vec![row.get(0)?, row.get(2)?, row.get(4)?];
+            let amount: Cent = row.get(3)?;
+            Ok((final_data, amount))
+        })?;
 
-    // 2025-05-10
+    // 2025-05-10 with the year range starting at 2022
     // take 2025 and subtract 2022 = 3, means the year number 3
     // take 05 -> 5 -> 5th month. 5 + (3 * 12) =  the row of this month's balance on balance_all table
     // we are not subtracting 1 from month because balance_all table starts at 1
     let splitted = data[0].split('-').collect::<Vec<&str>>();
     let (year, month) = (
-        splitted[0].parse::<i32>().unwrap() - 2022,
+        splitted[0].parse::<i32>().unwrap() - start_year,
         splitted[1].parse::<i32>().unwrap(),
     );
 
@@ -49,11 +61,10 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
         to_method = from_to[1];
     }
 
-    let amount = &data[2].parse::<f64>().unwrap();
-    let tx_type: &str = &data[3];
+    let tx_type: &str = &data[2];
 
     // loop through all rows in the balance_all table from the deletion point and update balance
-    // basically there are 193 rows(at the time of writing) on balance_all table. each row = 1 month. if month 4 had balance of 100,
+    // basically there are total year * 12 + 1 rows on balance_all table. each row = 1 month. if month 4 had balance of 100,
     // month 5 will also have the balance of 100 if no tx was added on month 5.
     // if the tx deletion happens on row/month 5, that means month 4 balance is correct but from the month from
     // 5 to the final row needs to be deduct that amount.
@@ -64,36 +75,26 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
     // Update balance_all table final balance row data which holds the absolute final amount after all tx
     loop {
         let query = format!(
-            "SELECT {} FROM balance_all WHERE id_num = {}",
-            tx_methods
-                .iter()
-                .map(|s| format!(r#""{s}""#,))
-                .collect::<Vec<_>>()
-                .join(", "),
-            target_id_num
+            "SELECT {} FROM balance_all WHERE id_num = ?",
+            quote_columns(&tx_methods)
         );
 
-        let current_month_balance = sp.query_row(&query, [], |row| {
-            let mut final_data: Vec<String> = Vec::new();
+        let current_month_balance = sp.query_row(&query, [target_id_num], |row| {
+            let mut final_data: Vec<Cent> = Vec::new();
             for i in 0..tx_methods.len() {
-                let row_data: f64 = row.get(i)?;
-                final_data.push(row_data.to_string());
+                let row_data: Cent = row.get(i)?;
+                final_data.push(row_data);
             }
             Ok(final_data)
         })?;
 
-        let mut untouched = true;
-
-        for x in &current_month_balance {
-            if x != "0" {
-                untouched = false;
-                break;
-            }
-        }
+        let untouched = current_month_balance
+            .iter()
+            .all(|balance| balance.is_zero());
 
         if untouched {
             target_id_num += 1;
-            if target_id_num == 193 {
+            if target_id_num == last_balance_id {
                 break;
             }
             continue;
@@ -102,88 +103,70 @@ pub fn delete_tx(id_num: i32, conn: &mut Connection) -> sqlResult<()> {
         let mut updated_month_balance = vec![];
 
         // add or subtract based on the tx type to the relevant method
-
-        // check the month balance as not zero because if it is 0, there was never any transaction
-        // done on that month
         for i in 0..tx_methods.len() {
-            if &tx_methods[i] == source && current_month_balance[i] != "0.00" {
-                let mut current_amount = current_month_balance[i].parse::<f64>().unwrap();
-                if tx_type == "Expense" {
+            let mut current_amount = current_month_balance[i];
+
+            if &tx_methods[i] == source {
+                if is_expense_like(tx_type) {
                     current_amount += amount;
-                } else if tx_type == "Income" {
+                } else if is_income_like(tx_type) {
                     current_amount -= amount;
                 }
-                updated_month_balance.push(format!("{current_amount:.2}",));
-            } else if tx_methods[i] == from_method && current_month_balance[i] != "0.00" {
-                let mut current_amount = current_month_balance[i].parse::<f64>().unwrap();
+            } else if tx_methods[i] == from_method {
                 current_amount += amount;
-                updated_month_balance.push(format!("{current_amount:.2}",));
-            } else if tx_methods[i] == to_method && current_month_balance[i] != "0.00" {
-                let mut current_amount = current_month_balance[i].parse::<f64>().unwrap();
+            } else if tx_methods[i] == to_method {
                 current_amount -= amount;
-                updated_month_balance.push(format!("{current_amount:.2}",));
-            } else {
-                updated_month_balance.push(format!(
-                    "{:.2}",
-                    current_month_balance[i].parse::<f64>().unwrap()
-                ));
             }
+            updated_month_balance.push(current_amount);
         }
 
-        let set_values = tx_methods
-            .iter()
-            .zip(updated_month_balance.iter())
-            .map(|(method, value)| format!(r#""{method}" = "{value}""#))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        let balance_query =
-            format!("UPDATE balance_all SET {set_values} WHERE id_num = {target_id_num}");
+        let mut balance_query = QueryBuilder::update("balance_all");
+        for (method, value) in tx_methods.iter().zip(updated_month_balance) {
+            balance_query = balance_query.set(method, value);
+        }
 
-        sp.execute(&balance_query, [])?;
+        balance_query
+            .where_cond("id_num = ?", [target_id_num])
+            .execute(sp)?;
 
-        // 193 is the absolute final balance which we don't need to modify
+        // the last row is the absolute final balance which we don't need to modify
         target_id_num += 1;
-        if target_id_num == 193 {
+        if target_id_num == last_balance_id {
             break;
         }
     }
 
-    // we need to update the 193 row with the latest balance.
-    // Based on the tx_type and method, edit the amount from the 193 row's balance
+    // we need to update the last row with the latest balance.
+    // Based on the tx_type and method, edit the amount from the last row's balance
     // we fetched earlier
     for i in 0..tx_methods.len() {
-        let mut current_balance = last_balance[i].parse::<f64>().unwrap();
+        let mut current_balance = last_balance[i].parse::<Cent>().unwrap();
         if &tx_methods[i] == source && tx_type != "Transfer" {
-            match tx_type {
-                "Expense" => current_balance += amount,
-                "Income" => current_balance -= amount,
-                _ => {}
+            if is_expense_like(tx_type) {
+                current_balance += amount;
+            } else if is_income_like(tx_type) {
+                current_balance -= amount;
             }
         } else if tx_methods[i] == from_method && tx_type == "Transfer" {
             current_balance += amount;
         } else if tx_methods[i] == to_method && tx_type == "Transfer" {
             current_balance -= amount;
         }
-        final_last_balance.push(format!("{current_balance:.2}",));
+        final_last_balance.push(current_balance);
     }
 
-    let del_query = format!("DELETE FROM tx_all WHERE id_num = {id_num}");
+    let mut last_balance_query = QueryBuilder::update("balance_all");
+    for (method, balance) in tx_methods.iter().zip(final_last_balance) {
+        last_balance_query = last_balance_query.set(method, balance);
+    }
 
-    let last_balance_query = format!(
-        "UPDATE balance_all SET {} WHERE id_num = {}",
-        tx_methods
-            .iter()
-            .zip(final_last_balance.iter())
-            .map(|(method, balance)| format!(r#""{method}" = "{balance}""#))
-            .collect::<Vec<_>>()
-            .join(", "),
-        last_balance_id
-    );
+    last_balance_query
+        .where_cond("id_num = ?", [last_balance_id])
+        .execute(sp)?;
 
-    sp.execute(&last_balance_query, [])?;
-    sp.execute(&del_query, [])?;
+    QueryBuilder::delete("tx_all")
+        .where_cond("id_num = ?", [id_num])
+        .execute(sp)?;
 
-    sp.commit()?;
     Ok(())
 }
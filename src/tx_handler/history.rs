@@ -0,0 +1,485 @@
+use rusqlite::{Connection, Result as sqlResult, Savepoint};
+
+use crate::db::{
+    get_saved_tx_status, get_tag_moves, parse_splits, set_budget_tag, set_goal_tag, set_tx_splits,
+    set_tx_status, set_tx_tags, BUDGETS_TABLE, GOALS_TABLE, RECONCILED_TX_STATUS,
+};
+use crate::outputs::HistoryError;
+use crate::page_handler::ActivityType;
+use crate::tx_handler::{add_tx_in_savepoint, delete_tx_in_savepoint};
+use crate::utility::{
+    add_new_activity, add_new_activity_tx, get_all_tx_methods, get_last_tx_id, get_tx_id_num,
+};
+
+/// A tx saved in `activity_txs` for an activity
+struct HistoryTx {
+    date: String,
+    details: String,
+    tx_method: String,
+    amount: String,
+    tx_type: String,
+    tags: String,
+    id_num: i32,
+    /// The split lines of the tx in their text form
+    splits: String,
+    status: String,
+}
+
+impl HistoryTx {
+    fn to_vec(&self) -> Vec<String> {
+        vec![
+            self.date.clone(),
+            self.details.clone(),
+            self.tx_method.clone(),
+            self.amount.clone(),
+            self.tx_type.clone(),
+            self.tags.clone(),
+            self.id_num.to_string(),
+            self.splits.clone(),
+            self.status.clone(),
+        ]
+    }
+
+    /// All tx methods this tx touches
+    fn get_methods(&self) -> Vec<&str> {
+        if self.tx_type == "Transfer" {
+            self.tx_method.split(" to ").collect()
+        } else {
+            vec![self.tx_method.as_str()]
+        }
+    }
+}
+
+/// Goes through the activity log from the start and returns the activities that can be undone
+/// and the activities that can be redone. The last value of both is the next one to reverse.
+///
+/// Every tx changing activity can be undone. Undo moves the last one to the redo list and redo
+/// moves it back. Any new tx changing activity clears the redo list.
+fn get_history(conn: &Connection) -> sqlResult<(Vec<i32>, Vec<i32>)> {
+    let mut statement =
+        conn.prepare("SELECT activity_num, activity_type FROM activities ORDER BY activity_num")?;
+    let mut rows = statement.query([])?;
+
+    let mut done = Vec::new();
+    let mut undone = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        let activity_num: i32 = row.get(0)?;
+        let activity_type: String = row.get(1)?;
+
+        match ActivityType::from_s(&activity_type) {
+            ActivityType::NewTX
+            | ActivityType::EditTX(_)
+            | ActivityType::DeleteTX(_)
+            | ActivityType::IDNumSwap(_, _)
+            | ActivityType::RestoreTX(_)
+            | ActivityType::RevertEdit(_)
+            | ActivityType::RenameTag(_, _)
+            | ActivityType::MergeTags(_, _)
+            | ActivityType::RemoveTag(_)
+            | ActivityType::Reconcile(..)
+            | ActivityType::Import(..) => {
+                done.push(activity_num);
+                undone.clear();
+            }
+            ActivityType::Undo(_) => {
+                if let Some(num) = done.pop() {
+                    undone.push(num);
+                }
+            }
+            ActivityType::Redo(_) => {
+                if let Some(num) = undone.pop() {
+                    done.push(num);
+                }
+            }
+            // The txs saved before a merge can use the merged tx method which no longer exists
+            ActivityType::MergeTxMethods(..) => {
+                done.clear();
+                undone.clear();
+            }
+            ActivityType::SearchTX(_) => {}
+        }
+    }
+
+    Ok((done, undone))
+}
+
+/// Returns the txs of an activity in the order they were saved
+fn get_history_txs(activity_num: i32, conn: &Connection) -> sqlResult<Vec<HistoryTx>> {
+    let mut statement = conn.prepare(
+        "SELECT date, details, tx_method, amount, tx_type, tags, id_num, splits, status FROM activity_txs
+        WHERE activity_num = ? ORDER BY insertion_id",
+    )?;
+
+    let rows = statement.query_map([activity_num], |row| {
+        let id_num: String = row.get(6)?;
+        Ok(HistoryTx {
+            date: row.get(0)?,
+            details: row.get(1)?,
+            tx_method: row.get(2)?,
+            amount: row.get(3)?,
+            tx_type: row.get(4)?,
+            tags: row.get(5)?,
+            id_num: id_num.parse().unwrap(),
+            splits: row.get(7)?,
+            status: row.get(8)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
+fn tx_exists(id_num: i32, conn: &Connection) -> sqlResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM tx_all WHERE id_num = ?)",
+        [id_num],
+        |row| row.get(0),
+    )
+}
+
+/// Reverses the last tx changing activity that has not been undone yet.
+/// The undo itself is saved as an activity
+pub fn undo_activity(conn: &mut Connection) -> Result<(), HistoryError> {
+    let (done, _) = get_history(conn).map_err(HistoryError::UpdateFailed)?;
+
+    let Some(activity_num) = done.last() else {
+        return Err(HistoryError::NothingToUndo);
+    };
+
+    reverse_activity(*activity_num, true, conn)
+}
+
+/// Applies the last undone activity again. The redo itself is saved as an activity
+pub fn redo_activity(conn: &mut Connection) -> Result<(), HistoryError> {
+    let (_, undone) = get_history(conn).map_err(HistoryError::UpdateFailed)?;
+
+    let Some(activity_num) = undone.last() else {
+        return Err(HistoryError::NothingToRedo);
+    };
+
+    reverse_activity(*activity_num, false, conn)
+}
+
+/// Returns the type, the description and the txs of an activity
+fn get_activity(
+    activity_num: i32,
+    conn: &Connection,
+) -> Result<(ActivityType, String, Vec<HistoryTx>), HistoryError> {
+    let (activity_type, description): (String, String) = conn
+        .query_row(
+            "SELECT activity_type, description FROM activities WHERE activity_num = ?",
+            [activity_num],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(HistoryError::UpdateFailed)?;
+
+    let txs = get_history_txs(activity_num, conn).map_err(HistoryError::UpdateFailed)?;
+
+    Ok((ActivityType::from_s(&activity_type), description, txs))
+}
+
+/// Deletes the txs of the given `id_num` and adds the given txs with the `id_num` they will get.
+/// A new `id_num` is used for txs without one. Reconciled txs are never deleted, the reconciliation
+/// has to be undone first. Everything is checked first and all changes are made inside the savepoint
+/// so the txs are either all changed or not touched at all once it's committed.
+/// Returns the `id_num` of the added txs
+fn apply_tx_changes(
+    to_delete: &[i32],
+    to_add: &[(&HistoryTx, Option<i32>)],
+    sp: &Savepoint,
+) -> Result<Vec<i32>, HistoryError> {
+    for id_num in to_delete {
+        if !tx_exists(*id_num, sp).map_err(HistoryError::UpdateFailed)? {
+            return Err(HistoryError::TxNotFound(*id_num));
+        }
+
+        let status = get_saved_tx_status(*id_num, sp).map_err(HistoryError::UpdateFailed)?;
+
+        if status == RECONCILED_TX_STATUS {
+            return Err(HistoryError::TxReconciled(*id_num));
+        }
+    }
+
+    let all_methods = get_all_tx_methods(sp);
+
+    for (tx, id_num) in to_add {
+        if let Some(id_num) = id_num {
+            if !to_delete.contains(id_num)
+                && tx_exists(*id_num, sp).map_err(HistoryError::UpdateFailed)?
+            {
+                return Err(HistoryError::TxExists(*id_num));
+            }
+        }
+
+        for method in tx.get_methods() {
+            if !all_methods.iter().any(|m| m == method) {
+                return Err(HistoryError::UnknownTxMethod(method.to_string()));
+            }
+        }
+    }
+
+    for id_num in to_delete {
+        delete_tx_in_savepoint(*id_num, sp).map_err(HistoryError::UpdateFailed)?;
+    }
+
+    let mut added_ids = Vec::new();
+
+    for (tx, id_num) in to_add {
+        let id_string = id_num.map(|id| id.to_string());
+
+        add_tx_in_savepoint(
+            &tx.date,
+            &tx.details,
+            &tx.tx_method,
+            &tx.amount,
+            &tx.tx_type,
+            &tx.tags,
+            id_string.as_deref(),
+            sp,
+        )
+        .map_err(HistoryError::UpdateFailed)?;
+
+        let added_id = match id_num {
+            Some(id) => *id,
+            None => get_last_tx_id(sp).map_err(HistoryError::UpdateFailed)?,
+        };
+
+        if !tx.splits.is_empty() {
+            let splits = parse_splits(&tx.splits).unwrap_or_default();
+            set_tx_splits(added_id, &splits, sp).map_err(HistoryError::UpdateFailed)?;
+        }
+
+        set_tx_status(added_id, &tx.status, sp).map_err(HistoryError::UpdateFailed)?;
+
+        added_ids.push(added_id);
+    }
+
+    Ok(added_ids)
+}
+
+/// Undoes or redoes the given activity with the txs saved with it. The undo or redo is saved as an
+/// activity in the same savepoint as the tx changes
+fn reverse_activity(
+    activity_num: i32,
+    undo: bool,
+    conn: &mut Connection,
+) -> Result<(), HistoryError> {
+    let (activity_type, description, txs) = get_activity(activity_num, conn)?;
+
+    let sp = conn.savepoint().map_err(HistoryError::UpdateFailed)?;
+
+    if let ActivityType::RenameTag(_, _)
+    | ActivityType::MergeTags(_, _)
+    | ActivityType::RemoveTag(_) = activity_type
+    {
+        // Saved in pairs of the tx after the change and the tx before it
+        let target = usize::from(undo);
+        let changed_txs = txs
+            .chunks(2)
+            .map(|pair| (pair[0].id_num, &pair[target]))
+            .collect::<Vec<(i32, &HistoryTx)>>();
+
+        apply_tag_changes(&changed_txs, &sp)?;
+        apply_tag_moves(activity_num, undo, &sp)?;
+    } else if let ActivityType::Reconcile(..) = activity_type {
+        // Saved the same way as tag changes
+        let target = usize::from(undo);
+        let changed_txs = txs
+            .chunks(2)
+            .map(|pair| (pair[0].id_num, &pair[target]))
+            .collect::<Vec<(i32, &HistoryTx)>>();
+
+        apply_status_changes(&changed_txs, &sp)?;
+    } else {
+        apply_tx_changes_of(activity_type, undo, &txs, &sp)?;
+    }
+
+    let activity_type = if undo {
+        ActivityType::Undo(Some(description))
+    } else {
+        ActivityType::Redo(Some(description))
+    };
+
+    let new_activity_num = add_new_activity(activity_type, &sp);
+
+    for tx in &txs {
+        add_new_activity_tx(&tx.to_vec(), new_activity_num, &sp);
+    }
+
+    sp.commit().map_err(HistoryError::UpdateFailed)
+}
+
+/// Sets the tags and the split lines of each given tx to the ones of the saved tx.
+/// Nothing is changed if any of the txs does not exist
+fn apply_tag_changes(
+    changed_txs: &[(i32, &HistoryTx)],
+    sp: &Savepoint,
+) -> Result<(), HistoryError> {
+    for (id_num, _) in changed_txs {
+        if !tx_exists(*id_num, sp).map_err(HistoryError::UpdateFailed)? {
+            return Err(HistoryError::TxNotFound(*id_num));
+        }
+    }
+
+    for (id_num, tx) in changed_txs {
+        set_tx_tags(*id_num, &tx.tags, sp).map_err(HistoryError::UpdateFailed)?;
+
+        let splits = parse_splits(&tx.splits).unwrap_or_default();
+        set_tx_splits(*id_num, &splits, sp).map_err(HistoryError::UpdateFailed)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the budgets and goals of a tag activity back to their old tag on undo and to their new tag
+/// on redo. A budget or goal that was deleted since then is skipped
+fn apply_tag_moves(activity_num: i32, undo: bool, sp: &Savepoint) -> Result<(), HistoryError> {
+    let mut tag_moves = get_tag_moves(activity_num, sp).map_err(HistoryError::UpdateFailed)?;
+
+    if undo {
+        tag_moves.reverse();
+    }
+
+    for tag_move in tag_moves {
+        let tag = if undo {
+            &tag_move.old_tag
+        } else {
+            &tag_move.new_tag
+        };
+
+        match tag_move.moved_table.as_str() {
+            BUDGETS_TABLE => set_budget_tag(tag_move.row_id, tag, sp),
+            GOALS_TABLE => set_goal_tag(tag_move.row_id, tag, sp),
+            _ => Ok(()),
+        }
+        .map_err(HistoryError::UpdateFailed)?;
+    }
+
+    Ok(())
+}
+
+/// Sets the status of each given tx to the one of the saved tx.
+/// Nothing is changed if any of the txs does not exist
+fn apply_status_changes(
+    changed_txs: &[(i32, &HistoryTx)],
+    sp: &Savepoint,
+) -> Result<(), HistoryError> {
+    for (id_num, _) in changed_txs {
+        if !tx_exists(*id_num, sp).map_err(HistoryError::UpdateFailed)? {
+            return Err(HistoryError::TxNotFound(*id_num));
+        }
+    }
+
+    for (id_num, tx) in changed_txs {
+        set_tx_status(*id_num, &tx.status, sp).map_err(HistoryError::UpdateFailed)?;
+    }
+
+    Ok(())
+}
+
+/// Undoes or redoes a tx changing activity by deleting and adding back the txs saved with it
+fn apply_tx_changes_of(
+    activity_type: ActivityType,
+    undo: bool,
+    txs: &[HistoryTx],
+    sp: &Savepoint,
+) -> Result<(), HistoryError> {
+    // The id_num of the txs to delete and the txs to add with the id_num they will get
+    let (to_delete, to_add) = match (activity_type, undo) {
+        (ActivityType::NewTX | ActivityType::RestoreTX(_), true)
+        | (ActivityType::DeleteTX(_), false) => (vec![txs[0].id_num], Vec::new()),
+        (ActivityType::NewTX | ActivityType::RestoreTX(_), false)
+        | (ActivityType::DeleteTX(_), true) => (Vec::new(), vec![(&txs[0], Some(txs[0].id_num))]),
+        // The first tx is the edited tx and the second one is the tx before the edit
+        (ActivityType::EditTX(_) | ActivityType::RevertEdit(_), true) => {
+            (vec![txs[0].id_num], vec![(&txs[1], Some(txs[1].id_num))])
+        }
+        (ActivityType::EditTX(_) | ActivityType::RevertEdit(_), false) => {
+            (vec![txs[0].id_num], vec![(&txs[0], Some(txs[0].id_num))])
+        }
+        // Both txs are saved with the id_num they were moved to
+        (ActivityType::IDNumSwap(_, _), true) => (
+            vec![txs[0].id_num, txs[1].id_num],
+            vec![
+                (&txs[0], Some(txs[1].id_num)),
+                (&txs[1], Some(txs[0].id_num)),
+            ],
+        ),
+        (ActivityType::IDNumSwap(_, _), false) => (
+            vec![txs[0].id_num, txs[1].id_num],
+            vec![
+                (&txs[0], Some(txs[0].id_num)),
+                (&txs[1], Some(txs[1].id_num)),
+            ],
+        ),
+        // Every imported tx is added or deleted together
+        (ActivityType::Import(..), true) => (txs.iter().map(|tx| tx.id_num).collect(), Vec::new()),
+        (ActivityType::Import(..), false) => (
+            Vec::new(),
+            txs.iter().map(|tx| (tx, Some(tx.id_num))).collect(),
+        ),
+        _ => unreachable!(),
+    };
+
+    apply_tx_changes(&to_delete, &to_add, sp)?;
+
+    Ok(())
+}
+
+/// Adds the tx of a "Delete TX" activity back. The original `id_num` is used if it's still free,
+/// otherwise the tx gets a new one. Returns the `id_num` of the restored tx
+pub fn restore_deleted_tx(activity_num: i32, conn: &mut Connection) -> Result<i32, HistoryError> {
+    let (activity_type, _, txs) = get_activity(activity_num, conn)?;
+
+    let ActivityType::DeleteTX(_) = activity_type else {
+        return Err(HistoryError::NotRestorable);
+    };
+
+    let tx = &txs[0];
+
+    let id_num = if tx_exists(tx.id_num, conn).map_err(HistoryError::UpdateFailed)? {
+        None
+    } else {
+        Some(tx.id_num)
+    };
+
+    let sp = conn.savepoint().map_err(HistoryError::UpdateFailed)?;
+
+    let restored_id = apply_tx_changes(&[], &[(tx, id_num)], &sp)?[0];
+
+    let new_activity_num = add_new_activity(ActivityType::RestoreTX(Some(restored_id)), &sp);
+    add_new_activity_tx(&get_tx_id_num(restored_id, &sp), new_activity_num, &sp);
+
+    sp.commit().map_err(HistoryError::UpdateFailed)?;
+
+    Ok(restored_id)
+}
+
+/// Changes the tx of an "Edit TX" activity back to the values it had before the edit
+pub fn revert_edit(activity_num: i32, conn: &mut Connection) -> Result<(), HistoryError> {
+    let (activity_type, _, txs) = get_activity(activity_num, conn)?;
+
+    let ActivityType::EditTX(_) = activity_type else {
+        return Err(HistoryError::NotRestorable);
+    };
+
+    // The second tx is the one before the edit
+    let old_tx = &txs[1];
+
+    if !tx_exists(old_tx.id_num, conn).map_err(HistoryError::UpdateFailed)? {
+        return Err(HistoryError::TxNotFound(old_tx.id_num));
+    }
+
+    let current_tx = get_tx_id_num(old_tx.id_num, conn);
+
+    let sp = conn.savepoint().map_err(HistoryError::UpdateFailed)?;
+
+    apply_tx_changes(&[old_tx.id_num], &[(old_tx, Some(old_tx.id_num))], &sp)?;
+
+    // Saved the same way as an edit. The reverted tx first and then the tx it replaced
+    let new_activity_num = add_new_activity(ActivityType::RevertEdit(Some(old_tx.id_num)), &sp);
+    add_new_activity_tx(&old_tx.to_vec(), new_activity_num, &sp);
+    add_new_activity_tx(&current_tx, new_activity_num, &sp);
+
+    sp.commit().map_err(HistoryError::UpdateFailed)
+}
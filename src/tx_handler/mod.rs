@@ -1,7 +1,13 @@
 mod add_tx;
+mod debt;
 mod delete_tx;
+mod history;
+mod recurring;
 mod tx_data;
 
-pub use add_tx::add_tx;
-pub use delete_tx::delete_tx;
+pub use add_tx::{add_tx, add_tx_in_savepoint};
+pub use debt::add_debt_tx;
+pub use delete_tx::{delete_tx, delete_tx_in_savepoint};
+pub use history::{redo_activity, restore_deleted_tx, revert_edit, undo_activity};
+pub use recurring::post_due_recurring_txs;
 pub use tx_data::*;
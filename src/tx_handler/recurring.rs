@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::db::{get_all_recurring_txs, link_recurring_tx};
+use crate::outputs::RecurringError;
+use crate::page_handler::ActivityType;
+use crate::tx_handler::add_tx_in_savepoint;
+use crate::utility::{add_new_activity, add_new_activity_tx, get_last_tx, get_last_tx_id};
+
+/// Posts every occurrence of the recurring txs that is due on or before `today` as a new tx.
+/// Each posted tx is linked to its recurring tx and saved as an activity so it can be undone.
+/// Every occurrence is posted in its own savepoint along with its link and activity.
+/// Stops at the first tx that fails to post. Returns the posted txs
+pub fn post_due_recurring_txs(
+    today: NaiveDate,
+    conn: &mut Connection,
+) -> Result<Vec<Vec<String>>, RecurringError> {
+    let mut posted_txs = Vec::new();
+
+    for recurring_tx in get_all_recurring_txs(conn) {
+        for date in recurring_tx.get_due_dates(today) {
+            let to_error = |e| RecurringError::PostFailed(recurring_tx.details.clone(), e);
+
+            // the tx, its link and its activity are saved together so a failure cannot leave a
+            // posted tx without moving the last posted date
+            let sp = conn.savepoint().map_err(to_error)?;
+
+            add_tx_in_savepoint(
+                &date.to_string(),
+                &recurring_tx.details,
+                &recurring_tx.tx_method,
+                &recurring_tx.amount.to_string(),
+                &recurring_tx.tx_type,
+                &recurring_tx.tags,
+                None,
+                &sp,
+            )
+            .map_err(to_error)?;
+
+            let id_num = get_last_tx_id(&sp).map_err(to_error)?;
+            link_recurring_tx(recurring_tx.recurring_id, id_num, date, &sp).map_err(to_error)?;
+
+            let last_tx = get_last_tx(&sp);
+            let activity_num = add_new_activity(ActivityType::NewTX, &sp);
+            add_new_activity_tx(&last_tx, activity_num, &sp);
+
+            sp.commit().map_err(to_error)?;
+
+            posted_txs.push(last_tx);
+        }
+    }
+
+    Ok(posted_txs)
+}
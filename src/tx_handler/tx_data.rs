@@ -3,16 +3,23 @@ use rusqlite::Connection;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use crate::db::{
+    get_saved_tx_status, get_split_tags, get_tx_splits, parse_splits, save_tx_splits,
+    set_tx_splits, set_tx_status, splits_to_text, DEBT_TX_TYPES, DEFAULT_TX_STATUS,
+    SETTABLE_TX_STATUSES,
+};
 use crate::outputs::{
-    CheckingError, ComparisonType, NAType, StepType, SteppingError, TxType, TxUpdateError,
+    AType, CheckingError, ComparisonType, NAType, StepType, SteppingError, TxType, TxUpdateError,
     VerifyingOutput,
 };
 use crate::page_handler::{ActivityType, DateType, TxTab};
-use crate::tx_handler::{add_tx, delete_tx};
+use crate::tx_handler::{add_tx, add_tx_in_savepoint, delete_tx_in_savepoint};
 use crate::utility::traits::{AutoFiller, DataVerifier, FieldStepper};
 use crate::utility::{
     add_char_to, add_new_activity, add_new_activity_tx, check_comparison, get_all_tx_methods,
-    get_empty_changes, get_last_balances, get_last_tx, get_search_data, get_tx_id_num,
+    get_empty_changes, get_last_balances, get_last_tx, get_last_tx_id, get_liability_tx_methods,
+    get_search_data, get_tx_id_num, get_tx_method_currencies, is_expense_like, is_income_like,
+    Cent, CurrencyConverter,
 };
 
 /// Contains all data for a Transaction to work
@@ -24,8 +31,15 @@ pub struct TxData {
     amount: String,
     tx_type: String,
     tags: String,
+    /// Split lines in their text form. Empty if the tx is not split
+    splits: String,
+    /// Pending, Cleared or Reconciled. Empty is saved as the default status
+    status: String,
     tx_status: Vec<String>,
     editing_tx: bool,
+    /// The type of the debt tx that is being edited. A debt tx keeps its type as the counterparty
+    /// balance depends on it
+    debt_tx_type: Option<String>,
     id_num: i32,
     current_index: usize,
     autofill: String,
@@ -57,8 +71,11 @@ impl TxData {
             amount: String::new(),
             tx_type: String::new(),
             tags: String::new(),
+            splits: String::new(),
+            status: String::new(),
             tx_status: Vec::new(),
             editing_tx: false,
+            debt_tx_type: None,
             id_num: 0,
             current_index: 0,
             autofill: String::new(),
@@ -74,8 +91,11 @@ impl TxData {
             amount: String::new(),
             tx_type: String::new(),
             tags: String::new(),
+            splits: String::new(),
+            status: String::new(),
             tx_status: Vec::new(),
             editing_tx: false,
+            debt_tx_type: None,
             id_num: 0,
             current_index: 0,
             autofill: String::new(),
@@ -112,8 +132,13 @@ impl TxData {
             amount: amount.to_string(),
             tx_type: tx_type.to_string(),
             tags: tags.to_string(),
+            splits: String::new(),
+            status: String::new(),
             tx_status: Vec::new(),
             editing_tx: true,
+            debt_tx_type: DEBT_TX_TYPES
+                .contains(&tx_type)
+                .then(|| tx_type.to_string()),
             id_num,
             current_index: 0,
             autofill: String::new(),
@@ -131,9 +156,21 @@ impl TxData {
             &self.tx_type,
             &self.tags,
             &self.autofill,
+            &self.splits,
+            &self.status,
         ]
     }
 
+    /// Loads the split lines of the tx that is being edited
+    pub fn load_splits(&mut self, conn: &Connection) {
+        self.splits = splits_to_text(&get_tx_splits(self.id_num, conn));
+    }
+
+    /// Loads the status of the tx that is being edited
+    pub fn load_status(&mut self, conn: &Connection) {
+        self.status = get_saved_tx_status(self.id_num, conn).unwrap_or_default();
+    }
+
     pub fn get_tx_method(&self) -> String {
         if self.tx_type == "Transfer" {
             format!("{} to {}", self.from_method, self.to_method)
@@ -192,9 +229,19 @@ impl TxData {
         add_char_to(to_add, &mut self.current_index, &mut self.tags);
     }
 
+    /// Insert or remove from splits field according to the index point
+    pub fn edit_splits(&mut self, to_add: Option<char>) {
+        add_char_to(to_add, &mut self.current_index, &mut self.splits);
+    }
+
+    /// Insert or remove from status field according to the index point
+    pub fn edit_status(&mut self, to_add: Option<char>) {
+        add_char_to(to_add, &mut self.current_index, &mut self.status);
+    }
+
     /// Takes all data and adds it as a transaction
     pub fn add_tx(&mut self, conn: &mut Connection) -> Result<(), String> {
-        if let Some(output) = self.check_all_fields() {
+        if let Some(output) = self.check_all_fields(conn) {
             return Err(output.to_string());
         }
 
@@ -204,65 +251,75 @@ impl TxData {
             self.editing_tx = false;
             // how saving an edited tx works
             // delete the tx that was being edited from the db using the id_num ->
-            // add another tx using the new data but take the earlier id to add to the db
+            // add another tx using the new data but take the earlier id to add to the db.
+            // Everything is done in one savepoint so a failed edit does not lose the tx
             let deleted_tx = get_tx_id_num(self.id_num, conn);
-            let status = delete_tx(self.id_num, conn);
-            match status {
-                Ok(()) => {}
-                Err(e) => return Err(TxUpdateError::FailedEditTx(e).to_string()),
-            }
-
             let id_num = self.id_num.to_string();
-            let status_add = add_tx(
-                &self.date,
-                &self.details,
-                &tx_method,
-                &self.amount,
-                &self.tx_type,
-                &self.tags,
-                Some(&id_num),
-                conn,
-            );
-
-            match status_add {
-                Ok(()) => {
-                    let activity_num =
-                        add_new_activity(ActivityType::EditTX(Some(self.id_num)), conn);
-                    let new_tx = vec![
-                        &self.date,
-                        &self.details,
-                        &tx_method,
-                        &self.amount,
-                        &self.tx_type,
-                        &self.tags,
-                        &id_num,
-                    ];
-                    add_new_activity_tx(&new_tx, activity_num, conn);
-                    add_new_activity_tx(&deleted_tx, activity_num, conn);
-                    Ok(())
-                }
-                Err(e) => Err(TxUpdateError::FailedEditTx(e).to_string()),
-            }
+
+            let status = conn.savepoint().and_then(|sp| {
+                delete_tx_in_savepoint(self.id_num, &sp)?;
+
+                add_tx_in_savepoint(
+                    &self.date,
+                    &self.details,
+                    &tx_method,
+                    &self.amount,
+                    &self.tx_type,
+                    &self.tags,
+                    Some(&id_num),
+                    &sp,
+                )?;
+
+                let splits = parse_splits(&self.splits).unwrap_or_default();
+                set_tx_splits(self.id_num, &splits, &sp)?;
+                set_tx_status(self.id_num, &self.status, &sp)?;
+
+                let activity_num = add_new_activity(ActivityType::EditTX(Some(self.id_num)), &sp);
+                let new_tx = vec![
+                    &self.date,
+                    &self.details,
+                    &tx_method,
+                    &self.amount,
+                    &self.tx_type,
+                    &self.tags,
+                    &id_num,
+                    &self.splits,
+                    &self.status,
+                ];
+                add_new_activity_tx(&new_tx, activity_num, &sp);
+                add_new_activity_tx(&deleted_tx, activity_num, &sp);
+
+                sp.commit()
+            });
+
+            status.map_err(|e| TxUpdateError::FailedEditTx(e).to_string())
         } else {
-            let status = add_tx(
-                &self.date,
-                &self.details,
-                &tx_method,
-                &self.amount,
-                &self.tx_type,
-                &self.tags,
-                None,
-                conn,
-            );
-            match status {
-                Ok(()) => {
-                    let activity_num = add_new_activity(ActivityType::NewTX, conn);
-                    let last_tx = get_last_tx(conn);
-                    add_new_activity_tx(&last_tx, activity_num, conn);
-                    Ok(())
-                }
-                Err(e) => Err(TxUpdateError::FailedAddTx(e).to_string()),
-            }
+            // the tx, its splits, its status and the activity are saved together
+            let status = conn.savepoint().and_then(|sp| {
+                add_tx_in_savepoint(
+                    &self.date,
+                    &self.details,
+                    &tx_method,
+                    &self.amount,
+                    &self.tx_type,
+                    &self.tags,
+                    None,
+                    &sp,
+                )?;
+
+                let id_num = get_last_tx_id(&sp)?;
+                let splits = parse_splits(&self.splits).unwrap_or_default();
+                set_tx_splits(id_num, &splits, &sp)?;
+                set_tx_status(id_num, &self.status, &sp)?;
+
+                let activity_num = add_new_activity(ActivityType::NewTX, &sp);
+                let last_tx = get_last_tx(&sp);
+                add_new_activity_tx(&last_tx, activity_num, &sp);
+
+                sp.commit()
+            });
+
+            status.map_err(|e| TxUpdateError::FailedAddTx(e).to_string())
         }
     }
 
@@ -279,6 +336,7 @@ impl TxData {
             &self.amount,
             &self.tx_type,
             &self.tags,
+            &self.status,
             date_type,
             conn,
         )
@@ -393,6 +451,12 @@ impl TxData {
 
     /// Checks the inputted Transaction Type by the user upon pressing Enter/Esc for various error.
     pub fn check_tx_type(&mut self) -> VerifyingOutput {
+        if let Some(debt_tx_type) = &self.debt_tx_type {
+            self.tx_type = debt_tx_type.clone();
+            self.go_current_index(&TxTab::TxType);
+            return VerifyingOutput::Accepted(AType::TxType);
+        }
+
         let mut tx_type = self.tx_type.clone();
 
         let status = self.verify_tx_type(&mut tx_type);
@@ -402,6 +466,17 @@ impl TxData {
         status
     }
 
+    /// Checks the inputted Status by the user upon pressing Enter/Esc for various error.
+    pub fn check_status(&mut self, is_search: bool) -> VerifyingOutput {
+        let mut status = self.status.clone();
+
+        let verify_status = self.verify_tx_status(&mut status, is_search);
+
+        self.status = status;
+        self.go_current_index(&TxTab::Status);
+        verify_status
+    }
+
     /// Checks the inputted tags to make sure it's properly separated by a comma
     pub fn check_tags(&mut self) {
         let mut tags = self.tags.clone();
@@ -423,8 +498,36 @@ impl TxData {
         status
     }
 
+    /// Checks the inputted split lines. The tags are replaced with the tags of the splits and an
+    /// empty amount is filled with the total of the splits
+    pub fn check_splits(&mut self) -> VerifyingOutput {
+        let mut splits = self.splits.clone();
+
+        let status = self.verify_splits(&mut splits);
+
+        self.splits = splits;
+        self.go_current_index(&TxTab::Splits);
+
+        let VerifyingOutput::Accepted(_) = status else {
+            return status;
+        };
+
+        let splits = parse_splits(&self.splits).unwrap();
+        let total: Cent = splits.iter().map(|split| split.amount).sum();
+
+        self.tags = get_split_tags(&splits);
+
+        if self.amount.is_empty() {
+            self.amount = total.to_string();
+        } else if self.amount.parse::<Cent>() != Ok(total) {
+            return VerifyingOutput::NotAccepted(NAType::SplitMismatch);
+        }
+
+        status
+    }
+
     /// Checks all field and verifies anything important is not empty
-    pub fn check_all_fields(&mut self) -> Option<CheckingError> {
+    pub fn check_all_fields(&mut self, conn: &Connection) -> Option<CheckingError> {
         if self.date.is_empty() {
             return Some(CheckingError::EmptyDate);
         } else if self.from_method.is_empty() && self.tx_type != "Transfer" {
@@ -440,10 +543,69 @@ impl TxData {
         {
             return Some(CheckingError::EmptyMethod);
         }
+
+        // Debt txs are only added from the Debts page and an edited debt tx keeps its type
+        match &self.debt_tx_type {
+            Some(debt_tx_type) if &self.tx_type != debt_tx_type => {
+                return Some(CheckingError::DebtTxType)
+            }
+            None if DEBT_TX_TYPES.contains(&self.tx_type.as_str()) => {
+                return Some(CheckingError::DebtTxType)
+            }
+            _ => {}
+        }
+
+        // The amount of a transfer is moved as is, so both tx methods must use the same currency
+        if self.tx_type == "Transfer" {
+            let currencies: HashMap<String, String> =
+                get_tx_method_currencies(conn).into_iter().collect();
+
+            if let (Some(from_currency), Some(to_currency)) = (
+                currencies.get(&self.from_method),
+                currencies.get(&self.to_method),
+            ) {
+                if from_currency != to_currency {
+                    return Some(CheckingError::CurrencyMismatch(
+                        from_currency.clone(),
+                        to_currency.clone(),
+                    ));
+                }
+            }
+        }
+
+        if !self.splits.is_empty() {
+            if self.tx_type == "Transfer" {
+                return Some(CheckingError::SplitOnTransfer);
+            }
+
+            let mut splits = self.splits.clone();
+
+            let VerifyingOutput::Accepted(_) = self.verify_splits(&mut splits) else {
+                return Some(CheckingError::SplitMismatch);
+            };
+
+            let splits = parse_splits(&splits).unwrap();
+            let total: Cent = splits.iter().map(|split| split.amount).sum();
+
+            if self.amount.parse::<Cent>() != Ok(total) {
+                return Some(CheckingError::SplitMismatch);
+            }
+
+            // The tags of a split tx always come from its split lines
+            self.splits = splits_to_text(&splits);
+            self.tags = get_split_tags(&splits);
+        }
+
         // empty tags in a tx becomes as unknown
         if self.tags.is_empty() {
             self.tags = "Unknown".to_string();
         }
+
+        if self.status.is_empty() {
+            self.status = DEFAULT_TX_STATUS.to_string();
+        } else if !SETTABLE_TX_STATUSES.contains(&self.status.as_str()) {
+            return Some(CheckingError::InvalidStatus);
+        }
         None
     }
 
@@ -456,6 +618,8 @@ impl TxData {
             &self.amount,
             &self.tx_type,
             &self.tags,
+            &self.splits,
+            &self.status,
         ];
         let non_empty_count = all_data.iter().filter(|&value| !value.is_empty()).count();
 
@@ -600,6 +764,8 @@ impl TxData {
             TxTab::Amount => self.amount.len(),
             TxTab::TxType => self.tx_type.len(),
             TxTab::Tags => self.tags.len(),
+            TxTab::Splits => self.splits.len(),
+            TxTab::Status => self.status.len(),
             TxTab::Nothing => 0,
         }
     }
@@ -705,6 +871,10 @@ impl TxData {
 
     /// Steps up Tx Type value by one
     pub fn do_tx_type_up(&mut self) -> Result<(), SteppingError> {
+        if self.debt_tx_type.is_some() {
+            return Ok(());
+        }
+
         let mut user_type = self.tx_type.clone();
 
         let step_status = self.step_tx_type(&mut user_type, StepType::StepUp);
@@ -717,6 +887,10 @@ impl TxData {
 
     /// Steps down Tx Type value by one
     pub fn do_tx_type_down(&mut self) -> Result<(), SteppingError> {
+        if self.debt_tx_type.is_some() {
+            return Ok(());
+        }
+
         let mut user_type = self.tx_type.clone();
 
         let step_status = self.step_tx_type(&mut user_type, StepType::StepDown);
@@ -809,6 +983,30 @@ impl TxData {
         step_status
     }
 
+    /// Steps up Status value by one
+    pub fn do_status_up(&mut self, is_search: bool) -> Result<(), SteppingError> {
+        let mut user_status = self.status.clone();
+
+        let step_status = self.step_tx_status(&mut user_status, StepType::StepUp, is_search);
+        self.status = user_status;
+
+        // reload index to the final point as some data just got added/changed
+        self.go_current_index(&TxTab::Status);
+        step_status
+    }
+
+    /// Steps down Status value by one
+    pub fn do_status_down(&mut self, is_search: bool) -> Result<(), SteppingError> {
+        let mut user_status = self.status.clone();
+
+        let step_status = self.step_tx_status(&mut user_status, StepType::StepDown, is_search);
+        self.status = user_status;
+
+        // reload index to the final point as some data just got added/changed
+        self.go_current_index(&TxTab::Status);
+        step_status
+    }
+
     /// Steps up Tags value by one
     pub fn do_tags_up(&mut self, conn: &Connection) -> Result<(), SteppingError> {
         let mut user_tag = self.tags.clone();
@@ -847,6 +1045,9 @@ impl TxData {
         )
         .unwrap();
 
+        save_tx_splits(new_id, &self.splits, conn).unwrap();
+        set_tx_status(new_id, &self.status, conn).unwrap();
+
         add_new_activity_tx(
             &[
                 &self.date,
@@ -856,6 +1057,8 @@ impl TxData {
                 &self.tx_type,
                 &self.tags,
                 &new_id.to_string(),
+                &self.splits,
+                &self.status,
             ],
             activity_num,
             conn,
@@ -909,12 +1112,12 @@ impl TxData {
                     new_balance.push(balance.to_string());
                     continue;
                 };
-                let change: f64 = change.replace("↑", "").replace("↓", "").parse().unwrap();
-                let balance: f64 = balance.parse().unwrap();
+                let change: Cent = change.replace("↑", "").replace("↓", "").parse().unwrap();
+                let balance: Cent = balance.parse().unwrap();
                 if income {
-                    new_balance.push(format!("{}", balance - change));
+                    new_balance.push((balance - change).to_string());
                 } else {
-                    new_balance.push(format!("{}", balance + change));
+                    new_balance.push((balance + change).to_string());
                 }
             }
             new_balance
@@ -922,20 +1125,25 @@ impl TxData {
             get_last_balances(conn)
         };
 
+        let all_tx_methods = get_all_tx_methods(conn);
+        let liability_methods = get_liability_tx_methods(conn);
+        let converter = CurrencyConverter::new(conn);
+
+        // The total is the liquid balance so liabilities are not counted
         if !self.generation_fields_exists() {
-            let mut total = 0.0;
+            let mut total = Cent::default();
 
-            for balance in last_balance_data {
-                total += balance.parse::<f64>().unwrap();
+            for (balance, method) in last_balance_data.into_iter().zip(all_tx_methods.iter()) {
+                if !liability_methods.contains(method) {
+                    total += converter.to_base(balance.parse().unwrap(), method);
+                }
                 balance_data.push(balance);
             }
             balance_data.push(total.to_string());
             return balance_data;
         }
 
-        let all_tx_methods = get_all_tx_methods(conn);
-
-        let mut last_balances: HashMap<String, f64> = all_tx_methods
+        let mut last_balances: HashMap<String, Cent> = all_tx_methods
             .iter()
             .zip(last_balance_data.iter())
             .map(|(method, balance)| (method.clone(), balance.parse().unwrap()))
@@ -943,19 +1151,15 @@ impl TxData {
 
         let from_method = &self.from_method;
         let to_method = &self.to_method;
-        let amount: f64 = self.amount.parse().unwrap();
+        let amount: Cent = self.amount.parse().unwrap();
 
         if self.tx_type != "Transfer" {
-            match self.tx_type.as_ref() {
-                "Income" => {
-                    let target_balance = last_balances.get_mut(from_method).unwrap();
-                    *target_balance += amount;
-                }
-                "Expense" => {
-                    let target_balance = last_balances.get_mut(from_method).unwrap();
-                    *target_balance -= amount;
-                }
-                _ => unreachable!(),
+            let target_balance = last_balances.get_mut(from_method).unwrap();
+
+            if is_income_like(&self.tx_type) {
+                *target_balance += amount;
+            } else if is_expense_like(&self.tx_type) {
+                *target_balance -= amount;
             }
         } else {
             let target_from_balance = last_balances.get_mut(from_method).unwrap();
@@ -965,9 +1169,11 @@ impl TxData {
             *target_to_balance += amount;
         }
 
-        let mut total_bal = 0.0;
-        for method in get_all_tx_methods(conn) {
-            total_bal += last_balances[&method];
+        let mut total_bal = Cent::default();
+        for method in all_tx_methods {
+            if !liability_methods.contains(&method) {
+                total_bal += converter.to_base(last_balances[&method], &method);
+            }
             balance_data.push(last_balances[&method].to_string());
         }
         balance_data.push(total_bal.to_string());
@@ -985,17 +1191,17 @@ impl TxData {
 
         let from_method = &self.from_method;
         let to_method = &self.to_method;
-        let amount: f64 = self.amount.parse().unwrap();
+        let amount: Cent = self.amount.parse().unwrap();
 
         let all_tx_methods = get_all_tx_methods(conn);
 
         if self.tx_type != "Transfer" {
             for method in all_tx_methods {
                 if &method == from_method {
-                    match self.tx_type.as_ref() {
-                        "Income" => changes_data.push(format!("↑{amount}")),
-                        "Expense" => changes_data.push(format!("↓{amount}")),
-                        _ => unreachable!(),
+                    if is_income_like(&self.tx_type) {
+                        changes_data.push(format!("↑{amount}"));
+                    } else {
+                        changes_data.push(format!("↓{amount}"));
                     }
                 } else {
                     changes_data.push("0.00".to_string());
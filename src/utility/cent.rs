@@ -0,0 +1,196 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+use std::fmt::{self, Display};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::outputs::CentParsingError;
+
+/// An amount of money stored as an exact integer number of cents.
+/// All balance and amount calculations go through this type so no rounding drift
+/// can happen the way it does with `f64`.
+///
+/// `Cent::new(1550)` = `15.50`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cent(i64);
+
+impl Cent {
+    pub const fn new(cents: i64) -> Self {
+        Cent(cents)
+    }
+
+    /// Returns the amount in cents
+    #[must_use]
+    pub fn value(self) -> i64 {
+        self.0
+    }
+
+    /// Converts the amount to a float. Only used for display purposes like percentages and the chart
+    #[must_use]
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// Converts a float to the nearest cent. Used where a multiplication or a division
+    /// of 2 amounts is necessary
+    #[must_use]
+    pub fn from_f64(value: f64) -> Self {
+        Cent((value * 100.0).round() as i64)
+    }
+
+    /// Multiplies the amount with an exchange rate and rounds it to the nearest cent
+    #[must_use]
+    pub fn convert(self, rate: f64) -> Self {
+        Cent((self.0 as f64 * rate).round() as i64)
+    }
+
+    #[must_use]
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Cent(self.0.abs())
+    }
+}
+
+impl FromStr for Cent {
+    type Err = CentParsingError;
+
+    /// Parses an amount like `15.5`, `-15.50` or `15` to cents without going through a float.
+    /// If there are more than 2 digits after the dot, the amount gets rounded to the nearest cent.
+    fn from_str(amount: &str) -> Result<Self, Self::Err> {
+        let error = || CentParsingError::InvalidAmount(amount.to_string());
+        let trimmed = amount.trim();
+
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(error());
+        }
+
+        let whole_value: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| error())?
+        };
+
+        // take the first 2 digits as cents and use the 3rd one to round
+        let mut fraction_digits = fraction.chars().map(|c| i64::from(c as u8 - b'0'));
+        let tens = fraction_digits.next().unwrap_or(0);
+        let ones = fraction_digits.next().unwrap_or(0);
+        let round_up = fraction_digits.next().unwrap_or(0) >= 5;
+
+        let cents = whole_value
+            .checked_mul(100)
+            .and_then(|value| value.checked_add(tens * 10 + ones + i64::from(round_up)))
+            .ok_or_else(error)?;
+
+        if negative {
+            Ok(Cent(-cents))
+        } else {
+            Ok(Cent(cents))
+        }
+    }
+}
+
+impl Display for Cent {
+    /// Formats the amount with exactly 2 digits after the dot. Example: `-15.50`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let absolute = self.0.unsigned_abs();
+        write!(f, "{sign}{}.{:02}", absolute / 100, absolute % 100)
+    }
+}
+
+impl Add for Cent {
+    type Output = Cent;
+
+    fn add(self, other: Cent) -> Cent {
+        Cent(self.0 + other.0)
+    }
+}
+
+impl Sub for Cent {
+    type Output = Cent;
+
+    fn sub(self, other: Cent) -> Cent {
+        Cent(self.0 - other.0)
+    }
+}
+
+impl AddAssign for Cent {
+    fn add_assign(&mut self, other: Cent) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Cent {
+    fn sub_assign(&mut self, other: Cent) {
+        self.0 -= other.0;
+    }
+}
+
+impl Neg for Cent {
+    type Output = Cent;
+
+    fn neg(self) -> Cent {
+        Cent(-self.0)
+    }
+}
+
+impl Div<i64> for Cent {
+    type Output = Cent;
+
+    /// Divides the amount and rounds the result to the nearest cent
+    fn div(self, divisor: i64) -> Cent {
+        let quotient = self.0 / divisor;
+        let remainder = self.0 % divisor;
+
+        if remainder.abs() * 2 >= divisor.abs() {
+            if (self.0 < 0) == (divisor < 0) {
+                Cent(quotient + 1)
+            } else {
+                Cent(quotient - 1)
+            }
+        } else {
+            Cent(quotient)
+        }
+    }
+}
+
+impl Sum for Cent {
+    fn sum<I: Iterator<Item = Cent>>(iter: I) -> Cent {
+        iter.fold(Cent::default(), |total, amount| total + amount)
+    }
+}
+
+impl ToSql for Cent {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0))
+    }
+}
+
+impl From<Cent> for Value {
+    fn from(amount: Cent) -> Value {
+        Value::Integer(amount.0)
+    }
+}
+
+impl FromSql for Cent {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(cents) => Ok(Cent(cents)),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
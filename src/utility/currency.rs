@@ -0,0 +1,153 @@
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::db::DEFAULT_CURRENCY;
+use crate::utility::{get_all_tx_methods, Cent};
+
+/// Returns the currency that converted totals are shown in
+pub fn get_base_currency(conn: &Connection) -> String {
+    conn.query_row(
+        "SELECT value FROM settings WHERE name = 'base_currency'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or_else(|_| DEFAULT_CURRENCY.to_string())
+}
+
+/// Returns the currency of each tx method in the order of the tx methods.
+/// Format: `[(method, currency),]`
+pub fn get_tx_method_currencies(conn: &Connection) -> Vec<(String, String)> {
+    let mut statement = conn
+        .prepare("SELECT name, currency FROM tx_methods")
+        .expect("could not prepare statement");
+
+    let saved_currencies: HashMap<String, String> = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(|data| data.unwrap())
+        .collect();
+
+    get_all_tx_methods(conn)
+        .into_iter()
+        .map(|method| {
+            let currency = saved_currencies
+                .get(&method)
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+            (method, currency)
+        })
+        .collect()
+}
+
+/// Returns all saved exchange rates. Format: `[(from currency, to currency, rate),]`
+pub fn get_exchange_rates(conn: &Connection) -> Vec<(String, String, f64)> {
+    let mut statement = conn
+        .prepare("SELECT from_currency, to_currency, rate FROM exchange_rates ORDER BY from_currency, to_currency")
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .map(|data| data.unwrap())
+        .collect()
+}
+
+/// Checks whether the given value is a 3 letter currency code. Example: USD, EUR, BDT
+#[must_use]
+pub fn is_valid_currency(currency: &str) -> bool {
+    currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Converts the amounts of tx methods to the base currency.
+/// A tx method currency is converted with a saved rate in either direction.
+/// Tx methods without a usable rate are left out of the converted totals
+pub struct CurrencyConverter {
+    base_currency: String,
+    method_rates: HashMap<String, f64>,
+    missing_currencies: Vec<String>,
+}
+
+impl CurrencyConverter {
+    pub fn new(conn: &Connection) -> Self {
+        let base_currency = get_base_currency(conn);
+
+        let rates: HashMap<(String, String), f64> = get_exchange_rates(conn)
+            .into_iter()
+            .map(|(from_currency, to_currency, rate)| ((from_currency, to_currency), rate))
+            .collect();
+
+        let mut method_rates = HashMap::new();
+        let mut missing_currencies = Vec::new();
+
+        for (method, currency) in get_tx_method_currencies(conn) {
+            let rate = if currency == base_currency {
+                Some(1.0)
+            } else if let Some(rate) = rates.get(&(currency.clone(), base_currency.clone())) {
+                Some(*rate)
+            } else {
+                rates
+                    .get(&(base_currency.clone(), currency.clone()))
+                    .map(|rate| 1.0 / rate)
+            };
+
+            match rate {
+                Some(rate) => {
+                    method_rates.insert(method, rate);
+                }
+                None => {
+                    if !missing_currencies.contains(&currency) {
+                        missing_currencies.push(currency);
+                    }
+                }
+            }
+        }
+
+        CurrencyConverter {
+            base_currency,
+            method_rates,
+            missing_currencies,
+        }
+    }
+
+    #[must_use]
+    pub fn base_currency(&self) -> &str {
+        &self.base_currency
+    }
+
+    /// Currencies in use that have no rate to the base currency
+    #[must_use]
+    pub fn missing_currencies(&self) -> &[String] {
+        &self.missing_currencies
+    }
+
+    /// Converts an amount of the given tx method to the base currency.
+    /// Returns 0 if the tx method has no rate to the base currency
+    #[must_use]
+    pub fn to_base(&self, amount: Cent, tx_method: &str) -> Cent {
+        match self.method_rates.get(tx_method) {
+            Some(rate) => amount.convert(*rate),
+            None => Cent::default(),
+        }
+    }
+
+    /// A note about the currencies left out of the converted totals to show next to them.
+    /// None if every currency has a rate. Example: `No rate to USD: EUR, GBP`
+    #[must_use]
+    pub fn missing_rates_note(&self) -> Option<String> {
+        if self.missing_currencies.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "No rate to {}: {}",
+            self.base_currency,
+            self.missing_currencies.join(", ")
+        ))
+    }
+
+    /// The header of the converted total column. Example: `Total USD`
+    #[must_use]
+    pub fn total_header(&self) -> String {
+        format!("Total {}", self.base_currency)
+    }
+}
@@ -1,6 +1,10 @@
+mod cent;
+mod currency;
 mod sub_func;
 pub mod traits;
 mod utils;
 
+pub use cent::*;
+pub use currency::*;
 pub use sub_func::*;
 pub use utils::*;
@@ -1,4 +1,5 @@
-use rusqlite::Connection;
+use chrono::{Datelike, Local, NaiveDate};
+use rusqlite::{params_from_iter, Connection};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::stdout;
@@ -6,12 +7,27 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use crate::activity_page::{ActivityDetails, ActivityTx};
+use crate::db::{
+    check_export_date_format, check_tag_name, escape_like, get_all_budgets, get_all_counterparties,
+    get_all_goals, get_all_holdings, get_all_recurring_txs, get_balance_on,
+    get_counterparty_outstanding, get_import_profiles, get_reconciled_balance, get_tx_splits,
+    is_valid_symbol, parse_exchange_rates, parse_export_columns, parse_export_delimiter,
+    parse_prices, parse_statement, quote_columns, Budget, BudgetPeriod, DebtAction, ExportCommand,
+    ExportOptions, ExportSource, Goal, GoalSource, Holding, ImportAmount, ImportProfile,
+    ImportTxMethod, NewDebtTx, OpeningBalance, QueryBuilder, Reconciliation, RecurringSchedule,
+    RecurringTx, EXPORT_COLUMNS, MAX_YEAR, MIN_YEAR, SPLIT_FIELD_SEPARATOR, SPLIT_LINE_SEPARATOR,
+    TAG_SEPARATOR, TX_METHOD_KINDS,
+};
 use crate::outputs::{ComparisonType, TerminalExecutionError};
 use crate::page_handler::{ActivityType, DateType, ResetType, UserInputType};
 use crate::tx_handler::{delete_tx, TxData};
 use crate::utility::{
     add_new_activity, add_new_activity_tx, check_comparison, check_restricted, clear_terminal,
-    flush_output, get_all_tx_methods, get_sql_dates, reverse_date_format, take_input,
+    flush_output, get_active_tx_methods, get_all_tags, get_all_tx_methods, get_archived_tx_methods,
+    get_exchange_rates, get_parent_tags, get_sql_dates, get_tx_method_currencies,
+    get_tx_method_kinds, get_tx_method_usage, get_year_from_index, get_year_range, is_expense_like,
+    is_in_tag_subtree, is_income_like, is_valid_currency, reverse_date_format, split_tags,
+    take_input, Cent, CurrencyConverter,
 };
 
 /// Returns the balance of all methods based on year and month point.
@@ -22,17 +38,17 @@ pub fn get_last_time_balance(
     year: usize,
     tx_method: &Vec<String>,
     conn: &Connection,
-) -> HashMap<String, f64> {
+) -> HashMap<String, Cent> {
     // We can get the id_num of the month which is saved in the database based on the
     // month and year index there is passed.
     let target_id_num = month as i32 + (year as i32 * 12);
 
     let mut final_value = HashMap::new();
     for i in tx_method {
-        final_value.insert(i.to_string(), 0.0);
+        final_value.insert(i.to_string(), Cent::default());
     }
 
-    // balance_all starts at point 1. 1 means month 1, year 2022/0.
+    // balance_all starts at point 1. 1 means month 1 of the first year of the year range.
     // There is no earlier balance than this
     if target_id_num == 0 {
         return final_value;
@@ -40,11 +56,7 @@ pub fn get_last_time_balance(
 
     let mut checked_methods: Vec<&str> = Vec::new();
 
-    let tx_method_string = tx_method
-        .iter()
-        .map(|m| format!("\"{m}\""))
-        .collect::<Vec<_>>()
-        .join(", ");
+    let tx_method_string = quote_columns(tx_method);
 
     // the process goes like this
     // m1  m2  m3  id
@@ -70,10 +82,10 @@ pub fn get_last_time_balance(
         // check each tx_method column in the current row
         for (i, item) in tx_method.iter().enumerate() {
             if !checked_methods.contains(&item.as_str()) {
-                let balance: f64 = row.get(i).unwrap();
+                let balance: Cent = row.get(i).unwrap();
 
                 // we only need non-zero balance
-                if balance != 0.0 {
+                if !balance.is_zero() {
                     *final_value.get_mut(item).unwrap() = balance;
                     checked_methods.push(item);
                 }
@@ -89,7 +101,8 @@ pub fn get_all_changes(month: usize, year: usize, conn: &Connection) -> Vec<Vec<
     let mut final_result = Vec::new();
     let tx_methods = get_all_tx_methods(conn);
 
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
+    let (datetime_1, datetime_2) =
+        get_sql_dates(month, get_year_from_index(year, conn), &DateType::Monthly);
 
     let mut statement = conn
         .prepare("SELECT * FROM changes_all Where date BETWEEN date(?) AND date(?) ORDER BY date, id_num")
@@ -132,7 +145,8 @@ pub fn get_all_txs(
 
     let mut last_month_balance = get_last_time_balance(month, year, &all_tx_methods, conn);
 
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
+    let (datetime_1, datetime_2) =
+        get_sql_dates(month, get_year_from_index(year, conn), &DateType::Monthly);
 
     // preparing the query for db, getting current month's all transactions
     let mut statement = conn
@@ -146,14 +160,16 @@ pub fn get_all_txs(
             // collect the row data and put them in a vec
             let date = reverse_date_format(row.get(0).unwrap());
             let id_num: i32 = row.get(5).unwrap();
+            let amount: Cent = row.get(3).unwrap();
 
             Ok(vec![
                 date,
                 row.get(1).unwrap(),
                 row.get(2).unwrap(),
-                row.get(3).unwrap(),
+                amount.to_string(),
                 row.get(4).unwrap(),
                 row.get(6).unwrap(),
+                row.get(7).unwrap(),
                 id_num.to_string(),
             ])
         })
@@ -173,22 +189,22 @@ pub fn get_all_txs(
 
         // collect data inside variables
         let tx_type = &i[4];
-        let amount = &i[3].to_string().parse::<f64>().unwrap();
+        let amount = i[3].parse::<Cent>().unwrap();
         let tx_method = &i[2];
 
         // If the transaction is not a transfer, default balance goes to new_balance_from
         // and new_balance_to remains empty. On transfer TX both of them are used
 
-        let mut new_balance_from: f64 = 0.0;
-        let mut new_balance_to: f64 = 0.0;
+        let mut new_balance_from = Cent::default();
+        let mut new_balance_to = Cent::default();
 
         let mut from_method = String::new();
         let mut to_method = String::new();
 
         // add or subtract the amount based on the tx type
-        if tx_type == "Expense" {
+        if is_expense_like(tx_type) {
             new_balance_from = last_month_balance[tx_method] - amount;
-        } else if tx_type == "Income" {
+        } else if is_income_like(tx_type) {
             new_balance_from = last_month_balance[tx_method] + amount;
         } else if tx_type == "Transfer" {
             let split_method = tx_method.split(" to ").collect::<Vec<&str>>();
@@ -201,7 +217,7 @@ pub fn get_all_txs(
         // make changes to the balance map based on the tx
         // for transfer TX first block executes
         // new_balance_to != 0 means it's a transfer transaction
-        if new_balance_to == 0.0 {
+        if new_balance_to.is_zero() {
             *last_month_balance.get_mut(tx_method).unwrap() = new_balance_from;
         } else {
             *last_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
@@ -211,7 +227,7 @@ pub fn get_all_txs(
         // push all the changes gathered to the return variable
         let mut to_push = vec![];
         for i in &all_tx_methods {
-            to_push.push(format!("{:.2}", last_month_balance[i]));
+            to_push.push(last_month_balance[i].to_string());
         }
 
         final_all_balances.push(to_push);
@@ -222,17 +238,15 @@ pub fn get_all_txs(
         let target_id_num = month as i32 + 1 + (year as i32 * 12);
         let final_index = final_all_balances.len() - 1;
 
-        let balance_query = format!(
-            "UPDATE balance_all SET {} WHERE id_num = {}",
-            final_all_balances[final_index]
-                .iter()
-                .enumerate()
-                .map(|(i, balance)| format!(r#""{}" = "{}""#, all_tx_methods[i], balance))
-                .collect::<Vec<String>>()
-                .join(", "),
-            target_id_num
-        );
-        conn.execute(&balance_query, [])
+        let mut balance_query = QueryBuilder::update("balance_all");
+        for (method, balance) in all_tx_methods.iter().zip(&final_all_balances[final_index]) {
+            let balance: Cent = balance.parse().unwrap();
+            balance_query = balance_query.set(method, balance);
+        }
+
+        balance_query
+            .where_cond("id_num = ?", [target_id_num])
+            .execute(conn)
             .expect("Error updating balance query");
     }
 
@@ -242,14 +256,15 @@ pub fn get_all_txs(
 /// Returns the absolute final balance or the last row on `balance_all` table.
 pub fn get_last_balances(conn: &Connection) -> Vec<String> {
     let tx_method = get_all_tx_methods(conn);
-    let mut query = format!("SELECT {tx_method:?} FROM balance_all ORDER BY id_num DESC LIMIT 1");
-    query = query.replace('[', "");
-    query = query.replace(']', "");
+    let query = format!(
+        "SELECT {} FROM balance_all ORDER BY id_num DESC LIMIT 1",
+        quote_columns(&tx_method)
+    );
 
     let final_balance = conn.query_row(&query, [], |row| {
         let mut final_data: Vec<String> = Vec::new();
         for i in 0..tx_method.len() {
-            let row_data: f64 = row.get(i).unwrap();
+            let row_data: Cent = row.get(i).unwrap();
             final_data.push(row_data.to_string());
         }
         Ok(final_data)
@@ -271,7 +286,21 @@ pub fn start_taking_input(conn: &Connection) -> UserInputType {
 2. Rename Transaction Method
 3. Reposition Transactions Methods
 4. Set a new location for app data
-5. Set backup DB paths\n"
+5. Set backup DB paths
+6. Set Transaction Method currency
+7. Set base currency
+8. Add or update exchange rates
+9. Import exchange rates from a CSV file
+10. Delete Transaction Method
+11. Archive or unarchive Transaction Method
+12. Merge Transaction Methods
+13. Verify balances and changes against the transactions
+14. Rebuild balances and changes from the transactions
+15. Set Transaction Method kind
+16. Add, update or remove investment holdings
+17. Add or update prices
+18. Import prices from a CSV file
+19. Import transactions from a bank statement CSV file\n"
         );
         print!("Proceed with option number: ");
         flush_output(&stdout);
@@ -280,12 +309,35 @@ pub fn start_taking_input(conn: &Connection) -> UserInputType {
         let input_type = UserInputType::from_string(&user_input.to_lowercase());
 
         match input_type {
-            UserInputType::AddNewTxMethod(_) => return get_user_tx_methods(true, Some(conn)),
+            UserInputType::AddNewTxMethod(..) => return get_user_tx_methods(true, Some(conn)),
             UserInputType::RenameTxMethod(_) => return get_rename_data(conn),
             UserInputType::RepositionTxMethod(_) => return get_reposition_data(conn),
             UserInputType::SetNewLocation(_) => return get_new_location(),
             UserInputType::BackupDBPath(_) => return get_backup_db_paths(),
-            UserInputType::CancelledOperation | UserInputType::ResetData(_) => return input_type,
+            UserInputType::SetTxMethodCurrency(_) => return get_method_currency_data(conn),
+            UserInputType::SetBaseCurrency(_) => return get_base_currency_data(conn),
+            UserInputType::SetExchangeRates(_) => return get_exchange_rate_data(conn),
+            UserInputType::ImportExchangeRates(_) => return get_exchange_rate_file(),
+            UserInputType::DeleteTxMethod(_) => return get_delete_method_data(conn),
+            UserInputType::ArchiveTxMethod(..) => return get_archive_method_data(conn),
+            UserInputType::MergeTxMethod(_) => return get_merge_method_data(conn),
+            UserInputType::RebuildLedger => return get_rebuild_confirmation(),
+            UserInputType::SetTxMethodKind(_) => return get_method_kind_data(conn),
+            UserInputType::SetHolding(_) => return get_holding_data(conn),
+            UserInputType::SetPrices(_) => return get_price_data(),
+            UserInputType::ImportPrices(_) => return get_price_file(),
+            UserInputType::ImportTxs(..) => return get_import_data(conn),
+            UserInputType::CancelledOperation
+            | UserInputType::RenameTag(_)
+            | UserInputType::MergeTags(..)
+            | UserInputType::SaveRecurringTx(_)
+            | UserInputType::SaveBudget(_)
+            | UserInputType::SaveGoal(_)
+            | UserInputType::StartReconciliation(_)
+            | UserInputType::AddDebtTx(_)
+            | UserInputType::ExportTxs(_)
+            | UserInputType::ResetData(_)
+            | UserInputType::VerifyLedger => return input_type,
             UserInputType::InvalidInput => clear_terminal(&mut stdout),
         }
     }
@@ -410,7 +462,187 @@ Example input: Bank, Cash, PayPal.\n\nEnter Transaction Methods: "
         }
         clear_terminal(&mut stdout);
     }
-    UserInputType::AddNewTxMethod(db_tx_methods)
+
+    let opening_balances = get_opening_balances(&db_tx_methods);
+
+    UserInputType::AddNewTxMethod(db_tx_methods, opening_balances)
+}
+
+/// Asks with the prompt until the input is accepted by `check` and returns the accepted value.
+/// An empty input uses the current value shown in the brackets. None if the operation was cancelled
+#[cfg(not(tarpaulin_include))]
+fn ask_until_valid(
+    prompt: &str,
+    current: String,
+    check: &dyn Fn(&str) -> Result<String, String>,
+) -> Option<String> {
+    let stdout = stdout();
+
+    loop {
+        print!("{prompt} [{current}]: ");
+        flush_output(&stdout);
+
+        let mut user_input = take_input();
+
+        if user_input.to_lowercase().starts_with("cancel") {
+            return None;
+        }
+
+        if user_input.is_empty() {
+            user_input.clone_from(&current);
+        }
+
+        match check(&user_input) {
+            Ok(value) => return Some(value),
+            Err(e) => println!("{e}\n"),
+        }
+    }
+}
+
+/// Asks for the balance each of the given tx methods starts with and the date of it.
+/// Methods starting at 0 are not asked for a date
+#[cfg(not(tarpaulin_include))]
+fn get_opening_balances(tx_methods: &[String]) -> Vec<OpeningBalance> {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let today = Local::now().date_naive();
+
+    println!(
+        "Enter the balance each Transaction Method starts with. It is added as an Opening Balance \
+transaction that does not count as an income. Empty inputs use the value in the brackets. \
+Enter 'cancel' to skip the remaining Transaction Methods.\n"
+    );
+
+    let mut opening_balances = Vec::new();
+
+    for tx_method in tx_methods {
+        let Some(amount) = ask_until_valid(
+            &format!("Opening balance of {tx_method}"),
+            Cent::default().to_string(),
+            &|amount| {
+                amount
+                    .trim()
+                    .parse::<Cent>()
+                    .map(|cent| cent.to_string())
+                    .map_err(|_| String::from("Opening balance must be a number"))
+            },
+        ) else {
+            break;
+        };
+
+        let amount: Cent = amount.parse().unwrap();
+
+        if amount.is_zero() {
+            continue;
+        }
+
+        let Some(date) = ask_until_valid(
+            &format!("Opening balance date of {tx_method} (YYYY-MM-DD)"),
+            today.to_string(),
+            &|date| match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+                Ok(date)
+                    if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) =>
+                {
+                    Ok(date.to_string())
+                }
+                _ => Err(format!(
+                    "Opening balance date must be YYYY-MM-DD between {MIN_YEAR} and {MAX_YEAR}"
+                )),
+            },
+        ) else {
+            break;
+        };
+
+        opening_balances.push(OpeningBalance {
+            tx_method: tx_method.clone(),
+            date: NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap(),
+            amount,
+        });
+    }
+
+    opening_balances
+}
+
+/// Gets a tx method and whether it is an asset or a liability from the user
+#[cfg(not(tarpaulin_include))]
+fn get_method_kind_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let method_kinds = get_tx_method_kinds(conn);
+
+    loop {
+        let mut method_line =
+            "Select a Transaction Method to proceed. Input 'Cancel' to cancel the operation.
+
+Liabilities like credit cards and loans go negative as the debt grows. They are not counted in the \
+Home page Total but they are counted in the net worth.
+
+Currently added Transaction Methods: \n"
+                .to_string();
+
+        for (i, (method, kind)) in method_kinds.iter().enumerate() {
+            method_line.push_str(&format!("\n{}. {method} ({kind})", i + 1));
+        }
+        println!("{method_line}");
+        print!("\nEnter the method number to edit: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let Ok(method_number) = user_input.parse::<usize>() else {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        };
+
+        if method_number == 0 || method_number > method_kinds.len() {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        }
+
+        let (method, kind) = &method_kinds[method_number - 1];
+
+        println!("\nSelected method: {method}. Current kind: {kind}. Enter the new kind. Values: Asset/Liability/A/L");
+        print!("New kind: ");
+        flush_output(&stdout);
+
+        let new_kind = match take_input().trim().to_lowercase().as_str() {
+            "a" | "asset" => TX_METHOD_KINDS[0],
+            "l" | "liability" => TX_METHOD_KINDS[1],
+            _ => {
+                clear_terminal(&mut stdout);
+                println!("Invalid kind. Example input: Asset, Liability, A, L\n");
+                continue;
+            }
+        };
+
+        println!(
+            "\nSet the kind of {method} to {new_kind}. The saved balances will not be changed."
+        );
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::SetTxMethodKind(vec![method.to_string(), new_kind.to_string()]);
+        }
+        clear_terminal(&mut stdout);
+    }
 }
 
 /// Gets a new tx method name from the user to replace an existing method
@@ -739,378 +971,2499 @@ Windows: C:\\data\\save\\, C:\\backup\\save\\, C:\\folder\\app\\";
     }
 }
 
-/// Tries to open terminal/cmd and run this app
-/// Currently supports windows cmd, konsole, gnome-terminal, kgx (also known as gnome-console)
+/// Returns the current currency state as lines to show before asking for currency related inputs
 #[cfg(not(tarpaulin_include))]
-pub fn start_terminal(original_dir: &str) -> Result<(), TerminalExecutionError> {
-    if cfg!(target_os = "windows") {
-        Command::new("cmd.exe")
-            .arg("start")
-            .arg("rex")
-            .output()
-            .map_err(TerminalExecutionError::ExecutionFailed)?;
-    } else {
-        let mut all_terminals = HashMap::new();
-        let gnome_dir = format!("--working-directory={original_dir}");
+fn get_currency_status(conn: &Connection) -> String {
+    let converter = CurrencyConverter::new(conn);
+    let mut status = format!(
+        "Base currency: {}\n\nTransaction Method currencies:\n",
+        converter.base_currency()
+    );
 
-        all_terminals.insert(
-            "konsole",
-            vec![
-                "--new-tab".to_string(),
-                "--workdir".to_string(),
-                original_dir.to_string(),
-                "-e".to_string(),
-                "./rex".to_string(),
-            ],
-        );
+    for (method, currency) in get_tx_method_currencies(conn) {
+        status.push_str(&format!("\n- {method}: {currency}"));
+    }
 
-        all_terminals.insert(
-            "gnome-terminal",
-            vec![
-                gnome_dir.clone(),
-                "--maximize".to_string(),
-                "--".to_string(),
-                "./rex".to_string(),
-            ],
-        );
+    let exchange_rates = get_exchange_rates(conn);
 
-        all_terminals.insert(
-            "kgx",
-            vec![gnome_dir, "-e".to_string(), "./rex".to_string()],
-        );
+    if !exchange_rates.is_empty() {
+        status.push_str("\n\nSaved exchange rates:\n");
+        for (from_currency, to_currency, rate) in exchange_rates {
+            status.push_str(&format!("\n- 1 {from_currency} = {rate} {to_currency}"));
+        }
+    }
 
-        let mut terminal_opened = false;
-        let mut result = None;
+    if !converter.missing_currencies().is_empty() {
+        status.push_str(&format!(
+            "\n\nNo exchange rate to {} found for: {}. These are left out of converted totals.",
+            converter.base_currency(),
+            converter.missing_currencies().join(", ")
+        ));
+    }
 
-        for (key, value) in all_terminals {
-            let status = Command::new(key).args(value).output();
-            match status {
-                Ok(out) => {
-                    if out.stderr.len() > 2 {
-                        result = Some(TerminalExecutionError::NotFound(out));
-                    } else {
-                        terminal_opened = true;
-                        break;
-                    }
-                }
-                Err(err) => result = Some(TerminalExecutionError::ExecutionFailed(err)),
-            }
-        }
-        if !terminal_opened {
-            return Err(result.unwrap());
-        }
-    };
-    Ok(())
+    status
 }
 
-/// Creates the query to search for specific tx, gathers all rows and id numbers
-pub fn get_search_data(
-    date: &str,
-    details: &str,
-    from_method: &str,
-    to_method: &str,
-    amount: &str,
-    tx_type: &str,
-    tags: &str,
-    date_type: &DateType,
-    conn: &Connection,
-) -> (Vec<Vec<String>>, Vec<String>) {
-    let mut all_txs = Vec::new();
-    let mut all_ids = Vec::new();
-
-    // This will be used for the activity tx
-    let tx_method = if tx_type == "Transfer" && !from_method.is_empty() && !to_method.is_empty() {
-        format!("{from_method} to {to_method}").trim().to_string()
-    } else if tx_type == "Transfer" && !from_method.is_empty() && to_method.is_empty() {
-        format!("{from_method} to ?").trim().to_string()
-    } else if tx_type == "Transfer" && from_method.is_empty() && !to_method.is_empty() {
-        format!("? to {to_method}").trim().to_string()
-    } else if from_method.is_empty() && to_method.is_empty() {
-        String::new()
-    } else {
-        from_method.to_string()
-    };
+/// Asks the user to select a tx method and the new currency for it
+#[cfg(not(tarpaulin_include))]
+fn get_method_currency_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
 
-    let mut valid_fields = 0;
+    clear_terminal(&mut stdout);
 
-    let mut query = "SELECT * FROM tx_all WHERE 1=1".to_string();
+    let method_currencies = get_tx_method_currencies(conn);
 
-    if !date.is_empty() {
-        valid_fields += 1;
-        match date_type {
-            DateType::Exact => query.push_str(&format!(r#" AND date = "{date}""#)),
-            DateType::Monthly => {
-                let split_date: Vec<usize> = date.split('-').map(|s| s.parse().unwrap()).collect();
+    loop {
+        let mut method_line =
+            "Select a Transaction Method to proceed. Input 'Cancel' to cancel the operation.
 
-                let month_index = split_date[1] - 1;
-                let year_index = split_date[0] - 2022;
+Currently added Transaction Methods: \n"
+                .to_string();
 
-                let (date_1, date_2) = get_sql_dates(month_index, year_index, date_type);
+        for (i, (method, currency)) in method_currencies.iter().enumerate() {
+            method_line.push_str(&format!("\n{}. {method} ({currency})", i + 1));
+        }
+        println!("{method_line}");
+        print!("\nEnter the method number to edit: ");
+        flush_output(&stdout);
 
-                query.push_str(&format!(
-                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#,
-                ));
-            }
-            DateType::Yearly => {
-                let year_index = date.parse::<usize>().unwrap() - 2022;
+        let user_input = take_input();
 
-                let (date_1, date_2) = get_sql_dates(0, year_index, date_type);
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
 
-                query.push_str(&format!(
-                    r#" AND date BETWEEN date("{date_1}") AND date("{date_2}")"#
-                ));
-            }
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
         }
-    }
 
-    if !details.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND details LIKE "%{details}%""#,));
-    }
+        let Ok(method_number) = user_input.parse::<usize>() else {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        };
 
-    if !tx_type.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND tx_type = "{tx_type}""#,));
-    }
+        if method_number == 0 || method_number > method_currencies.len() {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        }
 
-    if !amount.is_empty() {
-        valid_fields += 1;
-        let comparison_type = check_comparison(amount);
+        let (method, currency) = &method_currencies[method_number - 1];
 
-        let comparison_symbol = match comparison_type {
-            ComparisonType::BiggerThan => ">",
-            ComparisonType::SmallerThan => "<",
-            ComparisonType::Equal => "",
-            ComparisonType::EqualOrBigger => ">=",
-            ComparisonType::EqualOrSmaller => "<=",
-        };
-        let amount = amount.replace(comparison_symbol, "");
+        println!("\nSelected method: {method}. Current currency: {currency}. Enter the new 3 letter currency code.");
+        print!("New currency: ");
+        flush_output(&stdout);
 
-        query.push_str(&format!(r#" AND {comparison_type} "{amount}""#));
+        let new_currency = take_input().trim().to_uppercase();
+
+        if !is_valid_currency(&new_currency) {
+            clear_terminal(&mut stdout);
+            println!("Invalid currency. Example input: USD, EUR, BDT\n");
+            continue;
+        }
+
+        println!("\nSet the currency of {method} to {new_currency}. The saved balances will not be converted.");
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::SetTxMethodCurrency(vec![method.to_string(), new_currency]);
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for the currency that all converted totals will be shown in
+#[cfg(not(tarpaulin_include))]
+fn get_base_currency_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!(
+            "{}\n\nEnter the 3 letter currency code that totals will be converted to. Input 'Cancel' to cancel the operation.",
+            get_currency_status(conn)
+        );
+        print!("\nNew base currency: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let new_currency = user_input.trim().to_uppercase();
+
+        if !is_valid_currency(&new_currency) {
+            clear_terminal(&mut stdout);
+            println!("Invalid currency. Example input: USD, EUR, BDT\n");
+            continue;
+        }
+
+        return UserInputType::SetBaseCurrency(new_currency);
+    }
+}
+
+/// Asks the user for one or more exchange rates
+#[cfg(not(tarpaulin_include))]
+fn get_exchange_rate_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!(
+            "{}\n\nEnter one or more exchange rates. Separate multiple rates with a semicolon (;). Input 'Cancel' to cancel the operation.
+
+Format: from currency,to currency,rate
+Example input: EUR,USD,1.08; USD,BDT,119.5",
+            get_currency_status(conn)
+        );
+        print!("\nEnter exchange rates: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        // the same parser as the CSV import, one rate per line
+        let exchange_rates = match parse_exchange_rates(&user_input.replace(';', "\n")) {
+            Ok(rates) => rates,
+            Err(e) => {
+                clear_terminal(&mut stdout);
+                println!("{e}\n");
+                continue;
+            }
+        };
+
+        if exchange_rates.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        println!("\nExchange rates to save:\n");
+        for (from_currency, to_currency, rate) in &exchange_rates {
+            println!("- 1 {from_currency} = {rate} {to_currency}");
+        }
+
+        print!("\nAccept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::SetExchangeRates(exchange_rates);
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for the location of a CSV file containing exchange rates
+#[cfg(not(tarpaulin_include))]
+fn get_exchange_rate_file() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!(
+            "Enter the location of a CSV file with exchange rates. Input 'Cancel' to cancel the operation.
+
+Each line must be: from currency,to currency,rate
+A header line at the top is allowed. Existing rates of the same currency pairs will be replaced.
+
+Example line: EUR,USD,1.08"
+        );
+        print!("\nEnter file location: ");
+        flush_output(&stdout);
+
+        let given_location = take_input();
+
+        if given_location.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if given_location.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let target_path = PathBuf::from(given_location.trim());
+
+        if !target_path.is_file() {
+            clear_terminal(&mut stdout);
+            println!(
+                "The file {} was not found.\n",
+                target_path.to_string_lossy()
+            );
+            continue;
+        }
+
+        return UserInputType::ImportExchangeRates(target_path);
+    }
+}
+
+/// Asks the user for a tx method, a symbol and the quantity and cost basis of the holding
+#[cfg(not(tarpaulin_include))]
+fn get_holding_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let tx_methods = get_all_tx_methods(conn);
+    let holdings = get_all_holdings(conn);
+
+    loop {
+        let mut method_line =
+            "Select a Transaction Method to proceed. Input 'Cancel' to cancel the operation.
+
+Holdings are valued with the latest price of the symbol. Until a price is added, the cost basis is \
+used as the value.
+
+Currently added Transaction Methods: \n"
+                .to_string();
+
+        for (i, method) in tx_methods.iter().enumerate() {
+            method_line.push_str(&format!("\n{}. {method}", i + 1));
+
+            for holding in holdings.iter().filter(|h| &h.tx_method == method) {
+                method_line.push_str(&format!(
+                    "\n   - {} x {} (cost basis {})",
+                    holding.symbol, holding.quantity, holding.cost_basis
+                ));
+            }
+        }
+        println!("{method_line}");
+        print!("\nEnter the method number: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let method_number = user_input.trim().parse::<usize>().unwrap_or(0);
+
+        if method_number == 0 || method_number > tx_methods.len() {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        }
+
+        let method = &tx_methods[method_number - 1];
+
+        print!("\nSymbol. Example: AAPL, VTI, BTC: ");
+        flush_output(&stdout);
+
+        let symbol = take_input().trim().to_uppercase();
+
+        if !is_valid_symbol(&symbol) {
+            clear_terminal(&mut stdout);
+            println!("Invalid symbol. Symbols cannot be empty or contain a comma\n");
+            continue;
+        }
+
+        print!("Quantity. Input 0 to remove the holding: ");
+        flush_output(&stdout);
+
+        let quantity = match take_input().trim().parse::<f64>() {
+            Ok(quantity) if quantity.is_finite() && quantity >= 0.0 => quantity,
+            _ => {
+                clear_terminal(&mut stdout);
+                println!("Invalid quantity. Example input: 10, 2.5\n");
+                continue;
+            }
+        };
+
+        let cost_basis = if quantity == 0.0 {
+            Cent::default()
+        } else {
+            print!("Total cost basis. Example: 1500.50: ");
+            flush_output(&stdout);
+
+            match take_input().trim().parse::<Cent>() {
+                Ok(amount) if amount.value() >= 0 => amount,
+                _ => {
+                    clear_terminal(&mut stdout);
+                    println!("Invalid cost basis. Example input: 1500.50\n");
+                    continue;
+                }
+            }
+        };
+
+        if quantity == 0.0 {
+            println!("\nRemove the {symbol} holding of {method}.");
+        } else {
+            println!("\nSet the {symbol} holding of {method} to {quantity} with a cost basis of {cost_basis}.");
+        }
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::SetHolding(Holding {
+                tx_method: method.to_string(),
+                symbol,
+                quantity,
+                cost_basis,
+            });
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for one or more prices of investment symbols
+#[cfg(not(tarpaulin_include))]
+fn get_price_data() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!(
+            "Enter one or more prices. Separate multiple prices with a semicolon (;). Input 'Cancel' to cancel the operation.
+
+Format: symbol,date,price
+Example input: AAPL,2024-05-01,170.50; BTC,2024-05-01,60000"
+        );
+        print!("\nEnter prices: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        // the same parser as the CSV import, one price per line
+        let prices = match parse_prices(&user_input.replace(';', "\n")) {
+            Ok(prices) => prices,
+            Err(e) => {
+                clear_terminal(&mut stdout);
+                println!("{e}\n");
+                continue;
+            }
+        };
+
+        if prices.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        println!("\nPrices to save:\n");
+        for price in &prices {
+            println!("- {} on {}: {}", price.symbol, price.date, price.price);
+        }
+
+        print!("\nAccept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::SetPrices(prices);
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for the location of a CSV file containing prices
+#[cfg(not(tarpaulin_include))]
+fn get_price_file() -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    loop {
+        println!(
+            "Enter the location of a CSV file with prices. Input 'Cancel' to cancel the operation.
+
+Each line must be: symbol,date,price with the date as YYYY-MM-DD
+A header line at the top is allowed. Existing prices of the same symbol and date will be replaced.
+
+Example line: AAPL,2024-05-01,170.50"
+        );
+        print!("\nEnter file location: ");
+        flush_output(&stdout);
+
+        let given_location = take_input();
+
+        if given_location.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if given_location.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let target_path = PathBuf::from(given_location.trim());
+
+        if !target_path.is_file() {
+            clear_terminal(&mut stdout);
+            println!(
+                "The file {} was not found.\n",
+                target_path.to_string_lossy()
+            );
+            continue;
+        }
+
+        return UserInputType::ImportPrices(target_path);
+    }
+}
+
+/// Asks the user for an import profile and the location of a bank statement CSV. Picking a saved
+/// profile uses its values as the defaults so the column mapping of a bank only needs to be entered once
+#[cfg(not(tarpaulin_include))]
+fn get_import_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let profiles = get_import_profiles(conn);
+    let tx_methods = get_active_tx_methods(conn);
+
+    let mut profile_line = String::from(
+        "Input 'Cancel' to cancel the operation. Empty inputs use the value in the brackets.
+
+Column numbers start from 1. Date format uses chrono specifiers. Example: %d/%m/%Y, %m-%d-%Y
+With a single amount column, negative amounts are added as expenses and the rest as incomes.
+Every row is checked before anything is added. The whole import can be undone at once.
+
+Saved profiles:\n",
+    );
+
+    if profiles.is_empty() {
+        profile_line.push_str("\nNo profile has been saved yet");
+    }
+
+    for (i, profile) in profiles.iter().enumerate() {
+        profile_line.push_str(&format!("\n{}. {}", i + 1, profile.name));
+    }
+    println!("{profile_line}\n");
+
+    let check_column = |column: &str| match column.trim().parse::<usize>() {
+        Ok(column) if column > 0 => Ok(column.to_string()),
+        _ => Err(String::from("Column must be a number starting from 1")),
+    };
+
+    let Some(profile_input) = ask_until_valid(
+        "Profile number or a name for a new profile",
+        String::new(),
+        &|input| {
+            let input = input.trim();
+
+            match input.parse::<usize>() {
+                Ok(number) if number == 0 || number > profiles.len() => {
+                    Err(String::from("Invalid profile number"))
+                }
+                _ if input.is_empty() => Err(String::from("The profile name cannot be empty")),
+                _ => Ok(input.to_string()),
+            }
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let mut profile = match profile_input.parse::<usize>() {
+        Ok(number) => profiles[number - 1].clone(),
+        Err(_) => ImportProfile {
+            name: profile_input,
+            tx_method: ImportTxMethod::Fixed(tx_methods.first().cloned().unwrap_or_default()),
+            ..ImportProfile::default()
+        },
+    };
+
+    println!("\nProfile: {}\n", profile.name);
+
+    let current_delimiter = if profile.delimiter == '\t' {
+        String::from("tab")
+    } else {
+        profile.delimiter.to_string()
+    };
+
+    let Some(delimiter) = ask_until_valid(
+        "Delimiter (a character or tab)",
+        current_delimiter,
+        &|delimiter| {
+            parse_export_delimiter(delimiter)
+                .map(|_| delimiter.to_string())
+                .map_err(|e| e.to_string())
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.delimiter = parse_export_delimiter(&delimiter).unwrap();
+
+    let current_header = if profile.has_header { "y" } else { "n" };
+
+    let Some(has_header) = ask_until_valid(
+        "Is the first line a header? y/n",
+        current_header.to_string(),
+        &|answer| match answer.trim().to_lowercase().chars().next() {
+            Some('y' | 'n') => Ok(answer.trim().to_lowercase()),
+            _ => Err(String::from("Answer with y or n")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.has_header = has_header.starts_with('y');
+
+    let Some(date_column) = ask_until_valid(
+        "Date column",
+        profile.date_column.to_string(),
+        &check_column,
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.date_column = date_column.parse().unwrap();
+
+    let Some(date_format) =
+        ask_until_valid("Date format", profile.date_format.clone(), &|format| {
+            check_export_date_format(format)
+                .map(|()| format.to_string())
+                .map_err(|e| e.to_string())
+        })
+    else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.date_format = date_format;
+
+    let Some(details_column) = ask_until_valid(
+        "Details column",
+        profile.details_column.to_string(),
+        &check_column,
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.details_column = details_column.parse().unwrap();
+
+    let current_amount = match profile.amount {
+        ImportAmount::Single(column) => column.to_string(),
+        ImportAmount::DebitCredit(debit, credit) => format!("{debit},{credit}"),
+    };
+
+    let Some(amount) = ask_until_valid(
+        "Amount column, or the debit and credit columns separated by a comma",
+        current_amount,
+        &|columns| {
+            let columns = columns
+                .split(',')
+                .map(check_column)
+                .collect::<Result<Vec<String>, String>>()?;
+
+            if columns.len() > 2 {
+                return Err(String::from("Example input: 3 or 3,4"));
+            }
+            Ok(columns.join(","))
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let amount_columns = amount
+        .split(',')
+        .map(|column| column.parse().unwrap())
+        .collect::<Vec<usize>>();
+
+    profile.amount = if amount_columns.len() == 2 {
+        ImportAmount::DebitCredit(amount_columns[0], amount_columns[1])
+    } else {
+        ImportAmount::Single(amount_columns[0])
+    };
+
+    let Some(decimal_separator) = ask_until_valid(
+        "Decimal separator (. or ,)",
+        profile.decimal_separator.to_string(),
+        &|separator| match separator.trim() {
+            "." | "," => Ok(separator.trim().to_string()),
+            _ => Err(String::from("The decimal separator must be . or ,")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+    profile.decimal_separator = decimal_separator.chars().next().unwrap();
+
+    let current_method = match &profile.tx_method {
+        ImportTxMethod::Fixed(method) => method.to_string(),
+        ImportTxMethod::Column(column) => column.to_string(),
+    };
+
+    let Some(tx_method) = ask_until_valid(
+        &format!(
+            "Transaction Method of every row, or a column number to read it from. Methods: {}",
+            tx_methods.join(", ")
+        ),
+        current_method,
+        &|method| {
+            let method = method.trim();
+
+            if let Ok(column) = method.parse::<usize>() {
+                return check_column(&column.to_string());
+            }
+
+            tx_methods
+                .iter()
+                .find(|m| m.to_lowercase() == method.to_lowercase())
+                .cloned()
+                .ok_or_else(|| format!("{method} is not a Transaction Method"))
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    profile.tx_method = match tx_method.parse::<usize>() {
+        Ok(column) => ImportTxMethod::Column(column),
+        Err(_) => ImportTxMethod::Fixed(tx_method),
+    };
+
+    let Some(location) = ask_until_valid(
+        "Full location of the statement CSV file",
+        String::new(),
+        &|location| {
+            let path = PathBuf::from(location.trim());
+
+            if !path.is_file() {
+                return Err(format!("The file {} was not found", path.to_string_lossy()));
+            }
+
+            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+
+            match parse_statement(&content, &profile, conn) {
+                Ok(txs) if txs.is_empty() => {
+                    Err(String::from("The file does not have any transactions"))
+                }
+                Ok(_) => Ok(location.trim().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let target_path = PathBuf::from(location);
+
+    let content = fs::read_to_string(&target_path).unwrap_or_default();
+    let txs = parse_statement(&content, &profile, conn).unwrap_or_default();
+
+    println!(
+        "\n{} transactions will be imported and the {} profile will be saved:\n",
+        txs.len(),
+        profile.name
+    );
+
+    for tx in txs.iter().take(5) {
+        println!(
+            "- {} | {} | {} | {} | {}",
+            tx.date, tx.details, tx.tx_method, tx.amount, tx.tx_type
+        );
+    }
+
+    if txs.len() > 5 {
+        println!("- and {} more", txs.len() - 5);
+    }
+
+    print!("\nAccept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::ImportTxs(profile, target_path)
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Tries to open terminal/cmd and run this app
+/// Currently supports windows cmd, konsole, gnome-terminal, kgx (also known as gnome-console)
+#[cfg(not(tarpaulin_include))]
+pub fn start_terminal(original_dir: &str) -> Result<(), TerminalExecutionError> {
+    if cfg!(target_os = "windows") {
+        Command::new("cmd.exe")
+            .arg("start")
+            .arg("rex")
+            .output()
+            .map_err(TerminalExecutionError::ExecutionFailed)?;
+    } else {
+        let mut all_terminals = HashMap::new();
+        let gnome_dir = format!("--working-directory={original_dir}");
+
+        all_terminals.insert(
+            "konsole",
+            vec![
+                "--new-tab".to_string(),
+                "--workdir".to_string(),
+                original_dir.to_string(),
+                "-e".to_string(),
+                "./rex".to_string(),
+            ],
+        );
+
+        all_terminals.insert(
+            "gnome-terminal",
+            vec![
+                gnome_dir.clone(),
+                "--maximize".to_string(),
+                "--".to_string(),
+                "./rex".to_string(),
+            ],
+        );
+
+        all_terminals.insert(
+            "kgx",
+            vec![gnome_dir, "-e".to_string(), "./rex".to_string()],
+        );
+
+        let mut terminal_opened = false;
+        let mut result = None;
+
+        for (key, value) in all_terminals {
+            let status = Command::new(key).args(value).output();
+            match status {
+                Ok(out) => {
+                    if out.stderr.len() > 2 {
+                        result = Some(TerminalExecutionError::NotFound(out));
+                    } else {
+                        terminal_opened = true;
+                        break;
+                    }
+                }
+                Err(err) => result = Some(TerminalExecutionError::ExecutionFailed(err)),
+            }
+        }
+        if !terminal_opened {
+            return Err(result.unwrap());
+        }
+    };
+    Ok(())
+}
+
+/// Creates the query to search for specific tx, gathers all rows and id numbers
+pub fn get_search_data(
+    date: &str,
+    details: &str,
+    from_method: &str,
+    to_method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    status: &str,
+    date_type: &DateType,
+    conn: &Connection,
+) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut all_txs = Vec::new();
+    let mut all_ids = Vec::new();
+
+    // This will be used for the activity tx
+    let tx_method = if tx_type == "Transfer" && !from_method.is_empty() && !to_method.is_empty() {
+        format!("{from_method} to {to_method}").trim().to_string()
+    } else if tx_type == "Transfer" && !from_method.is_empty() && to_method.is_empty() {
+        format!("{from_method} to ?").trim().to_string()
+    } else if tx_type == "Transfer" && from_method.is_empty() && !to_method.is_empty() {
+        format!("? to {to_method}").trim().to_string()
+    } else if from_method.is_empty() && to_method.is_empty() {
+        String::new()
+    } else {
+        from_method.to_string()
+    };
+
+    let mut valid_fields = 0;
+
+    let mut query = QueryBuilder::select("*", "tx_all");
+
+    if !date.is_empty() {
+        valid_fields += 1;
+        match date_type {
+            DateType::Exact => query = query.where_cond("date = ?", [date.to_string()]),
+            DateType::Monthly => {
+                let split_date: Vec<usize> = date.split('-').map(|s| s.parse().unwrap()).collect();
+
+                let month_index = split_date[1] - 1;
+                let year = split_date[0] as i32;
+
+                let (date_1, date_2) = get_sql_dates(month_index, year, date_type);
+
+                query = query.where_cond("date BETWEEN date(?) AND date(?)", [date_1, date_2]);
+            }
+            DateType::Yearly => {
+                let year = date.parse::<i32>().unwrap();
+
+                let (date_1, date_2) = get_sql_dates(0, year, date_type);
+
+                query = query.where_cond("date BETWEEN date(?) AND date(?)", [date_1, date_2]);
+            }
+        }
+    }
+
+    if !details.is_empty() {
+        valid_fields += 1;
+        query = query.where_cond(
+            r"details LIKE ? ESCAPE '\'",
+            [format!("%{}%", escape_like(details))],
+        );
+    }
+
+    if !tx_type.is_empty() {
+        valid_fields += 1;
+        query = query.where_cond("tx_type = ?", [tx_type.to_string()]);
+    }
+
+    if !status.is_empty() {
+        valid_fields += 1;
+        query = query.where_cond("status = ?", [status.to_string()]);
+    }
+
+    let all_tags = split_tags(tags);
+
+    // The amount of split txs is checked after the query with only the split lines of the searched tags
+    let mut amount_filter = None;
+
+    if !amount.is_empty() {
+        valid_fields += 1;
+        let comparison_type = check_comparison(amount);
+
+        let comparison_symbol = match comparison_type {
+            ComparisonType::BiggerThan => ">",
+            ComparisonType::SmallerThan => "<",
+            ComparisonType::Equal => "",
+            ComparisonType::EqualOrBigger => ">=",
+            ComparisonType::EqualOrSmaller => "<=",
+        };
+        let amount = amount.replace(comparison_symbol, "");
+        let amount: Cent = amount.parse().unwrap_or_default();
+
+        if all_tags.is_empty() {
+            query = query.where_cond(&format!("{comparison_type} ?"), [amount]);
+        } else {
+            query = query.where_cond(
+                &format!("({comparison_type} ? OR id_num IN (SELECT id_num FROM tx_splits))"),
+                [amount],
+            );
+            amount_filter = Some((comparison_type, amount));
+        }
+    }
+
+    if tx_type == "Transfer" {
+        // If neither are empty, look for exact matches
+        // Otherwise do partial matching
+        if !from_method.is_empty() && !to_method.is_empty() {
+            valid_fields += 1;
+            query = query.where_cond("tx_method = ?", [format!("{from_method} to {to_method}")]);
+        } else if !from_method.is_empty() {
+            valid_fields += 1;
+            query = query.where_cond(
+                r"tx_method LIKE ? ESCAPE '\'",
+                [format!("{} to %", escape_like(from_method))],
+            );
+        } else if !to_method.is_empty() {
+            valid_fields += 1;
+            query = query.where_cond(
+                r"tx_method LIKE ? ESCAPE '\'",
+                [format!("% to {}", escape_like(to_method))],
+            );
+        }
+    } else if tx_type != "Transfer" && !from_method.is_empty() {
+        valid_fields += 1;
+        query = query.where_cond("tx_method = ?", [from_method.to_string()]);
+    }
+
+    if !tags.is_empty() {
+        valid_fields += 1;
+
+        // Txs that have any of the given tags or any child of them
+        if !all_tags.is_empty() {
+            let tag_conditions =
+                vec![r"tags.name = ? OR tags.name LIKE ? ESCAPE '\'"; all_tags.len()].join(" OR ");
+
+            let tag_values = all_tags.iter().flat_map(|tag| {
+                [
+                    tag.to_string(),
+                    format!("{}{TAG_SEPARATOR}%", escape_like(tag)),
+                ]
+            });
+
+            query = query.where_cond(
+                &format!(
+                    "id_num IN (SELECT tx_tags.id_num FROM tx_tags
+                    JOIN tags ON tags.tag_id = tx_tags.tag_id WHERE {tag_conditions})"
+                ),
+                tag_values,
+            );
+        }
+    }
+
+    let (query, values) = query.build();
+
+    let mut statement = conn.prepare(&query).unwrap();
+
+    let rows = statement
+        .query_map(params_from_iter(values), |row| {
+            let date = reverse_date_format(row.get(0).unwrap());
+            let id_num: i32 = row.get(5).unwrap();
+            let amount: Cent = row.get(3).unwrap();
+
+            Ok(vec![
+                date,
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                amount.to_string(),
+                row.get(4).unwrap(),
+                row.get(6).unwrap(),
+                row.get(7).unwrap(),
+                id_num.to_string(),
+            ])
+        })
+        .unwrap();
+
+    for i in rows.flatten() {
+        let mut data = i;
+        let id_num = data.pop().unwrap();
+
+        // A split tx only counts the amount of the split lines that have the searched tags
+        if !all_tags.is_empty() {
+            let splits = get_tx_splits(id_num.parse().unwrap(), conn);
+
+            if !splits.is_empty() {
+                let split_amount: Cent = splits
+                    .iter()
+                    .filter(|split| {
+                        all_tags
+                            .iter()
+                            .any(|tag| is_in_tag_subtree(&split.tag, tag))
+                    })
+                    .map(|split| split.amount)
+                    .sum();
+
+                if let Some((comparison_type, amount)) = &amount_filter {
+                    let is_match = match comparison_type {
+                        ComparisonType::Equal => split_amount == *amount,
+                        ComparisonType::BiggerThan => split_amount > *amount,
+                        ComparisonType::SmallerThan => split_amount < *amount,
+                        ComparisonType::EqualOrBigger => split_amount >= *amount,
+                        ComparisonType::EqualOrSmaller => split_amount <= *amount,
+                    };
+
+                    if !is_match {
+                        continue;
+                    }
+                }
+
+                data[3] = split_amount.to_string();
+            }
+        }
+
+        all_ids.push(id_num);
+        all_txs.push(data);
+    }
+
+    let activity_type = ActivityType::SearchTX(Some(valid_fields));
+    let search_data = vec![
+        date, details, &tx_method, amount, tx_type, tags, "", "", status,
+    ];
+
+    let activity_num = add_new_activity(activity_type, conn);
+    add_new_activity_tx(&search_data, activity_num, conn);
+
+    (all_txs, all_ids)
+}
+
+/// Switches `id_num` of 2 txs in the DB to switch the indexes of the value in the UI table
+pub fn switch_tx_index(
+    id_1: i32,
+    id_2: i32,
+    tx_1: &[String],
+    tx_2: &[String],
+    conn: &mut Connection,
+) {
+    let tx_type_1 = &tx_1[4];
+    let tx_type_2 = &tx_2[4];
+
+    let mut tx_data_1 = if tx_type_1 == "Transfer" {
+        let split_method = tx_1[2].split(" to ").collect::<Vec<&str>>();
+        let from_method = split_method[0];
+        let to_method = split_method[1];
+
+        TxData::custom(
+            &tx_1[0],
+            &tx_1[1],
+            from_method,
+            to_method,
+            &tx_1[3],
+            "Transfer",
+            &tx_1[5],
+            id_1,
+        )
+    } else {
+        TxData::custom(
+            &tx_1[0], &tx_1[1], &tx_1[2], "", &tx_1[3], &tx_1[4], &tx_1[5], id_1,
+        )
+    };
+
+    let mut tx_data_2 = if tx_type_2 == "Transfer" {
+        let split_method = tx_2[2].split(" to ").collect::<Vec<&str>>();
+        let from_method = split_method[0];
+        let to_method = split_method[1];
+
+        TxData::custom(
+            &tx_2[0],
+            &tx_2[1],
+            from_method,
+            to_method,
+            &tx_2[3],
+            "Transfer",
+            &tx_2[5],
+            id_2,
+        )
+    } else {
+        TxData::custom(
+            &tx_2[0], &tx_2[1], &tx_2[2], "", &tx_2[3], &tx_2[4], &tx_2[5], id_2,
+        )
+    };
+
+    // The split lines and the status get deleted with the txs so they are kept to add them back
+    tx_data_1.load_splits(conn);
+    tx_data_2.load_splits(conn);
+    tx_data_1.load_status(conn);
+    tx_data_2.load_status(conn);
+
+    delete_tx(id_1, conn).unwrap();
+    delete_tx(id_2, conn).unwrap();
+
+    let activity_num = add_new_activity(ActivityType::IDNumSwap(Some(id_1), Some(id_2)), conn);
+
+    tx_data_1.switch_tx_id(id_2, activity_num, conn);
+    tx_data_2.switch_tx_id(id_1, activity_num, conn);
+}
+
+/// Returns all activities recorded within a given month and a year and all the activity txs related to the activities
+pub fn get_all_activities(
+    month: usize,
+    year: usize,
+    conn: &Connection,
+) -> (Vec<ActivityDetails>, HashMap<i32, Vec<ActivityTx>>) {
+    let (datetime_1, datetime_2) =
+        get_sql_dates(month, get_year_from_index(year, conn), &DateType::Monthly);
+
+    let mut statement = conn
+        .prepare("SELECT * from activities WHERE date BETWEEN date(?) AND date(?)")
+        .unwrap();
+
+    // Activity tx fetching happens based on the minimum activity num and the maximum activity num
+    let mut min_activity_num = i32::MAX;
+    let mut max_activity_num = i32::MIN;
+
+    let rows: Vec<ActivityDetails> = statement
+        .query_map([datetime_1, datetime_2], |row| {
+            let date = reverse_date_format(row.get(0).unwrap());
+            Ok(ActivityDetails::new(
+                date,
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                row.get(3).unwrap(),
+            ))
+        })
+        .unwrap()
+        .map(|wrapped_data| {
+            let data = wrapped_data.unwrap();
+            let id = data.activity_num();
+
+            if id > max_activity_num {
+                max_activity_num = id;
+            }
+
+            if id < min_activity_num {
+                min_activity_num = id;
+            }
+            data
+        })
+        .collect();
+
+    let activity_txs = get_all_activity_txs(min_activity_num, max_activity_num, conn);
+
+    (rows, activity_txs)
+}
+
+/// Returns all activity txs within the given activity number range
+pub fn get_all_activity_txs(
+    min_num: i32,
+    max_num: i32,
+    conn: &Connection,
+) -> HashMap<i32, Vec<ActivityTx>> {
+    let mut statement = conn
+        .prepare("SELECT * from activity_txs WHERE activity_num >= ? AND activity_num <= ?")
+        .unwrap();
+
+    // Contains data in the format {activity_num: Vec<ActivityTx>}
+    // In case of search or edit txs, a single activity can impact multiple txs
+    // The rest will always have only 1 tx
+    let mut activity_tx_data = HashMap::new();
+
+    for wrapped_data in statement
+        .query_map([min_num, max_num], |row| {
+            let date = reverse_date_format(row.get(0).unwrap());
+            Ok(ActivityTx::new(
+                date,
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                row.get(3).unwrap(),
+                row.get(4).unwrap(),
+                row.get(5).unwrap(),
+                row.get(6).unwrap(),
+                row.get(7).unwrap(),
+                row.get(8).unwrap(),
+            ))
+        })
+        .unwrap()
+    {
+        let data = wrapped_data.unwrap();
+        activity_tx_data
+            .entry(data.activity_num())
+            .or_insert(Vec::new())
+            .push(data);
+    }
+
+    activity_tx_data
+}
+
+/// Asks the user for a tx method to delete. Methods that are still used by any tx cannot be selected
+#[cfg(not(tarpaulin_include))]
+fn get_delete_method_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let tx_methods = get_all_tx_methods(conn);
+    let active_methods = get_active_tx_methods(conn);
+
+    loop {
+        let mut method_line =
+            "Select a Transaction Method to delete. Input 'Cancel' to cancel the operation.
+
+Currently added Transaction Methods: \n"
+                .to_string();
+
+        for (i, item) in tx_methods.iter().enumerate() {
+            let total_txs = get_tx_method_usage(item, conn).unwrap_or_default();
+            method_line.push_str(&format!("\n{}. {item} ({total_txs} transactions)", i + 1));
+        }
+        println!("{method_line}");
+        print!("\nEnter the method number to delete: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let Ok(method_number) = user_input.parse::<usize>() else {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        };
+
+        if method_number == 0 || method_number > tx_methods.len() {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        }
+
+        let method = &tx_methods[method_number - 1];
+        let total_txs = get_tx_method_usage(method, conn).unwrap_or_default();
+
+        if total_txs > 0 {
+            clear_terminal(&mut stdout);
+            println!("{total_txs} transactions still use {method}. Reassign them to a different Transaction Method before deleting.\n");
+            continue;
+        }
+
+        if active_methods == [method.as_str()] {
+            clear_terminal(&mut stdout);
+            println!("{method} is the only active Transaction Method and cannot be deleted.\n");
+            continue;
+        }
+
+        println!("\nDelete {method}? This cannot be undone.");
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::DeleteTxMethod(method.to_string());
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for a tx method to archive. Selecting an archived method unarchives it
+#[cfg(not(tarpaulin_include))]
+fn get_archive_method_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let tx_methods = get_all_tx_methods(conn);
+    let archived_methods = get_archived_tx_methods(conn);
+    let active_methods = get_active_tx_methods(conn);
+
+    loop {
+        let mut method_line =
+            "Select a Transaction Method to archive or unarchive. Archived methods are hidden from the Home and Add Transaction page. Input 'Cancel' to cancel the operation.
+
+Currently added Transaction Methods: \n"
+                .to_string();
+
+        for (i, item) in tx_methods.iter().enumerate() {
+            if archived_methods.contains(item) {
+                method_line.push_str(&format!("\n{}. {item} (archived)", i + 1));
+            } else {
+                method_line.push_str(&format!("\n{}. {item}", i + 1));
+            }
+        }
+        println!("{method_line}");
+        print!("\nEnter the method number to edit: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let Ok(method_number) = user_input.parse::<usize>() else {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        };
+
+        if method_number == 0 || method_number > tx_methods.len() {
+            clear_terminal(&mut stdout);
+            println!("Invalid method number. Example input: 1\n");
+            continue;
+        }
+
+        let method = &tx_methods[method_number - 1];
+        let to_archive = !archived_methods.contains(method);
+
+        if to_archive && active_methods == [method.as_str()] {
+            clear_terminal(&mut stdout);
+            println!("{method} is the only active Transaction Method and cannot be archived.\n");
+            continue;
+        }
+
+        if to_archive {
+            println!("\nArchive {method}? Its transactions will stay in search and activities.");
+        } else {
+            println!("\nUnarchive {method}?");
+        }
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::ArchiveTxMethod(method.to_string(), to_archive);
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user for a tx method to merge and the tx method to merge it into
+#[cfg(not(tarpaulin_include))]
+fn get_merge_method_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let method_currencies = get_tx_method_currencies(conn);
+
+    loop {
+        let mut method_line =
+            "Select the Transaction Method to merge and the Transaction Method to merge it into. Input 'Cancel' to cancel the operation.
+
+Example input: 2 1
+
+Currently added Transaction Methods: \n"
+                .to_string();
+
+        for (i, (method, currency)) in method_currencies.iter().enumerate() {
+            method_line.push_str(&format!("\n{}. {method} ({currency})", i + 1));
+        }
+        println!("{method_line}");
+        print!("\nEnter the method numbers: ");
+        flush_output(&stdout);
+
+        let user_input = take_input();
+
+        if user_input.is_empty() {
+            clear_terminal(&mut stdout);
+            continue;
+        }
+
+        if user_input.trim().to_lowercase().starts_with("cancel") {
+            return UserInputType::CancelledOperation;
+        }
+
+        let method_numbers = user_input
+            .split_whitespace()
+            .map(str::parse::<usize>)
+            .collect::<Result<Vec<usize>, _>>();
+
+        let Ok([from_number, to_number]) = method_numbers.as_deref() else {
+            clear_terminal(&mut stdout);
+            println!("Invalid method numbers. Example input: 2 1\n");
+            continue;
+        };
+
+        let valid_number = |number: usize| number != 0 && number <= method_currencies.len();
+
+        if !valid_number(*from_number) || !valid_number(*to_number) || from_number == to_number {
+            clear_terminal(&mut stdout);
+            println!("Invalid method numbers. Example input: 2 1\n");
+            continue;
+        }
+
+        let (from_method, from_currency) = &method_currencies[from_number - 1];
+        let (to_method, to_currency) = &method_currencies[to_number - 1];
+
+        if from_currency != to_currency {
+            clear_terminal(&mut stdout);
+            println!("Transaction Methods with different currencies cannot be merged.\n");
+            continue;
+        }
+
+        let total_txs = get_tx_method_usage(from_method, conn).unwrap_or_default();
+        let self_transfers: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM tx_all WHERE tx_method = ?1 || ' to ' || ?2 OR tx_method = ?2 || ' to ' || ?1",
+                [from_method, to_method],
+                |row| row.get(0),
+            )
+            .unwrap_or_default();
+
+        println!("\nMerge {from_method} into {to_method}. {total_txs} transactions will be moved to {to_method} and {from_method} will be deleted.");
+
+        if self_transfers > 0 {
+            println!(
+                "{self_transfers} transfers between {from_method} and {to_method} will be deleted."
+            );
+        }
+
+        println!(
+            "The merge cannot be undone and the activities before it can no longer be undone."
+        );
+
+        print!("Accept the values? y/n: ");
+        flush_output(&stdout);
+
+        let confirm_operation = take_input();
+
+        if confirm_operation.to_lowercase().starts_with('y') {
+            return UserInputType::MergeTxMethod(vec![
+                from_method.to_string(),
+                to_method.to_string(),
+            ]);
+        }
+        clear_terminal(&mut stdout);
+    }
+}
+
+/// Asks the user to confirm replacing all saved balances and changes with the ones calculated from the transactions
+#[cfg(not(tarpaulin_include))]
+fn get_rebuild_confirmation() -> UserInputType {
+    let stdout = stdout();
+
+    println!("\nAll saved balances and changes will be recalculated from the transactions. Use option 13 first to see what will change.");
+    print!("Proceed? y/n: ");
+    flush_output(&stdout);
+
+    let confirm_operation = take_input();
+
+    if confirm_operation.to_lowercase().starts_with('y') {
+        UserInputType::RebuildLedger
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Asks the user for the new name of the tag selected on the Tags page
+#[cfg(not(tarpaulin_include))]
+pub fn get_tag_rename_data(tag: &str, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let all_tags = get_all_tags(conn);
+
+    println!(
+        "Input 'Cancel' to cancel the operation. Empty inputs use the value in the brackets.
+
+Renaming the tag {tag} in every transaction, budget and goal that uses it\n"
+    );
+
+    let Some(new_tag) = ask_until_valid("New name", tag.to_string(), &|new_tag| {
+        let new_tag = check_tag_name(new_tag).map_err(|e| e.to_string())?;
+
+        if new_tag == tag {
+            return Err(format!("The tag is already named {tag}"));
+        }
+
+        if all_tags.iter().any(|saved_tag| saved_tag == &new_tag) {
+            return Err(format!(
+                "{new_tag} already exists. Merge the tags with M on the Tags page instead"
+            ));
+        }
+
+        Ok(new_tag)
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    println!("\nRename {tag} to {new_tag} in all transactions.");
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::RenameTag(vec![tag.to_string(), new_tag])
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Asks the user for the tag the tag selected on the Tags page will be merged into
+#[cfg(not(tarpaulin_include))]
+pub fn get_tag_merge_data(tag: &str, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let other_tags = get_all_tags(conn)
+        .into_iter()
+        .filter(|saved_tag| saved_tag != tag)
+        .collect::<Vec<String>>();
+
+    println!(
+        "Input 'Cancel' to cancel the operation.
+
+Merging the tag {tag} into another tag. Every transaction, budget and goal using {tag} moves to it
+
+Currently added tags: {}\n",
+        other_tags.join(", ")
+    );
+
+    let Some(to_tag) = ask_until_valid("Merge into", String::new(), &|to_tag| {
+        let to_tag = check_tag_name(to_tag).map_err(|e| e.to_string())?;
+
+        // Use the saved casing of the tag
+        other_tags
+            .iter()
+            .find(|saved_tag| saved_tag.eq_ignore_ascii_case(&to_tag))
+            .cloned()
+            .ok_or(format!("Tag {to_tag} does not exist"))
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    println!("\nReplace {tag} with {to_tag} in all transactions.");
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::MergeTags(vec![tag.to_string()], to_tag)
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Asks the user for the data of a recurring tx. Editing an existing recurring tx shows its current values
+/// and an empty input keeps the current value
+#[cfg(not(tarpaulin_include))]
+pub fn get_recurring_tx_data(recurring_id: Option<i32>, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let tx_methods = get_all_tx_methods(conn);
+    let today = Local::now().date_naive();
+
+    let existing_tx = recurring_id.and_then(|id| {
+        get_all_recurring_txs(conn)
+            .into_iter()
+            .find(|recurring_tx| recurring_tx.recurring_id == id)
+    });
+
+    let mut recurring_tx = existing_tx.unwrap_or_else(|| RecurringTx {
+        recurring_id: 0,
+        details: String::new(),
+        tx_method: String::new(),
+        amount: Cent::default(),
+        tx_type: String::new(),
+        tags: String::new(),
+        schedule: RecurringSchedule::parse("monthly").unwrap(),
+        start_date: today,
+        end_date: None,
+        last_posted: None,
+        paused: false,
+    });
+
+    let is_new = recurring_id.is_none();
+
+    println!(
+        "Input 'Cancel' to cancel the operation. {}
+
+Currently added Transaction Methods: {}
+Schedule examples: daily, weekly, monthly, yearly, every 2 weeks, monthly on day 15\n",
+        if is_new {
+            "Empty inputs use the value in the brackets."
+        } else {
+            "Empty inputs keep the current value in the brackets."
+        },
+        tx_methods.join(", ")
+    );
+
+    let check_method = |method: &str| {
+        tx_methods
+            .iter()
+            .find(|tx_method| tx_method.eq_ignore_ascii_case(method.trim()))
+            .cloned()
+            .ok_or(format!("{method} is not a Transaction Method"))
+    };
+
+    let check_date = |date: &str| match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+        Ok(date) if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) => {
+            Ok(date.to_string())
+        }
+        _ => Err(format!(
+            "Dates must be YYYY-MM-DD between {MIN_YEAR} and {MAX_YEAR}"
+        )),
+    };
+
+    let Some(details) = ask_until_valid("Details", recurring_tx.details.clone(), &|details| {
+        Ok(details.trim().to_string())
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(tx_type) = ask_until_valid(
+        "TX Type (Income, Expense or Transfer)",
+        recurring_tx.tx_type.clone(),
+        &|tx_type| match tx_type.trim().to_lowercase().as_str() {
+            "i" | "income" => Ok(String::from("Income")),
+            "e" | "expense" => Ok(String::from("Expense")),
+            "t" | "transfer" => Ok(String::from("Transfer")),
+            _ => Err(String::from("TX Type must be Income, Expense or Transfer")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let (current_from, current_to) = match recurring_tx.tx_method.split_once(" to ") {
+        Some((from_method, to_method)) => (from_method.to_string(), to_method.to_string()),
+        None => (recurring_tx.tx_method.clone(), String::new()),
+    };
+
+    let tx_method = if tx_type == "Transfer" {
+        let Some(from_method) = ask_until_valid("From Method", current_from, &check_method) else {
+            return UserInputType::CancelledOperation;
+        };
+
+        let currencies: HashMap<String, String> =
+            get_tx_method_currencies(conn).into_iter().collect();
+
+        let Some(to_method) = ask_until_valid("To Method", current_to, &|method| {
+            let method = check_method(method)?;
+            if method == from_method {
+                return Err(String::from("From and To Method cannot be the same"));
+            }
+            if currencies.get(&method) != currencies.get(&from_method) {
+                return Err(String::from(
+                    "From and To Method must use the same currency",
+                ));
+            }
+            Ok(method)
+        }) else {
+            return UserInputType::CancelledOperation;
+        };
+
+        format!("{from_method} to {to_method}")
+    } else {
+        let Some(method) = ask_until_valid("TX Method", current_from, &check_method) else {
+            return UserInputType::CancelledOperation;
+        };
+        method
+    };
+
+    let current_amount = if is_new {
+        String::new()
+    } else {
+        recurring_tx.amount.to_string()
+    };
+
+    let Some(amount) = ask_until_valid("Amount", current_amount, &|amount| match amount
+        .trim()
+        .parse::<Cent>()
+    {
+        Ok(cent) if cent > Cent::default() => Ok(cent.to_string()),
+        _ => Err(String::from("Amount must be a number bigger than 0")),
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(tags) = ask_until_valid(
+        "Tags separated by a comma",
+        recurring_tx.tags.clone(),
+        &|tags| {
+            let all_tags = split_tags(tags);
+            if all_tags
+                .iter()
+                .any(|tag| tag.contains([SPLIT_FIELD_SEPARATOR, SPLIT_LINE_SEPARATOR]))
+            {
+                return Err(format!(
+                    "Tags cannot contain {SPLIT_FIELD_SEPARATOR} or {SPLIT_LINE_SEPARATOR}"
+                ));
+            }
+            if all_tags.is_empty() {
+                return Ok(String::from("Unknown"));
+            }
+            Ok(all_tags.join(", "))
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(schedule) =
+        ask_until_valid("Schedule", recurring_tx.schedule.to_string(), &|schedule| {
+            RecurringSchedule::parse(schedule)
+                .map(|schedule| schedule.to_string())
+                .ok_or(String::from(
+                    "Invalid schedule. Example: monthly, every 2 weeks, monthly on day 15",
+                ))
+        })
+    else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(start_date) = ask_until_valid(
+        "Start date (YYYY-MM-DD)",
+        recurring_tx.start_date.to_string(),
+        &check_date,
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_end = recurring_tx
+        .end_date
+        .map_or(String::from("none"), |date| date.to_string());
+
+    let Some(end_date) = ask_until_valid("End date (YYYY-MM-DD or none)", current_end, &|date| {
+        if date.trim().eq_ignore_ascii_case("none") {
+            return Ok(String::from("none"));
+        }
+        let date = check_date(date)?;
+        if date < start_date {
+            return Err(String::from("End date cannot be before the start date"));
+        }
+        Ok(date)
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    recurring_tx.details = details;
+    recurring_tx.tx_type = tx_type;
+    recurring_tx.tx_method = tx_method;
+    recurring_tx.amount = amount.parse().unwrap();
+    recurring_tx.tags = tags;
+    recurring_tx.schedule = RecurringSchedule::parse(&schedule).unwrap();
+    recurring_tx.start_date = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d").unwrap();
+    recurring_tx.end_date = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d").ok();
+
+    let next_date = recurring_tx
+        .get_next_date()
+        .map_or(String::from("never"), |date| date.to_string());
+
+    println!(
+        "\n{} {} {} {} with {}. Tags: {}. Schedule: {}. Next date: {next_date}",
+        recurring_tx.tx_type,
+        recurring_tx.amount,
+        recurring_tx.details,
+        if recurring_tx.tx_type == "Transfer" {
+            "from"
+        } else {
+            "on"
+        },
+        recurring_tx.tx_method,
+        recurring_tx.tags,
+        recurring_tx.schedule,
+    );
+
+    let total_due = recurring_tx.get_due_dates(today).len();
+
+    if total_due > 0 {
+        println!("{total_due} transactions are already due and will be added after saving.");
+    }
+
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::SaveRecurringTx(recurring_tx)
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Asks the user for the data of a budget. Editing an existing budget shows its current values
+/// and an empty input keeps the current value
+#[cfg(not(tarpaulin_include))]
+pub fn get_budget_data(budget_id: Option<i32>, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let all_budgets = get_all_budgets(conn);
+    let today = Local::now().date_naive();
+
+    let mut budget = all_budgets
+        .iter()
+        .find(|budget| Some(budget.budget_id) == budget_id)
+        .cloned()
+        .unwrap_or_else(|| Budget {
+            budget_id: 0,
+            tag: String::new(),
+            period: BudgetPeriod::Monthly,
+            amount: Cent::default(),
+            rollover: false,
+            start_date: today.with_day(1).unwrap(),
+        });
+
+    let is_new = budget_id.is_none();
+
+    // Parent tags can have a budget even if no tx uses them directly
+    let mut all_tags: Vec<String> = Vec::new();
+    for tag in get_all_tags(conn) {
+        for tree_tag in get_parent_tags(&tag).into_iter().chain([tag]) {
+            if !all_tags.contains(&tree_tag) {
+                all_tags.push(tree_tag);
+            }
+        }
+    }
+    all_tags.sort();
+
+    println!(
+        "Input 'Cancel' to cancel the operation. {}
+
+Currently used Tags: {}
+A budget of a parent tag such as Food also counts the expenses of Food:Groceries\n",
+        if is_new {
+            "Empty inputs use the value in the brackets."
+        } else {
+            "Empty inputs keep the current value in the brackets."
+        },
+        all_tags.join(", ")
+    );
+
+    let Some(tag) = ask_until_valid("Tag", budget.tag.clone(), &|tag| {
+        let tag = check_tag_name(tag).map_err(|e| e.to_string())?;
+        // Use the saved casing of the tag if it exists
+        Ok(all_tags
+            .iter()
+            .find(|saved_tag| saved_tag.eq_ignore_ascii_case(&tag))
+            .cloned()
+            .unwrap_or(tag))
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(period) = ask_until_valid(
+        "Period (Monthly or Yearly)",
+        budget.period.to_str().to_string(),
+        &|period| {
+            let period = BudgetPeriod::from_s(period)
+                .ok_or(String::from("Period must be Monthly or Yearly"))?;
+
+            let exists = all_budgets.iter().any(|saved_budget| {
+                saved_budget.tag == tag
+                    && saved_budget.period == period
+                    && Some(saved_budget.budget_id) != budget_id
+            });
+
+            if exists {
+                return Err(format!(
+                    "{tag} already has a {} budget",
+                    period.to_str().to_lowercase()
+                ));
+            }
+            Ok(period.to_str().to_string())
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_amount = if is_new {
+        String::new()
+    } else {
+        budget.amount.to_string()
+    };
+
+    let Some(amount) = ask_until_valid("Amount", current_amount, &|amount| match amount
+        .trim()
+        .parse::<Cent>()
+    {
+        Ok(cent) if cent > Cent::default() => Ok(cent.to_string()),
+        _ => Err(String::from("Amount must be a number bigger than 0")),
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_rollover = if budget.rollover { "y" } else { "n" };
+
+    let Some(rollover) = ask_until_valid(
+        "Roll the unspent amount over to the next period? y/n",
+        current_rollover.to_string(),
+        &|rollover| match rollover.trim().to_lowercase().as_str() {
+            "y" | "yes" => Ok(String::from("y")),
+            "n" | "no" => Ok(String::from("n")),
+            _ => Err(String::from("Input must be y or n")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(start_date) = ask_until_valid(
+        "Start month (YYYY-MM)",
+        budget.start_date.format("%Y-%m").to_string(),
+        &|month| match NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d") {
+            Ok(date)
+                if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) =>
+            {
+                Ok(date.to_string())
+            }
+            _ => Err(format!(
+                "Start month must be YYYY-MM between {MIN_YEAR} and {MAX_YEAR}"
+            )),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    budget.tag = tag;
+    budget.period = BudgetPeriod::from_s(&period).unwrap();
+    budget.amount = amount.parse().unwrap();
+    budget.rollover = rollover == "y";
+    budget.start_date = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d").unwrap();
+
+    println!(
+        "\n{} budget of {} for {} starting from {}. Rollover: {}",
+        budget.period.to_str(),
+        budget.amount,
+        budget.tag,
+        budget.start_date.format("%Y-%m"),
+        if budget.rollover { "Yes" } else { "No" },
+    );
+
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::SaveBudget(budget)
+    } else {
+        UserInputType::CancelledOperation
+    }
+}
+
+/// Asks the user for the data of a goal. Editing an existing goal shows its current values
+/// and an empty input keeps the current value
+#[cfg(not(tarpaulin_include))]
+pub fn get_goal_data(goal_id: Option<i32>, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
+
+    clear_terminal(&mut stdout);
+
+    let all_goals = get_all_goals(conn);
+    let all_tx_methods = get_all_tx_methods(conn);
+
+    let mut goal = all_goals
+        .iter()
+        .find(|goal| Some(goal.goal_id) == goal_id)
+        .cloned()
+        .unwrap_or_else(|| Goal {
+            goal_id: 0,
+            name: String::new(),
+            target: Cent::default(),
+            target_date: Local::now().date_naive(),
+            source: GoalSource::TxMethod(all_tx_methods[0].clone()),
+        });
+
+    let is_new = goal_id.is_none();
+
+    // A goal can track a parent tag even if no tx uses it directly
+    let mut all_tags: Vec<String> = Vec::new();
+    for tag in get_all_tags(conn) {
+        for tree_tag in get_parent_tags(&tag).into_iter().chain([tag]) {
+            if !all_tags.contains(&tree_tag) {
+                all_tags.push(tree_tag);
+            }
+        }
     }
+    all_tags.sort();
+
+    println!(
+        "Input 'Cancel' to cancel the operation. {}
+
+Currently added Tx Methods: {}
+Currently used Tags: {}
+A goal tracks the balance of a tx method or the total of the txs of a tag and its child tags\n",
+        if is_new {
+            "Empty inputs use the value in the brackets."
+        } else {
+            "Empty inputs keep the current value in the brackets."
+        },
+        all_tx_methods.join(", "),
+        all_tags.join(", ")
+    );
+
+    let Some(name) = ask_until_valid("Name", goal.name.clone(), &|name| {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(String::from("Name cannot be empty"));
+        }
+
+        let exists = all_goals
+            .iter()
+            .any(|saved_goal| saved_goal.name == name && Some(saved_goal.goal_id) != goal_id);
+
+        if exists {
+            return Err(format!("A goal named {name} already exists"));
+        }
+        Ok(name.to_string())
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_target = if is_new {
+        String::new()
+    } else {
+        goal.target.to_string()
+    };
+
+    let Some(target) = ask_until_valid("Target amount", current_target, &|amount| match amount
+        .trim()
+        .parse::<Cent>()
+    {
+        Ok(cent) if cent > Cent::default() => Ok(cent.to_string()),
+        _ => Err(String::from("Target amount must be a number bigger than 0")),
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_target_date = if is_new {
+        String::new()
+    } else {
+        goal.target_date.to_string()
+    };
+
+    // A month without a day targets the last day of the month
+    let Some(target_date) = ask_until_valid(
+        "Target date (YYYY-MM-DD or YYYY-MM)",
+        current_target_date,
+        &|date| {
+            let date = date.trim();
+            let parsed_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").or_else(|_| {
+                NaiveDate::parse_from_str(&format!("{date}-01"), "%Y-%m-%d")
+                    .map(|date| date + chrono::Months::new(1) - chrono::Days::new(1))
+            });
+
+            match parsed_date {
+                Ok(date)
+                    if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) =>
+                {
+                    Ok(date.to_string())
+                }
+                _ => Err(format!(
+                    "Target date must be YYYY-MM-DD or YYYY-MM between {MIN_YEAR} and {MAX_YEAR}"
+                )),
+            }
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let current_kind = match goal.source {
+        GoalSource::TxMethod(_) => "m",
+        GoalSource::Tag(_) => "t",
+    };
+
+    let Some(kind) = ask_until_valid(
+        "Track a tx method balance or a tag total? m/t",
+        current_kind.to_string(),
+        &|kind| match kind.trim().to_lowercase().as_str() {
+            "m" | "method" | "tx method" => Ok(String::from("m")),
+            "t" | "tag" => Ok(String::from("t")),
+            _ => Err(String::from("Input must be m or t")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    // Only keep the current source as the default if it is of the selected kind
+    let current_source = match (&goal.source, kind.as_str()) {
+        (GoalSource::TxMethod(method), "m") => method.clone(),
+        (GoalSource::Tag(tag), "t") => tag.clone(),
+        _ => String::new(),
+    };
+
+    let source = if kind == "m" {
+        let Some(method) = ask_until_valid("Tx Method", current_source, &|method| {
+            all_tx_methods
+                .iter()
+                .find(|saved_method| saved_method.eq_ignore_ascii_case(method.trim()))
+                .cloned()
+                .ok_or(format!("Tx Method {} does not exist", method.trim()))
+        }) else {
+            return UserInputType::CancelledOperation;
+        };
+        GoalSource::TxMethod(method)
+    } else {
+        let Some(tag) = ask_until_valid("Tag", current_source, &|tag| {
+            let tag = check_tag_name(tag).map_err(|e| e.to_string())?;
+            // Use the saved casing of the tag if it exists
+            Ok(all_tags
+                .iter()
+                .find(|saved_tag| saved_tag.eq_ignore_ascii_case(&tag))
+                .cloned()
+                .unwrap_or(tag))
+        }) else {
+            return UserInputType::CancelledOperation;
+        };
+        GoalSource::Tag(tag)
+    };
 
-    if tx_type == "Transfer" {
-        // If neither are empty, look for exact matches
-        // Otherwise do partial matching
-        if !from_method.is_empty() && !to_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(
-                r#" AND tx_method = "{from_method} to {to_method}""#
-            ));
-        } else if !from_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(r#" AND tx_method LIKE "{from_method} to %" "#));
-        } else if !to_method.is_empty() {
-            valid_fields += 1;
-            query.push_str(&format!(r#" AND tx_method LIKE "% to {to_method}""#));
-        }
-    } else if tx_type != "Transfer" && !from_method.is_empty() {
-        valid_fields += 1;
-        query.push_str(&format!(r#" AND tx_method = "{from_method}""#));
-    }
+    goal.name = name;
+    goal.target = target.parse().unwrap();
+    goal.target_date = NaiveDate::parse_from_str(&target_date, "%Y-%m-%d").unwrap();
+    goal.source = source;
+
+    println!(
+        "\nGoal {}: {} by {} tracking {} {}",
+        goal.name,
+        goal.target,
+        goal.target_date,
+        goal.source.kind(),
+        goal.source.name(),
+    );
 
-    if !tags.is_empty() {
-        valid_fields += 1;
-        let all_tags = tags.split(", ");
-        let tag_conditions = all_tags
-            .map(|tag| {
-                format!(
-                    r#"CASE 
-                          WHEN tags LIKE "{tag}, %" THEN 1
-                          WHEN tags LIKE "%, {tag}" THEN 1
-                          WHEN tags LIKE "%, {tag}," THEN 1
-                          WHEN tags = "{tag}" THEN 1
-                          ELSE 0
-                      END = 1"#,
-                )
-            })
-            .collect::<Vec<String>>()
-            .join(" OR ");
-        query.push_str(&format!(" AND ({tag_conditions})"));
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::SaveGoal(goal)
+    } else {
+        UserInputType::CancelledOperation
     }
+}
 
-    let mut statement = conn.prepare(&query).unwrap();
+/// Asks the user for the tx method, the date and the balance of a bank statement to start reconciling
+#[cfg(not(tarpaulin_include))]
+pub fn get_reconcile_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
 
-    let rows = statement
-        .query_map([], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            let id_num: i32 = row.get(5).unwrap();
+    clear_terminal(&mut stdout);
 
-            Ok(vec![
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                row.get(6).unwrap(),
-                id_num.to_string(),
-            ])
+    let all_tx_methods = get_active_tx_methods(conn);
+    let today = Local::now().date_naive();
+
+    println!(
+        "Input 'Cancel' to cancel the operation. Empty inputs use the value in the brackets.
+
+Currently added Tx Methods: {}
+Pick the date and the closing balance of the bank statement. Transactions up to the date can then be \
+ticked on the Reconcile page until the difference reaches 0\n",
+        all_tx_methods.join(", ")
+    );
+
+    let Some(tx_method) = ask_until_valid("Tx Method", all_tx_methods[0].clone(), &|method| {
+        all_tx_methods
+            .iter()
+            .find(|saved_method| saved_method.eq_ignore_ascii_case(method.trim()))
+            .cloned()
+            .ok_or(format!("Tx Method {} does not exist", method.trim()))
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(statement_date) =
+        ask_until_valid("Statement date (YYYY-MM-DD)", today.to_string(), &|date| {
+            match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+                Ok(date)
+                    if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) =>
+                {
+                    Ok(date.to_string())
+                }
+                _ => Err(format!(
+                    "Statement date must be YYYY-MM-DD between {MIN_YEAR} and {MAX_YEAR}"
+                )),
+            }
         })
-        .unwrap();
+    else {
+        return UserInputType::CancelledOperation;
+    };
 
-    for i in rows.flatten() {
-        let mut data = i;
-        let id_num = &data.pop().unwrap();
-        all_ids.push(id_num.to_string());
-        all_txs.push(data);
-    }
+    let statement_date = NaiveDate::parse_from_str(&statement_date, "%Y-%m-%d").unwrap();
 
-    let activity_type = ActivityType::SearchTX(Some(valid_fields));
-    let search_data = vec![date, details, &tx_method, amount, tx_type, tags, ""];
+    let mut reconciliation = Reconciliation {
+        tx_method,
+        statement_date,
+        statement_balance: Cent::default(),
+    };
 
-    let activity_num = add_new_activity(activity_type, conn);
-    add_new_activity_tx(&search_data, activity_num, conn);
+    let balance = get_balance_on(&reconciliation.tx_method, statement_date, conn)
+        .expect("could not get the balance");
+    let reconciled_balance = get_reconciled_balance(&reconciliation, conn)
+        .expect("could not get the reconciled balance");
 
-    (all_txs, all_ids)
+    println!(
+        "\nBalance of {} on {statement_date}: {balance}. Reconciled balance: {reconciled_balance}\n",
+        reconciliation.tx_method
+    );
+
+    let Some(statement_balance) =
+        ask_until_valid("Statement balance", balance.to_string(), &|amount| {
+            amount
+                .trim()
+                .parse::<Cent>()
+                .map(|cent| cent.to_string())
+                .map_err(|_| String::from("Statement balance must be a number"))
+        })
+    else {
+        return UserInputType::CancelledOperation;
+    };
+
+    reconciliation.statement_balance = statement_balance.parse().unwrap();
+
+    println!(
+        "\nReconcile {} up to {} with statement balance {}",
+        reconciliation.tx_method, reconciliation.statement_date, reconciliation.statement_balance
+    );
+
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::StartReconciliation(reconciliation)
+    } else {
+        UserInputType::CancelledOperation
+    }
 }
 
-/// Switches `id_num` of 2 txs in the DB to switch the indexes of the value in the UI table
-pub fn switch_tx_index(
-    id_1: i32,
-    id_2: i32,
-    tx_1: &[String],
-    tx_2: &[String],
-    conn: &mut Connection,
-) {
-    let tx_type_1 = &tx_1[4];
-    let tx_type_2 = &tx_2[4];
+/// Asks the user for the person, the action, the tx method, the date, the amount and the details
+/// of a debt tx
+#[cfg(not(tarpaulin_include))]
+pub fn get_debt_tx_data(conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
 
-    let tx_data_1 = if tx_type_1 == "Transfer" {
-        let split_method = tx_1[2].split(" to ").collect::<Vec<&str>>();
-        let from_method = split_method[0];
-        let to_method = split_method[1];
+    clear_terminal(&mut stdout);
 
-        TxData::custom(
-            &tx_1[0],
-            &tx_1[1],
-            from_method,
-            to_method,
-            &tx_1[3],
-            "Transfer",
-            &tx_1[5],
-            id_1,
-        )
+    let all_tx_methods = get_active_tx_methods(conn);
+    let counterparties = get_all_counterparties(conn).expect("could not get the counterparties");
+    let today = Local::now().date_naive();
+
+    let people = if counterparties.is_empty() {
+        String::from("None")
     } else {
-        TxData::custom(
-            &tx_1[0], &tx_1[1], &tx_1[2], "", &tx_1[3], &tx_1[4], &tx_1[5], id_1,
-        )
+        counterparties
+            .iter()
+            .map(|counterparty| counterparty.name.clone())
+            .collect::<Vec<String>>()
+            .join(", ")
     };
 
-    let tx_data_2 = if tx_type_2 == "Transfer" {
-        let split_method = tx_2[2].split(" to ").collect::<Vec<&str>>();
-        let from_method = split_method[0];
-        let to_method = split_method[1];
+    println!(
+        "Input 'Cancel' to cancel the operation. Empty inputs use the value in the brackets.
 
-        TxData::custom(
-            &tx_2[0],
-            &tx_2[1],
-            from_method,
-            to_method,
-            &tx_2[3],
-            "Transfer",
-            &tx_2[5],
-            id_2,
-        )
+Currently added Tx Methods: {}
+People with debt transactions: {people}
+Lend takes the money out of the Tx Method, Borrow adds it. Repay pays back what is outstanding with the person\n",
+        all_tx_methods.join(", ")
+    );
+
+    let Some(counterparty) = ask_until_valid("Person", String::new(), &|name| {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(String::from("The name of the person cannot be empty"));
+        }
+
+        // Existing people are matched without case so their txs stay together
+        Ok(counterparties
+            .iter()
+            .find(|counterparty| counterparty.name.eq_ignore_ascii_case(name))
+            .map_or(name.to_string(), |counterparty| counterparty.name.clone()))
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let outstanding = get_counterparty_outstanding(&counterparty, conn)
+        .expect("could not get the outstanding balance");
+
+    match outstanding.value() {
+        0 => println!("\nNothing is outstanding with {counterparty}\n"),
+        value if value > 0 => println!("\n{counterparty} owes you {outstanding}\n"),
+        _ => println!("\nYou owe {counterparty} {}\n", -outstanding),
+    }
+
+    let Some(action) = ask_until_valid(
+        "Action (Lend/Borrow/Repay)",
+        String::from("Lend"),
+        &|action| match DebtAction::from_s(action) {
+            Some(DebtAction::Lend) => Ok(String::from("Lend")),
+            Some(DebtAction::Borrow) => Ok(String::from("Borrow")),
+            Some(DebtAction::Repay) if outstanding.is_zero() => Err(format!(
+                "Nothing is outstanding with {counterparty}. There is nothing to repay"
+            )),
+            Some(DebtAction::Repay) => Ok(String::from("Repay")),
+            None => Err(String::from("Action must be Lend, Borrow or Repay")),
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let action = DebtAction::from_s(&action).unwrap();
+
+    let Some(tx_method) = ask_until_valid("Tx Method", all_tx_methods[0].clone(), &|method| {
+        all_tx_methods
+            .iter()
+            .find(|saved_method| saved_method.eq_ignore_ascii_case(method.trim()))
+            .cloned()
+            .ok_or(format!("Tx Method {} does not exist", method.trim()))
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(date) = ask_until_valid("Date (YYYY-MM-DD)", today.to_string(), &|date| {
+        match NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") {
+            Ok(date)
+                if date.year() >= i32::from(MIN_YEAR) && date.year() <= i32::from(MAX_YEAR) =>
+            {
+                Ok(date.to_string())
+            }
+            _ => Err(format!(
+                "Date must be YYYY-MM-DD between {MIN_YEAR} and {MAX_YEAR}"
+            )),
+        }
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    // A repayment defaults to settling everything that is outstanding
+    let default_amount = if action == DebtAction::Repay {
+        outstanding.abs().to_string()
     } else {
-        TxData::custom(
-            &tx_2[0], &tx_2[1], &tx_2[2], "", &tx_2[3], &tx_2[4], &tx_2[5], id_1,
-        )
+        String::new()
     };
 
-    delete_tx(id_1, conn).unwrap();
-    delete_tx(id_2, conn).unwrap();
+    let Some(amount) = ask_until_valid("Amount", default_amount, &|amount| match amount
+        .trim()
+        .parse::<Cent>()
+    {
+        Ok(cent) if cent.value() <= 0 => Err(String::from("Amount must be more than 0")),
+        Ok(cent) if action == DebtAction::Repay && cent > outstanding.abs() => Err(format!(
+            "Only {} is outstanding with {counterparty}",
+            outstanding.abs()
+        )),
+        Ok(cent) => Ok(cent.to_string()),
+        Err(_) => Err(String::from("Amount must be a number")),
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
 
-    let activity_num = add_new_activity(ActivityType::IDNumSwap(Some(id_1), Some(id_2)), conn);
+    let Some(details) = ask_until_valid("Details", String::new(), &|details| {
+        Ok(details.trim().to_string())
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
 
-    tx_data_1.switch_tx_id(id_2, activity_num, conn);
-    tx_data_2.switch_tx_id(id_1, activity_num, conn);
+    let debt_tx = NewDebtTx {
+        counterparty,
+        action,
+        date: NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap(),
+        tx_method,
+        amount: amount.parse().unwrap(),
+        details,
+    };
+
+    println!(
+        "\n{:?} {} with {} from {} on {}",
+        debt_tx.action, debt_tx.amount, debt_tx.counterparty, debt_tx.tx_method, debt_tx.date
+    );
+
+    print!("Accept the values? y/n: ");
+    flush_output(&stdout);
+
+    if take_input().to_lowercase().starts_with('y') {
+        UserInputType::AddDebtTx(debt_tx)
+    } else {
+        UserInputType::CancelledOperation
+    }
 }
 
-/// Returns all activities recorded within a given month and a year and all the activity txs related to the activities
-pub fn get_all_activities(
-    month: usize,
-    year: usize,
-    conn: &Connection,
-) -> (Vec<ActivityDetails>, HashMap<i32, Vec<ActivityTx>>) {
-    let (datetime_1, datetime_2) = get_sql_dates(month, year, &DateType::Monthly);
+/// Asks the user for the date range, the columns, the delimiter, the date format and the location of
+/// a CSV export. The date range is skipped when the current search result is being exported
+#[cfg(not(tarpaulin_include))]
+pub fn get_export_data(tx_ids: Option<Vec<String>>, conn: &Connection) -> UserInputType {
+    let mut stdout = stdout();
 
-    let mut statement = conn
-        .prepare("SELECT * from activities WHERE date BETWEEN date(?) AND date(?)")
-        .unwrap();
+    clear_terminal(&mut stdout);
 
-    // Activity tx fetching happens based on the minimum activity num and the maximum activity num
-    let mut min_activity_num = i32::MAX;
-    let mut max_activity_num = i32::MIN;
+    let default_options = ExportOptions::default();
+    let (start_year, end_year) = get_year_range(conn).expect("could not get the year range");
 
-    let rows: Vec<ActivityDetails> = statement
-        .query_map([datetime_1, datetime_2], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            Ok(ActivityDetails::new(
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-            ))
-        })
-        .unwrap()
-        .map(|wrapped_data| {
-            let data = wrapped_data.unwrap();
-            let id = data.activity_num();
+    let exporting = match tx_ids {
+        Some(ref ids) => format!("Exporting {} transactions of the search result", ids.len()),
+        None => String::from("Exporting the transactions within a date range"),
+    };
 
-            if id > max_activity_num {
-                max_activity_num = id;
-            }
+    println!(
+        "Input 'Cancel' to cancel the operation. Empty inputs use the value in the brackets.
 
-            if id < min_activity_num {
-                min_activity_num = id;
-            }
-            data
-        })
-        .collect();
+{exporting}
+Columns: {}. Balance adds the running balance of every Tx Method
+Date format uses chrono specifiers. Example: %d-%m-%Y, %Y/%m/%d\n",
+        EXPORT_COLUMNS.join(", ")
+    );
 
-    let activity_txs = get_all_activity_txs(min_activity_num, max_activity_num, conn);
+    let check_date = |date: &str| {
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map(|date| date.to_string())
+            .map_err(|_| String::from("Date must be YYYY-MM-DD"))
+    };
 
-    (rows, activity_txs)
-}
+    let source = if let Some(ids) = tx_ids {
+        ExportSource::TxIds(ids)
+    } else {
+        let Some(from_date) = ask_until_valid(
+            "From date (YYYY-MM-DD)",
+            format!("{start_year}-01-01"),
+            &check_date,
+        ) else {
+            return UserInputType::CancelledOperation;
+        };
 
-/// Returns all activity txs within the given activity number range
-pub fn get_all_activity_txs(
-    min_num: i32,
-    max_num: i32,
-    conn: &Connection,
-) -> HashMap<i32, Vec<ActivityTx>> {
-    let mut statement = conn
-        .prepare("SELECT * from activity_txs WHERE activity_num >= ? AND activity_num <= ?")
-        .unwrap();
+        let Some(to_date) = ask_until_valid(
+            "To date (YYYY-MM-DD)",
+            format!("{end_year}-12-31"),
+            &check_date,
+        ) else {
+            return UserInputType::CancelledOperation;
+        };
 
-    // Contains data in the format {activity_num: Vec<ActivityTx>}
-    // In case of search or edit txs, a single activity can impact multiple txs
-    // The rest will always have only 1 tx
-    let mut activity_tx_data = HashMap::new();
+        ExportSource::DateRange(
+            NaiveDate::parse_from_str(&from_date, "%Y-%m-%d").unwrap(),
+            NaiveDate::parse_from_str(&to_date, "%Y-%m-%d").unwrap(),
+        )
+    };
 
-    for wrapped_data in statement
-        .query_map([min_num, max_num], |row| {
-            let date = reverse_date_format(row.get(0).unwrap());
-            Ok(ActivityTx::new(
-                date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                row.get(5).unwrap(),
-                row.get(6).unwrap(),
-                row.get(7).unwrap(),
-                row.get(8).unwrap(),
-            ))
+    let Some(columns) = ask_until_valid("Columns", EXPORT_COLUMNS.join(","), &|columns| {
+        parse_export_columns(columns)
+            .map(|_| columns.to_string())
+            .map_err(|e| e.to_string())
+    }) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(delimiter) = ask_until_valid(
+        "Delimiter (a character or tab)",
+        default_options.delimiter.to_string(),
+        &|delimiter| {
+            parse_export_delimiter(delimiter)
+                .map(|_| delimiter.to_string())
+                .map_err(|e| e.to_string())
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    let Some(date_format) =
+        ask_until_valid("Date format", default_options.date_format, &|format| {
+            check_export_date_format(format)
+                .map(|()| format.to_string())
+                .map_err(|e| e.to_string())
         })
-        .unwrap()
-    {
-        let data = wrapped_data.unwrap();
-        activity_tx_data
-            .entry(data.activity_num())
-            .or_insert(Vec::new())
-            .push(data);
-    }
+    else {
+        return UserInputType::CancelledOperation;
+    };
 
-    activity_tx_data
+    let Some(output) = ask_until_valid(
+        "Full file location to save the CSV",
+        String::new(),
+        &|location| {
+            let location = location.trim();
+
+            if location.is_empty() {
+                return Err(String::from("The file location cannot be empty"));
+            }
+
+            let path = PathBuf::from(location);
+
+            match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => Err(format!(
+                    "The folder {} was not found",
+                    parent.to_string_lossy()
+                )),
+                _ => Ok(location.to_string()),
+            }
+        },
+    ) else {
+        return UserInputType::CancelledOperation;
+    };
+
+    UserInputType::ExportTxs(ExportCommand {
+        source,
+        options: ExportOptions {
+            columns: parse_export_columns(&columns).unwrap(),
+            delimiter: parse_export_delimiter(&delimiter).unwrap(),
+            date_format,
+        },
+        output: PathBuf::from(output),
+    })
 }
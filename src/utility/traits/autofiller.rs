@@ -1,9 +1,9 @@
-use crate::utility::{get_all_details, get_all_tags, get_all_tx_methods, get_best_match};
+use crate::utility::{get_active_tx_methods, get_all_details, get_all_tags, get_best_match};
 use rusqlite::Connection;
 
 pub trait AutoFiller {
     fn autofill_tx_method(&self, user_input: &str, conn: &Connection) -> String {
-        let all_tx_methods = get_all_tx_methods(conn);
+        let all_tx_methods = get_active_tx_methods(conn);
         if !user_input.trim().is_empty() && !all_tx_methods.is_empty() {
             let best_match = get_best_match(user_input, &all_tx_methods);
 
@@ -1,7 +1,8 @@
+use crate::db::{DEFAULT_TX_STATUS, MAX_YEAR, MIN_YEAR, SETTABLE_TX_STATUSES, TX_STATUSES};
 use crate::outputs::{NAType, StepType, SteppingError, VerifyingOutput};
 use crate::page_handler::DateType;
 use crate::utility::traits::DataVerifier;
-use crate::utility::{get_all_tags, get_all_tx_methods};
+use crate::utility::{get_active_tx_methods, get_all_tags, Cent};
 use chrono::{Duration, NaiveDate};
 use rusqlite::Connection;
 
@@ -22,14 +23,14 @@ pub trait FieldStepper: DataVerifier {
                     match step_type {
                         StepType::StepUp => {
                             let final_date =
-                                NaiveDate::parse_from_str("2037-12-31", "%Y-%m-%d").unwrap();
+                                NaiveDate::from_ymd_opt(MAX_YEAR.into(), 12, 31).unwrap();
                             if current_date != final_date {
                                 current_date += Duration::days(1);
                             }
                         }
                         StepType::StepDown => {
                             let final_date =
-                                NaiveDate::parse_from_str("2022-01-01", "%Y-%m-%d").unwrap();
+                                NaiveDate::from_ymd_opt(MIN_YEAR.into(), 1, 1).unwrap();
                             if current_date != final_date {
                                 current_date -= Duration::days(1);
                             }
@@ -50,7 +51,7 @@ pub trait FieldStepper: DataVerifier {
                         StepType::StepUp => {
                             if month < 12 {
                                 month += 1;
-                            } else if month == 12 && year != 2037 {
+                            } else if month == 12 && year != MAX_YEAR {
                                 month = 1;
                                 year += 1;
                             }
@@ -58,7 +59,7 @@ pub trait FieldStepper: DataVerifier {
                         StepType::StepDown => {
                             if month > 1 {
                                 month -= 1;
-                            } else if month == 1 && year != 2022 {
+                            } else if month == 1 && year != MIN_YEAR {
                                 month = 12;
                                 year -= 1;
                             }
@@ -70,12 +71,12 @@ pub trait FieldStepper: DataVerifier {
                     let mut int_year: u16 = user_date.parse().unwrap();
                     match step_type {
                         StepType::StepUp => {
-                            if int_year != 2037 {
+                            if int_year != MAX_YEAR {
                                 int_year += 1;
                             }
                         }
                         StepType::StepDown => {
-                            if int_year != 2022 {
+                            if int_year != MIN_YEAR {
                                 int_year -= 1;
                             }
                         }
@@ -105,23 +106,28 @@ pub trait FieldStepper: DataVerifier {
         step_type: StepType,
         conn: &Connection,
     ) -> Result<(), SteppingError> {
-        let all_methods = get_all_tx_methods(conn);
+        let all_methods = get_active_tx_methods(conn);
         let verify_status: VerifyingOutput = self.verify_tx_method(user_method, conn);
 
         match verify_status {
             VerifyingOutput::Accepted(_) => {
-                let current_method_index =
-                    all_methods.iter().position(|e| e == user_method).unwrap();
-
-                let next_method_index = match step_type {
-                    StepType::StepUp => (current_method_index + 1) % all_methods.len(),
-                    StepType::StepDown => {
-                        if current_method_index == 0 {
-                            all_methods.len() - 1
-                        } else {
-                            (current_method_index - 1) % all_methods.len()
+                // Archived methods are accepted but not stepped through.
+                // Stepping from one goes to the first or the last active method
+                let next_method_index = match all_methods.iter().position(|e| e == user_method) {
+                    Some(current_method_index) => match step_type {
+                        StepType::StepUp => (current_method_index + 1) % all_methods.len(),
+                        StepType::StepDown => {
+                            if current_method_index == 0 {
+                                all_methods.len() - 1
+                            } else {
+                                (current_method_index - 1) % all_methods.len()
+                            }
                         }
-                    }
+                    },
+                    None => match step_type {
+                        StepType::StepUp => 0,
+                        StepType::StepDown => all_methods.len() - 1,
+                    },
                 };
                 *user_method = String::from(&all_methods[next_method_index]);
             }
@@ -147,22 +153,23 @@ pub trait FieldStepper: DataVerifier {
 
         match verify_status {
             VerifyingOutput::Accepted(_) => {
-                let mut current_amount: f64 = user_amount.parse().unwrap();
+                let mut current_amount: Cent = user_amount.parse().unwrap();
+                let step = Cent::new(100);
 
                 match step_type {
                     StepType::StepUp => {
-                        if 9_999_999_999.99 >= current_amount + 1.0 {
-                            current_amount += 1.0;
+                        if Cent::new(999_999_999_999) >= current_amount + step {
+                            current_amount += step;
                         }
                     }
                     StepType::StepDown => {
-                        if (current_amount - 1.0) >= 0.00 {
-                            current_amount -= 1.0;
+                        if current_amount - step >= Cent::default() {
+                            current_amount -= step;
                         }
                     }
                 }
 
-                *user_amount = format!("{current_amount:.2}");
+                *user_amount = current_amount.to_string();
             }
             VerifyingOutput::NotAccepted(err_type) => match err_type {
                 // if value went below 0, make it 1
@@ -217,6 +224,44 @@ pub trait FieldStepper: DataVerifier {
         }
     }
 
+    fn step_tx_status(
+        &self,
+        user_status: &mut String,
+        step_type: StepType,
+        is_search: bool,
+    ) -> Result<(), SteppingError> {
+        let verify_status = self.verify_tx_status(user_status, is_search);
+
+        let statuses = if is_search {
+            TX_STATUSES.as_slice()
+        } else {
+            SETTABLE_TX_STATUSES.as_slice()
+        };
+
+        match verify_status {
+            VerifyingOutput::NotAccepted(_) => Err(SteppingError::InvalidTxStatus),
+            VerifyingOutput::Nothing(_) => {
+                *user_status = DEFAULT_TX_STATUS.to_string();
+                Ok(())
+            }
+            VerifyingOutput::Accepted(_) => {
+                let mut current_index = statuses
+                    .iter()
+                    .position(|status| status == user_status)
+                    .unwrap();
+
+                match step_type {
+                    StepType::StepUp => current_index = (current_index + 1) % statuses.len(),
+                    StepType::StepDown => {
+                        current_index = (current_index + statuses.len() - 1) % statuses.len();
+                    }
+                }
+                *user_status = statuses[current_index].to_string();
+                Ok(())
+            }
+        }
+    }
+
     fn step_tags(
         &self,
         user_tag: &mut String,
@@ -1,6 +1,10 @@
+use crate::db::{
+    splits_to_text, TxSplit, MAX_YEAR, MIN_YEAR, OPENING_BALANCE_TX_TYPE, SETTABLE_TX_STATUSES,
+    SPLIT_FIELD_SEPARATOR, SPLIT_LINE_SEPARATOR, TX_STATUSES,
+};
 use crate::outputs::{AType, NAType, VerifyingOutput};
 use crate::page_handler::DateType;
-use crate::utility::{get_all_tags, get_all_tx_methods, get_best_match};
+use crate::utility::{get_all_tags, get_all_tx_methods, get_best_match, Cent};
 use chrono::naive::NaiveDate;
 use rusqlite::Connection;
 use std::cmp::Ordering;
@@ -9,7 +13,7 @@ use std::collections::HashSet;
 pub trait DataVerifier {
     /// Checks if:
     ///
-    /// - the inputted year is between 2022 to 2037
+    /// - the inputted year is between `MIN_YEAR` to `MAX_YEAR`
     /// - the inputted month is between 01 to 12
     /// - the inputted date is between 01 to 31
     /// - the inputted date is empty
@@ -178,23 +182,23 @@ pub trait DataVerifier {
             }
         }
 
-        // checks if the year value is between 2022 and 2037
-        if !(2022..=2037).contains(&int_year) {
-            if int_year < 2022 {
+        // checks if the year value is between MIN_YEAR and MAX_YEAR
+        if !(MIN_YEAR..=MAX_YEAR).contains(&int_year) {
+            if int_year < MIN_YEAR {
                 match date_type {
                     DateType::Exact => {
-                        *user_date = format!("2022-{}-{}", split_date[1], split_date[2]);
+                        *user_date = format!("{MIN_YEAR}-{}-{}", split_date[1], split_date[2]);
                     }
-                    DateType::Monthly => *user_date = format!("2022-{}", split_date[1]),
-                    DateType::Yearly => *user_date = "2022".to_string(),
+                    DateType::Monthly => *user_date = format!("{MIN_YEAR}-{}", split_date[1]),
+                    DateType::Yearly => *user_date = MIN_YEAR.to_string(),
                 }
-            } else if int_year > 2037 {
+            } else if int_year > MAX_YEAR {
                 match date_type {
                     DateType::Exact => {
-                        *user_date = format!("2037-{}-{}", split_date[1], split_date[2]);
+                        *user_date = format!("{MAX_YEAR}-{}-{}", split_date[1], split_date[2]);
                     }
-                    DateType::Monthly => *user_date = format!("2037-{}", split_date[1]),
-                    DateType::Yearly => *user_date = "2037".to_string(),
+                    DateType::Monthly => *user_date = format!("{MAX_YEAR}-{}", split_date[1]),
+                    DateType::Yearly => *user_date = MAX_YEAR.to_string(),
                 }
             }
 
@@ -266,7 +270,6 @@ pub trait DataVerifier {
     /// - removes any extra spaces and non-numeric characters
     ///
     /// if the value is not float, tries to make it float ending with double zero
-
     fn verify_amount(&self, user_amount: &mut String) -> VerifyingOutput {
         // cancel all verification if the amount is empty
         if user_amount.is_empty() {
@@ -346,7 +349,7 @@ pub trait DataVerifier {
                             }
                         } else {
                             // if both value is intact, do the calculation and the result is for replacement
-                            let first_num: f64 = match first_value.parse() {
+                            let first_num: Cent = match first_value.parse() {
                                 Ok(v) => v,
                                 Err(_) => {
                                     return VerifyingOutput::NotAccepted(NAType::ParsingError(
@@ -355,7 +358,7 @@ pub trait DataVerifier {
                                 }
                             };
 
-                            let last_num: f64 = match last_value.parse() {
+                            let last_num: Cent = match last_value.parse() {
                                 Ok(v) => v,
                                 Err(_) => {
                                     return VerifyingOutput::NotAccepted(NAType::ParsingError(
@@ -364,12 +367,26 @@ pub trait DataVerifier {
                                 }
                             };
 
+                            // addition and subtraction stay exact. Multiplication and division
+                            // go through a float and get rounded back to the nearest cent
+                            let calculated = match *symbol {
+                                '*' => first_num.as_f64() * last_num.as_f64(),
+                                '/' => first_num.as_f64() / last_num.as_f64(),
+                                '+' => (first_num + last_num).as_f64(),
+                                '-' => (first_num - last_num).as_f64(),
+                                _ => unreachable!(),
+                            };
+
+                            if !calculated.is_finite() {
+                                return VerifyingOutput::NotAccepted(NAType::ParsingError(
+                                    AType::Amount,
+                                ));
+                            }
+
                             match *symbol {
-                                '*' => format!("{:.2}", (first_num * last_num)),
-                                '/' => format!("{:.2}", (first_num / last_num)),
-                                '+' => format!("{:.2}", (first_num + last_num)),
-                                '-' => format!("{:.2}", (first_num - last_num)),
-                                _ => String::new(),
+                                '+' => (first_num + last_num).to_string(),
+                                '-' => (first_num - last_num).to_string(),
+                                _ => Cent::from_f64(calculated).to_string(),
                             }
                         };
 
@@ -397,13 +414,13 @@ pub trait DataVerifier {
             *user_amount = format!("{user_amount}.00");
         }
 
-        let float_amount: f64 = match user_amount.parse() {
+        let cent_amount: Cent = match user_amount.parse() {
             Ok(v) => v,
             Err(_) => return VerifyingOutput::NotAccepted(NAType::ParsingError(AType::Amount)),
         };
 
-        if float_amount <= 0.0 {
-            *user_amount = format!("{:.2}", (float_amount - (float_amount * 2.0)));
+        if cent_amount <= Cent::default() {
+            *user_amount = (-cent_amount).to_string();
             return VerifyingOutput::NotAccepted(NAType::AmountBelowZero);
         }
 
@@ -440,7 +457,6 @@ pub trait DataVerifier {
     ///
     /// if the Transaction is not found, matches each character with the available
     /// Transaction Methods and corrects to the best matching one.
-
     fn verify_tx_method(&self, user_method: &mut String, conn: &Connection) -> VerifyingOutput {
         // get all currently added tx methods
         let all_tx_methods = get_all_tx_methods(conn);
@@ -467,15 +483,16 @@ pub trait DataVerifier {
 
     /// Checks if:
     ///
-    /// - The transaction method starts with E, I or T
+    /// - The transaction method starts with E, I, T or O
     ///
-    /// Auto expands E to Expense, I to Income and T to transfer.
+    /// Auto expands E to Expense, I to Income, T to transfer and O to Opening Balance.
     fn verify_tx_type(&self, user_type: &mut String) -> VerifyingOutput {
         *user_type = user_type.replace(' ', "");
 
         if user_type.is_empty() {
             return VerifyingOutput::Nothing(AType::TxType);
         }
+
         if user_type.to_lowercase().starts_with('e') {
             *user_type = "Expense".to_string();
             VerifyingOutput::Accepted(AType::TxType)
@@ -485,12 +502,47 @@ pub trait DataVerifier {
         } else if user_type.to_lowercase().starts_with('t') {
             *user_type = "Transfer".to_string();
             VerifyingOutput::Accepted(AType::TxType)
+        } else if user_type.to_lowercase().starts_with('o') {
+            *user_type = OPENING_BALANCE_TX_TYPE.to_string();
+            VerifyingOutput::Accepted(AType::TxType)
         } else {
             *user_type = String::new();
             VerifyingOutput::NotAccepted(NAType::InvalidTxType)
         }
     }
 
+    /// Checks if:
+    ///
+    /// - The tx status starts with P, C or R. R is only accepted for searching
+    ///
+    /// Auto expands P to Pending, C to Cleared and R to Reconciled.
+    fn verify_tx_status(&self, user_status: &mut String, is_search: bool) -> VerifyingOutput {
+        *user_status = user_status.replace(' ', "");
+
+        if user_status.is_empty() {
+            return VerifyingOutput::Nothing(AType::Status);
+        }
+
+        let first_letter = user_status.to_lowercase().chars().next().unwrap();
+
+        let statuses = if is_search {
+            TX_STATUSES.as_slice()
+        } else {
+            SETTABLE_TX_STATUSES.as_slice()
+        };
+
+        if let Some(status) = statuses
+            .iter()
+            .find(|status| status.to_lowercase().starts_with(first_letter))
+        {
+            *user_status = status.to_string();
+            VerifyingOutput::Accepted(AType::Status)
+        } else {
+            *user_status = String::new();
+            VerifyingOutput::NotAccepted(NAType::InvalidTxStatus)
+        }
+    }
+
     /// Checks if:
     ///
     /// - All tags inserted is unique and is properly separated by commas
@@ -545,4 +597,59 @@ pub trait DataVerifier {
             VerifyingOutput::NotAccepted(NAType::NonExistingTag)
         }
     }
+
+    /// Checks if:
+    ///
+    /// - Every split line has a tag and an amount separated by `|` with an optional note after them
+    /// - The tag of every split line is a valid tag
+    /// - The amount of every split line is a valid amount
+    ///
+    /// Amounts are verified the same way as the Amount field so calculations are accepted
+    fn verify_splits(&self, user_splits: &mut String) -> VerifyingOutput {
+        if user_splits.trim().is_empty() {
+            user_splits.clear();
+            return VerifyingOutput::Nothing(AType::Splits);
+        }
+
+        let mut all_splits = Vec::new();
+
+        for line in user_splits.split(SPLIT_LINE_SEPARATOR).map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields = line
+                .splitn(3, SPLIT_FIELD_SEPARATOR)
+                .map(str::trim)
+                .collect::<Vec<&str>>();
+
+            if fields.len() < 2 || fields[0].is_empty() || fields[0].contains(',') {
+                return VerifyingOutput::NotAccepted(NAType::InvalidSplit);
+            }
+
+            let mut amount = fields[1].to_string();
+
+            let VerifyingOutput::Accepted(_) = self.verify_amount(&mut amount) else {
+                return VerifyingOutput::NotAccepted(NAType::InvalidSplit);
+            };
+
+            let Ok(amount) = amount.parse() else {
+                return VerifyingOutput::NotAccepted(NAType::InvalidSplit);
+            };
+
+            all_splits.push(TxSplit {
+                tag: fields[0].to_string(),
+                amount,
+                note: fields.get(2).unwrap_or(&"").to_string(),
+            });
+        }
+
+        *user_splits = splits_to_text(&all_splits);
+
+        if all_splits.is_empty() {
+            VerifyingOutput::Nothing(AType::Splits)
+        } else {
+            VerifyingOutput::Accepted(AType::Splits)
+        }
+    }
 }
@@ -20,18 +20,23 @@ use std::time::Duration;
 use std::{process, thread};
 use strsim::normalized_levenshtein;
 
-use crate::db::{add_tags_column, create_db, migrate_to_activities, update_balance_type, YEARS};
+use crate::db::{
+    add_opening_balances, create_db, get_pending_migrations, get_tx_splits, run_migrations,
+    splits_to_text, QueryBuilder, BORROW_TX_TYPE, DEFAULT_TX_METHOD_KIND, DEFAULT_TX_STATUS,
+    LEND_TX_TYPE, LIABILITY_TX_METHOD_KIND, OPENING_BALANCE_TX_TYPE, REPAY_IN_TX_TYPE,
+    REPAY_OUT_TX_TYPE, TAG_SEPARATOR,
+};
 use crate::outputs::ComparisonType;
 use crate::page_handler::{
     ActivityType, DateType, IndexedData, SortingType, UserInputType, BACKGROUND, BOX, HIGHLIGHTED,
     RED, TEXT,
 };
-use crate::utility::get_user_tx_methods;
+use crate::utility::{get_user_tx_methods, Cent};
 
 const RESTRICTED: [&str; 6] = ["Total", "Balance", "Changes", "Income", "Expense", "Cancel"];
 
 /// Makes a call to the database to find out all the columns in the `balance_all` section
-/// so we can determine the number of TX Methods that has been added.
+/// so we can determine the number of TX Methods that has been added. Archived methods are included.
 /// return example: `["source_1", "source_2", "source_3"]`
 pub fn get_all_tx_methods(conn: &Connection) -> Vec<String> {
     // returns all transaction methods added to the database
@@ -48,6 +53,101 @@ pub fn get_all_tx_methods(conn: &Connection) -> Vec<String> {
     data
 }
 
+/// Returns the archived tx methods. These are hidden from the Home and Add TX page but their txs are kept
+pub fn get_archived_tx_methods(conn: &Connection) -> Vec<String> {
+    let mut statement = conn
+        .prepare("SELECT name FROM tx_methods WHERE archived = 1")
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|data| data.unwrap())
+        .collect()
+}
+
+/// Returns all tx methods that are not archived in the order of the `balance_all` columns
+pub fn get_active_tx_methods(conn: &Connection) -> Vec<String> {
+    let archived_methods = get_archived_tx_methods(conn);
+
+    get_all_tx_methods(conn)
+        .into_iter()
+        .filter(|method| !archived_methods.contains(method))
+        .collect()
+}
+
+/// Returns the kind of every tx method in the order of the `balance_all` columns
+pub fn get_tx_method_kinds(conn: &Connection) -> Vec<(String, String)> {
+    let mut statement = conn
+        .prepare("SELECT name, kind FROM tx_methods")
+        .expect("could not prepare statement");
+
+    let saved_kinds: HashMap<String, String> = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(|data| data.unwrap())
+        .collect();
+
+    get_all_tx_methods(conn)
+        .into_iter()
+        .map(|method| {
+            let kind = saved_kinds
+                .get(&method)
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_TX_METHOD_KIND.to_string());
+            (method, kind)
+        })
+        .collect()
+}
+
+/// Returns all tx methods that are liabilities
+pub fn get_liability_tx_methods(conn: &Connection) -> Vec<String> {
+    get_tx_method_kinds(conn)
+        .into_iter()
+        .filter(|(_, kind)| kind == LIABILITY_TX_METHOD_KIND)
+        .map(|(method, _)| method)
+        .collect()
+}
+
+/// Removes the columns of the archived tx methods from the Balance section rows.
+/// Each row is expected to be `[title, method_1, method_2, ..., total]`
+pub fn hide_archived_columns(rows: Vec<Vec<String>>, conn: &Connection) -> Vec<Vec<String>> {
+    let archived_methods = get_archived_tx_methods(conn);
+
+    if archived_methods.is_empty() {
+        return rows;
+    }
+
+    let archived_indexes: Vec<usize> = get_all_tx_methods(conn)
+        .iter()
+        .enumerate()
+        .filter(|(_, method)| archived_methods.contains(method))
+        // + 1 for the title column
+        .map(|(index, _)| index + 1)
+        .collect();
+
+    rows.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .enumerate()
+                .filter(|(index, _)| !archived_indexes.contains(index))
+                .map(|(_, data)| data)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the amount of txs that use the given tx method, including transfers from or to it
+pub fn get_tx_method_usage(method: &str, conn: &Connection) -> sqlResult<i64> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM tx_all WHERE tx_method = ?1
+        OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+        OR substr(tx_method, -(length(?1) + 4)) = ' to ' || ?1",
+        [method],
+        |row| row.get(0),
+    )
+}
+
 pub fn get_all_tx_methods_cumulative(conn: &Connection) -> Vec<String> {
     // returns all transaction methods added to the database
     let column_names = conn
@@ -64,34 +164,98 @@ pub fn get_all_tx_methods_cumulative(conn: &Connection) -> Vec<String> {
     data
 }
 
-/// Returns all unique tags from the db
+/// Returns all unique tags that are used by at least one tx
 pub fn get_all_tags(conn: &Connection) -> Vec<String> {
     let mut query = conn
-        .prepare("SELECT tags FROM tx_all")
+        .prepare("SELECT name FROM tags WHERE tag_id IN (SELECT tag_id FROM tx_tags) ORDER BY name")
         .expect("could not prepare statement");
 
-    let mut tags_data: HashSet<String> = HashSet::new();
+    query
+        .query_map([], |row| row.get(0))
+        .expect("could not get tags")
+        .flatten()
+        .collect()
+}
 
-    if let Ok(rows) = query.query_map([], |row| {
-        let row_data: String = row.get(0).unwrap();
-        let split_tags = row_data.split(',');
-        let final_data = split_tags
-            .into_iter()
-            .map(|s| s.trim().to_string())
-            .collect::<Vec<String>>();
-        Ok(final_data)
-    }) {
-        for inner_data in rows.flatten() {
-            for x in inner_data {
-                tags_data.insert(x);
-            }
+/// Returns the tags of every tx that has any, in the order they were added to the tx
+pub fn get_all_tx_tags(conn: &Connection) -> HashMap<i32, Vec<String>> {
+    let mut query = conn
+        .prepare(
+            "SELECT tx_tags.id_num, tags.name FROM tx_tags
+            JOIN tags ON tags.tag_id = tx_tags.tag_id ORDER BY tx_tags.rowid",
+        )
+        .expect("could not prepare statement");
+
+    let mut tx_tags: HashMap<i32, Vec<String>> = HashMap::new();
+
+    let rows = query
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .expect("could not get tx tags");
+
+    for (id_num, tag) in rows.flatten() {
+        tx_tags.entry(id_num).or_default().push(tag);
+    }
+
+    tx_tags
+}
+
+/// Splits comma separated tags into unique, trimmed and non-empty tags
+#[must_use]
+pub fn split_tags(tags: &str) -> Vec<String> {
+    let mut all_tags: Vec<String> = Vec::new();
+
+    for tag in tags.split(',').map(str::trim) {
+        if !tag.is_empty() && !all_tags.iter().any(|t| t == tag) {
+            all_tags.push(tag.to_string());
         }
     }
 
-    let mut sorted_tags = tags_data.into_iter().collect::<Vec<String>>();
-    sorted_tags.sort();
+    all_tags
+}
+
+/// Returns whether the tx type adds the amount to the tx method
+#[must_use]
+pub fn is_income_like(tx_type: &str) -> bool {
+    matches!(
+        tx_type,
+        "Income" | OPENING_BALANCE_TX_TYPE | BORROW_TX_TYPE | REPAY_IN_TX_TYPE
+    )
+}
+
+/// Returns whether the tx type subtracts the amount from the tx method
+#[must_use]
+pub fn is_expense_like(tx_type: &str) -> bool {
+    matches!(tx_type, "Expense" | LEND_TX_TYPE | REPAY_OUT_TX_TYPE)
+}
+
+/// Returns all parents of the tag starting from the top one. `Food:Out:Lunch` returns `Food` and `Food:Out`
+#[must_use]
+pub fn get_parent_tags(tag: &str) -> Vec<String> {
+    tag.match_indices(TAG_SEPARATOR)
+        .map(|(index, _)| tag[..index].to_string())
+        .collect()
+}
 
-    sorted_tags
+/// Whether the tag is the given parent tag or one of its children
+#[must_use]
+pub fn is_in_tag_subtree(tag: &str, parent: &str) -> bool {
+    tag.strip_prefix(parent)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(TAG_SEPARATOR))
+}
+
+/// Removes the rows of every tag that is a child of one of the collapsed tags
+#[must_use]
+pub fn hide_collapsed_tags<S: ::std::hash::BuildHasher>(
+    data: Vec<Vec<String>>,
+    collapsed_tags: &HashSet<String, S>,
+) -> Vec<Vec<String>> {
+    data.into_iter()
+        .filter(|row| {
+            !get_parent_tags(&row[0])
+                .iter()
+                .any(|parent| collapsed_tags.contains(parent))
+        })
+        .collect()
 }
 
 /// Returns all unique details from the db
@@ -167,72 +331,79 @@ pub fn get_last_balance_id(conn: &Connection) -> sqlResult<i32> {
     last_id
 }
 
-/// Returns two dates based on the month and year index. used for the purpose of searching
+/// Returns the first and the last year that the `balance_all` table has rows for
+pub fn get_year_range(conn: &Connection) -> sqlResult<(i32, i32)> {
+    conn.query_row("SELECT start_year, end_year FROM year_range", [], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })
+}
+
+/// Returns all years inside the year range of the db.
+/// return example: `["2022", "2023", "2024"]`
+pub fn get_all_years(conn: &Connection) -> Vec<String> {
+    let (start_year, end_year) = get_year_range(conn).expect("could not get the year range");
+    (start_year..=end_year)
+        .map(|year| year.to_string())
+        .collect()
+}
+
+/// Returns the year for the given year index of the db's year range
+pub fn get_year_from_index(year: usize, conn: &Connection) -> i32 {
+    let (start_year, _) = get_year_range(conn).expect("could not get the year range");
+    start_year + year as i32
+}
+
+/// Returns two dates based on the month index and the year. used for the purpose of searching
 /// tx based on date
-pub fn get_sql_dates(month: usize, year: usize, date_type: &DateType) -> (String, String) {
+pub fn get_sql_dates(month: usize, year: i32, date_type: &DateType) -> (String, String) {
     match date_type {
         DateType::Monthly => {
-            let datetime_1 = format!("{}-{:02}-01", YEARS[year], month + 1);
-            let last_date =
-                NaiveDate::from_ymd_opt(YEARS[year].parse().unwrap(), (month + 1) as u32, 1)
-                    .unwrap()
-                    .checked_add_months(Months::new(1))
-                    .unwrap()
-                    .pred_opt()
-                    .unwrap();
+            let datetime_1 = format!("{year}-{:02}-01", month + 1);
+            let last_date = NaiveDate::from_ymd_opt(year, (month + 1) as u32, 1)
+                .unwrap()
+                .checked_add_months(Months::new(1))
+                .unwrap()
+                .pred_opt()
+                .unwrap();
             (datetime_1, last_date.to_string())
         }
         DateType::Yearly => {
-            let datetime_1 = format!("{}-01-01", YEARS[year]);
-            let datetime_2 = format!("{}-12-31", YEARS[year]);
+            let datetime_1 = format!("{year}-01-01");
+            let datetime_2 = format!("{year}-12-31");
             (datetime_1, datetime_2)
         }
         DateType::Exact => (String::new(), String::new()),
     }
 }
 
-/// Verifies the db version is up to date
+/// Runs all pending migrations if an old database is detected.
+/// Exits if the database is from a newer version of the app or a migration fails
 #[cfg(not(tarpaulin_include))]
 pub fn check_old_sql(conn: &mut Connection) {
-    // earlier version of the database didn't had the Tag column
-    if !get_all_tx_columns(conn).contains(&"tags".to_string()) {
-        println!("Old database detected. Starting migration...");
-        let status = add_tags_column(conn);
-        match status {
-            Ok(()) => start_timer("Database migration successfully complete."),
-            Err(e) => {
-                println!("Database migration failed. Try again. Error: {e}");
-                println!("Commits reversed. Exiting...");
-                process::exit(1);
-            }
+    let pending_migrations = match get_pending_migrations(conn) {
+        Ok(migrations) => migrations,
+        Err(e) => {
+            println!("{e}");
+            println!("Exiting...");
+            process::exit(1);
         }
+    };
+
+    if pending_migrations.is_empty() {
+        return;
     }
 
-    // earlier version of the database's balance_all columns were all TEXT type.
-    // Convert to REAL type if found
-    if check_old_balance_sql(conn) {
-        println!("Outdated database detected. Updating...");
-        let status = update_balance_type(conn);
-        match status {
-            Ok(()) => start_timer("Database updating successfully complete."),
-            Err(e) => {
-                println!("Database updating failed. Try again. Error: {e}");
-                println!("Commits reversed. Exiting...");
-                process::exit(1);
-            }
-        }
+    println!("Outdated database detected. Updating...");
+    for migration in pending_migrations {
+        println!("- {}", migration.description);
     }
 
-    if !get_all_table_names(conn).contains(&"activities".to_string()) {
-        println!("Outdated database detected. Updating...");
-        let status = migrate_to_activities(conn);
-        match status {
-            Ok(()) => start_timer("Database updating successfully complete."),
-            Err(e) => {
-                println!("Database updating failed. Try again. Error: {e}");
-                println!("Commits reversed. Exiting...");
-                process::exit(1);
-            }
+    match run_migrations(conn) {
+        Ok(_) => start_timer("Database updating successfully complete."),
+        Err(e) => {
+            println!("{e}");
+            println!("Exiting...");
+            process::exit(1);
         }
     }
 }
@@ -257,6 +428,12 @@ pub fn check_old_balance_sql(conn: &Connection) -> bool {
     result
 }
 
+/// Returns the schema version of the DB saved in `PRAGMA user_version`
+pub fn get_db_version(conn: &Connection) -> i32 {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .unwrap()
+}
+
 pub fn get_all_table_names(conn: &Connection) -> Vec<String> {
     let mut stmt = conn
         .prepare("SELECT name FROM sqlite_master WHERE type='table'")
@@ -299,13 +476,16 @@ pub fn exit_tui_interface() -> Result<(), Box<dyn Error>> {
 #[cfg(not(tarpaulin_include))]
 pub fn check_n_create_db(verifying_path: &PathBuf) -> Result<(), Box<dyn Error>> {
     if !verifying_path.exists() {
-        let UserInputType::AddNewTxMethod(db_tx_methods) = get_user_tx_methods(false, None) else {
+        let UserInputType::AddNewTxMethod(db_tx_methods, opening_balances) =
+            get_user_tx_methods(false, None)
+        else {
             return Err("Failed to get tx methods.".into());
         };
         println!("Creating New Database. It may take some time...");
 
         let mut conn = Connection::open(verifying_path)?;
-        let status = create_db(&db_tx_methods, &mut conn);
+        let status = create_db(&db_tx_methods, &mut conn)
+            .and_then(|()| add_opening_balances(&opening_balances, &mut conn));
         conn.close().unwrap();
         match status {
             Ok(()) => start_timer("Database creation successful."),
@@ -321,7 +501,7 @@ pub fn check_n_create_db(verifying_path: &PathBuf) -> Result<(), Box<dyn Error>>
 
 /// Returns a styled block for ui to use
 #[cfg(not(tarpaulin_include))]
-pub fn styled_block(title: &str) -> Block {
+pub fn styled_block(title: &str) -> Block<'_> {
     Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -340,7 +520,7 @@ pub fn main_block<'a>() -> Block<'a> {
 /// takes a string and makes any word before the first occurrence of : to Bold
 /// Used for rendering
 #[cfg(not(tarpaulin_include))]
-pub fn create_bolded_text(text: &str) -> Vec<Line> {
+pub fn create_bolded_text(text: &str) -> Vec<Line<'_>> {
     let mut text_data = Vec::new();
 
     for line in text.split('\n') {
@@ -486,17 +666,20 @@ pub fn get_best_match(data: &str, matching_set: &[String]) -> String {
 /// Used for sorting summary table data
 pub fn sort_table_data(mut data: Vec<Vec<String>>, sort_type: &SortingType) -> Vec<Vec<String>> {
     match sort_type {
-        SortingType::ByTags => data.sort(),
+        // Child tags come right after their parent tag
+        SortingType::ByTags => {
+            data.sort_by(|a, b| a[0].split(TAG_SEPARATOR).cmp(b[0].split(TAG_SEPARATOR)));
+        }
         SortingType::ByIncome => data.sort_by(|a, b| {
-            let val_a: f64 = a[1].parse().unwrap();
-            let val_b: f64 = b[1].parse().unwrap();
-            val_b.partial_cmp(&val_a).unwrap()
+            let val_a: Cent = a[1].parse().unwrap();
+            let val_b: Cent = b[1].parse().unwrap();
+            val_b.cmp(&val_a)
         }),
         SortingType::ByExpense => {
             data.sort_by(|a, b| {
-                let val_a: f64 = a[2].parse().unwrap();
-                let val_b: f64 = b[2].parse().unwrap();
-                val_b.partial_cmp(&val_a).unwrap()
+                let val_a: Cent = a[2].parse().unwrap();
+                let val_b: Cent = b[2].parse().unwrap();
+                val_b.cmp(&val_a)
             });
         }
     }
@@ -663,11 +846,9 @@ pub fn delete_location_change(original_db_path: &PathBuf) -> ioResult<()> {
     fs::remove_file(json_path)
 }
 
-/// Returns a transaction detail from a given ID number
+/// Returns a transaction detail from a given ID number. The split lines and the status of the tx are at the end
 pub fn get_tx_id_num(id_num: i32, conn: &Connection) -> Vec<String> {
-    let query = format!("SELECT * FROM tx_all WHERE id_num = {id_num}");
-
-    let tx_data = conn.query_row(&query, [], |row| {
+    let tx_data = conn.query_row("SELECT * FROM tx_all WHERE id_num = ?", [id_num], |row| {
         let date: String = row.get(0).unwrap();
         let id_num: i32 = row.get(5).unwrap();
         let collected_date = date.split('-').collect::<Vec<&str>>();
@@ -675,21 +856,30 @@ pub fn get_tx_id_num(id_num: i32, conn: &Connection) -> Vec<String> {
             "{}-{}-{}",
             collected_date[2], collected_date[1], collected_date[0]
         );
-        Ok(vec![
-            new_date,
-            row.get(1).unwrap(),
-            row.get(2).unwrap(),
-            row.get(3).unwrap(),
-            row.get(4).unwrap(),
-            row.get(6).unwrap(),
-            id_num.to_string(),
-        ])
+        let amount: Cent = row.get(3).unwrap();
+        let status: String = row.get(7).unwrap();
+        Ok((
+            vec![
+                new_date,
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                amount.to_string(),
+                row.get(4).unwrap(),
+                row.get(6).unwrap(),
+                id_num.to_string(),
+            ],
+            status,
+        ))
     });
 
-    tx_data.unwrap()
+    let (mut tx_data, status) = tx_data.unwrap();
+    let id_num = tx_data[6].parse().unwrap();
+    tx_data.push(splits_to_text(&get_tx_splits(id_num, conn)));
+    tx_data.push(status);
+    tx_data
 }
 
-/// Returns the details of the last added transaction
+/// Returns the details of the last added transaction. The split lines and the status of the tx are at the end
 pub fn get_last_tx(conn: &Connection) -> Vec<String> {
     let query = "SELECT * FROM tx_all ORDER BY id_num DESC LIMIT 1";
 
@@ -701,18 +891,27 @@ pub fn get_last_tx(conn: &Connection) -> Vec<String> {
             "{}-{}-{}",
             collected_date[2], collected_date[1], collected_date[0]
         );
-        Ok(vec![
-            new_date,
-            row.get(1).unwrap(),
-            row.get(2).unwrap(),
-            row.get(3).unwrap(),
-            row.get(4).unwrap(),
-            row.get(6).unwrap(),
-            id_num.to_string(),
-        ])
+        let amount: Cent = row.get(3).unwrap();
+        let status: String = row.get(7).unwrap();
+        Ok((
+            vec![
+                new_date,
+                row.get(1).unwrap(),
+                row.get(2).unwrap(),
+                amount.to_string(),
+                row.get(4).unwrap(),
+                row.get(6).unwrap(),
+                id_num.to_string(),
+            ],
+            status,
+        ))
     });
 
-    tx_data.unwrap()
+    let (mut tx_data, status) = tx_data.unwrap();
+    let id_num = tx_data[6].parse().unwrap();
+    tx_data.push(splits_to_text(&get_tx_splits(id_num, conn)));
+    tx_data.push(status);
+    tx_data
 }
 
 /// Add a new activity row to the DB
@@ -721,13 +920,12 @@ pub fn add_new_activity(activity_type: ActivityType, conn: &Connection) -> i32 {
     let activity_details = activity_type.to_details();
     let current_date = Local::now().date_naive().to_string();
 
-    let query = format!(
-        r#"INSERT INTO activities 
-    (date, activity_type, description) 
-    VALUES ("{current_date}", "{activity_type_str}", "{activity_details}")"#
-    );
-
-    conn.execute(&query, []).unwrap();
+    QueryBuilder::insert("activities")
+        .set("date", current_date)
+        .set("activity_type", activity_type_str)
+        .set("description", activity_details)
+        .execute(conn)
+        .unwrap();
 
     // Fetch the latest row's activity num so this can be used to reference activity txs
     let query = "SELECT activity_num FROM activities ORDER BY activity_num DESC LIMIT 1";
@@ -740,7 +938,8 @@ pub fn add_new_activity(activity_type: ActivityType, conn: &Connection) -> i32 {
     activity_num.unwrap()
 }
 
-/// Add a new tx that is related to a given activity number
+/// Add a new tx that is related to a given activity number. The optional 8th and 9th values are the
+/// split lines and the status of the tx
 pub fn add_new_activity_tx<T: AsRef<str> + Display>(
     tx_data: &[T],
     activity_num: i32,
@@ -761,13 +960,27 @@ pub fn add_new_activity_tx<T: AsRef<str> + Display>(
         string_date = reverse_date_format(string_date);
     }
 
-    let query = format!(
-        r#"INSERT INTO activity_txs 
-    (date, details, tx_method, amount, tx_type, tags, id_num, activity_num)
-    VALUES ("{string_date}", "{details}", "{tx_method}", "{amount}", "{tx_type}", "{tags}", "{id_num}", "{activity_num}")"#
-    );
-
-    conn.execute(&query, []).unwrap();
+    QueryBuilder::insert("activity_txs")
+        .set("date", string_date)
+        .set("details", details.to_string())
+        .set("tx_method", tx_method.to_string())
+        .set("amount", amount.to_string())
+        .set("tx_type", tx_type.to_string())
+        .set("tags", tags.to_string())
+        .set("id_num", id_num.to_string())
+        .set("activity_num", activity_num)
+        .set(
+            "splits",
+            tx_data.get(7).map_or(String::new(), ToString::to_string),
+        )
+        .set(
+            "status",
+            tx_data
+                .get(8)
+                .map_or(DEFAULT_TX_STATUS.to_string(), ToString::to_string),
+        )
+        .execute(conn)
+        .unwrap();
 }
 
 /// Switch from YYYY-MM-DD to DD-MM-YYYY or vice versa.
@@ -1,5 +1,5 @@
 extern crate rex_tui;
-use chrono::{naive::NaiveDate, Duration};
+use chrono::{naive::NaiveDate, Datelike, Duration, Local};
 use rex_tui::db::create_db;
 use rex_tui::tx_handler::*;
 use rex_tui::utility::*;
@@ -24,7 +24,7 @@ fn check_last_balances_1() {
     let file_name = "last_balances_1.sqlite";
     let conn = create_test_db(file_name);
     let data = get_last_balances(&conn);
-    let expected_data = vec!["0".to_string(), "0".to_string()];
+    let expected_data = vec!["0.00".to_string(), "0.00".to_string()];
     conn.close().unwrap();
 
     fs::remove_file(file_name).unwrap();
@@ -62,12 +62,12 @@ fn check_last_balances_2() {
     .unwrap();
 
     let data = get_last_balances(&conn);
-    let expected_data = vec!["-159".to_string(), "159.19".to_string()];
+    let expected_data = vec!["-159.00".to_string(), "159.19".to_string()];
 
     delete_tx(1, &mut conn).unwrap();
 
     let data_2 = get_last_balances(&conn);
-    let expected_data_2 = vec!["0".to_string(), "159.19".to_string()];
+    let expected_data_2 = vec!["0.00".to_string(), "159.19".to_string()];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -106,12 +106,12 @@ fn check_last_balances_3() {
     .unwrap();
 
     let data = get_last_balances(&conn);
-    let expected_data = vec!["0".to_string(), "0".to_string()];
+    let expected_data = vec!["0.00".to_string(), "0.00".to_string()];
 
     delete_tx(1, &mut conn).unwrap();
 
     let data_2 = get_last_balances(&conn);
-    let expected_data_2 = vec!["159".to_string(), "-159".to_string()];
+    let expected_data_2 = vec!["159.00".to_string(), "-159.00".to_string()];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -127,7 +127,10 @@ fn check_last_month_balance_1() {
     let tx_methods = get_all_tx_methods(&conn);
 
     let data = get_last_time_balance(6, 1, &tx_methods, &conn);
-    let expected_data = HashMap::from([("test1".to_string(), 0.0), ("test 2".to_string(), 0.0)]);
+    let expected_data = HashMap::from([
+        ("test1".to_string(), Cent::default()),
+        ("test 2".to_string(), Cent::default()),
+    ]);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -141,7 +144,7 @@ fn check_last_balance_id() {
     let conn = create_test_db(file_name);
 
     let data = get_last_balance_id(&conn);
-    let expected_data: sqlResult<i32> = Ok(193);
+    let expected_data: sqlResult<i32> = Ok(13);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -149,6 +152,76 @@ fn check_last_balance_id() {
     assert_eq!(data, expected_data);
 }
 
+#[test]
+fn check_year_range_growth() {
+    let file_name = "year_range_growth.sqlite";
+    let mut conn = create_test_db(file_name);
+    let current_year = Local::now().year();
+
+    let starting_range = get_year_range(&conn).unwrap();
+    let starting_balance_id = get_last_balance_id(&conn).unwrap();
+
+    add_tx(
+        "2040-03-10",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let forward_range = get_year_range(&conn).unwrap();
+    let forward_balance_id = get_last_balance_id(&conn).unwrap();
+
+    add_tx(
+        "2019-06-01",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let backward_range = get_year_range(&conn).unwrap();
+    let backward_balance_id = get_last_balance_id(&conn).unwrap();
+    let all_years = get_all_years(&conn);
+
+    let data = get_last_balances(&conn);
+    let (_, month_balance, _) = get_all_txs(&conn, 2, 2040 - 2019);
+
+    delete_tx(1, &mut conn).unwrap();
+    let data_2 = get_last_balances(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let total_years = (2040 - 2019 + 1) as usize;
+
+    assert_eq!(starting_range, (current_year, current_year));
+    assert_eq!(starting_balance_id, 13);
+
+    assert_eq!(forward_range, (current_year, 2040));
+    assert_eq!(forward_balance_id, (2040 - current_year + 1) * 12 + 1);
+
+    assert_eq!(backward_range, (2019, 2040));
+    assert_eq!(backward_balance_id, total_years as i32 * 12 + 1);
+    assert_eq!(all_years.len(), total_years);
+    assert_eq!(all_years[0], "2019");
+
+    assert_eq!(data, vec!["100.00".to_string(), "-50.00".to_string()]);
+    assert_eq!(
+        month_balance,
+        vec![vec!["100.00".to_string(), "-50.00".to_string()]]
+    );
+    assert_eq!(data_2, vec!["0.00".to_string(), "-50.00".to_string()]);
+}
+
 #[test]
 fn check_last_month_balance_2() {
     let file_name = "last_month_balance_2.sqlite";
@@ -216,15 +289,19 @@ fn check_last_month_balance_2() {
     .unwrap();
 
     let data_1 = get_last_time_balance(8, 0, &tx_methods, &conn);
-    let expected_data_1 =
-        HashMap::from([("test 2".to_string(), 100.0), ("test1".to_string(), 200.0)]);
+    let expected_data_1 = HashMap::from([
+        ("test 2".to_string(), Cent::new(10000)),
+        ("test1".to_string(), Cent::new(20000)),
+    ]);
 
     delete_tx(1, &mut conn).unwrap();
     delete_tx(2, &mut conn).unwrap();
 
     let data_2 = get_last_time_balance(10, 3, &tx_methods, &conn);
-    let expected_data_2 =
-        HashMap::from([("test 2".to_string(), 0.0), ("test1".to_string(), 300.0)]);
+    let expected_data_2 = HashMap::from([
+        ("test 2".to_string(), Cent::default()),
+        ("test1".to_string(), Cent::new(30000)),
+    ]);
 
     add_tx(
         "2028-08-19",
@@ -263,8 +340,10 @@ fn check_last_month_balance_2() {
     .unwrap();
 
     let data_3 = get_last_time_balance(10, 4, &tx_methods, &conn);
-    let expected_data_3 =
-        HashMap::from([("test 2".to_string(), 0.0), ("test1".to_string(), 500.0)]);
+    let expected_data_3 = HashMap::from([
+        ("test 2".to_string(), Cent::default()),
+        ("test1".to_string(), Cent::new(50000)),
+    ]);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -313,7 +392,7 @@ fn check_balance_all_day() {
     }
 
     let data = get_last_balances(&conn);
-    let expected = vec![total_amount.to_string(), "0".to_string()];
+    let expected = vec![format!("{total_amount}.00"), "0.00".to_string()];
     assert_eq!(data, expected);
 
     let mut delete_id_num = total_days;
@@ -329,10 +408,10 @@ fn check_balance_all_day() {
     let data_1 = get_last_balances(&conn);
     let data_2 = get_last_time_balance(12, 3, &tx_methods, &conn);
 
-    let expected_data_1 = vec!["0".to_string(), "0".to_string()];
+    let expected_data_1 = vec!["0.00".to_string(), "0.00".to_string()];
     let mut expected_data_2 = HashMap::new();
     for i in data_2.keys() {
-        expected_data_2.insert(i.to_string(), 0.0);
+        expected_data_2.insert(i.to_string(), Cent::default());
     }
 
     conn.close().unwrap();
@@ -0,0 +1,210 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::budget_page::BudgetData;
+use rex_tui::db::{
+    add_budget, create_db, delete_budget, get_all_budgets, rename_tag, update_budget, Budget,
+    BudgetPeriod,
+};
+use rex_tui::summary_page::SummaryData;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::{get_year_range, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_expense(date: &str, amount: &str, tags: &str, conn: &mut Connection) {
+    add_tx(
+        date,
+        "Testing transaction",
+        "test1",
+        amount,
+        "Expense",
+        tags,
+        None,
+        conn,
+    )
+    .unwrap();
+}
+
+fn new_budget(tag: &str, period: BudgetPeriod, amount: i64, rollover: bool) -> Budget {
+    Budget {
+        budget_id: 0,
+        tag: tag.to_string(),
+        period,
+        amount: Cent::new(amount),
+        rollover,
+        start_date: NaiveDate::from_ymd_opt(2023, 7, 1).unwrap(),
+    }
+}
+
+/// Returns the budget data of the given month (1 to 12) of the given year
+fn get_budget_data(month: usize, year: i32, conn: &Connection) -> BudgetData {
+    let (start_year, _) = get_year_range(conn).unwrap();
+    BudgetData::new(
+        month - 1,
+        (year - start_year) as usize,
+        &SummaryData::new(conn),
+        conn,
+    )
+}
+
+#[test]
+fn check_budget_progress() {
+    let file_name = "budget_progress.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_budget(
+        &new_budget("Food", BudgetPeriod::Monthly, 40000, false),
+        &mut conn,
+    )
+    .unwrap();
+    add_budget(
+        &new_budget("Car", BudgetPeriod::Yearly, 100_000, false),
+        &mut conn,
+    )
+    .unwrap();
+
+    add_expense("2023-06-10", "500.00", "Food", &mut conn);
+    add_expense("2023-07-10", "150.00", "Food:Groceries", &mut conn);
+    add_expense("2023-07-15", "100.00", "Food", &mut conn);
+    add_expense("2023-07-20", "300.00", "Car", &mut conn);
+    add_expense("2023-11-20", "800.00", "Car", &mut conn);
+    add_tx(
+        "2023-07-21",
+        "Testing transaction",
+        "test1",
+        "1000.00",
+        "Income",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    // Monthly budgets apply from the start month and yearly budgets from the start year
+    let budget_data = get_budget_data(6, 2023, &conn);
+    assert_eq!(budget_data.get_txs().len(), 1);
+    assert_eq!(budget_data.get_budget(0).budget.tag, "Car");
+
+    // The parent tag counts the child tag expenses while income is ignored
+    let budget_data = get_budget_data(7, 2023, &conn);
+    assert_eq!(
+        budget_data.get_txs(),
+        vec![
+            vec!["Car", "Yearly", "1000.00", "-", "1100.00", "-100.00", "110.00"],
+            vec!["Food", "Monthly", "400.00", "-", "250.00", "150.00", "62.50"],
+        ]
+    );
+
+    let exceeded = budget_data.get_exceeded_budgets();
+    assert_eq!(exceeded.len(), 1);
+    assert_eq!(exceeded[0].budget.tag, "Car");
+
+    let budget_data = get_budget_data(8, 2023, &conn);
+    assert_eq!(budget_data.get_budget(1).spent, Cent::default());
+    assert_eq!(budget_data.get_budget(1).used_percentage(), 0.0);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_budget_rollover() {
+    let file_name = "budget_rollover.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_budget(
+        &new_budget("Food", BudgetPeriod::Monthly, 40000, true),
+        &mut conn,
+    )
+    .unwrap();
+
+    add_expense("2023-07-10", "100.00", "Food", &mut conn);
+    add_expense("2023-08-10", "500.00", "Food", &mut conn);
+    add_expense("2023-09-10", "200.00", "Food", &mut conn);
+
+    // Only the unspent amount of the month before is added to the next month
+    let rollovers: Vec<Cent> = (7..=10)
+        .map(|month| get_budget_data(month, 2023, &conn).get_budget(0).rollover)
+        .collect();
+    assert_eq!(
+        rollovers,
+        vec![
+            Cent::new(0),
+            Cent::new(30000),
+            Cent::new(20000),
+            Cent::new(40000)
+        ]
+    );
+
+    let budget_data = get_budget_data(8, 2023, &conn);
+    assert_eq!(budget_data.get_budget(0).available(), Cent::new(70000));
+    assert_eq!(budget_data.get_budget(0).remaining(), Cent::new(20000));
+    assert!(budget_data.get_exceeded_budgets().is_empty());
+
+    // Rollover goes across years
+    let mut budget = get_all_budgets(&conn).remove(0);
+    budget.period = BudgetPeriod::Yearly;
+    budget.amount = Cent::new(100_000);
+    update_budget(&budget, &mut conn).unwrap();
+
+    let budget_data = get_budget_data(1, 2024, &conn);
+    assert_eq!(budget_data.get_budget(0).rollover, Cent::new(20000));
+    assert_eq!(budget_data.get_budget(0).available(), Cent::new(120_000));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_budget_tags() {
+    let file_name = "budget_tags.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_expense("2023-07-10", "100.00", "Food", &mut conn);
+    add_expense("2023-07-10", "100.00", "Snacks", &mut conn);
+
+    let food_id = add_budget(
+        &new_budget("Food", BudgetPeriod::Monthly, 40000, false),
+        &mut conn,
+    )
+    .unwrap();
+    add_budget(
+        &new_budget("Snacks", BudgetPeriod::Monthly, 10000, false),
+        &mut conn,
+    )
+    .unwrap();
+
+    // A tag can only have one budget per period
+    assert!(add_budget(
+        &new_budget("Food", BudgetPeriod::Monthly, 100, false),
+        &mut conn
+    )
+    .is_err());
+
+    // Renaming a tag moves its budget unless the new tag already has one
+    rename_tag("Food", "Groceries", &mut conn).unwrap();
+    rename_tag("Groceries", "Snacks", &mut conn).unwrap();
+
+    let budgets = get_all_budgets(&conn);
+    assert_eq!(budgets[0].tag, "Groceries");
+    assert_eq!(budgets[1].tag, "Snacks");
+    assert_eq!(budgets[1].amount, Cent::new(10000));
+
+    delete_budget(food_id, &mut conn).unwrap();
+    assert_eq!(get_all_budgets(&conn).len(), 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
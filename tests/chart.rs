@@ -136,6 +136,7 @@ fn check_chart_data() {
         "100.00",
         "Expense",
         "Car",
+        "Cleared",
     ]
     .map(|a| a.to_string())
     .into_iter()
@@ -148,6 +149,7 @@ fn check_chart_data() {
         "100.00",
         "Expense",
         "Food",
+        "Cleared",
     ]
     .map(|a| a.to_string())
     .into_iter()
@@ -160,6 +162,7 @@ fn check_chart_data() {
         "200.00",
         "Income",
         "Food",
+        "Cleared",
     ]
     .map(|a| a.to_string())
     .into_iter()
@@ -240,3 +243,52 @@ fn check_chart_data() {
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 }
+
+#[test]
+fn check_chart_tag_data() {
+    let file_name = "chart_tag_data.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    add_tx(
+        "2023-07-28",
+        "Testing transaction",
+        "test1 to test 2",
+        "50.00",
+        "Transfer",
+        "Food:Groceries",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let chart_data = ChartData::new(&conn);
+    let chart_mode = IndexedData::new_modes();
+
+    // Only the txs in the tag subtree are counted, starting from zero
+    let (txs, balances) = chart_data.get_tag_data(&chart_mode, 6, 1, "Food");
+
+    let dates: Vec<&str> = txs.iter().map(|tx| tx[0].as_str()).collect();
+    assert_eq!(dates, vec!["19-07-2023", "25-07-2023", "28-07-2023"]);
+    assert_eq!(
+        balances,
+        vec![
+            vec!["0.00".to_string(), "-100.00".to_string()],
+            vec!["200.00".to_string(), "-100.00".to_string()],
+            vec!["150.00".to_string(), "-50.00".to_string()],
+        ]
+    );
+
+    let (txs, balances) = chart_data.get_tag_data(&chart_mode, 6, 1, "Food:Groceries");
+    assert_eq!(txs.len(), 1);
+    assert_eq!(
+        balances,
+        vec![vec!["-50.00".to_string(), "50.00".to_string()]]
+    );
+
+    let (txs, _) = chart_data.get_tag_data(&chart_mode, 6, 1, "Car");
+    assert!(txs.is_empty());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,254 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    create_db, get_all_counterparties, get_counterparty_history, get_counterparty_outstanding,
+    verify_ledger, Counterparty, DebtAction, NewDebtTx, LEND_TX_TYPE, REPAY_IN_TX_TYPE,
+    REPAY_OUT_TX_TYPE,
+};
+use rex_tui::debt_page::DebtData;
+use rex_tui::home_page::TransactionData;
+use rex_tui::outputs::{AType, CheckingError, CounterpartyError, VerifyingOutput};
+use rex_tui::page_handler::TxTab;
+use rex_tui::tx_handler::{add_debt_tx, delete_tx, redo_activity, undo_activity, TxData};
+use rex_tui::utility::{get_last_balances, Cent, CurrencyConverter};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn debt_tx(counterparty: &str, action: DebtAction, date: &str, amount: i64) -> NewDebtTx {
+    NewDebtTx {
+        counterparty: counterparty.to_string(),
+        action,
+        date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+        tx_method: "test1".to_string(),
+        amount: Cent::new(amount),
+        details: String::new(),
+    }
+}
+
+#[test]
+fn check_debt_txs() {
+    let file_name = "debt_txs.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let txs = [
+        debt_tx("Alice", DebtAction::Lend, "2023-07-01", 5000),
+        debt_tx("Alice", DebtAction::Lend, "2023-07-05", 3000),
+        debt_tx("Bob", DebtAction::Borrow, "2023-07-06", 10000),
+        debt_tx("Alice", DebtAction::Repay, "2023-07-10", 6000),
+    ];
+
+    for tx in &txs {
+        add_debt_tx(tx, &mut conn).unwrap();
+    }
+
+    // Lending takes money out, borrowing and getting repaid brings it in
+    assert_eq!(get_last_balances(&conn), vec!["80.00", "0.00"]);
+    assert!(verify_ledger(&conn).unwrap().is_empty());
+
+    assert_eq!(
+        get_all_counterparties(&conn).unwrap(),
+        vec![
+            Counterparty {
+                name: "Alice".to_string(),
+                outstanding: Cent::new(2000),
+            },
+            Counterparty {
+                name: "Bob".to_string(),
+                outstanding: Cent::new(-10000),
+            },
+        ]
+    );
+
+    // The repayment settles the first loan fully and the second one partly
+    let history = get_counterparty_history("Alice", &conn).unwrap();
+    assert_eq!(
+        history.iter().map(|item| item.open).collect::<Vec<Cent>>(),
+        vec![Cent::default(), Cent::new(2000), Cent::default()]
+    );
+    assert_eq!(history[2].tx_type, REPAY_IN_TX_TYPE);
+    assert_eq!(history[2].details, "Repay In Alice");
+
+    add_debt_tx(
+        &debt_tx("Bob", DebtAction::Repay, "2023-07-20", 4000),
+        &mut conn,
+    )
+    .unwrap();
+
+    let history = get_counterparty_history("Bob", &conn).unwrap();
+    assert_eq!(history[1].tx_type, REPAY_OUT_TX_TYPE);
+    assert_eq!(history[0].open, Cent::new(6000));
+    assert_eq!(
+        get_counterparty_outstanding("Bob", &conn).unwrap(),
+        Cent::new(-6000)
+    );
+
+    let status = add_debt_tx(
+        &debt_tx("Bob", DebtAction::Repay, "2023-07-21", 7000),
+        &mut conn,
+    );
+    assert!(matches!(
+        status,
+        Err(CounterpartyError::RepayTooLarge(_, _))
+    ));
+
+    let status = add_debt_tx(
+        &debt_tx("Carol", DebtAction::Repay, "2023-07-21", 100),
+        &mut conn,
+    );
+    assert!(matches!(status, Err(CounterpartyError::NothingOwed(_))));
+
+    let status = add_debt_tx(
+        &debt_tx(" ", DebtAction::Lend, "2023-07-21", 100),
+        &mut conn,
+    );
+    assert!(matches!(status, Err(CounterpartyError::EmptyName)));
+
+    // Debt txs are not counted as income or expense
+    let tx_data = TransactionData::new(6, 0, &conn);
+    let converter = CurrencyConverter::new(&conn);
+    assert_eq!(
+        tx_data.get_total_income(None, &converter, &conn),
+        vec!["Income", "0.00", "0.00", "0.00"]
+    );
+
+    let debt_data = DebtData::new(&conn);
+    assert_eq!(
+        debt_data.get_txs(),
+        vec![
+            vec!["Alice", "20.00", "Owes you"],
+            vec!["Bob", "60.00", "You owe"]
+        ]
+    );
+    assert_eq!(
+        debt_data.get_history(0)[1],
+        vec![
+            "05-07-2023",
+            "Lend Alice",
+            "test1",
+            LEND_TX_TYPE,
+            "30.00",
+            "20.00"
+        ]
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_debt_tx_history() {
+    let file_name = "debt_tx_history.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_debt_tx(
+        &debt_tx("Alice", DebtAction::Lend, "2023-07-01", 5000),
+        &mut conn,
+    )
+    .unwrap();
+    add_debt_tx(
+        &debt_tx("Alice", DebtAction::Repay, "2023-07-02", 5000),
+        &mut conn,
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_counterparty_outstanding("Alice", &conn).unwrap(),
+        Cent::default()
+    );
+
+    // Undoing the repayment brings back the outstanding balance
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(
+        get_counterparty_outstanding("Alice", &conn).unwrap(),
+        Cent::new(5000)
+    );
+
+    // The link is kept so the redone tx belongs to the same person
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(get_counterparty_history("Alice", &conn).unwrap().len(), 2);
+    assert_eq!(
+        get_counterparty_outstanding("Alice", &conn).unwrap(),
+        Cent::default()
+    );
+
+    delete_tx(1, &mut conn).unwrap();
+    delete_tx(2, &mut conn).unwrap();
+
+    assert!(get_all_counterparties(&conn).unwrap().is_empty());
+    assert_eq!(get_last_balances(&conn), vec!["0.00", "0.00"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_debt_tx_type_fixed() {
+    let file_name = "debt_tx_type.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_debt_tx(
+        &debt_tx("Alice", DebtAction::Lend, "2023-07-01", 5000),
+        &mut conn,
+    )
+    .unwrap();
+
+    // Editing a debt tx keeps its type no matter what gets typed or stepped
+    let mut tx_data = TxData::custom(
+        "01-07-2023",
+        "",
+        "test1",
+        "",
+        "60.00",
+        LEND_TX_TYPE,
+        "Unknown",
+        1,
+    );
+    tx_data.go_current_index(&TxTab::TxType);
+    tx_data.do_tx_type_up().unwrap();
+    tx_data.edit_tx_type(Some('b'));
+    let type_status = tx_data.check_tx_type();
+    let edited_type = tx_data.get_all_texts()[5].to_string();
+    tx_data.add_tx(&mut conn).unwrap();
+
+    // A new tx can not be a debt tx as it would not be linked to any counterparty
+    let mut new_tx = TxData::custom(
+        "01-07-2023",
+        "",
+        "test1",
+        "",
+        "10.00",
+        "Expense",
+        "Unknown",
+        0,
+    );
+    new_tx.go_current_index(&TxTab::TxType);
+    for _ in 0.."Expense".len() {
+        new_tx.edit_tx_type(None);
+    }
+    for c in "Borrow".chars() {
+        new_tx.edit_tx_type(Some(c));
+    }
+    let new_debt_tx = new_tx.check_all_fields(&conn);
+
+    let outstanding = get_counterparty_outstanding("Alice", &conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(type_status, VerifyingOutput::Accepted(AType::TxType));
+    assert_eq!(edited_type, LEND_TX_TYPE);
+    assert_eq!(new_debt_tx, Some(CheckingError::DebtTxType));
+    assert_eq!(outstanding, Cent::new(6000));
+}
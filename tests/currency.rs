@@ -0,0 +1,199 @@
+extern crate rex_tui;
+use rex_tui::db::*;
+use rex_tui::home_page::TransactionData;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+#[test]
+fn check_tx_method_currencies() {
+    let file_name = "currency_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let default_currencies = get_tx_method_currencies(&conn);
+    let default_base = get_base_currency(&conn);
+
+    set_tx_method_currency("test1", "EUR", &mut conn).unwrap();
+    set_base_currency("BDT", &mut conn).unwrap();
+    rename_column("test1", "Euro Bank", &mut conn).unwrap();
+    add_new_tx_methods(&["Cash".to_string()], &mut conn).unwrap();
+
+    let currencies = get_tx_method_currencies(&conn);
+    let base = get_base_currency(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        default_currencies,
+        vec![
+            ("test1".to_string(), "USD".to_string()),
+            ("test 2".to_string(), "USD".to_string())
+        ]
+    );
+    assert_eq!(default_base, "USD");
+
+    assert_eq!(
+        currencies,
+        vec![
+            ("Euro Bank".to_string(), "EUR".to_string()),
+            ("test 2".to_string(), "USD".to_string()),
+            ("Cash".to_string(), "USD".to_string())
+        ]
+    );
+    assert_eq!(base, "BDT");
+}
+
+#[test]
+fn check_exchange_rate_parsing() {
+    let content = "from,to,rate\neur, usd, 1.08\n\nUSD,BDT,119.5\n";
+    let rates = parse_exchange_rates(content).unwrap();
+
+    assert_eq!(
+        rates,
+        vec![
+            ("EUR".to_string(), "USD".to_string(), 1.08),
+            ("USD".to_string(), "BDT".to_string(), 119.5)
+        ]
+    );
+
+    let invalid_content = [
+        "EUR,USD,1.08\nEUR,USD,abc",
+        "EUR,USD",
+        "EURO,USD,1.08",
+        "EUR,EUR,1",
+        "EUR,USD,0",
+        "EUR,USD,-1.5",
+        "EUR,USD,1.08\nfrom,to,rate",
+    ];
+
+    for content in invalid_content {
+        assert!(parse_exchange_rates(content).is_err());
+    }
+}
+
+#[test]
+fn check_exchange_rate_import() {
+    let file_name = "currency_2.sqlite";
+    let csv_name = "currency_2_rates.csv";
+    let mut conn = create_test_db(file_name);
+
+    fs::write(csv_name, "from,to,rate\nEUR,USD,1.08\nUSD,BDT,119.5\n").unwrap();
+    let total_imported = import_exchange_rates(csv_name.as_ref(), &mut conn).unwrap();
+
+    // an existing pair gets replaced
+    set_exchange_rates(&[("EUR".to_string(), "USD".to_string(), 1.1)], &mut conn).unwrap();
+
+    fs::write(csv_name, "GBP,USD,1.27\nbroken line\n").unwrap();
+    let failed_import = import_exchange_rates(csv_name.as_ref(), &mut conn);
+
+    let rates = get_exchange_rates(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(csv_name).unwrap();
+
+    assert_eq!(total_imported, 2);
+    assert!(failed_import.is_err());
+    assert_eq!(
+        rates,
+        vec![
+            ("EUR".to_string(), "USD".to_string(), 1.1),
+            ("USD".to_string(), "BDT".to_string(), 119.5)
+        ]
+    );
+}
+
+#[test]
+fn check_converted_totals() {
+    let file_name = "currency_3.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2022-08-20",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let tx_data = TransactionData::new(7, 0, &conn);
+    let same_currency_balance = tx_data.get_last_balance(&CurrencyConverter::new(&conn), &conn);
+    let same_currency_note = CurrencyConverter::new(&conn).missing_rates_note();
+
+    set_tx_method_currency("test1", "EUR", &mut conn).unwrap();
+
+    // no EUR rate is saved yet so test1 is left out of the total
+    let missing_rate_converter = CurrencyConverter::new(&conn);
+    let missing_rate_balance = tx_data.get_last_balance(&missing_rate_converter, &conn);
+    let missing_currencies = missing_rate_converter.missing_currencies().to_vec();
+    let missing_rate_note = missing_rate_converter.missing_rates_note();
+
+    set_exchange_rates(&[("EUR".to_string(), "USD".to_string(), 1.1)], &mut conn).unwrap();
+
+    let rate_converter = CurrencyConverter::new(&conn);
+    let converted_balance = tx_data.get_last_balance(&rate_converter, &conn);
+    let converted_income = tx_data.get_total_income(None, &rate_converter, &conn);
+
+    // the reverse rate is used when only the base to method currency rate exists
+    set_base_currency("EUR", &mut conn).unwrap();
+    set_exchange_rates(&[("EUR".to_string(), "USD".to_string(), 1.25)], &mut conn).unwrap();
+
+    let converter = CurrencyConverter::new(&conn);
+    let reverse_converted = converter.to_base(Cent::new(5000), "test 2");
+    let total_header = converter.total_header();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        same_currency_balance,
+        vec!["Balance", "100.00", "50.00", "150.00"]
+    );
+    assert_eq!(
+        missing_rate_balance,
+        vec!["Balance", "100.00", "50.00", "50.00"]
+    );
+    assert_eq!(same_currency_note, None);
+    assert_eq!(missing_currencies, vec!["EUR".to_string()]);
+    assert_eq!(missing_rate_note, Some("No rate to USD: EUR".to_string()));
+    assert_eq!(
+        converted_balance,
+        vec!["Balance", "100.00", "50.00", "160.00"]
+    );
+    assert_eq!(
+        converted_income,
+        vec!["Income", "100.00", "50.00", "160.00"]
+    );
+    assert_eq!(reverse_converted, Cent::new(4000));
+    assert_eq!(total_header, "Total EUR");
+}
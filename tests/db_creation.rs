@@ -103,6 +103,6 @@ fn check_repositioning_columns() {
 
     assert_eq!(status, Ok(()));
     assert_eq!(expected_tx_methods, tx_methods);
-    assert_eq!(old_last_balances, vec!["159", "0"]);
-    assert_eq!(last_balances, vec!["0", "159"]);
+    assert_eq!(old_last_balances, vec!["159.00", "0.00"]);
+    assert_eq!(last_balances, vec!["0.00", "159.00"]);
 }
@@ -1,8 +1,18 @@
 extern crate rex_tui;
-use rex_tui::db::{add_tags_column, update_balance_type};
-use rex_tui::utility::{check_old_balance_sql, get_all_tx_columns, get_last_balance_id};
+use rex_tui::db::{
+    add_tags_column, get_migration_backup_path, migrate_to_cents, migrate_to_currencies,
+    migrate_to_tags, migrate_to_year_range, run_migrations, update_balance_type, DB_VERSION,
+    MIGRATIONS,
+};
+use rex_tui::outputs::MigrationError;
+use rex_tui::utility::{
+    check_old_balance_sql, get_all_table_names, get_all_tags, get_all_tx_columns, get_all_tx_tags,
+    get_base_currency, get_db_version, get_last_balance_id, get_last_balances,
+    get_tx_method_currencies, get_year_range, Cent,
+};
 use rusqlite::Connection;
 use std::fs;
+use std::path::Path;
 
 fn check_test_db(file_name: &str) {
     if let Ok(metadata) = fs::metadata(file_name) {
@@ -32,7 +42,9 @@ fn check_tags_migration() {
     .unwrap();
 
     let old_columns = get_all_tx_columns(&conn);
-    add_tags_column(&mut conn).unwrap();
+    let sp = conn.savepoint().unwrap();
+    add_tags_column(&sp).unwrap();
+    sp.commit().unwrap();
     let new_columns = get_all_tx_columns(&conn);
 
     let expected_columns = vec![
@@ -92,7 +104,9 @@ fn check_balance_migration() {
     let old_db_status = check_old_balance_sql(&conn);
     let old_last_balance_id = get_last_balance_id(&conn).unwrap();
 
-    update_balance_type(&mut conn).unwrap();
+    let sp = conn.savepoint().unwrap();
+    update_balance_type(&sp).unwrap();
+    sp.commit().unwrap();
 
     let last_balances = conn
         .query_row(query, [], |row| {
@@ -122,3 +136,335 @@ fn check_balance_migration() {
         vec!["200.19".to_string(), "159.19".to_string()]
     );
 }
+
+#[test]
+fn check_year_range_migration() {
+    let file_name = "db_update_3.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    conn.execute(
+        r#"CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        test1 REAL DEFAULT 0.00,
+        "test 2" REAL DEFAULT 0.00
+    );"#,
+        [],
+    )
+    .unwrap();
+
+    let query = r#"INSERT INTO balance_all ("test1", "test 2") VALUES (0.00, 0.00)"#.to_string();
+    for _i in 0..193 {
+        conn.execute(&query, []).unwrap();
+    }
+
+    let old_tables = get_all_table_names(&conn);
+
+    let sp = conn.savepoint().unwrap();
+    migrate_to_year_range(&sp).unwrap();
+    sp.commit().unwrap();
+
+    let year_range = get_year_range(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(!old_tables.contains(&"year_range".to_string()));
+    assert_eq!(year_range, (2022, 2037));
+}
+
+#[test]
+fn check_cent_migration() {
+    let file_name = "db_update_4.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    conn.execute_batch(
+        r#"CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tags TEXT
+    );
+    CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        test1 REAL DEFAULT 0.00,
+        "test 2" REAL DEFAULT 0.00
+    );
+    CREATE TABLE changes_all (
+        date TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        test1 TEXT DEFAULT 0.00,
+        "test 2" TEXT DEFAULT 0.00,
+        CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+        VALUES ("2022-08-19", "Testing transaction", "test1", "100.19", "Income", "Unknown");
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+        VALUES ("2022-08-20", "Testing transaction", "test 2", "0.1", "Expense", "Unknown");
+    INSERT INTO changes_all (date, id_num, test1, "test 2") VALUES ("2022-08-19", 1, "↑100.19", "0.00");
+    INSERT INTO changes_all (date, id_num, test1, "test 2") VALUES ("2022-08-20", 2, "0.00", "↓0.10");
+    INSERT INTO balance_all (test1, "test 2") VALUES (100.19, -0.1);
+    INSERT INTO balance_all (test1, "test 2") VALUES (100.19, -0.1);"#,
+    )
+    .unwrap();
+
+    // the migration runner sets these before migrating
+    conn.pragma_update(None, "foreign_keys", false).unwrap();
+    conn.pragma_update(None, "legacy_alter_table", true)
+        .unwrap();
+
+    let sp = conn.savepoint().unwrap();
+    migrate_to_cents(&sp).unwrap();
+    sp.commit().unwrap();
+
+    let amounts = conn
+        .prepare("SELECT amount FROM tx_all ORDER BY id_num")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|amount| amount.unwrap())
+        .collect::<Vec<Cent>>();
+
+    let total_changes: i64 = conn
+        .query_row("SELECT COUNT(*) FROM changes_all", [], |row| row.get(0))
+        .unwrap();
+
+    let changes_schema: String = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'changes_all'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let last_balances = get_last_balances(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(changes_schema.contains("REFERENCES tx_all(id_num)"));
+    assert_eq!(amounts, vec![Cent::new(10019), Cent::new(10)]);
+    assert_eq!(total_changes, 2);
+    assert_eq!(
+        last_balances,
+        vec!["100.19".to_string(), "-0.10".to_string()]
+    );
+}
+
+#[test]
+fn check_currency_migration() {
+    let file_name = "db_update_5.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    conn.execute(
+        r#"CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        test1 INTEGER DEFAULT 0,
+        "test 2" INTEGER DEFAULT 0
+    );"#,
+        [],
+    )
+    .unwrap();
+
+    let old_tables = get_all_table_names(&conn);
+
+    let sp = conn.savepoint().unwrap();
+    migrate_to_currencies(&sp).unwrap();
+    sp.commit().unwrap();
+
+    let tables = get_all_table_names(&conn);
+    let currencies = get_tx_method_currencies(&conn);
+    let base_currency = get_base_currency(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(!old_tables.contains(&"tx_methods".to_string()));
+    assert!(tables.contains(&"tx_methods".to_string()));
+    assert!(tables.contains(&"exchange_rates".to_string()));
+    assert_eq!(
+        currencies,
+        vec![
+            ("test1".to_string(), "USD".to_string()),
+            ("test 2".to_string(), "USD".to_string())
+        ]
+    );
+    assert_eq!(base_currency, "USD");
+}
+
+#[test]
+fn check_tags_table_migration() {
+    let file_name = "db_update_7.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    conn.execute_batch(
+        r#"CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount INTEGER,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        tags TEXT
+    );
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+        VALUES ("2022-08-19", "Testing transaction", "test1", 100, "Income", "Food, Car");
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+        VALUES ("2022-08-20", "Testing transaction", "test1", 100, "Expense", "Car,Food ,  Gift,");
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type, tags)
+        VALUES ("2022-08-21", "Testing transaction", "test1", 100, "Expense", "");"#,
+    )
+    .unwrap();
+
+    let sp = conn.savepoint().unwrap();
+    migrate_to_tags(&sp).unwrap();
+    sp.commit().unwrap();
+
+    let all_tags = get_all_tags(&conn);
+    let tx_tags = get_all_tx_tags(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        all_tags,
+        vec!["Car".to_string(), "Food".to_string(), "Gift".to_string()]
+    );
+    assert_eq!(tx_tags[&1], vec!["Food".to_string(), "Car".to_string()]);
+    assert_eq!(
+        tx_tags[&2],
+        vec!["Car".to_string(), "Food".to_string(), "Gift".to_string()]
+    );
+    assert!(!tx_tags.contains_key(&3));
+}
+
+#[test]
+fn check_migration_order() {
+    let versions = MIGRATIONS
+        .iter()
+        .map(|migration| migration.version)
+        .collect::<Vec<i32>>();
+
+    let expected_versions = (1..=DB_VERSION).collect::<Vec<i32>>();
+
+    assert_eq!(versions, expected_versions);
+}
+
+#[test]
+fn check_legacy_db_migration() {
+    let file_name = "db_update_6.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    // the oldest database layout without the tags column and with TEXT balances
+    conn.execute_batch(
+        r#"CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount TEXT,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );
+    CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        test1 TEXT DEFAULT 0.00,
+        "test 2" TEXT DEFAULT 0.00
+    );
+    CREATE TABLE changes_all (
+        date TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        test1 TEXT DEFAULT 0.00,
+        "test 2" TEXT DEFAULT 0.00,
+        CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type)
+        VALUES ("2022-08-19", "Testing transaction", "test1", "100.19", "Income");
+    INSERT INTO tx_all (date, details, tx_method, amount, tx_type)
+        VALUES ("2022-08-20", "Testing transaction", "test 2", "0.10", "Expense");
+    INSERT INTO changes_all (date, id_num, test1, "test 2") VALUES ("2022-08-19", 1, "↑100.19", "0.00");
+    INSERT INTO changes_all (date, id_num, test1, "test 2") VALUES ("2022-08-20", 2, "0.00", "↓0.10");"#,
+    )
+    .unwrap();
+
+    for _i in 0..48 {
+        conn.execute(
+            r#"INSERT INTO balance_all ("test1", "test 2") VALUES ("0.00", "0.00")"#,
+            [],
+        )
+        .unwrap();
+    }
+    conn.execute(
+        r#"INSERT INTO balance_all ("test1", "test 2") VALUES ("100.19", "-0.10")"#,
+        [],
+    )
+    .unwrap();
+
+    let total_migrations = run_migrations(&mut conn).unwrap();
+    let second_run = run_migrations(&mut conn).unwrap();
+
+    let db_version = get_db_version(&conn);
+    let last_balances = get_last_balances(&conn);
+    let year_range = get_year_range(&conn).unwrap();
+    let currencies = get_tx_method_currencies(&conn);
+
+    let total_changes: i64 = conn
+        .query_row("SELECT COUNT(*) FROM changes_all", [], |row| row.get(0))
+        .unwrap();
+
+    let foreign_keys: bool = conn
+        .pragma_query_value(None, "foreign_keys", |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // Only the starting version gets a backup
+    let mut backups_exist = Vec::new();
+    for version in 0..DB_VERSION {
+        let backup_path = get_migration_backup_path(Path::new(file_name), version);
+        backups_exist.push(backup_path.is_file());
+        let _ = fs::remove_file(backup_path);
+    }
+
+    assert_eq!(total_migrations, MIGRATIONS.len());
+    assert_eq!(second_run, 0);
+    assert_eq!(db_version, DB_VERSION);
+    assert_eq!(
+        last_balances,
+        vec!["100.19".to_string(), "-0.10".to_string()]
+    );
+    assert_eq!(year_range, (2022, 2037));
+    assert_eq!(currencies.len(), 2);
+    assert_eq!(total_changes, 2);
+    assert!(foreign_keys);
+    assert!(backups_exist[0]);
+    assert!(backups_exist[1..].iter().all(|exists| !exists));
+}
+
+#[test]
+fn check_newer_db_refused() {
+    let file_name = "db_update_7.sqlite";
+    check_test_db(file_name);
+    let mut conn = Connection::open(file_name).unwrap();
+
+    conn.pragma_update(None, "user_version", DB_VERSION + 1)
+        .unwrap();
+
+    let result = run_migrations(&mut conn);
+    let db_version = get_db_version(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(MigrationError::NewerVersion(version, DB_VERSION)) if version == DB_VERSION + 1
+    ));
+    assert_eq!(db_version, DB_VERSION + 1);
+}
@@ -0,0 +1,235 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    create_db, export_txs, get_export_rows, parse_export_args, ExportColumn, ExportOptions,
+    ExportSource,
+};
+use rex_tui::outputs::ExportError;
+use rex_tui::tx_handler::add_tx;
+use rusqlite::Connection;
+use std::fs;
+use std::path::PathBuf;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_test_txs(conn: &mut Connection) {
+    for (date, details, method, amount, tx_type, tags) in [
+        ("2023-07-01", "Salary", "test1", "1000.00", "Income", "Work"),
+        (
+            "2023-07-05",
+            "Food, drinks",
+            "test 2",
+            "50.00",
+            "Expense",
+            "Food",
+        ),
+        (
+            "2023-08-10",
+            "Move",
+            "test1 to test 2",
+            "200.00",
+            "Transfer",
+            "Unknown",
+        ),
+        (
+            "2023-09-01",
+            "Rent \"July\"",
+            "test1",
+            "300.00",
+            "Expense",
+            "Home",
+        ),
+    ] {
+        add_tx(date, details, method, amount, tx_type, tags, None, conn).unwrap();
+    }
+}
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn check_export_rows() {
+    let file_name = "export_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_test_txs(&mut conn);
+
+    let all_columns = get_export_rows(
+        &ExportSource::DateRange(date("2023-07-02"), date("2023-08-31")),
+        &ExportOptions::default(),
+        &conn,
+    )
+    .unwrap();
+
+    let options = ExportOptions {
+        columns: vec![
+            ExportColumn::IdNum,
+            ExportColumn::Date,
+            ExportColumn::Amount,
+        ],
+        delimiter: ';',
+        date_format: "%d/%m/%Y".to_string(),
+    };
+
+    // The search result only has the ids, the order stays the same as the Home page
+    let search_rows = get_export_rows(
+        &ExportSource::TxIds(vec!["4".to_string(), "1".to_string()]),
+        &options,
+        &conn,
+    )
+    .unwrap();
+
+    let invalid_format = get_export_rows(
+        &ExportSource::TxIds(Vec::new()),
+        &ExportOptions {
+            date_format: "%Q".to_string(),
+            ..ExportOptions::default()
+        },
+        &conn,
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        all_columns,
+        vec![
+            vec![
+                "Date",
+                "Details",
+                "Transaction Method",
+                "Amount",
+                "Transaction Type",
+                "Tags",
+                "ID",
+                "test1 Balance",
+                "test 2 Balance",
+            ],
+            vec![
+                "2023-07-05",
+                "Food, drinks",
+                "test 2",
+                "50.00",
+                "Expense",
+                "Food",
+                "2",
+                "1000.00",
+                "-50.00",
+            ],
+            vec![
+                "2023-08-10",
+                "Move",
+                "test1 to test 2",
+                "200.00",
+                "Transfer",
+                "Unknown",
+                "3",
+                "800.00",
+                "150.00",
+            ],
+        ]
+    );
+    assert_eq!(
+        search_rows,
+        vec![
+            vec!["ID", "Date", "Amount"],
+            vec!["1", "01/07/2023", "1000.00"],
+            vec!["4", "01/09/2023", "300.00"],
+        ]
+    );
+    assert!(matches!(
+        invalid_format,
+        Err(ExportError::InvalidDateFormat(_))
+    ));
+}
+
+#[test]
+fn check_export_file() {
+    let file_name = "export_2.sqlite";
+    let csv_name = "export_2.csv";
+    let mut conn = create_test_db(file_name);
+
+    add_test_txs(&mut conn);
+    add_tx(
+        "2023-09-02",
+        "Line\rbreak",
+        "test1",
+        "10.00",
+        "Expense",
+        "Home",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let command = parse_export_args(
+        &[
+            "--columns",
+            "date,details,amount",
+            "--from",
+            "2023-07-05",
+            "--output",
+            csv_name,
+        ]
+        .map(String::from),
+    )
+    .unwrap();
+
+    let total = export_txs(&command.source, &command.options, &command.output, &conn).unwrap();
+    let content = fs::read_to_string(csv_name).unwrap();
+
+    let missing_output = parse_export_args(&["--from".to_string(), "2023-07-05".to_string()]);
+    let invalid_column =
+        parse_export_args(&["--columns", "date,price", "--output", csv_name].map(String::from));
+    let invalid_delimiter =
+        parse_export_args(&["--delimiter", "\"", "--output", csv_name].map(String::from));
+    let invalid_date = parse_export_args(&["--to", "07-05-2023"].map(String::from));
+    let unknown_argument = parse_export_args(&["--format", "csv"].map(String::from));
+    let tab_delimiter =
+        parse_export_args(&["--delimiter", "tab", "--output", csv_name].map(String::from)).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(csv_name).unwrap();
+
+    assert_eq!(total, 4);
+    assert_eq!(command.output, PathBuf::from(csv_name));
+    assert_eq!(
+        content,
+        "Date,Details,Amount
+2023-07-05,\"Food, drinks\",50.00
+2023-08-10,Move,200.00
+2023-09-01,\"Rent \"\"July\"\"\",300.00
+2023-09-02,\"Line\rbreak\",10.00
+"
+    );
+    assert!(matches!(
+        missing_output,
+        Err(ExportError::InvalidArgument(arg)) if arg == "--output"
+    ));
+    assert!(matches!(
+        invalid_column,
+        Err(ExportError::InvalidColumn(column)) if column == "price"
+    ));
+    assert!(matches!(
+        invalid_delimiter,
+        Err(ExportError::InvalidDelimiter(_))
+    ));
+    assert!(matches!(invalid_date, Err(ExportError::InvalidDate(_))));
+    assert!(matches!(
+        unknown_argument,
+        Err(ExportError::InvalidArgument(arg)) if arg == "--format"
+    ));
+    assert_eq!(tab_delimiter.options.delimiter, '\t');
+}
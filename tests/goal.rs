@@ -0,0 +1,269 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    add_goal, create_db, delete_goal, delete_tx_method, get_all_goals, rename_column, rename_tag,
+    update_goal, Goal, GoalSource,
+};
+use rex_tui::goal_page::GoalData;
+use rex_tui::outputs::TxMethodError;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::Cent;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_test_tx(
+    date: &str,
+    method: &str,
+    amount: &str,
+    tx_type: &str,
+    tags: &str,
+    conn: &mut Connection,
+) {
+    add_tx(
+        date,
+        "Testing transaction",
+        method,
+        amount,
+        tx_type,
+        tags,
+        None,
+        conn,
+    )
+    .unwrap();
+}
+
+fn new_goal(name: &str, target: i64, target_date: &str, source: GoalSource) -> Goal {
+    Goal {
+        goal_id: 0,
+        name: name.to_string(),
+        target: Cent::new(target),
+        target_date: NaiveDate::parse_from_str(target_date, "%Y-%m-%d").unwrap(),
+        source,
+    }
+}
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn check_goal_progress() {
+    let file_name = "goal_progress.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_goal(
+        &new_goal(
+            "Emergency fund",
+            500_000,
+            "2023-12-31",
+            GoalSource::TxMethod("test1".to_string()),
+        ),
+        &mut conn,
+    )
+    .unwrap();
+    add_goal(
+        &new_goal(
+            "Vacation",
+            50000,
+            "2023-08-15",
+            GoalSource::Tag("Vacation".to_string()),
+        ),
+        &mut conn,
+    )
+    .unwrap();
+
+    add_test_tx(
+        "2023-07-10",
+        "test1",
+        "1000.00",
+        "Income",
+        "Salary",
+        &mut conn,
+    );
+    add_test_tx(
+        "2023-07-11",
+        "test 2",
+        "300.00",
+        "Income",
+        "Vacation:Flights",
+        &mut conn,
+    );
+    add_test_tx(
+        "2023-07-12",
+        "test1 to test 2",
+        "250.00",
+        "Transfer",
+        "Vacation",
+        &mut conn,
+    );
+    add_test_tx(
+        "2023-07-13",
+        "test 2",
+        "100.00",
+        "Expense",
+        "Vacation, Food",
+        &mut conn,
+    );
+    add_test_tx(
+        "2023-07-14",
+        "test 2",
+        "40.00",
+        "Expense",
+        "Food",
+        &mut conn,
+    );
+
+    // The tx method goal uses the balance while the tag goal counts the txs of the tag and its children.
+    // Expenses take from the tag total
+    let goal_data = GoalData::new(date("2023-07-20"), &conn);
+    assert_eq!(
+        goal_data.get_txs(),
+        vec![
+            vec![
+                "Vacation",
+                "Tag: Vacation",
+                "500.00",
+                "450.00",
+                "50.00",
+                "90.00",
+                "2023-08-15",
+                "2",
+                "25.00",
+                "Saving"
+            ],
+            vec![
+                "Emergency fund",
+                "TX Method: test1",
+                "5000.00",
+                "750.00",
+                "4250.00",
+                "15.00",
+                "2023-12-31",
+                "6",
+                "708.33",
+                "Saving"
+            ],
+        ]
+    );
+
+    // Once the target date passes, the whole remaining amount is needed
+    let goal_data = GoalData::new(date("2024-01-01"), &conn);
+    let status = goal_data.get_goal(1);
+    assert_eq!(status.months_left(date("2024-01-01")), 0);
+    assert_eq!(
+        status.monthly_needed(date("2024-01-01")),
+        Cent::new(425_000)
+    );
+    assert_eq!(goal_data.get_txs()[1][9], "Overdue");
+
+    add_test_tx(
+        "2023-08-01",
+        "test 2",
+        "60.00",
+        "Income",
+        "Vacation",
+        &mut conn,
+    );
+
+    let goal_data = GoalData::new(date("2023-08-02"), &conn);
+    let status = goal_data.get_goal(0);
+    assert!(status.is_reached());
+    assert_eq!(status.remaining(), Cent::default());
+    assert_eq!(status.monthly_needed(date("2023-08-02")), Cent::default());
+    assert_eq!(goal_data.get_txs()[0][9], "Reached");
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_goal_changes() {
+    let file_name = "goal_changes.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_test_tx(
+        "2023-07-11",
+        "test 2",
+        "300.00",
+        "Income",
+        "Vacation",
+        &mut conn,
+    );
+
+    let method_goal_id = add_goal(
+        &new_goal(
+            "Emergency fund",
+            500_000,
+            "2027-12-31",
+            GoalSource::TxMethod("test1".to_string()),
+        ),
+        &mut conn,
+    )
+    .unwrap();
+    add_goal(
+        &new_goal(
+            "Vacation",
+            50000,
+            "2027-08-15",
+            GoalSource::Tag("Vacation".to_string()),
+        ),
+        &mut conn,
+    )
+    .unwrap();
+
+    // Goal names are unique
+    assert!(add_goal(
+        &new_goal(
+            "Vacation",
+            100,
+            "2027-08-15",
+            GoalSource::Tag("Travel".to_string()),
+        ),
+        &mut conn
+    )
+    .is_err());
+
+    // Renaming a tag or a tx method keeps the goals tracking them
+    rename_tag("Vacation", "Travel", &mut conn).unwrap();
+    rename_column("test1", "Savings", &mut conn).unwrap();
+
+    let goals = get_all_goals(&conn);
+    assert_eq!(goals[0].source, GoalSource::Tag("Travel".to_string()));
+    assert_eq!(goals[1].source, GoalSource::TxMethod("Savings".to_string()));
+
+    let goal_data = GoalData::new(date("2023-07-20"), &conn);
+    assert_eq!(goal_data.get_goal(0).saved, Cent::new(30000));
+
+    // A tx method can not be deleted while a goal tracks it
+    let status = delete_tx_method("Savings", &mut conn);
+    assert!(matches!(status, Err(TxMethodError::UsedByGoal(_, 1))));
+
+    let mut goal = goals[1].clone();
+    goal.source = GoalSource::Tag("Travel".to_string());
+    goal.target_date = date("2026-01-31");
+    update_goal(&goal, &mut conn).unwrap();
+
+    let goals = get_all_goals(&conn);
+    assert_eq!(goals[0].name, "Emergency fund");
+    assert_eq!(goals[0].source, GoalSource::Tag("Travel".to_string()));
+
+    delete_tx_method("Savings", &mut conn).unwrap();
+
+    delete_goal(method_goal_id, &mut conn).unwrap();
+    assert_eq!(get_all_goals(&conn).len(), 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,363 @@
+extern crate rex_tui;
+use rex_tui::db::{create_db, get_tx_splits, rename_column};
+use rex_tui::outputs::HistoryError;
+use rex_tui::page_handler::{ActivityType, DateType, TxTab};
+use rex_tui::tx_handler::{
+    add_tx, delete_tx, redo_activity, restore_deleted_tx, revert_edit, undo_activity, TxData,
+};
+use rex_tui::utility::{
+    add_new_activity, add_new_activity_tx, get_last_balances, get_last_tx, get_tx_id_num,
+    switch_tx_index,
+};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+/// Adds a tx the same way the Add TX page does
+fn add_new_tx(date: &str, tx_method: &str, amount: &str, tx_type: &str, conn: &mut Connection) {
+    add_tx(
+        date, "Testing", tx_method, amount, tx_type, "Food", None, conn,
+    )
+    .unwrap();
+
+    let activity_num = add_new_activity(ActivityType::NewTX, conn);
+    add_new_activity_tx(&get_last_tx(conn), activity_num, conn);
+}
+
+/// All txs and the final balance
+fn get_state(conn: &Connection) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut statement = conn
+        .prepare("SELECT date, details, tx_method, amount, tx_type, tags, id_num FROM tx_all ORDER BY id_num")
+        .unwrap();
+
+    let txs = statement
+        .query_map([], |row| {
+            let amount: i64 = row.get(3)?;
+            let id_num: i32 = row.get(6)?;
+            Ok(vec![
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                amount.to_string(),
+                row.get(4)?,
+                row.get(5)?,
+                id_num.to_string(),
+            ])
+        })
+        .unwrap()
+        .collect::<Result<Vec<Vec<String>>, _>>()
+        .unwrap();
+
+    (txs, get_last_balances(conn))
+}
+
+#[test]
+fn check_undo_redo() {
+    let file_name = "undo_redo.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    assert!(matches!(
+        undo_activity(&mut conn),
+        Err(HistoryError::NothingToUndo)
+    ));
+
+    let mut states = vec![get_state(&conn)];
+
+    add_new_tx("2023-07-19", "test1", "100.00", "Expense", &mut conn);
+    states.push(get_state(&conn));
+
+    add_new_tx("2023-07-19", "test 2", "50.00", "Income", &mut conn);
+    states.push(get_state(&conn));
+
+    add_new_tx(
+        "2023-08-01",
+        "test1 to test 2",
+        "20.00",
+        "Transfer",
+        &mut conn,
+    );
+    states.push(get_state(&conn));
+
+    let mut tx_data = TxData::custom(
+        "19-07-2023",
+        "Edited",
+        "test 2",
+        "",
+        "30.00",
+        "Income",
+        "Car",
+        1,
+    );
+    tx_data.add_tx(&mut conn).unwrap();
+    states.push(get_state(&conn));
+
+    let tx_1 = get_tx_id_num(1, &conn);
+    let tx_2 = get_tx_id_num(2, &conn);
+    switch_tx_index(1, 2, &tx_1, &tx_2, &mut conn);
+    states.push(get_state(&conn));
+
+    let deleted_tx = get_tx_id_num(3, &conn);
+    delete_tx(3, &mut conn).unwrap();
+    let activity_num = add_new_activity(ActivityType::DeleteTX(Some(3)), &conn);
+    add_new_activity_tx(&deleted_tx, activity_num, &conn);
+    states.push(get_state(&conn));
+
+    // Searching does not change any tx so it is skipped by undo
+    TxData::custom("", "Testing", "", "", "", "", "", 0).get_search_tx(&DateType::Exact, &conn);
+
+    assert!(matches!(
+        redo_activity(&mut conn),
+        Err(HistoryError::NothingToRedo)
+    ));
+
+    for expected_state in states.iter().rev().skip(1) {
+        undo_activity(&mut conn).unwrap();
+        assert_eq!(&get_state(&conn), expected_state);
+    }
+
+    assert!(matches!(
+        undo_activity(&mut conn),
+        Err(HistoryError::NothingToUndo)
+    ));
+
+    for expected_state in states.iter().skip(1) {
+        redo_activity(&mut conn).unwrap();
+        assert_eq!(&get_state(&conn), expected_state);
+    }
+
+    assert!(matches!(
+        redo_activity(&mut conn),
+        Err(HistoryError::NothingToRedo)
+    ));
+
+    // A new change after undoing clears the redo history
+    undo_activity(&mut conn).unwrap();
+    undo_activity(&mut conn).unwrap();
+    add_new_tx("2023-09-01", "test1", "10.00", "Expense", &mut conn);
+
+    assert!(matches!(
+        redo_activity(&mut conn),
+        Err(HistoryError::NothingToRedo)
+    ));
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_state(&conn), states[4]);
+
+    let undo_count: i32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM activities WHERE activity_type = 'Undo'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let redo_count: i32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM activities WHERE activity_type = 'Redo'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+
+    assert_eq!(undo_count, 9);
+    assert_eq!(redo_count, 6);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_undo_unknown_method() {
+    let file_name = "undo_unknown_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_new_tx("2023-07-19", "test1", "100.00", "Expense", &mut conn);
+
+    let deleted_tx = get_tx_id_num(1, &conn);
+    delete_tx(1, &mut conn).unwrap();
+    let activity_num = add_new_activity(ActivityType::DeleteTX(Some(1)), &conn);
+    add_new_activity_tx(&deleted_tx, activity_num, &conn);
+
+    rename_column("test1", "test 3", &mut conn).unwrap();
+
+    let state = get_state(&conn);
+
+    assert!(matches!(
+        undo_activity(&mut conn),
+        Err(HistoryError::UnknownTxMethod(method)) if method == "test1"
+    ));
+    assert_eq!(get_state(&conn), state);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+fn get_last_activity_num(conn: &Connection) -> i32 {
+    conn.query_row(
+        "SELECT activity_num FROM activities ORDER BY activity_num DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn check_restoring_deleted_tx() {
+    let file_name = "restore_deleted_tx.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_new_tx("2023-07-19", "test1", "100.00", "Expense", &mut conn);
+    add_new_tx("2023-07-20", "test 2", "50.00", "Income", &mut conn);
+    let expected_state = get_state(&conn);
+
+    let add_activity_num = get_last_activity_num(&conn);
+
+    assert!(matches!(
+        restore_deleted_tx(add_activity_num, &mut conn),
+        Err(HistoryError::NotRestorable)
+    ));
+
+    let deleted_tx = get_tx_id_num(1, &conn);
+    delete_tx(1, &mut conn).unwrap();
+    let delete_activity_num = add_new_activity(ActivityType::DeleteTX(Some(1)), &conn);
+    add_new_activity_tx(&deleted_tx, delete_activity_num, &conn);
+
+    // The original id_num is free so it is used again
+    let restored_id = restore_deleted_tx(delete_activity_num, &mut conn).unwrap();
+    assert_eq!(restored_id, 1);
+    assert_eq!(get_state(&conn), expected_state);
+
+    // Restoring again gives the tx a new id_num
+    let restored_id = restore_deleted_tx(delete_activity_num, &mut conn).unwrap();
+    assert_eq!(restored_id, 3);
+
+    let (txs, balances) = get_state(&conn);
+    assert_eq!(txs.len(), 3);
+    assert_eq!(txs[2][..6], expected_state.0[0][..6]);
+    assert_eq!(balances, vec!["-200.00".to_string(), "50.00".to_string()]);
+
+    // Restoring is an activity that can be undone
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_state(&conn), expected_state);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_reverting_edit() {
+    let file_name = "revert_edit.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_new_tx("2023-07-19", "test1", "100.00", "Expense", &mut conn);
+    let expected_state = get_state(&conn);
+
+    let mut tx_data = TxData::custom(
+        "20-08-2023",
+        "Edited",
+        "test1",
+        "test 2",
+        "30.00",
+        "Transfer",
+        "Car",
+        1,
+    );
+    tx_data.add_tx(&mut conn).unwrap();
+    let edit_activity_num = get_last_activity_num(&conn);
+    let edited_state = get_state(&conn);
+
+    // Edit the tx again before reverting the first edit
+    let mut tx_data = TxData::custom(
+        "21-08-2023",
+        "Edited",
+        "test 2",
+        "",
+        "5.00",
+        "Income",
+        "Car",
+        1,
+    );
+    tx_data.add_tx(&mut conn).unwrap();
+
+    revert_edit(edit_activity_num, &mut conn).unwrap();
+    assert_eq!(get_state(&conn), expected_state);
+
+    assert!(matches!(
+        revert_edit(1, &mut conn),
+        Err(HistoryError::NotRestorable)
+    ));
+
+    undo_activity(&mut conn).unwrap();
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_state(&conn), edited_state);
+
+    delete_tx(1, &mut conn).unwrap();
+
+    assert!(matches!(
+        revert_edit(edit_activity_num, &mut conn),
+        Err(HistoryError::TxNotFound(1))
+    ));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_undo_keeps_splits() {
+    let file_name = "undo_keeps_splits.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_new_tx("2023-07-19", "test1", "50.00", "Expense", &mut conn);
+
+    let splits = "Food | 30.00 | Milk; Home | 20.00";
+    let mut tx_data = TxData::custom(
+        "19-07-2023",
+        "Testing",
+        "test1",
+        "",
+        "50.00",
+        "Expense",
+        "",
+        1,
+    );
+    tx_data.go_current_index(&TxTab::Splits);
+
+    for i in splits.chars() {
+        tx_data.edit_splits(Some(i));
+    }
+
+    tx_data.add_tx(&mut conn).unwrap();
+    assert_eq!(get_tx_id_num(1, &conn)[7], splits);
+
+    let deleted_tx = get_tx_id_num(1, &conn);
+    delete_tx(1, &mut conn).unwrap();
+    let activity_num = add_new_activity(ActivityType::DeleteTX(Some(1)), &conn);
+    add_new_activity_tx(&deleted_tx, activity_num, &conn);
+
+    assert!(get_tx_splits(1, &conn).is_empty());
+
+    // Undoing the delete brings back the split lines too
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_tx_id_num(1, &conn)[7], splits);
+
+    // Undoing the edit removes the split lines that were added with it
+    undo_activity(&mut conn).unwrap();
+    assert!(get_tx_splits(1, &conn).is_empty());
+
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(get_tx_id_num(1, &conn)[5], "Food, Home");
+    assert_eq!(get_tx_id_num(1, &conn)[7], splits);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,270 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    create_db, delete_tx_method, get_all_holdings, import_prices, merge_tx_methods, parse_prices,
+    rename_column, set_holding, set_prices, Holding, MarketValues, Price,
+};
+use rex_tui::home_page::TransactionData;
+use rex_tui::outputs::{PriceImportError, TxMethodError};
+use rex_tui::summary_page::SummaryData;
+use rex_tui::tx_handler::add_tx;
+use rex_tui::utility::{Cent, CurrencyConverter};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn holding(tx_method: &str, symbol: &str, quantity: f64, cost_basis: i64) -> Holding {
+    Holding {
+        tx_method: tx_method.to_string(),
+        symbol: symbol.to_string(),
+        quantity,
+        cost_basis: Cent::new(cost_basis),
+    }
+}
+
+fn price(symbol: &str, date: &str, amount: i64) -> Price {
+    Price {
+        symbol: symbol.to_string(),
+        date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+        price: Cent::new(amount),
+    }
+}
+
+fn date(date: &str) -> Option<NaiveDate> {
+    Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap())
+}
+
+#[test]
+fn check_price_parsing() {
+    let file_name = "holding_1.sqlite";
+    let csv_name = "holding_1_prices.csv";
+    let mut conn = create_test_db(file_name);
+
+    let parsed =
+        parse_prices("symbol,date,price\n\naapl,2023-07-01,170.50\nBTC, 2023-07-02, 30000")
+            .unwrap();
+    let invalid_date = parse_prices("AAPL,07-01-2023,170.50");
+    let invalid_line = parse_prices("AAPL,2023-07-01,170.50\nAAPL,2023-07-02");
+
+    fs::write(csv_name, "AAPL,2023-07-01,170.50\nAAPL,2023-07-03,175\n").unwrap();
+    let total_imported = import_prices(csv_name.as_ref(), &mut conn).unwrap();
+
+    // an existing price of the same date gets replaced
+    set_prices(&[price("AAPL", "2023-07-03", 18000)], &mut conn).unwrap();
+
+    fs::write(csv_name, "AAPL,2023-07-05,190\nbroken line\n").unwrap();
+    let failed_import = import_prices(csv_name.as_ref(), &mut conn);
+
+    set_holding(&holding("test1", "AAPL", 2.0, 30000), &mut conn).unwrap();
+    let market_values = MarketValues::new(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(csv_name).unwrap();
+
+    assert_eq!(
+        parsed,
+        vec![
+            price("AAPL", "2023-07-01", 17050),
+            price("BTC", "2023-07-02", 3_000_000),
+        ]
+    );
+    assert!(matches!(
+        invalid_date,
+        Err(PriceImportError::InvalidLine(1, _))
+    ));
+    assert!(matches!(
+        invalid_line,
+        Err(PriceImportError::InvalidLine(2, _))
+    ));
+    assert_eq!(total_imported, 2);
+    assert!(matches!(
+        failed_import,
+        Err(PriceImportError::InvalidLine(2, _))
+    ));
+
+    // Nothing was saved from the failed import and the last price on or before the date is used
+    assert_eq!(market_values.price_on("AAPL", None), Some(Cent::new(18000)));
+    assert_eq!(market_values.price_on("AAPL", date("2023-06-30")), None);
+    assert_eq!(
+        market_values.price_on("AAPL", date("2023-07-02")),
+        Some(Cent::new(17050))
+    );
+    assert_eq!(
+        market_values.method_value("test1", date("2023-07-04")),
+        Cent::new(36000)
+    );
+    // The cost basis is used until a price exists
+    assert_eq!(
+        market_values.method_value("test1", date("2023-06-30")),
+        Cent::new(30000)
+    );
+}
+
+#[test]
+fn check_holding_values() {
+    let file_name = "holding_2.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2023-07-01",
+        "Details",
+        "test1",
+        "1000.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+    add_tx(
+        "2023-07-10",
+        "Details",
+        "test1",
+        "100.00",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    set_holding(&holding("test1", "AAPL", 10.0, 150_000), &mut conn).unwrap();
+    set_holding(&holding("test 2", "BTC", 0.5, 1_000_000), &mut conn).unwrap();
+    // updating an existing holding replaces it
+    set_holding(&holding("test1", "AAPL", 4.0, 60000), &mut conn).unwrap();
+
+    set_prices(
+        &[
+            price("AAPL", "2023-07-01", 16000),
+            price("AAPL", "2023-07-10", 14000),
+            price("BTC", "2023-07-05", 3_000_000),
+        ],
+        &mut conn,
+    )
+    .unwrap();
+
+    let holdings = get_all_holdings(&conn);
+
+    let tx_data = TransactionData::new(6, 0, &conn);
+    let converter = CurrencyConverter::new(&conn);
+    let market_value = tx_data.get_market_value(None, &converter, &conn);
+    let first_tx_market_value = tx_data.get_market_value(Some(0), &converter, &conn);
+    let net_worth = tx_data.get_net_worth(None, &converter, &conn);
+
+    let summary_holdings = SummaryData::new(&conn).get_holdings();
+    let total_value = MarketValues::new(&conn).total_value(None, &CurrencyConverter::new(&conn));
+
+    // a quantity of 0 removes the holding
+    set_holding(&holding("test 2", "BTC", 0.0, 0), &mut conn).unwrap();
+    let holdings_after_removal = get_all_holdings(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        holdings,
+        vec![
+            holding("test 2", "BTC", 0.5, 1_000_000),
+            holding("test1", "AAPL", 4.0, 60000),
+        ]
+    );
+    assert_eq!(market_value, Some(Cent::new(1_556_000)));
+    assert_eq!(total_value, Cent::new(1_556_000));
+    // BTC has no price on the first tx date so the cost basis is used
+    assert_eq!(first_tx_market_value, Some(Cent::new(1_064_000)));
+    assert_eq!(net_worth, Cent::new(90000 + 1_556_000));
+
+    assert_eq!(
+        summary_holdings,
+        vec![
+            vec![
+                "BTC".to_string(),
+                "test 2".to_string(),
+                "0.5".to_string(),
+                "10000.00".to_string(),
+                "30000.00".to_string(),
+                "15000.00".to_string(),
+                "5000.00".to_string(),
+                "50.00".to_string(),
+            ],
+            vec![
+                "AAPL".to_string(),
+                "test1".to_string(),
+                "4".to_string(),
+                "600.00".to_string(),
+                "140.00".to_string(),
+                "560.00".to_string(),
+                "-40.00".to_string(),
+                "-6.67".to_string(),
+            ],
+            vec![
+                "Total (USD)".to_string(),
+                String::new(),
+                String::new(),
+                "10600.00".to_string(),
+                String::new(),
+                "15560.00".to_string(),
+                "4960.00".to_string(),
+                "46.79".to_string(),
+            ],
+        ]
+    );
+    assert_eq!(
+        holdings_after_removal,
+        vec![holding("test1", "AAPL", 4.0, 60000)]
+    );
+}
+
+#[test]
+fn check_holding_tx_method_changes() {
+    let file_name = "holding_3.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    set_holding(&holding("test1", "AAPL", 2.0, 30000), &mut conn).unwrap();
+    set_holding(&holding("test 2", "AAPL", 1.0, 16000), &mut conn).unwrap();
+    set_holding(&holding("test 2", "BTC", 0.1, 300_000), &mut conn).unwrap();
+
+    let delete_status = delete_tx_method("test1", &mut conn);
+
+    rename_column("test1", "Broker", &mut conn).unwrap();
+    let renamed_holdings = get_all_holdings(&conn);
+
+    // merging adds the quantity and the cost basis of the same symbol
+    merge_tx_methods("test 2", "Broker", &mut conn).unwrap();
+    let merged_holdings = get_all_holdings(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(matches!(
+        delete_status,
+        Err(TxMethodError::UsedByHolding(method, 1)) if method == "test1"
+    ));
+    assert_eq!(
+        renamed_holdings,
+        vec![
+            holding("Broker", "AAPL", 2.0, 30000),
+            holding("test 2", "AAPL", 1.0, 16000),
+            holding("test 2", "BTC", 0.1, 300_000),
+        ]
+    );
+    assert_eq!(
+        merged_holdings,
+        vec![
+            holding("Broker", "AAPL", 3.0, 46000),
+            holding("Broker", "BTC", 0.1, 300_000),
+        ]
+    );
+}
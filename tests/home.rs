@@ -2,7 +2,7 @@ extern crate rex_tui;
 use rex_tui::db::*;
 use rex_tui::home_page::TransactionData;
 use rex_tui::tx_handler::add_tx;
-use rex_tui::utility::get_all_txs;
+use rex_tui::utility::{get_all_txs, CurrencyConverter};
 use rusqlite::Connection;
 use std::fs;
 
@@ -64,6 +64,7 @@ fn test_home_data() {
 
     let tx_data_1 = TransactionData::new(1, 1, &conn);
     let tx_data_2 = TransactionData::new(6, 1, &conn);
+    let converter = CurrencyConverter::new(&conn);
 
     let is_tx_empty_1 = tx_data_1.is_tx_empty();
     let is_tx_empty_2 = tx_data_2.is_tx_empty();
@@ -83,6 +84,7 @@ fn test_home_data() {
         "100.00",
         "Expense",
         "Food",
+        "Cleared",
     ]
     .into_iter()
     .map(|a| a.to_string())
@@ -94,6 +96,7 @@ fn test_home_data() {
         "200.00",
         "Income",
         "Food",
+        "Cleared",
     ]
     .into_iter()
     .map(|a| a.to_string())
@@ -106,8 +109,8 @@ fn test_home_data() {
 
     let all_changes_1 = tx_data_2.get_changes(0);
     let all_changes_2 = tx_data_2.get_changes(1);
-    let all_balance_1 = tx_data_2.get_balance(0);
-    let all_balance_2 = tx_data_2.get_balance(1);
+    let all_balance_1 = tx_data_2.get_balance(0, &converter, &conn);
+    let all_balance_2 = tx_data_2.get_balance(1, &converter, &conn);
 
     // Tx method changes for that 1 tx
     let expected_changes_1: Vec<String> = vec!["Changes", "0.00", "↓100.00"]
@@ -134,8 +137,8 @@ fn test_home_data() {
     assert_eq!(all_balance_2, expected_balance_2);
     assert_eq!(all_changes_2, expected_changes_2);
 
-    let last_balance_1 = tx_data_1.get_last_balance(&conn);
-    let last_balance_2 = tx_data_2.get_last_balance(&conn);
+    let last_balance_1 = tx_data_1.get_last_balance(&converter, &conn);
+    let last_balance_2 = tx_data_2.get_last_balance(&converter, &conn);
 
     // Regardless of the month of the TransactionData, the last balance will be the same
     let expected_data: Vec<_> = vec!["Balance", "100.00", "-100.00", "0.00"]
@@ -153,8 +156,8 @@ fn test_home_data() {
     assert_eq!(id_num_1, 2);
     assert_eq!(id_num_2, 3);
 
-    let total_income_1 = tx_data_1.get_total_income(None, &conn);
-    let total_income_2 = tx_data_2.get_total_income(None, &conn);
+    let total_income_1 = tx_data_1.get_total_income(None, &converter, &conn);
+    let total_income_2 = tx_data_2.get_total_income(None, &converter, &conn);
 
     // No tx available within the selected index so 0 balance
     let expected_data_1: Vec<String> = vec!["Income", "0.00", "0.00", "0.00"]
@@ -170,8 +173,8 @@ fn test_home_data() {
     assert_eq!(total_income_1, expected_data_1);
     assert_eq!(total_income_2, expected_data_2);
 
-    let total_expense_1 = tx_data_1.get_total_expense(None, &conn);
-    let total_expense_2 = tx_data_2.get_total_expense(None, &conn);
+    let total_expense_1 = tx_data_1.get_total_expense(None, &converter, &conn);
+    let total_expense_2 = tx_data_2.get_total_expense(None, &converter, &conn);
 
     // No tx available within the selected index so 0
     let expected_data_1: Vec<String> = vec!["Expense", "0.00", "0.00", "0.00"]
@@ -0,0 +1,331 @@
+extern crate rex_tui;
+use rex_tui::db::{
+    create_db, get_import_profiles, import_txs, parse_statement, parse_statement_amount,
+    rename_column, save_import_profile, ImportAmount, ImportProfile, ImportTxMethod, ImportedTx,
+};
+use rex_tui::outputs::ImportError;
+use rex_tui::tx_handler::{redo_activity, undo_activity};
+use rex_tui::utility::{get_last_balances, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn imported_tx(date: &str, details: &str, method: &str, amount: &str, tx_type: &str) -> ImportedTx {
+    ImportedTx {
+        date: date.to_string(),
+        details: details.to_string(),
+        tx_method: method.to_string(),
+        amount: amount.to_string(),
+        tx_type: tx_type.to_string(),
+    }
+}
+
+fn get_tx_count(conn: &Connection) -> i32 {
+    conn.query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn check_statement_parsing() {
+    let file_name = "import_1.sqlite";
+    let conn = create_test_db(file_name);
+
+    let amounts = [
+        parse_statement_amount("1,234.50", '.'),
+        parse_statement_amount("-1.234,50", ','),
+        parse_statement_amount("(12.5)", '.'),
+        parse_statement_amount("€ 7,25-", ','),
+        parse_statement_amount("", '.'),
+        parse_statement_amount("12abc", '.'),
+    ];
+
+    let european_profile = ImportProfile {
+        name: "Euro Bank".to_string(),
+        delimiter: ';',
+        date_format: "%d.%m.%Y".to_string(),
+        decimal_separator: ',',
+        tx_method: ImportTxMethod::Fixed("test1".to_string()),
+        ..ImportProfile::default()
+    };
+
+    let european_txs = parse_statement(
+        "Date;Details;Amount\n01.07.2023;\"Salary; July\";2.500,00\n\n05.07.2023;Food;-12,5\n",
+        &european_profile,
+        &conn,
+    )
+    .unwrap();
+
+    // A quoted value continues on the next line until its quotes are closed
+    let multi_line_txs = parse_statement(
+        "Date;Details;Amount\n01.07.2023;\"Salary\nJuly \"\"bonus\"\"\";2.500,00\n",
+        &european_profile,
+        &conn,
+    )
+    .unwrap();
+    let multi_line_invalid = parse_statement(
+        "Date;Details;Amount\n01.07.2023;\"Salary\nJuly\";2.500,00\n05.07.2023;Food;abc\n",
+        &european_profile,
+        &conn,
+    );
+
+    let debit_credit_profile = ImportProfile {
+        name: "US Bank".to_string(),
+        has_header: false,
+        date_format: "%m/%d/%Y".to_string(),
+        amount: ImportAmount::DebitCredit(3, 4),
+        tx_method: ImportTxMethod::Column(5),
+        ..ImportProfile::default()
+    };
+
+    let debit_credit_txs = parse_statement(
+        "07/10/2023,Rent,\"1,000.00\",,TEST 2\n07/11/2023,Refund,,20,test1\n",
+        &debit_credit_profile,
+        &conn,
+    )
+    .unwrap();
+
+    let invalid_date = parse_statement(
+        "07/10/2023,Rent,10,,test1\n2023-07-11,Refund,,20,test1",
+        &debit_credit_profile,
+        &conn,
+    );
+    let unknown_method = parse_statement("07/10/2023,Rent,10,,Cash", &debit_credit_profile, &conn);
+    let empty_amount = parse_statement("07/10/2023,Rent,,,test1", &debit_credit_profile, &conn);
+    let missing_column = parse_statement("07/10/2023,Rent,10", &debit_credit_profile, &conn);
+    let zero_amount = parse_statement(
+        "Date,Details,Amount\n2023-07-10,Nothing,0.00",
+        &european_profile,
+        &conn,
+    );
+    let invalid_profile = parse_statement(
+        "",
+        &ImportProfile {
+            date_column: 0,
+            ..european_profile.clone()
+        },
+        &conn,
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        amounts,
+        [
+            Some(Cent::new(123_450)),
+            Some(Cent::new(-123_450)),
+            Some(Cent::new(-1250)),
+            Some(Cent::new(-725)),
+            None,
+            None,
+        ]
+    );
+    assert_eq!(
+        european_txs,
+        vec![
+            imported_tx("2023-07-01", "Salary; July", "test1", "2500.00", "Income"),
+            imported_tx("2023-07-05", "Food", "test1", "12.50", "Expense"),
+        ]
+    );
+    assert_eq!(
+        multi_line_txs,
+        vec![imported_tx(
+            "2023-07-01",
+            "Salary\nJuly \"bonus\"",
+            "test1",
+            "2500.00",
+            "Income"
+        )]
+    );
+    assert!(matches!(
+        multi_line_invalid,
+        Err(ImportError::InvalidRow(4, _))
+    ));
+    // Tx method names are matched without the case
+    assert_eq!(
+        debit_credit_txs,
+        vec![
+            imported_tx("2023-07-10", "Rent", "test 2", "1000.00", "Expense"),
+            imported_tx("2023-07-11", "Refund", "test1", "20.00", "Income"),
+        ]
+    );
+    assert!(matches!(invalid_date, Err(ImportError::InvalidRow(2, _))));
+    assert!(matches!(unknown_method, Err(ImportError::InvalidRow(1, _))));
+    assert!(matches!(empty_amount, Err(ImportError::InvalidRow(1, _))));
+    assert!(matches!(missing_column, Err(ImportError::InvalidRow(1, _))));
+    assert!(matches!(zero_amount, Err(ImportError::InvalidRow(2, _))));
+    assert!(matches!(
+        invalid_profile,
+        Err(ImportError::InvalidProfile(_))
+    ));
+}
+
+#[test]
+fn check_import_profiles() {
+    let file_name = "import_2.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let bank_profile = ImportProfile {
+        name: "Bank".to_string(),
+        delimiter: '\t',
+        has_header: false,
+        date_format: "%d/%m/%Y".to_string(),
+        amount: ImportAmount::DebitCredit(4, 5),
+        decimal_separator: ',',
+        tx_method: ImportTxMethod::Fixed("test1".to_string()),
+        ..ImportProfile::default()
+    };
+
+    let card_profile = ImportProfile {
+        name: "Card".to_string(),
+        tx_method: ImportTxMethod::Column(4),
+        ..ImportProfile::default()
+    };
+
+    save_import_profile(&card_profile, &conn).unwrap();
+    save_import_profile(&bank_profile, &conn).unwrap();
+
+    let saved_profiles = get_import_profiles(&conn);
+
+    // saving with the same name replaces the profile
+    let updated_card_profile = ImportProfile {
+        details_column: 3,
+        amount: ImportAmount::Single(2),
+        ..card_profile.clone()
+    };
+    save_import_profile(&updated_card_profile, &conn).unwrap();
+
+    let invalid_name = save_import_profile(
+        &ImportProfile {
+            name: " ".to_string(),
+            ..card_profile.clone()
+        },
+        &conn,
+    );
+    let invalid_separator = save_import_profile(
+        &ImportProfile {
+            decimal_separator: ';',
+            ..card_profile.clone()
+        },
+        &conn,
+    );
+
+    // renaming the tx method updates the profiles that use it
+    rename_column("test1", "Checking", &mut conn).unwrap();
+    let renamed_profiles = get_import_profiles(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(saved_profiles, vec![bank_profile.clone(), card_profile]);
+    assert!(matches!(invalid_name, Err(ImportError::InvalidProfile(_))));
+    assert!(matches!(
+        invalid_separator,
+        Err(ImportError::InvalidProfile(_))
+    ));
+    assert_eq!(
+        renamed_profiles,
+        vec![
+            ImportProfile {
+                tx_method: ImportTxMethod::Fixed("Checking".to_string()),
+                ..bank_profile
+            },
+            updated_card_profile,
+        ]
+    );
+}
+
+#[test]
+fn check_import_undo_redo() {
+    let file_name = "import_3.sqlite";
+    let csv_name = "import_3.csv";
+    let mut conn = create_test_db(file_name);
+
+    let profile = ImportProfile {
+        name: "Bank".to_string(),
+        tx_method: ImportTxMethod::Fixed("test1".to_string()),
+        ..ImportProfile::default()
+    };
+
+    let starting_balances = get_last_balances(&conn);
+
+    // the second row is invalid so nothing gets added
+    fs::write(
+        csv_name,
+        "Date,Details,Amount\n2023-07-01,Salary,1000\n2023-07-02,Food,-abc\n",
+    )
+    .unwrap();
+    let failed_import = import_txs(csv_name.as_ref(), &profile, &mut conn);
+    let txs_after_failure = get_tx_count(&conn);
+    let profiles_after_failure = get_import_profiles(&conn).len();
+
+    fs::write(
+        csv_name,
+        "Date,Details,Amount\n2023-07-01,Salary,1000\n2023-07-02,Food,-50.25\n2023-08-01,Rent,-300\n",
+    )
+    .unwrap();
+    let total_imported = import_txs(csv_name.as_ref(), &profile, &mut conn).unwrap();
+    let saved_profiles = get_import_profiles(&conn);
+    let imported_balances = get_last_balances(&conn);
+
+    let activities: Vec<(String, String, i32)> = conn
+        .prepare(
+            "SELECT activity_type, description, (SELECT COUNT(*) FROM activity_txs
+            WHERE activity_txs.activity_num = activities.activity_num) FROM activities",
+        )
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    // the whole import gets undone and redone at once
+    undo_activity(&mut conn).unwrap();
+    let txs_after_undo = get_tx_count(&conn);
+    let undo_balances = get_last_balances(&conn);
+
+    redo_activity(&mut conn).unwrap();
+    let txs_after_redo = get_tx_count(&conn);
+    let redo_balances = get_last_balances(&conn);
+
+    fs::write(csv_name, "Date,Details,Amount\n").unwrap();
+    let empty_import = import_txs(csv_name.as_ref(), &profile, &mut conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(csv_name).unwrap();
+
+    assert!(matches!(failed_import, Err(ImportError::InvalidRow(3, _))));
+    assert_eq!(txs_after_failure, 0);
+    // The profile is only saved along with a successful import
+    assert_eq!(profiles_after_failure, 0);
+    assert_eq!(saved_profiles, vec![profile]);
+
+    assert_eq!(total_imported, 3);
+    assert_eq!(imported_balances, vec!["649.75", "0.00"]);
+    assert_eq!(
+        activities,
+        vec![(
+            "Import".to_string(),
+            "3 transactions were imported with the Bank profile".to_string(),
+            3
+        )]
+    );
+
+    assert_eq!(txs_after_undo, 0);
+    assert_eq!(undo_balances, starting_balances);
+    assert_eq!(txs_after_redo, 3);
+    assert_eq!(redo_balances, imported_balances);
+    assert!(matches!(empty_import, Err(ImportError::NoTransactions)));
+}
@@ -0,0 +1,140 @@
+extern crate rex_tui;
+use rex_tui::db::*;
+use rex_tui::tx_handler::{add_tx, delete_tx};
+use rex_tui::utility::{get_last_balances, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_dummy_txs(conn: &mut Connection) {
+    let txs = [
+        ("2022-08-19", "test1", "100.00", "Income"),
+        ("2022-08-20", "test 2", "30.50", "Expense"),
+        ("2022-09-01", "test1 to test 2", "20.00", "Transfer"),
+        ("2022-10-05", "test1", "5.00", "Expense"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date, "Details", method, amount, tx_type, "Unknown", None, conn,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn check_verifying_ledger() {
+    let file_name = "verifying_ledger.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_dummy_txs(&mut conn);
+    delete_tx(4, &mut conn).unwrap();
+
+    let untouched = verify_ledger(&conn).unwrap();
+
+    conn.pragma_update(None, "foreign_keys", false).unwrap();
+    conn.execute(
+        r#"UPDATE balance_all SET "test 2" = 1 WHERE id_num = (SELECT MAX(id_num) FROM balance_all)"#,
+        [],
+    )
+    .unwrap();
+    conn.execute(
+        r#"UPDATE changes_all SET test1 = "↑1.00" WHERE id_num = 1"#,
+        [],
+    )
+    .unwrap();
+    conn.execute("DELETE FROM changes_all WHERE id_num = 2", [])
+        .unwrap();
+    conn.execute(
+        r#"INSERT INTO changes_all (date, id_num, test1, "test 2") VALUES ("2022-08-21", 10, "0.00", "0.00")"#,
+        [],
+    )
+    .unwrap();
+
+    let differences = verify_ledger(&conn).unwrap();
+    let differences_again = verify_ledger(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(untouched.is_empty(), "{untouched:?}");
+    assert_eq!(
+        differences,
+        vec![
+            LedgerDifference::Balance(
+                "Final Balance".to_string(),
+                "test 2".to_string(),
+                Cent::new(1),
+                Cent::new(-1050)
+            ),
+            LedgerDifference::Changes(
+                1,
+                "test1".to_string(),
+                "↑1.00".to_string(),
+                "↑100.00".to_string()
+            ),
+            LedgerDifference::UnknownChanges(10),
+            LedgerDifference::MissingChanges(2),
+        ]
+    );
+    assert_eq!(differences, differences_again);
+}
+
+#[test]
+fn check_rebuilding_ledger() {
+    let file_name = "rebuilding_ledger.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_dummy_txs(&mut conn);
+
+    // Adding a tx to an earlier month leaves the saved balance of the later months behind
+    add_tx(
+        "2022-08-25",
+        "Details",
+        "test 2",
+        "10.00",
+        "Income",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    conn.pragma_update(None, "foreign_keys", false).unwrap();
+    conn.execute("UPDATE balance_all SET test1 = 0", [])
+        .unwrap();
+    conn.execute("DELETE FROM changes_all", []).unwrap();
+    conn.pragma_update(None, "foreign_keys", true).unwrap();
+
+    let differences = verify_ledger(&conn).unwrap();
+
+    rebuild_ledger(&mut conn).unwrap();
+
+    let differences_after = verify_ledger(&conn).unwrap();
+    let last_balances = get_last_balances(&conn);
+    let total_changes: i64 = conn
+        .query_row("SELECT COUNT(*) FROM changes_all", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(!differences.is_empty());
+    assert!(differences_after.is_empty());
+    assert_eq!(
+        last_balances,
+        vec!["75.00".to_string(), "-0.50".to_string()]
+    );
+    assert_eq!(total_changes, 5);
+}
@@ -0,0 +1,121 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    add_opening_balances, create_db, verify_ledger, OpeningBalance, OPENING_BALANCE_TX_TYPE,
+};
+use rex_tui::home_page::TransactionData;
+use rex_tui::page_handler::IndexedData;
+use rex_tui::summary_page::SummaryData;
+use rex_tui::tx_handler::{add_tx, delete_tx};
+use rex_tui::utility::{get_last_balances, is_income_like, Cent, CurrencyConverter};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+#[test]
+fn check_opening_balances() {
+    let file_name = "opening_balances.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let opening_balances = vec![
+        OpeningBalance {
+            tx_method: "test1".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 7, 1).unwrap(),
+            amount: Cent::new(100000),
+        },
+        OpeningBalance {
+            tx_method: "test 2".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 7, 1).unwrap(),
+            amount: Cent::default(),
+        },
+    ];
+
+    add_opening_balances(&opening_balances, &mut conn).unwrap();
+
+    add_tx(
+        "2023-07-10",
+        "Testing transaction",
+        "test1",
+        "200.00",
+        "Income",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-12",
+        "Testing transaction",
+        "test1",
+        "50.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    // The balance of 0 does not get a tx
+    let tx_count: i32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tx_all WHERE tx_type = ?",
+            [OPENING_BALANCE_TX_TYPE],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(tx_count, 1);
+
+    assert_eq!(get_last_balances(&conn), vec!["1150.00", "0.00"]);
+    assert!(verify_ledger(&conn).unwrap().is_empty());
+
+    let tx_data = TransactionData::new(6, 0, &conn);
+    let converter = CurrencyConverter::new(&conn);
+    assert_eq!(
+        tx_data.get_txs()[0],
+        vec![
+            "01-07-2023",
+            "Opening Balance",
+            "test1",
+            "1000.00",
+            "Opening Balance",
+            "Unknown",
+            "Cleared"
+        ]
+    );
+    assert_eq!(
+        tx_data.get_total_income(None, &converter, &conn),
+        vec!["Income", "200.00", "0.00", "200.00"]
+    );
+
+    // Only the actual income is counted
+    let summary_modes = IndexedData::new_modes();
+    let my_summary = SummaryData::new(&conn);
+    let table_data = my_summary.get_table_data(&summary_modes, 6, 0);
+
+    assert_eq!(
+        table_data,
+        vec![vec!["Food", "200.00", "50.00", "100.00", "100.00"]]
+    );
+
+    delete_tx(1, &mut conn).unwrap();
+    assert_eq!(get_last_balances(&conn), vec!["150.00", "0.00"]);
+
+    assert!(is_income_like("Income"));
+    assert!(is_income_like(OPENING_BALANCE_TX_TYPE));
+    assert!(!is_income_like("Expense"));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,228 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    check_tx_editable, create_db, get_balance_on, get_reconciled_balance, get_saved_tx_status,
+    get_unreconciled_txs, reconcile_txs, set_tx_status, Reconciliation,
+};
+use rex_tui::outputs::{HistoryError, ReconcileError};
+use rex_tui::page_handler::ActivityType;
+use rex_tui::reconcile_page::ReconcileData;
+use rex_tui::tx_handler::{add_tx, redo_activity, undo_activity};
+use rex_tui::utility::{add_new_activity, add_new_activity_tx, get_last_tx, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_dummy_tx(conn: &mut Connection) {
+    let txs = [
+        ("2023-06-20", "test1", "500.00", "Income"),
+        ("2023-07-05", "test1", "100.00", "Expense"),
+        ("2023-07-10", "test 2", "300.00", "Income"),
+        ("2023-07-15", "test1 to test 2", "50.00", "Transfer"),
+        ("2023-08-01", "test1", "20.00", "Expense"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            "Food",
+            None,
+            conn,
+        )
+        .unwrap();
+    }
+}
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn check_reconcile_balances() {
+    let file_name = "reconcile_balances.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    assert_eq!(
+        get_balance_on("test1", date("2023-06-30"), &conn).unwrap(),
+        Cent::new(50000)
+    );
+    assert_eq!(
+        get_balance_on("test1", date("2023-07-10"), &conn).unwrap(),
+        Cent::new(40000)
+    );
+    assert_eq!(
+        get_balance_on("test1", date("2023-07-31"), &conn).unwrap(),
+        Cent::new(35000)
+    );
+    assert_eq!(
+        get_balance_on("test 2", date("2023-07-31"), &conn).unwrap(),
+        Cent::new(35000)
+    );
+
+    // Only the txs of the method up to the statement date are shown
+    let txs = get_unreconciled_txs("test1", date("2023-07-31"), &conn).unwrap();
+    assert_eq!(
+        txs.iter().map(|tx| tx.id_num).collect::<Vec<i32>>(),
+        vec![1, 2, 4]
+    );
+    assert_eq!(
+        txs.iter().map(|tx| tx.change).collect::<Vec<Cent>>(),
+        vec![Cent::new(50000), Cent::new(-10000), Cent::new(-5000)]
+    );
+
+    let reconciliation = Reconciliation {
+        tx_method: "test1".to_string(),
+        statement_date: date("2023-07-31"),
+        statement_balance: Cent::new(40000),
+    };
+
+    assert_eq!(
+        get_reconciled_balance(&reconciliation, &conn).unwrap(),
+        Cent::default()
+    );
+
+    let mut reconcile_data = ReconcileData::new(Some(&reconciliation), &conn);
+    assert_eq!(reconcile_data.difference(), Cent::new(40000));
+    assert!(!reconcile_data.is_balanced());
+
+    reconcile_data.toggle_tick(0);
+    reconcile_data.toggle_tick(1);
+
+    assert_eq!(reconcile_data.ticked_id_nums(), vec![1, 2]);
+    assert_eq!(reconcile_data.ticked_total(), Cent::new(40000));
+    assert!(reconcile_data.is_balanced());
+    assert_eq!(
+        reconcile_data.get_txs()[0],
+        vec![
+            "[x]",
+            "20-06-2023",
+            "Testing transaction",
+            "Income",
+            "500.00",
+            "Cleared"
+        ]
+    );
+
+    let empty_data = ReconcileData::new(None, &conn);
+    assert!(empty_data.is_empty());
+    assert!(!empty_data.is_balanced());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_reconcile_txs() {
+    let file_name = "reconcile_txs.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    set_tx_status(4, "Pending", &conn).unwrap();
+
+    let reconciliation = Reconciliation {
+        tx_method: "test1".to_string(),
+        statement_date: date("2023-07-31"),
+        statement_balance: Cent::new(40000),
+    };
+
+    // The transfer is not on the statement yet
+    let status = reconcile_txs(&reconciliation, &[1, 2, 4], &mut conn);
+    assert!(matches!(status, Err(ReconcileError::NotBalanced(_))));
+    assert_eq!(get_saved_tx_status(1, &conn).unwrap(), "Cleared");
+
+    reconcile_txs(&reconciliation, &[1, 2], &mut conn).unwrap();
+
+    assert_eq!(get_saved_tx_status(1, &conn).unwrap(), "Reconciled");
+    assert_eq!(get_saved_tx_status(2, &conn).unwrap(), "Reconciled");
+    assert_eq!(get_saved_tx_status(4, &conn).unwrap(), "Pending");
+
+    assert!(matches!(
+        check_tx_editable(1, &conn),
+        Err(ReconcileError::Locked(1))
+    ));
+    assert!(check_tx_editable(4, &conn).is_ok());
+
+    assert_eq!(
+        get_reconciled_balance(&reconciliation, &conn).unwrap(),
+        Cent::new(40000)
+    );
+    assert_eq!(
+        get_unreconciled_txs("test1", date("2023-07-31"), &conn)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let (activity_type, description): (String, String) = conn
+        .query_row(
+            "SELECT activity_type, description FROM activities ORDER BY activity_num DESC LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+
+    assert_eq!(activity_type, "Reconcile");
+    assert_eq!(
+        description,
+        "test1 was reconciled up to 2023-07-31 with statement balance 400.00"
+    );
+
+    // Undo brings back the status the txs had before the reconciliation
+    undo_activity(&mut conn).unwrap();
+
+    assert_eq!(get_saved_tx_status(1, &conn).unwrap(), "Cleared");
+    assert!(check_tx_editable(1, &conn).is_ok());
+
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(get_saved_tx_status(2, &conn).unwrap(), "Reconciled");
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_reconciled_tx_history() {
+    let file_name = "reconcile_history.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    // Saved the same way as adding a tx from the Add Tx page
+    let activity_num = add_new_activity(ActivityType::NewTX, &conn);
+    add_new_activity_tx(&get_last_tx(&conn), activity_num, &conn);
+
+    // Marked the same way reconcile_txs marks the ticked txs
+    set_tx_status(5, "Reconciled", &conn).unwrap();
+
+    let locked_undo = undo_activity(&mut conn);
+    let status_after_locked_undo = get_saved_tx_status(5, &conn).unwrap();
+
+    set_tx_status(5, "Cleared", &conn).unwrap();
+    undo_activity(&mut conn).unwrap();
+
+    let tx_count: i32 = conn
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(matches!(locked_undo, Err(HistoryError::TxReconciled(5))));
+    assert_eq!(status_after_locked_undo, "Reconciled");
+    assert_eq!(tx_count, 4);
+}
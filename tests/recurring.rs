@@ -0,0 +1,242 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    add_recurring_tx, create_db, delete_recurring_tx, delete_tx_method, get_all_recurring_txs,
+    get_recurring_tx_ids, get_recurring_tx_method_usage, get_tx_recurring_id, rename_column,
+    set_recurring_paused, update_recurring_tx, RecurringSchedule, RecurringTx, RecurringUnit,
+};
+use rex_tui::outputs::TxMethodError;
+use rex_tui::tx_handler::{post_due_recurring_txs, undo_activity};
+use rex_tui::utility::{get_all_txs, get_last_balances, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+fn new_recurring_tx(schedule: &str, start_date: &str) -> RecurringTx {
+    RecurringTx {
+        recurring_id: 0,
+        details: "Rent".to_string(),
+        tx_method: "test1".to_string(),
+        amount: Cent::new(50000),
+        tx_type: "Expense".to_string(),
+        tags: "Home".to_string(),
+        schedule: RecurringSchedule::parse(schedule).unwrap(),
+        start_date: date(start_date),
+        end_date: None,
+        last_posted: None,
+        paused: false,
+    }
+}
+
+#[test]
+fn check_recurring_schedule() {
+    let schedule = RecurringSchedule::parse("Every 3 Months on day 15").unwrap();
+    assert_eq!(
+        schedule,
+        RecurringSchedule {
+            unit: RecurringUnit::Month,
+            interval: 3,
+            day_of_month: Some(15),
+        }
+    );
+    assert_eq!(schedule.to_string(), "every 3 months on day 15");
+
+    for (schedule, expected) in [
+        ("daily", "daily"),
+        ("every week", "weekly"),
+        ("every 1 months", "monthly"),
+        ("monthly on day 31", "monthly on day 31"),
+        ("every 2 weeks", "every 2 weeks"),
+        ("yearly", "yearly"),
+    ] {
+        assert_eq!(
+            RecurringSchedule::parse(schedule).unwrap().to_string(),
+            expected
+        );
+    }
+
+    for schedule in [
+        "",
+        "days",
+        "every 0 days",
+        "every -1 days",
+        "every two weeks",
+        "weekly on day 3",
+        "monthly on day 32",
+        "monthly on day 0",
+        "hourly",
+    ] {
+        assert_eq!(RecurringSchedule::parse(schedule), None);
+    }
+}
+
+#[test]
+fn check_recurring_due_dates() {
+    // Days that do not exist in a month are moved to the last day of it
+    let recurring_tx = new_recurring_tx("monthly on day 31", "2023-01-15");
+    assert_eq!(
+        recurring_tx.get_due_dates(date("2023-04-29")),
+        vec![date("2023-01-31"), date("2023-02-28"), date("2023-03-31")]
+    );
+
+    // The day of the month before the start date is not due in the first month
+    let recurring_tx = new_recurring_tx("monthly on day 5", "2023-01-15");
+    assert_eq!(
+        recurring_tx.get_due_dates(date("2023-03-05")),
+        vec![date("2023-02-05"), date("2023-03-05")]
+    );
+
+    let mut recurring_tx = new_recurring_tx("every 2 weeks", "2023-07-03");
+    recurring_tx.end_date = Some(date("2023-07-31"));
+    assert_eq!(
+        recurring_tx.get_due_dates(date("2024-01-01")),
+        vec![date("2023-07-03"), date("2023-07-17"), date("2023-07-31")]
+    );
+    assert_eq!(recurring_tx.get_next_date(), Some(date("2023-07-03")));
+
+    recurring_tx.last_posted = Some(date("2023-07-17"));
+    assert_eq!(
+        recurring_tx.get_due_dates(date("2024-01-01")),
+        vec![date("2023-07-31")]
+    );
+
+    recurring_tx.last_posted = Some(date("2023-07-31"));
+    assert!(recurring_tx.get_due_dates(date("2024-01-01")).is_empty());
+    assert_eq!(recurring_tx.get_next_date(), None);
+
+    let recurring_tx = new_recurring_tx("yearly", "2024-02-29");
+    assert_eq!(
+        recurring_tx.get_due_dates(date("2028-03-01")),
+        vec![
+            date("2024-02-29"),
+            date("2025-02-28"),
+            date("2026-02-28"),
+            date("2027-02-28"),
+            date("2028-02-29")
+        ]
+    );
+
+    let mut recurring_tx = new_recurring_tx("daily", "2023-07-01");
+    assert_eq!(recurring_tx.get_due_dates(date("2023-07-03")).len(), 3);
+    assert!(recurring_tx.get_due_dates(date("2023-06-30")).is_empty());
+
+    recurring_tx.paused = true;
+    assert!(recurring_tx.get_due_dates(date("2023-07-03")).is_empty());
+}
+
+#[test]
+fn check_posting_recurring_txs() {
+    let file_name = "posting_recurring_txs.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let recurring_id =
+        add_recurring_tx(&new_recurring_tx("monthly", "2023-07-01"), &mut conn).unwrap();
+
+    let mut salary = new_recurring_tx("monthly on day 31", "2023-07-01");
+    salary.details = "Salary".to_string();
+    salary.tx_type = "Income".to_string();
+    salary.amount = Cent::new(100_000);
+    salary.end_date = Some(date("2023-08-31"));
+    let salary_id = add_recurring_tx(&salary, &mut conn).unwrap();
+
+    let posted_txs = post_due_recurring_txs(date("2023-09-15"), &mut conn).unwrap();
+    assert_eq!(posted_txs.len(), 5);
+    assert_eq!(
+        posted_txs[0][..6],
+        ["01-07-2023", "Rent", "test1", "500.00", "Expense", "Home"]
+    );
+
+    assert_eq!(get_recurring_tx_ids(recurring_id, &conn), vec![1, 2, 3]);
+    assert_eq!(get_recurring_tx_ids(salary_id, &conn), vec![4, 5]);
+    assert_eq!(get_tx_recurring_id(4, &conn), Some(salary_id));
+
+    let all_recurring_txs = get_all_recurring_txs(&conn);
+    assert_eq!(all_recurring_txs[0].last_posted, Some(date("2023-09-01")));
+    assert_eq!(
+        all_recurring_txs[0].get_next_date(),
+        Some(date("2023-10-01"))
+    );
+    assert_eq!(all_recurring_txs[1].get_next_date(), None);
+
+    assert_eq!(get_last_balances(&conn)[0], "500.00");
+
+    // Nothing is posted twice
+    assert!(post_due_recurring_txs(date("2023-09-15"), &mut conn)
+        .unwrap()
+        .is_empty());
+
+    // The dates that were missed while paused are skipped after resuming
+    set_recurring_paused(recurring_id, true, date("2023-09-15"), &mut conn).unwrap();
+    assert!(post_due_recurring_txs(date("2023-12-15"), &mut conn)
+        .unwrap()
+        .is_empty());
+
+    set_recurring_paused(recurring_id, false, date("2023-12-15"), &mut conn).unwrap();
+    assert!(post_due_recurring_txs(date("2023-12-15"), &mut conn)
+        .unwrap()
+        .is_empty());
+
+    let posted_txs = post_due_recurring_txs(date("2024-01-01"), &mut conn).unwrap();
+    assert_eq!(posted_txs.len(), 1);
+    assert_eq!(posted_txs[0][0], "01-01-2024");
+
+    // Posted txs are activities that can be undone
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_recurring_tx_ids(recurring_id, &conn), vec![1, 2, 3]);
+
+    // Renaming a tx method also renames it in the recurring txs
+    rename_column("test1", "Bank", &mut conn).unwrap();
+    let mut recurring_tx = get_all_recurring_txs(&conn).remove(0);
+    assert_eq!(recurring_tx.tx_method, "Bank");
+
+    recurring_tx.amount = Cent::new(60000);
+    update_recurring_tx(&recurring_tx, &mut conn).unwrap();
+    assert_eq!(get_all_recurring_txs(&conn)[0].amount.to_string(), "600.00");
+
+    // Deleting a recurring tx keeps the txs it has posted
+    delete_recurring_tx(recurring_id, &mut conn).unwrap();
+    assert_eq!(get_all_recurring_txs(&conn).len(), 1);
+    assert_eq!(get_tx_recurring_id(1, &conn), None);
+    assert_eq!(get_all_txs(&conn, 6, 0).0.len(), 2);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_recurring_tx_method_deletion() {
+    let file_name = "recurring_tx_method_deletion.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut recurring_tx = new_recurring_tx("weekly", "2023-07-01");
+    recurring_tx.tx_method = "test1 to test 2".to_string();
+    recurring_tx.tx_type = "Transfer".to_string();
+    let recurring_id = add_recurring_tx(&recurring_tx, &mut conn).unwrap();
+
+    assert_eq!(get_recurring_tx_method_usage("test 2", &conn).unwrap(), 1);
+
+    // A tx method that a recurring tx still posts to cannot be deleted
+    let status = delete_tx_method("test 2", &mut conn);
+    assert!(matches!(status, Err(TxMethodError::UsedByRecurring(method, 1)) if method == "test 2"));
+
+    delete_recurring_tx(recurring_id, &mut conn).unwrap();
+    delete_tx_method("test 2", &mut conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
@@ -0,0 +1,224 @@
+extern crate rex_tui;
+use rex_tui::db::{create_db, get_pending_changes, get_saved_tx_status, set_tx_status};
+use rex_tui::home_page::TransactionData;
+use rex_tui::outputs::{AType, CheckingError, NAType, SteppingError, VerifyingOutput};
+use rex_tui::page_handler::{DateType, TxTab};
+use rex_tui::tx_handler::{add_tx, redo_activity, undo_activity, TxData};
+use rex_tui::utility::{Cent, CurrencyConverter};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_dummy_tx(conn: &mut Connection) {
+    let txs = [
+        ("2023-07-10", "test1", "1000.00", "Income"),
+        ("2023-07-12", "test1", "100.00", "Expense"),
+        ("2023-07-15", "test1 to test 2", "200.00", "Transfer"),
+        ("2023-07-20", "test 2", "50.00", "Income"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date,
+            "Testing transaction",
+            method,
+            amount,
+            tx_type,
+            "Food",
+            None,
+            conn,
+        )
+        .unwrap();
+    }
+}
+
+fn to_strings(data: &[&str]) -> Vec<String> {
+    data.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn check_tx_status() {
+    let file_name = "tx_status.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    assert_eq!(get_saved_tx_status(2, &conn).unwrap(), "Cleared");
+
+    set_tx_status(2, "Pending", &conn).unwrap();
+    set_tx_status(3, "Pending", &conn).unwrap();
+    set_tx_status(4, "", &conn).unwrap();
+
+    assert_eq!(get_saved_tx_status(2, &conn).unwrap(), "Pending");
+    assert_eq!(get_saved_tx_status(4, &conn).unwrap(), "Cleared");
+
+    let pending_changes = get_pending_changes(None, &conn).unwrap();
+    let expected_changes = HashMap::from([
+        ("test1".to_string(), Cent::new(-30000)),
+        ("test 2".to_string(), Cent::new(20000)),
+    ]);
+    assert_eq!(pending_changes, expected_changes);
+
+    // The transfer comes after the given tx so it is not counted
+    let pending_changes = get_pending_changes(Some(("2023-07-12", 2)), &conn).unwrap();
+    let expected_changes = HashMap::from([("test1".to_string(), Cent::new(-10000))]);
+    assert_eq!(pending_changes, expected_changes);
+
+    let tx_data = TransactionData::new(6, 0, &conn);
+    let converter = CurrencyConverter::new(&conn);
+
+    assert_eq!(
+        tx_data.get_last_balance(&converter, &conn),
+        to_strings(&["Balance", "700.00", "250.00", "950.00"])
+    );
+    assert_eq!(
+        tx_data.get_cleared_balance(None, &converter, &conn),
+        to_strings(&["Cleared", "1000.00", "50.00", "1050.00"])
+    );
+    assert_eq!(
+        tx_data.get_cleared_balance(Some(1), &converter, &conn),
+        to_strings(&["Cleared", "1000.00", "0.00", "1000.00"])
+    );
+    assert_eq!(tx_data.get_txs()[1][6], "Pending");
+
+    let mut search_data = TxData::custom("", "", "", "", "", "", "", 0);
+    search_data.go_current_index(&TxTab::Status);
+    search_data.edit_status(Some('p'));
+
+    assert_eq!(
+        search_data.check_status(true),
+        VerifyingOutput::Accepted(AType::Status)
+    );
+
+    let (search_txs, search_ids) = search_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(search_ids, to_strings(&["2", "3"]));
+    assert!(search_txs.iter().all(|tx| tx[6] == "Pending"));
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_tx_status_fields() {
+    let mut tx_data = TxData::custom("", "", "", "", "", "", "", 0);
+    tx_data.go_current_index(&TxTab::Status);
+
+    // Only a reconciliation can mark a tx as Reconciled so the Add TX page skips it
+    tx_data.do_status_up(false).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Cleared");
+
+    tx_data.do_status_up(false).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Pending");
+
+    tx_data.do_status_down(false).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Cleared");
+
+    // Searching can step through every status
+    tx_data.do_status_up(true).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Reconciled");
+
+    tx_data.do_status_up(true).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Pending");
+
+    tx_data.do_status_down(true).unwrap();
+    assert_eq!(tx_data.get_all_texts()[9], "Reconciled");
+
+    let mut tx_data = TxData::custom("", "", "", "", "", "", "", 0);
+    tx_data.go_current_index(&TxTab::Status);
+    tx_data.edit_status(Some('x'));
+
+    assert_eq!(
+        tx_data.do_status_up(false),
+        Err(SteppingError::InvalidTxStatus)
+    );
+
+    // Invalid status gets cleared after checking
+    tx_data.edit_status(Some('x'));
+    assert_eq!(
+        tx_data.check_status(false),
+        VerifyingOutput::NotAccepted(NAType::InvalidTxStatus)
+    );
+    assert_eq!(
+        tx_data.check_status(false),
+        VerifyingOutput::Nothing(AType::Status)
+    );
+
+    tx_data.edit_status(Some('r'));
+    assert_eq!(
+        tx_data.check_status(false),
+        VerifyingOutput::NotAccepted(NAType::InvalidTxStatus)
+    );
+
+    tx_data.edit_status(Some('r'));
+    assert_eq!(
+        tx_data.check_status(true),
+        VerifyingOutput::Accepted(AType::Status)
+    );
+    assert_eq!(tx_data.get_all_texts()[9], "Reconciled");
+}
+
+#[test]
+fn check_tx_status_undo() {
+    let file_name = "tx_status_undo.sqlite";
+    let mut conn = create_test_db(file_name);
+    add_dummy_tx(&mut conn);
+
+    let mut tx_data = TxData::custom(
+        "20-07-2023",
+        "Testing transaction",
+        "test 2",
+        "",
+        "50.00",
+        "Income",
+        "Food",
+        4,
+    );
+    tx_data.load_status(&conn);
+    assert_eq!(tx_data.get_all_texts()[9], "Cleared");
+
+    tx_data.go_current_index(&TxTab::Status);
+    tx_data.do_status_down(false).unwrap();
+    tx_data.add_tx(&mut conn).unwrap();
+
+    assert_eq!(get_saved_tx_status(4, &conn).unwrap(), "Pending");
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_saved_tx_status(4, &conn).unwrap(), "Cleared");
+
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(get_saved_tx_status(4, &conn).unwrap(), "Pending");
+
+    let mut tx_data = TxData::custom(
+        "20-07-2023",
+        "Testing transaction",
+        "test 2",
+        "",
+        "50.00",
+        "Income",
+        "Food",
+        0,
+    );
+    tx_data.go_current_index(&TxTab::Status);
+    for c in "Reconciled".chars() {
+        tx_data.edit_status(Some(c));
+    }
+
+    assert_eq!(
+        tx_data.add_tx(&mut conn),
+        Err(CheckingError::InvalidStatus.to_string())
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
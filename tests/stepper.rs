@@ -77,11 +77,13 @@ fn add_dummy_tx(conn: &mut Connection) {
 fn test_stepper_date() {
     let data = vec![
         "",
-        "2020-05-01",
+        "1890-05-01",
         "2025-05-15",
         "2025-13-01",
         "2025-05-35",
-        "2040-05-01",
+        "2240-05-01",
+        "2199-12-31",
+        "1900-01-01",
         "2037-12-31",
         "2022-01-01",
     ]
@@ -91,12 +93,14 @@ fn test_stepper_date() {
 
     let expected = vec![
         "2022-01-01",
-        "2022-05-01",
+        "1900-05-01",
         "2025-05-16",
         "2025-12-01",
         "2025-05-31",
-        "2037-05-01",
-        "2037-12-31",
+        "2199-05-01",
+        "2199-12-31",
+        "1900-01-02",
+        "2038-01-01",
         "2022-01-02",
     ]
     .into_iter()
@@ -112,6 +116,8 @@ fn test_stepper_date() {
         Err(SteppingError::InvalidDate),
         Ok(()),
         Ok(()),
+        Ok(()),
+        Ok(()),
     ];
 
     let test_data = Testing {
@@ -130,13 +136,15 @@ fn test_stepper_date() {
 
     let expected = vec![
         "2022-01-01",
-        "2022-05-01",
+        "1900-05-01",
         "2025-05-14",
         "2025-12-01",
         "2025-05-31",
-        "2037-05-01",
+        "2199-05-01",
+        "2199-12-30",
+        "1900-01-01",
         "2037-12-30",
-        "2022-01-01",
+        "2021-12-31",
     ]
     .into_iter()
     .map(|a| a.to_string())
@@ -151,6 +159,8 @@ fn test_stepper_date() {
         Err(SteppingError::InvalidDate),
         Ok(()),
         Ok(()),
+        Ok(()),
+        Ok(()),
     ];
 
     let test_data = Testing {
@@ -167,12 +177,12 @@ fn test_stepper_date() {
         assert_eq!(result, test_data.result[i]);
     }
 
-    let data = vec!["", "2022-01", "2022-13", "2040-01", "2037-12"]
+    let data = vec!["", "2022-01", "2022-13", "2240-01", "2199-12"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
 
-    let expected = vec!["2022-01", "2022-02", "2022-12", "2037-01", "2037-12"]
+    let expected = vec!["2022-01", "2022-02", "2022-12", "2199-01", "2199-12"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
@@ -199,7 +209,7 @@ fn test_stepper_date() {
         assert_eq!(result, test_data.result[i]);
     }
 
-    let expected = vec!["2022-01", "2022-01", "2022-12", "2037-01", "2037-11"]
+    let expected = vec!["2022-01", "2021-12", "2022-12", "2199-01", "2199-11"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
@@ -226,12 +236,12 @@ fn test_stepper_date() {
         assert_eq!(result, test_data.result[i]);
     }
 
-    let data = vec!["", "2022", "2037", "2040"]
+    let data = vec!["", "2022", "2199", "2240"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
 
-    let expected = vec!["2022", "2023", "2037", "2037"]
+    let expected = vec!["2022", "2023", "2199", "2199"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
@@ -252,7 +262,7 @@ fn test_stepper_date() {
         assert_eq!(result, test_data.result[i]);
     }
 
-    let expected = vec!["2022", "2022", "2036", "2037"]
+    let expected = vec!["2022", "2021", "2198", "2199"]
         .into_iter()
         .map(|a| a.to_string())
         .collect::<Vec<String>>();
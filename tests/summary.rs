@@ -3,8 +3,9 @@ use rex_tui::db::*;
 use rex_tui::page_handler::{IndexedData, SortingType};
 use rex_tui::summary_page::SummaryData;
 use rex_tui::tx_handler::add_tx;
-use rex_tui::utility::sort_table_data;
+use rex_tui::utility::{get_last_tx_id, hide_collapsed_tags, sort_table_data};
 use rusqlite::Connection;
+use std::collections::HashSet;
 use std::fs;
 
 fn create_test_db(file_name: &str) -> Connection {
@@ -582,3 +583,119 @@ fn check_summary_sorting() {
     assert_eq!(sorted_data_2, expected_data_2);
     assert_eq!(sorted_data_3, expected_data_3);
 }
+
+#[test]
+fn check_summary_tag_subtotals() {
+    let file_name = "summary_tag_subtotals.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for (amount, tx_type, tags) in [
+        ("100.00", "Expense", "Food:Groceries"),
+        ("50.00", "Expense", "Food:Out:Lunch, Food:Groceries"),
+        ("30.00", "Expense", "Food Court"),
+        ("200.00", "Income", "Food:Out"),
+    ] {
+        add_tx(
+            "2023-07-19",
+            "Testing transaction",
+            "test1",
+            amount,
+            tx_type,
+            tags,
+            None,
+            &mut conn,
+        )
+        .unwrap();
+    }
+
+    let summary_modes = IndexedData::new_modes();
+    let my_summary = SummaryData::new(&conn);
+    let table_data = my_summary.get_table_data(&summary_modes, 6, 0);
+
+    // Parent tags count each tx once and child tags come right after their parent
+    let expected_data = vec![
+        vec!["Food", "200.00", "150.00", "100.00", "65.22"],
+        vec!["Food:Groceries", "0.00", "150.00", "0.00", "65.22"],
+        vec!["Food:Out", "200.00", "50.00", "100.00", "21.74"],
+        vec!["Food:Out:Lunch", "0.00", "50.00", "0.00", "21.74"],
+        vec!["Food Court", "0.00", "30.00", "0.00", "13.04"],
+    ];
+
+    assert_eq!(table_data, expected_data);
+
+    let collapsed_tags = HashSet::from(["Food:Out".to_string()]);
+    let visible_tags = hide_collapsed_tags(table_data.clone(), &collapsed_tags)
+        .into_iter()
+        .map(|row| row[0].clone())
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        visible_tags,
+        vec!["Food", "Food:Groceries", "Food:Out", "Food Court"]
+    );
+
+    let collapsed_tags = HashSet::from(["Food".to_string()]);
+    let visible_tags = hide_collapsed_tags(table_data, &collapsed_tags)
+        .into_iter()
+        .map(|row| row[0].clone())
+        .collect::<Vec<String>>();
+
+    assert_eq!(visible_tags, vec!["Food", "Food Court"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_summary_tx_splits() {
+    let file_name = "summary_tx_splits.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2023-07-19",
+        "Testing transaction",
+        "test1",
+        "50.00",
+        "Expense",
+        "Food:Groceries, Home",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let id_num = get_last_tx_id(&conn).unwrap();
+    save_tx_splits(
+        id_num,
+        "Food:Groceries | 30.00 | Milk; Home | 20.00",
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-19",
+        "Testing transaction",
+        "test1",
+        "10.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let summary_modes = IndexedData::new_modes();
+    let my_summary = SummaryData::new(&conn);
+    let table_data = my_summary.get_table_data(&summary_modes, 6, 0);
+
+    // Each split line only counts towards its own tag and the tx total is not counted twice
+    let expected_data = vec![
+        vec!["Food", "0.00", "40.00", "0.00", "66.67"],
+        vec!["Food:Groceries", "0.00", "30.00", "0.00", "50.00"],
+        vec!["Home", "0.00", "20.00", "0.00", "33.33"],
+    ];
+
+    assert_eq!(table_data, expected_data);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
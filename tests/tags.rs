@@ -0,0 +1,406 @@
+extern crate rex_tui;
+use chrono::NaiveDate;
+use rex_tui::db::{
+    add_budget, add_goal, create_db, get_all_budgets, get_all_goals, get_tag_usage, get_tx_splits,
+    merge_tags, remove_tag, rename_tag, save_tx_splits, Budget, BudgetPeriod, Goal, GoalSource,
+};
+use rex_tui::outputs::TagError;
+use rex_tui::tag_page::TagData;
+use rex_tui::tx_handler::{add_tx, redo_activity, undo_activity};
+use rex_tui::utility::{get_all_tags, get_all_tx_tags, Cent};
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
+
+fn add_tagged_tx(amount: &str, tx_type: &str, tags: &str, conn: &mut Connection) {
+    add_tx(
+        "2023-07-19",
+        "Testing",
+        "test1",
+        amount,
+        tx_type,
+        tags,
+        None,
+        conn,
+    )
+    .unwrap();
+}
+
+/// The tags column of all txs in `id_num` order
+fn get_tags_column(conn: &Connection) -> Vec<String> {
+    let mut statement = conn
+        .prepare("SELECT tags FROM tx_all ORDER BY id_num")
+        .unwrap();
+
+    statement
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap()
+}
+
+fn get_activities(conn: &Connection) -> Vec<(String, String)> {
+    let mut statement = conn
+        .prepare("SELECT activity_type, description FROM activities ORDER BY activity_num")
+        .unwrap();
+
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<Vec<(String, String)>, _>>()
+        .unwrap()
+}
+
+#[test]
+fn check_tag_usage() {
+    let file_name = "tag_usage.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("100.00", "Expense", "Food, Car", &mut conn);
+    add_tagged_tx("50.50", "Income", "Food", &mut conn);
+    add_tagged_tx("20.00", "Expense", "Car", &mut conn);
+
+    let usage = get_tag_usage(&conn).unwrap();
+
+    let usage = usage
+        .iter()
+        .map(|usage| {
+            (
+                usage.name.as_str(),
+                usage.tx_count,
+                usage.income.to_string(),
+                usage.expense.to_string(),
+            )
+        })
+        .collect::<Vec<(&str, usize, String, String)>>();
+
+    let tag_rows = TagData::new(&conn).get_txs();
+
+    assert_eq!(
+        usage,
+        vec![
+            ("Car", 2, "0.00".to_string(), "120.00".to_string()),
+            ("Food", 2, "50.50".to_string(), "100.00".to_string()),
+        ]
+    );
+    assert_eq!(
+        tag_rows,
+        vec![
+            vec!["Car", "2", "0.00", "120.00"],
+            vec!["Food", "2", "50.50", "100.00"],
+        ]
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_tag_changes() {
+    let file_name = "tag_changes.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("100.00", "Expense", "Grocceries, Car", &mut conn);
+    add_tagged_tx("50.00", "Expense", "Grocceries", &mut conn);
+    add_tagged_tx("20.00", "Expense", "Car", &mut conn);
+
+    assert!(matches!(
+        rename_tag("Food", "Groceries", &mut conn),
+        Err(TagError::NotFound(tag)) if tag == "Food"
+    ));
+    assert!(matches!(
+        rename_tag("Grocceries", "Food, Car", &mut conn),
+        Err(TagError::InvalidName(_))
+    ));
+    assert!(matches!(
+        rename_tag("Car", "Car", &mut conn),
+        Err(TagError::SameTag)
+    ));
+
+    assert_eq!(rename_tag("Grocceries", "Groceries", &mut conn).unwrap(), 2);
+    assert_eq!(
+        get_tags_column(&conn),
+        vec!["Groceries, Car", "Groceries", "Car"]
+    );
+    assert_eq!(get_all_tags(&conn), vec!["Car", "Groceries"]);
+
+    // Merging into a tag the tx already has does not add it twice
+    let from_tags = vec!["Groceries".to_string(), "Car".to_string()];
+    assert!(matches!(
+        merge_tags(&from_tags, "Car", &mut conn),
+        Err(TagError::SameTag)
+    ));
+    assert_eq!(merge_tags(&from_tags, "Daily", &mut conn).unwrap(), 3);
+    assert_eq!(get_tags_column(&conn), vec!["Daily", "Daily", "Daily"]);
+    assert_eq!(get_all_tags(&conn), vec!["Daily"]);
+
+    // Txs without any tag left are tagged as Unknown
+    assert_eq!(remove_tag("Daily", &mut conn).unwrap(), 3);
+    assert_eq!(
+        get_tags_column(&conn),
+        vec!["Unknown", "Unknown", "Unknown"]
+    );
+    assert_eq!(get_all_tx_tags(&conn)[&2], vec!["Unknown"]);
+
+    assert_eq!(
+        get_activities(&conn),
+        vec![
+            (
+                "Rename Tag".to_string(),
+                "Tag Grocceries was renamed to Groceries".to_string()
+            ),
+            (
+                "Merge Tags".to_string(),
+                "Tags Groceries, Car were merged into Daily".to_string()
+            ),
+            (
+                "Remove Tag".to_string(),
+                "Tag Daily was removed from all transactions".to_string()
+            ),
+        ]
+    );
+
+    // Each changed tx is saved with the activity as it was after and before the change
+    let activity_txs: i32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM activity_txs WHERE activity_num = 2",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(activity_txs, 6);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_undo_tag_changes() {
+    let file_name = "undo_tag_changes.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("100.00", "Expense", "Grocceries, Car", &mut conn);
+    add_tagged_tx("50.00", "Expense", "Grocceries", &mut conn);
+
+    rename_tag("Grocceries", "Groceries", &mut conn).unwrap();
+    remove_tag("Car", &mut conn).unwrap();
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(get_tags_column(&conn), vec!["Groceries, Car", "Groceries"]);
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(
+        get_tags_column(&conn),
+        vec!["Grocceries, Car", "Grocceries"]
+    );
+    assert_eq!(get_all_tags(&conn), vec!["Car", "Grocceries"]);
+
+    redo_activity(&mut conn).unwrap();
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(get_tags_column(&conn), vec!["Groceries", "Groceries"]);
+    assert_eq!(get_all_tags(&conn), vec!["Groceries"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_split_tag_changes() {
+    let file_name = "split_tag_changes.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("50.00", "Expense", "Food, Home", &mut conn);
+    save_tx_splits(1, "Food | 30.00 | Milk; Home | 20.00", &mut conn).unwrap();
+
+    let split_tags = |conn: &Connection| {
+        get_tx_splits(1, conn)
+            .into_iter()
+            .map(|split| split.tag)
+            .collect::<Vec<String>>()
+    };
+
+    rename_tag("Food", "Groceries", &mut conn).unwrap();
+    assert_eq!(split_tags(&conn), vec!["Groceries", "Home"]);
+    assert_eq!(get_tags_column(&conn), vec!["Groceries, Home"]);
+
+    // A removed tag leaves the split line behind as Unknown so the amounts still add up
+    remove_tag("Home", &mut conn).unwrap();
+    assert_eq!(split_tags(&conn), vec!["Groceries", "Unknown"]);
+    assert_eq!(get_tags_column(&conn), vec!["Groceries, Unknown"]);
+
+    undo_activity(&mut conn).unwrap();
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(split_tags(&conn), vec!["Food", "Home"]);
+    assert_eq!(get_tags_column(&conn), vec!["Food, Home"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_tag_changes_cascade() {
+    let file_name = "tag_changes_cascade.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("100.00", "Expense", "Food, Food:Groceries", &mut conn);
+    add_tagged_tx("50.00", "Expense", "Food:Groceries:Milk", &mut conn);
+    add_tagged_tx("20.00", "Expense", "Foodstuff", &mut conn);
+
+    rename_tag("Food", "Meals", &mut conn).unwrap();
+    assert_eq!(
+        get_tags_column(&conn),
+        vec![
+            "Meals, Meals:Groceries",
+            "Meals:Groceries:Milk",
+            "Foodstuff"
+        ]
+    );
+
+    merge_tags(&["Meals:Groceries".to_string()], "Shop", &mut conn).unwrap();
+    assert_eq!(
+        get_tags_column(&conn),
+        vec!["Meals, Shop", "Shop:Milk", "Foodstuff"]
+    );
+
+    undo_activity(&mut conn).unwrap();
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(
+        get_tags_column(&conn),
+        vec!["Food, Food:Groceries", "Food:Groceries:Milk", "Foodstuff"]
+    );
+
+    // Moving a tag under itself leaves its children where they are
+    rename_tag("Food", "Food:Old", &mut conn).unwrap();
+    assert_eq!(
+        get_tags_column(&conn),
+        vec![
+            "Food:Old, Food:Groceries",
+            "Food:Groceries:Milk",
+            "Foodstuff"
+        ]
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn check_undo_budget_goal_tag_changes() {
+    let file_name = "undo_budget_goal_tag_changes.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tagged_tx("100.00", "Expense", "Food, Car", &mut conn);
+    add_tagged_tx("50.00", "Expense", "Food:Groceries", &mut conn);
+
+    let budget = |tag: &str, period: BudgetPeriod| Budget {
+        budget_id: 0,
+        tag: tag.to_string(),
+        period,
+        amount: Cent::new(10000),
+        rollover: false,
+        start_date: NaiveDate::from_ymd_opt(2023, 7, 1).unwrap(),
+    };
+
+    add_budget(&budget("Food", BudgetPeriod::Monthly), &mut conn).unwrap();
+    add_budget(&budget("Food:Groceries", BudgetPeriod::Monthly), &mut conn).unwrap();
+    add_budget(&budget("Car", BudgetPeriod::Monthly), &mut conn).unwrap();
+    add_budget(&budget("Car", BudgetPeriod::Yearly), &mut conn).unwrap();
+    add_goal(
+        &Goal {
+            goal_id: 0,
+            name: "Less Food".to_string(),
+            target: Cent::new(5000),
+            target_date: NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+            source: GoalSource::Tag("Food".to_string()),
+        },
+        &mut conn,
+    )
+    .unwrap();
+
+    let budget_tags = |conn: &Connection| {
+        get_all_budgets(conn)
+            .into_iter()
+            .map(|budget| format!("{} {}", budget.tag, budget.period.to_str()))
+            .collect::<Vec<String>>()
+    };
+    let goal_tags = |conn: &Connection| {
+        get_all_goals(conn)
+            .into_iter()
+            .map(|goal| goal.source.name().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    rename_tag("Food", "Meals", &mut conn).unwrap();
+    assert_eq!(
+        budget_tags(&conn),
+        vec![
+            "Car Monthly",
+            "Car Yearly",
+            "Meals Monthly",
+            "Meals:Groceries Monthly"
+        ]
+    );
+    assert_eq!(goal_tags(&conn), vec!["Meals"]);
+
+    // Only the yearly budget of Car can move, Meals already has a monthly one
+    merge_tags(&["Car".to_string()], "Meals", &mut conn).unwrap();
+    assert_eq!(
+        budget_tags(&conn),
+        vec![
+            "Car Monthly",
+            "Meals Monthly",
+            "Meals Yearly",
+            "Meals:Groceries Monthly"
+        ]
+    );
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(
+        budget_tags(&conn),
+        vec![
+            "Car Monthly",
+            "Car Yearly",
+            "Meals Monthly",
+            "Meals:Groceries Monthly"
+        ]
+    );
+
+    undo_activity(&mut conn).unwrap();
+    assert_eq!(
+        budget_tags(&conn),
+        vec![
+            "Car Monthly",
+            "Car Yearly",
+            "Food Monthly",
+            "Food:Groceries Monthly"
+        ]
+    );
+    assert_eq!(goal_tags(&conn), vec!["Food"]);
+
+    redo_activity(&mut conn).unwrap();
+    redo_activity(&mut conn).unwrap();
+    assert_eq!(
+        budget_tags(&conn),
+        vec![
+            "Car Monthly",
+            "Meals Monthly",
+            "Meals Yearly",
+            "Meals:Groceries Monthly"
+        ]
+    );
+    assert_eq!(goal_tags(&conn), vec!["Meals"]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
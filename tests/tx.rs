@@ -149,6 +149,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Expense".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
             vec![
                 "19-07-2022".to_string(),
@@ -157,6 +158,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Expense".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
         ],
         vec![
@@ -175,6 +177,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Expense".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
             vec![
                 "20-05-2022".to_string(),
@@ -183,6 +186,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Income".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
             vec![
                 "25-05-2022".to_string(),
@@ -191,6 +195,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Transfer".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
         ],
         vec![
@@ -226,6 +231,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Expense".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
             vec![
                 "20-05-2022".to_string(),
@@ -234,6 +240,7 @@ fn check_getting_all_tx_2() {
                 "100.00".to_string(),
                 "Income".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
             vec![
                 "25-05-2022".to_string(),
@@ -242,6 +249,7 @@ fn check_getting_all_tx_2() {
                 "500.00".to_string(),
                 "Transfer".to_string(),
                 "Unknown".to_string(),
+                "Cleared".to_string(),
             ],
         ],
         vec![
@@ -275,11 +283,12 @@ fn check_tx_columns() {
         "tx_type".to_string(),
         "id_num".to_string(),
         "tags".to_string(),
+        "status".to_string(),
     ];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
 
     assert_eq!(columns, expected_data);
-    assert_eq!(columns.len(), 7);
+    assert_eq!(columns.len(), 8);
 }
@@ -1,9 +1,10 @@
 extern crate rex_tui;
 use chrono::prelude::Local;
-use rex_tui::db::create_db;
+use rex_tui::db::{create_db, get_tx_splits, rename_column, set_tx_method_currency};
 use rex_tui::outputs::{AType, CheckingError, NAType, TxType, VerifyingOutput};
 use rex_tui::page_handler::{DateType, TxTab};
-use rex_tui::tx_handler::{add_tx, TxData};
+use rex_tui::tx_handler::{add_tx, delete_tx, TxData};
+use rex_tui::utility::{get_all_tags, get_last_balances};
 use rusqlite::Connection;
 use std::fs;
 
@@ -59,15 +60,18 @@ fn add_dummy_tx(conn: &mut Connection) {
 
 #[test]
 fn test_tx_data_1() {
+    let file_name = "tx_data_1.sqlite";
+    let mut conn = create_test_db(file_name);
+
     let mut tx_data = TxData::new();
 
     let local_time = Local::now().to_string();
-    let expected_data = vec![&local_time[0..10], "", "", "", "", "", "", ""];
+    let expected_data = vec![&local_time[0..10], "", "", "", "", "", "", "", "", ""];
     assert_eq!(tx_data.get_all_texts(), expected_data);
 
     tx_data.clear_date();
 
-    let expected_data = vec!["", "", "", "", "", "", "", ""];
+    let expected_data = vec!["", "", "", "", "", "", "", "", "", ""];
     assert_eq!(tx_data.get_all_texts(), expected_data);
     assert_eq!(tx_data.get_tx_status(), &Vec::<String>::new());
     assert!(tx_data.check_all_empty());
@@ -103,7 +107,7 @@ fn test_tx_data_1() {
 
     assert_eq!(tx_data.get_tx_type(), TxType::IncomeExpense);
     assert_eq!(tx_data.get_tx_method(), "test1".to_string());
-    assert!(tx_data.check_all_fields().is_none());
+    assert!(tx_data.check_all_fields(&conn).is_none());
     assert!(!tx_data.check_all_empty());
 
     let current_index = tx_data.get_current_index();
@@ -130,7 +134,7 @@ fn test_tx_data_1() {
 
     let mut tx_data = TxData::custom("", "details", "test1", "", "100", "Expense", "tags", 0);
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::EmptyDate
     );
 
@@ -145,7 +149,7 @@ fn test_tx_data_1() {
         0,
     );
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::SameTxMethod
     );
 
@@ -160,7 +164,7 @@ fn test_tx_data_1() {
         0,
     );
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::EmptyMethod
     );
 
@@ -175,7 +179,7 @@ fn test_tx_data_1() {
         0,
     );
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::EmptyAmount
     );
 
@@ -190,7 +194,7 @@ fn test_tx_data_1() {
         0,
     );
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::EmptyTxType
     );
 
@@ -205,7 +209,7 @@ fn test_tx_data_1() {
         0,
     );
     assert_eq!(
-        tx_data.check_all_fields().unwrap(),
+        tx_data.check_all_fields(&conn).unwrap(),
         CheckingError::EmptyMethod
     );
 
@@ -219,7 +223,29 @@ fn test_tx_data_1() {
         "",
         0,
     );
-    assert!(tx_data.check_all_fields().is_none());
+    assert!(tx_data.check_all_fields(&conn).is_none());
+
+    set_tx_method_currency("test 2", "EUR", &mut conn).unwrap();
+
+    let mut tx_data = TxData::custom(
+        "2023-07-19",
+        "details",
+        "test1",
+        "test 2",
+        "100",
+        "Transfer",
+        "",
+        0,
+    );
+    let currency_mismatch = tx_data.check_all_fields(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        currency_mismatch.unwrap(),
+        CheckingError::CurrencyMismatch("USD".to_string(), "EUR".to_string())
+    );
 }
 
 #[test]
@@ -431,6 +457,195 @@ fn tx_data_searching() {
     fs::remove_file(file_name).unwrap();
 }
 
+#[test]
+fn tx_data_searching_special_characters() {
+    let file_name = "tx_data_searching_special_characters_test.sqlite";
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(
+        &[r#"Cash "Main""#.to_string(), "100%_Bank".to_string()],
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-19",
+        r#"Bought a "gift""#,
+        r#"Cash "Main""#,
+        "100.00",
+        "Income",
+        r#"Gift "Box""#,
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-20",
+        "Paid 50% off",
+        "100%_Bank",
+        "50.00",
+        "Expense",
+        "Sale",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-21",
+        "Paid 50 off",
+        r#"Cash "Main" to 100%_Bank"#,
+        "20.00",
+        "Transfer",
+        "Sale",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let tx_data = TxData::custom("", r#""gift""#, "", "", "", "", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    // % must only match itself and not any text
+    let tx_data = TxData::custom("", "50%", "", "", "", "", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    let tx_data = TxData::custom("", "", "", "", "", "", r#"Gift "Box""#, 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    let tx_data = TxData::custom("", "", r#"Cash "Main""#, "", "", "Transfer", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    let tx_data = TxData::custom("", "", "", "100%_Bank", "", "Transfer", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    let tx_data = TxData::custom("", "", "100%_Bank", "", "", "Expense", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    assert_eq!(
+        get_last_balances(&conn),
+        vec!["80.00".to_string(), "-30.00".to_string()]
+    );
+
+    delete_tx(3, &mut conn).unwrap();
+
+    assert_eq!(
+        get_last_balances(&conn),
+        vec!["100.00".to_string(), "-50.00".to_string()]
+    );
+
+    rename_column(r#"Cash "Main""#, r#"Wallet "Old""#, &mut conn).unwrap();
+
+    let tx_data = TxData::custom("", "", r#"Wallet "Old""#, "", "", "Income", "", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    assert_eq!(
+        get_last_balances(&conn),
+        vec!["100.00".to_string(), "-50.00".to_string()]
+    );
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn tx_data_searching_tags() {
+    let file_name = "tx_data_searching_tags_test.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2023-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Food,Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-20",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Fast Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let tx_data = TxData::custom("", "", "", "", "", "", "Car", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    // A tag must match fully and the spacing between tags does not matter
+    let tx_data = TxData::custom("", "", "", "", "", "", "Food", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    let tx_data = TxData::custom("", "", "", "", "", "", "Fast Food ,Car", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 2);
+
+    add_tx(
+        "2023-07-21",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Food:Groceries",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    add_tx(
+        "2023-07-22",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Foodie",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    // A parent tag also matches its children
+    let tx_data = TxData::custom("", "", "", "", "", "", "Food", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 2);
+
+    let tx_data = TxData::custom("", "", "", "", "", "", "Food:Groceries", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+
+    delete_tx(4, &mut conn).unwrap();
+    delete_tx(3, &mut conn).unwrap();
+    delete_tx(1, &mut conn).unwrap();
+
+    assert_eq!(get_all_tags(&conn), vec!["Fast Food".to_string()]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
+
 #[test]
 fn tx_data_editing() {
     let mut tx_data = TxData::new_empty();
@@ -484,6 +699,8 @@ fn tx_data_editing() {
         "E",
         "Tag",
         "",
+        "",
+        "",
     ]
     .into_iter()
     .map(ToString::to_string)
@@ -533,10 +750,112 @@ fn tx_data_editing() {
         tx_data.edit_tags(None)
     }
 
-    let expected_data: Vec<String> = vec!["", "", "", "", "", "", "", ""]
+    let expected_data: Vec<String> = vec!["", "", "", "", "", "", "", "", "", ""]
         .into_iter()
         .map(ToString::to_string)
         .collect();
 
     assert_eq!(tx_data.get_all_texts(), expected_data);
 }
+
+#[test]
+fn tx_data_splits() {
+    let file_name = "tx_data_splits_test.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "19-07-2023",
+        "Groceries",
+        "test1",
+        "10.00",
+        "Expense",
+        "Food",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let mut tx_data = TxData::custom("19-07-2023", "Groceries", "test1", "", "", "Expense", "", 1);
+    tx_data.go_current_index(&TxTab::Splits);
+
+    for i in "Food|30|Milk;Home |20".chars() {
+        tx_data.edit_splits(Some(i));
+    }
+
+    // The amount and the tags are filled from the split lines
+    assert_eq!(
+        tx_data.check_splits(),
+        VerifyingOutput::Accepted(AType::Splits)
+    );
+    assert_eq!(tx_data.get_all_texts()[4], "50.00");
+    assert_eq!(tx_data.get_all_texts()[6], "Food, Home");
+    assert_eq!(
+        tx_data.get_all_texts()[8],
+        "Food | 30.00 | Milk; Home | 20.00"
+    );
+
+    tx_data.add_tx(&mut conn).unwrap();
+
+    let splits = get_tx_splits(1, &conn);
+    assert_eq!(splits.len(), 2);
+    assert_eq!(splits[0].note, "Milk");
+
+    let mut tx_data = TxData::custom(
+        "19-07-2023",
+        "Groceries",
+        "test1",
+        "",
+        "60.00",
+        "Expense",
+        "",
+        1,
+    );
+    tx_data.go_current_index(&TxTab::Splits);
+
+    for i in "Food | 30".chars() {
+        tx_data.edit_splits(Some(i));
+    }
+
+    assert_eq!(
+        tx_data.check_splits(),
+        VerifyingOutput::NotAccepted(NAType::SplitMismatch)
+    );
+    assert_eq!(
+        tx_data.add_tx(&mut conn),
+        Err(CheckingError::SplitMismatch.to_string())
+    );
+
+    let mut tx_data = TxData::custom(
+        "19-07-2023",
+        "Groceries",
+        "test1",
+        "test 2",
+        "30.00",
+        "Transfer",
+        "",
+        1,
+    );
+    tx_data.go_current_index(&TxTab::Splits);
+
+    for i in "Food | 30".chars() {
+        tx_data.edit_splits(Some(i));
+    }
+
+    assert_eq!(
+        tx_data.add_tx(&mut conn),
+        Err(CheckingError::SplitOnTransfer.to_string())
+    );
+
+    // Only the split lines of the searched tag count towards the amount
+    let tx_data = TxData::custom("", "", "", "", "", "", "Home", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert_eq!(data.0.len(), 1);
+    assert_eq!(data.0[0][3], "20.00");
+
+    let tx_data = TxData::custom("", "", "", "", ">25", "", "Home", 0);
+    let data = tx_data.get_search_tx(&DateType::Exact, &conn);
+    assert!(data.0.is_empty());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+}
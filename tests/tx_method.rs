@@ -1,9 +1,44 @@
 extern crate rex_tui;
 use rex_tui::db::*;
-use rex_tui::utility::get_all_tx_methods;
+use rex_tui::home_page::TransactionData;
+use rex_tui::outputs::{HistoryError, StepType, TxMethodError};
+use rex_tui::page_handler::IndexedData;
+use rex_tui::summary_page::SummaryData;
+use rex_tui::tx_handler::{add_tx, delete_tx, undo_activity};
+use rex_tui::utility::traits::{AutoFiller, DataVerifier, FieldStepper};
+use rex_tui::utility::{
+    get_active_tx_methods, get_all_tx_methods, get_archived_tx_methods, get_last_balances,
+    get_liability_tx_methods, get_tx_method_kinds, get_tx_method_usage, hide_archived_columns,
+    Cent, CurrencyConverter,
+};
+use rusqlite::types::ValueRef;
 use rusqlite::Connection;
 use std::fs;
 
+struct Testing;
+impl FieldStepper for Testing {}
+impl DataVerifier for Testing {}
+impl AutoFiller for Testing {}
+
+fn get_table_data(query: &str, conn: &Connection) -> Vec<Vec<String>> {
+    let mut statement = conn.prepare(query).unwrap();
+    let total_columns = statement.column_count();
+
+    statement
+        .query_map([], |row| {
+            Ok((0..total_columns)
+                .map(|i| match row.get_ref(i).unwrap() {
+                    ValueRef::Integer(value) => value.to_string(),
+                    ValueRef::Text(value) => String::from_utf8(value.to_vec()).unwrap(),
+                    _ => String::new(),
+                })
+                .collect())
+        })
+        .unwrap()
+        .map(|data| data.unwrap())
+        .collect()
+}
+
 fn create_test_db(file_name: &str) -> Connection {
     if let Ok(metadata) = fs::metadata(file_name) {
         if metadata.is_file() {
@@ -54,3 +89,352 @@ fn check_getting_tx_methods_2() {
         ]
     );
 }
+
+#[test]
+fn check_deleting_tx_method() {
+    let file_name = "deleting_tx_method.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_new_tx_methods(&["test3".to_string()], &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Transfer out",
+        "test1 to test3",
+        "100.00",
+        "Transfer",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    let usage = get_tx_method_usage("test3", &conn).unwrap();
+    let in_use = delete_tx_method("test3", &mut conn);
+    let missing = delete_tx_method("test4", &mut conn);
+    let methods_in_use = get_all_tx_methods(&conn);
+
+    delete_tx(1, &mut conn).unwrap();
+
+    let usage_after = get_tx_method_usage("test3", &conn).unwrap();
+    delete_tx_method("test3", &mut conn).unwrap();
+    let methods_after = get_all_tx_methods(&conn);
+
+    let changes_columns = conn
+        .prepare("SELECT * FROM changes_all")
+        .unwrap()
+        .column_names()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    let saved_methods: i64 = conn
+        .query_row("SELECT COUNT(*) FROM tx_methods", [], |row| row.get(0))
+        .unwrap();
+
+    delete_tx_method("test 2", &mut conn).unwrap();
+    let last_method = delete_tx_method("test1", &mut conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(usage, 1);
+    assert!(matches!(in_use, Err(TxMethodError::InUse(method, 1)) if method == "test3"));
+    assert!(matches!(missing, Err(TxMethodError::NotFound(_))));
+    assert_eq!(methods_in_use.len(), 3);
+
+    assert_eq!(usage_after, 0);
+    assert_eq!(
+        methods_after,
+        vec!["test1".to_string(), "test 2".to_string()]
+    );
+    assert_eq!(
+        changes_columns,
+        vec![
+            "date".to_string(),
+            "id_num".to_string(),
+            "test1".to_string(),
+            "test 2".to_string()
+        ]
+    );
+    assert_eq!(saved_methods, 2);
+    assert!(matches!(last_method, Err(TxMethodError::NoActiveMethod)));
+}
+
+#[test]
+fn check_archiving_tx_method() {
+    let file_name = "archiving_tx_method.sqlite";
+    let mut conn = create_test_db(file_name);
+    let testing = Testing;
+
+    add_new_tx_methods(&["test3".to_string()], &mut conn).unwrap();
+
+    add_tx(
+        "2022-08-19",
+        "Car expense",
+        "test 2",
+        "100.00",
+        "Expense",
+        "Car",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
+    set_tx_method_archived("test 2", true, &mut conn).unwrap();
+
+    let all_methods = get_all_tx_methods(&conn);
+    let active_methods = get_active_tx_methods(&conn);
+    let archived_methods = get_archived_tx_methods(&conn);
+
+    let rows = vec![
+        vec![
+            String::new(),
+            "test1".to_string(),
+            "test 2".to_string(),
+            "test3".to_string(),
+            "Total USD".to_string(),
+        ],
+        vec![
+            "Changes".to_string(),
+            "0.00".to_string(),
+            "↓100.00".to_string(),
+            "0.00".to_string(),
+        ],
+    ];
+    let hidden_rows = hide_archived_columns(rows, &conn);
+
+    // Archived methods are still accepted but skipped by the stepper and autofill
+    let mut archived_method = "test 2".to_string();
+    let verify_status = testing.verify_tx_method(&mut archived_method, &conn);
+
+    let mut step_up = "test1".to_string();
+    testing
+        .step_tx_method(&mut step_up, StepType::StepUp, &conn)
+        .unwrap();
+
+    let mut step_from_archived = "test 2".to_string();
+    testing
+        .step_tx_method(&mut step_from_archived, StepType::StepUp, &conn)
+        .unwrap();
+
+    let autofill = testing.autofill_tx_method("test 2", &conn);
+
+    set_tx_method_archived("test3", true, &mut conn).unwrap();
+    let last_active = set_tx_method_archived("test1", true, &mut conn);
+
+    set_tx_method_archived("test 2", false, &mut conn).unwrap();
+    let active_after = get_active_tx_methods(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(all_methods.len(), 3);
+    assert_eq!(
+        active_methods,
+        vec!["test1".to_string(), "test3".to_string()]
+    );
+    assert_eq!(archived_methods, vec!["test 2".to_string()]);
+    assert_eq!(
+        hidden_rows,
+        vec![
+            vec![
+                String::new(),
+                "test1".to_string(),
+                "test3".to_string(),
+                "Total USD".to_string()
+            ],
+            vec![
+                "Changes".to_string(),
+                "0.00".to_string(),
+                "0.00".to_string()
+            ],
+        ]
+    );
+    assert!(matches!(
+        verify_status,
+        rex_tui::outputs::VerifyingOutput::Accepted(_)
+    ));
+    assert_eq!(step_up, "test3");
+    assert_eq!(step_from_archived, "test1");
+    assert_ne!(autofill, "test 2");
+    assert!(matches!(last_active, Err(TxMethodError::NoActiveMethod)));
+    assert_eq!(
+        active_after,
+        vec!["test1".to_string(), "test 2".to_string()]
+    );
+}
+
+#[test]
+fn check_merging_tx_methods() {
+    let file_name = "merging_tx_methods.sqlite";
+    let expected_file_name = "merging_tx_methods_expected.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    let mut expected_conn = Connection::open(expected_file_name).unwrap();
+    create_db(
+        &["test 2".to_string(), "test3".to_string()],
+        &mut expected_conn,
+    )
+    .unwrap();
+
+    add_new_tx_methods(&["test3".to_string()], &mut conn).unwrap();
+
+    let txs = [
+        ("2022-08-19", "test1", "100.00", "Income"),
+        ("2022-08-20", "test 2", "30.00", "Expense"),
+        ("2022-09-01", "test1 to test 2", "20.00", "Transfer"),
+        ("2022-09-02", "test3 to test1", "10.00", "Transfer"),
+        ("2022-10-01", "test1 to test3", "5.00", "Transfer"),
+    ];
+
+    for (date, method, amount, tx_type) in txs {
+        add_tx(
+            date, "Details", method, amount, tx_type, "Unknown", None, &mut conn,
+        )
+        .unwrap();
+
+        // The same txs added directly to test 2, except the transfer between test1 and test 2
+        if method != "test1 to test 2" {
+            let merged_method = method.replace("test1", "test 2");
+            add_tx(
+                date,
+                "Details",
+                &merged_method,
+                amount,
+                tx_type,
+                "Unknown",
+                None,
+                &mut expected_conn,
+            )
+            .unwrap();
+        }
+    }
+
+    let same_method = merge_tx_methods("test1", "test1", &mut conn);
+    let deleted_transfers = merge_tx_methods("test1", "test 2", &mut conn).unwrap();
+
+    let methods = get_all_tx_methods(&conn);
+    let tx_methods = get_table_data("SELECT tx_method FROM tx_all ORDER BY id_num", &conn);
+    let last_balances = get_last_balances(&conn);
+
+    let balance_query = "SELECT * FROM balance_all ORDER BY id_num";
+    let changes_query = r#"SELECT date, "test 2", test3 FROM changes_all ORDER BY id_num"#;
+
+    let balances = get_table_data(balance_query, &conn);
+    let expected_balances = get_table_data(balance_query, &expected_conn);
+    let changes = get_table_data(changes_query, &conn);
+    let expected_changes = get_table_data(changes_query, &expected_conn);
+
+    let merge_activity = get_table_data(
+        "SELECT activity_type, description FROM activities ORDER BY activity_num DESC LIMIT 1",
+        &conn,
+    );
+    let activity_txs = get_table_data(
+        "SELECT date, tx_method, amount, tx_type, id_num FROM activity_txs
+        WHERE activity_num = (SELECT MAX(activity_num) FROM activities)",
+        &conn,
+    );
+
+    // The txs added before the merge can not be undone anymore
+    let undo_status = undo_activity(&mut conn);
+
+    conn.close().unwrap();
+    expected_conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(expected_file_name).unwrap();
+
+    assert!(matches!(same_method, Err(TxMethodError::SameMethod)));
+    assert_eq!(deleted_transfers, 1);
+    assert_eq!(methods, vec!["test 2".to_string(), "test3".to_string()]);
+    assert_eq!(
+        tx_methods,
+        vec![
+            vec!["test 2".to_string()],
+            vec!["test 2".to_string()],
+            vec!["test3 to test 2".to_string()],
+            vec!["test 2 to test3".to_string()],
+        ]
+    );
+    assert_eq!(
+        last_balances,
+        vec!["75.00".to_string(), "-5.00".to_string()]
+    );
+    assert_eq!(balances, expected_balances);
+    assert_eq!(changes, expected_changes);
+    assert_eq!(
+        merge_activity,
+        vec![vec![
+            "Merge TX Methods".to_string(),
+            "TX Method test1 was merged into test 2".to_string(),
+        ]]
+    );
+    assert_eq!(
+        activity_txs,
+        vec![vec![
+            "2022-09-01".to_string(),
+            "test1 to test 2".to_string(),
+            "20.00".to_string(),
+            "Transfer".to_string(),
+            "3".to_string(),
+        ]]
+    );
+    assert!(matches!(undo_status, Err(HistoryError::NothingToUndo)));
+}
+
+#[test]
+fn check_tx_method_kind() {
+    let file_name = "tx_method_kind.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    for (date, method, amount, tx_type) in [
+        ("2023-07-01", "test1", "1000.00", "Income"),
+        ("2023-07-05", "test 2", "300.00", "Expense"),
+        ("2023-07-10", "test1 to test 2", "100.00", "Transfer"),
+    ] {
+        add_tx(
+            date, "Details", method, amount, tx_type, "Unknown", None, &mut conn,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        get_tx_method_kinds(&conn),
+        vec![
+            ("test1".to_string(), "Asset".to_string()),
+            ("test 2".to_string(), "Asset".to_string()),
+        ]
+    );
+
+    let invalid_kind = set_tx_method_kind("test 2", "Debt", &mut conn);
+    let missing_method = set_tx_method_kind("test3", "Liability", &mut conn);
+
+    set_tx_method_kind("test 2", "Liability", &mut conn).unwrap();
+
+    let liability_methods = get_liability_tx_methods(&conn);
+
+    // Paying the card is a transfer that brings the debt closer to 0
+    let tx_data = TransactionData::new(6, 0, &conn);
+    let converter = CurrencyConverter::new(&conn);
+    let last_balance = tx_data.get_last_balance(&converter, &conn);
+    let net_worth = tx_data.get_net_worth(None, &converter, &conn);
+    let net_worth_before_payment = tx_data.get_net_worth(Some(1), &converter, &conn);
+
+    let summary_modes = IndexedData::new_modes();
+    let summary_data = SummaryData::new(&conn);
+    let summary_table = summary_data.get_table_data(&summary_modes, 6, 0);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(matches!(invalid_kind, Err(TxMethodError::InvalidKind(_))));
+    assert!(matches!(missing_method, Err(TxMethodError::NotFound(_))));
+    assert_eq!(liability_methods, vec!["test 2".to_string()]);
+    assert_eq!(last_balance, vec!["Balance", "900.00", "-200.00", "900.00"]);
+    assert_eq!(net_worth, Cent::new(70000));
+    assert_eq!(net_worth_before_payment, Cent::new(70000));
+    assert_eq!(
+        summary_table,
+        vec![vec!["Unknown", "1000.00", "300.00", "100.00", "100.00"]]
+    );
+}
@@ -1,7 +1,22 @@
 extern crate rex_tui;
 use chrono::{Datelike, Local};
-use rex_tui::db::{MODES, MONTHS, YEARS};
+use rex_tui::db::{create_db, MODES, MONTHS};
 use rex_tui::page_handler::*;
+use rex_tui::tx_handler::add_tx;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    if let Ok(metadata) = fs::metadata(file_name) {
+        if metadata.is_file() {
+            fs::remove_file(file_name).expect("Failed to delete existing file");
+        }
+    }
+
+    let mut conn = Connection::open(file_name).unwrap();
+    create_db(&["test1".to_string(), "test 2".to_string()], &mut conn).unwrap();
+    conn
+}
 
 #[test]
 fn test_table_data() {
@@ -55,15 +70,36 @@ fn test_table_data() {
 
 #[test]
 fn test_indexed_data() {
+    let file_name = "test_indexed_data.sqlite";
+    let mut conn = create_test_db(file_name);
+
+    add_tx(
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Unknown",
+        None,
+        &mut conn,
+    )
+    .unwrap();
+
     let local_month_index = Local::now().month() as usize - 1;
     let local_year_index = Local::now().year() as usize - 2022;
+    let all_years = (2022..=Local::now().year())
+        .map(|year| year.to_string())
+        .collect::<Vec<String>>();
 
     let mut index_data_monthly = IndexedData::new_monthly();
-    let mut index_data_yearly = IndexedData::new_yearly();
+    let mut index_data_yearly = IndexedData::new_yearly(&conn);
     let index_data_modes = IndexedData::new_modes();
 
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
     assert_eq!(index_data_monthly.titles, MONTHS);
-    assert_eq!(index_data_yearly.titles, YEARS);
+    assert_eq!(index_data_yearly.titles, all_years);
     assert_eq!(index_data_modes.titles, MODES);
 
     assert_eq!(index_data_monthly.index, local_month_index);
@@ -81,5 +117,5 @@ fn test_indexed_data() {
 
     index_data_yearly.previous();
     index_data_yearly.previous();
-    assert_eq!(index_data_yearly.index, YEARS.len() - 2);
+    assert_eq!(index_data_yearly.index, all_years.len() - 2);
 }
@@ -130,3 +130,39 @@ fn test_location_json() {
     current_dir.push("location.json");
     fs::remove_file(current_dir).unwrap();
 }
+
+#[test]
+fn check_cent_parsing() {
+    let parsed = vec!["15.5", "-15.50", "15", ".5", "0.005", "100.194", "+1.00"]
+        .into_iter()
+        .map(|amount| amount.parse::<Cent>().unwrap())
+        .collect::<Vec<Cent>>();
+
+    let expected = vec![
+        Cent::new(1550),
+        Cent::new(-1550),
+        Cent::new(1500),
+        Cent::new(50),
+        Cent::new(1),
+        Cent::new(10019),
+        Cent::new(100),
+    ];
+
+    assert_eq!(parsed, expected);
+
+    for invalid in ["", "-", ".", "1.2.3", "abc", "1e5"] {
+        assert!(invalid.parse::<Cent>().is_err());
+    }
+
+    let displayed = vec![
+        Cent::new(1550),
+        Cent::new(-5),
+        Cent::default(),
+        Cent::new(101) / 3,
+    ]
+    .into_iter()
+    .map(|amount| amount.to_string())
+    .collect::<Vec<String>>();
+
+    assert_eq!(displayed, vec!["15.50", "-0.05", "0.00", "0.34"]);
+}
@@ -28,7 +28,7 @@ fn create_test_db(file_name: &str) -> Connection {
 
 #[test]
 fn check_sql_dates() {
-    let data = get_sql_dates(11, 2, &DateType::Monthly);
+    let data = get_sql_dates(11, 2024, &DateType::Monthly);
     let expected_data = ("2024-12-01".to_string(), "2024-12-31".to_string());
     assert_eq!(data, expected_data);
 }
@@ -47,7 +47,7 @@ fn check_verifier_date() {
             "2022-01-32".to_string(),
             "2022-02-31".to_string(),
             "2022-13-31".to_string(),
-            "2038-01-31".to_string(),
+            "2200-01-31".to_string(),
             "2022-01-".to_string(),
             "20222-01-01".to_string(),
             "2022-015-01".to_string(),
@@ -64,7 +64,7 @@ fn check_verifier_date() {
             "2022-01-31".to_string(),
             "2022-02-31".to_string(),
             "2022-12-31".to_string(),
-            "2037-01-31".to_string(),
+            "2199-01-31".to_string(),
             "2022-01-".to_string(),
             "2022-01-01".to_string(),
             "2022-12-01".to_string(),
@@ -215,6 +215,10 @@ fn check_verifier_tx_type() {
             "w".to_string(),
             "   i".to_string(),
             "  i   ".to_string(),
+            "o".to_string(),
+            "Lend".to_string(),
+            "b".to_string(),
+            "Repay In".to_string(),
         ],
         expected: vec![
             "".to_string(),
@@ -223,6 +227,10 @@ fn check_verifier_tx_type() {
             "".to_string(),
             "Income".to_string(),
             "Income".to_string(),
+            "Opening Balance".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
         ],
         result: vec![
             VerifyingOutput::Nothing(AType::TxType),
@@ -231,6 +239,11 @@ fn check_verifier_tx_type() {
             VerifyingOutput::NotAccepted(NAType::InvalidTxType),
             VerifyingOutput::Accepted(AType::TxType),
             VerifyingOutput::Accepted(AType::TxType),
+            VerifyingOutput::Accepted(AType::TxType),
+            // Debt txs are only added from the Debts page
+            VerifyingOutput::NotAccepted(NAType::InvalidTxType),
+            VerifyingOutput::NotAccepted(NAType::InvalidTxType),
+            VerifyingOutput::NotAccepted(NAType::InvalidTxType),
         ],
     };
 